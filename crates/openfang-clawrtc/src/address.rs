@@ -0,0 +1,224 @@
+//! RTC address derivation, validation, and case checksums.
+//!
+//! Address format: `"RTC"` + the first [`ADDRESS_HASH_LEN`] hex chars of
+//! `SHA-256(public_key_bytes)`. Networks that use a different truncation (e.g.
+//! a testnet) describe it with an [`AddressFormat`]; the free functions here
+//! use the mainnet format.
+
+use crate::error::{ClawRtcError, ClawRtcResult};
+use ed25519_dalek::VerifyingKey;
+use sha2::{Digest, Sha256};
+
+/// Prefix of every RTC address.
+pub const ADDRESS_PREFIX: &str = "RTC";
+
+/// Hex chars of the public key hash kept in a mainnet address (160 bits).
+pub const ADDRESS_HASH_LEN: usize = 40;
+
+/// Hex chars in a full SHA-256 hash, the longest possible truncation.
+const MAX_HASH_LEN: usize = 64;
+
+/// How much of the public key hash a network keeps in its addresses.
+///
+/// Derivation and validation both read the length from here, so they can't
+/// drift apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressFormat {
+    hash_len: usize,
+}
+
+impl Default for AddressFormat {
+    fn default() -> Self {
+        Self::MAINNET
+    }
+}
+
+impl AddressFormat {
+    /// The RustChain mainnet format: [`ADDRESS_HASH_LEN`] hex chars.
+    pub const MAINNET: Self = Self {
+        hash_len: ADDRESS_HASH_LEN,
+    };
+
+    /// A format keeping `hash_len` hex chars of the hash (1 to 64).
+    pub fn new(hash_len: usize) -> ClawRtcResult<Self> {
+        if hash_len == 0 || hash_len > MAX_HASH_LEN {
+            return Err(ClawRtcError::InvalidAddress(format!(
+                "address hash length must be 1-{MAX_HASH_LEN}, got {hash_len}"
+            )));
+        }
+        Ok(Self { hash_len })
+    }
+
+    /// Hex chars of the hash kept in an address.
+    pub fn hash_len(&self) -> usize {
+        self.hash_len
+    }
+
+    /// Total address length, prefix included.
+    pub fn address_len(&self) -> usize {
+        ADDRESS_PREFIX.len() + self.hash_len
+    }
+
+    /// Derive the address of a verifying (public) key.
+    pub fn derive(&self, verifying_key: &VerifyingKey) -> String {
+        let hex_hash = hex::encode(Sha256::digest(verifying_key.as_bytes()));
+        format!("{ADDRESS_PREFIX}{}", &hex_hash[..self.hash_len])
+    }
+
+    /// Check that `address` is well-formed in this format.
+    ///
+    /// The address must be `"RTC"` followed by [`hash_len`](Self::hash_len)
+    /// hex characters. If the hex part uses mixed case it is treated as
+    /// checksummed and must pass [`verify_checksum`](Self::verify_checksum);
+    /// all-lowercase or all-uppercase addresses carry no checksum and are
+    /// accepted as-is.
+    pub fn validate(&self, address: &str) -> ClawRtcResult<()> {
+        let invalid = |reason: &str| Err(ClawRtcError::InvalidAddress(reason.to_string()));
+        let Some(hex_part) = address.strip_prefix(ADDRESS_PREFIX) else {
+            return invalid("address must start with \"RTC\"");
+        };
+        if address.len() != self.address_len() {
+            return invalid(&format!(
+                "address must be {} characters, got {}",
+                self.address_len(),
+                address.len()
+            ));
+        }
+        if !hex_part.chars().all(|c| c.is_ascii_hexdigit()) {
+            return invalid("address must be \"RTC\" followed by hex characters");
+        }
+        if !self.verify_checksum(address) {
+            return invalid("address checksum mismatch");
+        }
+        Ok(())
+    }
+
+    /// Encode a case checksum into an address.
+    ///
+    /// Each hex letter is uppercased when the matching nibble of
+    /// `SHA-256(lowercase hex part)` is 8 or above (the EIP-55 scheme), so a
+    /// mistyped character is very likely to break the case pattern.
+    pub fn checksum(&self, address: &str) -> ClawRtcResult<String> {
+        let hex_part = address
+            .strip_prefix(ADDRESS_PREFIX)
+            .filter(|h| h.len() == self.hash_len && h.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| ClawRtcError::InvalidAddress(address.to_string()))?
+            .to_ascii_lowercase();
+        let hash = hex::encode(Sha256::digest(hex_part.as_bytes()));
+        let mixed: String = hex_part
+            .chars()
+            .zip(hash.chars())
+            .map(|(c, h)| {
+                if c.is_ascii_alphabetic() && h.to_digit(16).unwrap_or(0) >= 8 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect();
+        Ok(format!("{ADDRESS_PREFIX}{mixed}"))
+    }
+
+    /// Check the case checksum of an address.
+    ///
+    /// Returns `true` for addresses without a checksum (single-case hex part)
+    /// and for mixed-case addresses whose case matches
+    /// [`checksum`](Self::checksum).
+    pub fn verify_checksum(&self, address: &str) -> bool {
+        let Some(hex_part) = address.strip_prefix(ADDRESS_PREFIX) else {
+            return false;
+        };
+        let has_lower = hex_part.chars().any(|c| c.is_ascii_lowercase());
+        let has_upper = hex_part.chars().any(|c| c.is_ascii_uppercase());
+        if !(has_lower && has_upper) {
+            return true;
+        }
+        self.checksum(address)
+            .is_ok_and(|expected| expected == address)
+    }
+}
+
+/// Check that `address` is a well-formed mainnet RTC address.
+///
+/// See [`AddressFormat::validate`].
+pub fn validate_rtc_address(address: &str) -> ClawRtcResult<()> {
+    AddressFormat::MAINNET.validate(address)
+}
+
+/// Whether `address` passes [`validate_rtc_address`].
+pub fn is_valid_rtc_address(address: &str) -> bool {
+    validate_rtc_address(address).is_ok()
+}
+
+/// Encode a case checksum into a mainnet RTC address.
+///
+/// See [`AddressFormat::checksum`].
+pub fn checksum_address(address: &str) -> ClawRtcResult<String> {
+    AddressFormat::MAINNET.checksum(address)
+}
+
+/// Check the case checksum of a mainnet RTC address.
+///
+/// See [`AddressFormat::verify_checksum`].
+pub fn verify_address_checksum(address: &str) -> bool {
+    AddressFormat::MAINNET.verify_checksum(address)
+}
+
+/// Derive a mainnet RTC address from a verifying (public) key.
+pub(crate) fn derive_address(verifying_key: &VerifyingKey) -> String {
+    AddressFormat::MAINNET.derive(verifying_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::RtcWallet;
+
+    fn key(byte: u8) -> VerifyingKey {
+        VerifyingKey::from_bytes(
+            &hex::decode(
+                RtcWallet::from_private_key_hex(&format!("{byte:02x}").repeat(32))
+                    .unwrap()
+                    .public_key_hex(),
+            )
+            .unwrap()
+            .try_into()
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mainnet_is_default_length() {
+        assert_eq!(AddressFormat::default(), AddressFormat::MAINNET);
+        assert_eq!(AddressFormat::MAINNET.address_len(), 43);
+        assert_eq!(derive_address(&key(1)).len(), 43);
+    }
+
+    #[test]
+    fn test_derivation_and_validation_follow_hash_len() {
+        let testnet = AddressFormat::new(32).unwrap();
+        let address = testnet.derive(&key(1));
+        assert_eq!(address.len(), 35);
+        // Same hash, shorter truncation
+        assert!(derive_address(&key(1)).starts_with(&address));
+
+        assert!(testnet.validate(&address).is_ok());
+        assert!(testnet
+            .validate(&testnet.checksum(&address).unwrap())
+            .is_ok());
+        assert!(testnet.validate(&derive_address(&key(1))).is_err());
+
+        let err = validate_rtc_address(&address).unwrap_err().to_string();
+        assert!(err.contains("43 characters"), "{err}");
+        assert!(checksum_address(&address).is_err());
+    }
+
+    #[test]
+    fn test_hash_len_bounds() {
+        assert!(AddressFormat::new(0).is_err());
+        assert!(AddressFormat::new(65).is_err());
+        let full = AddressFormat::new(64).unwrap();
+        assert_eq!(full.derive(&key(2)).len(), 67);
+    }
+}
@@ -0,0 +1,64 @@
+//! Exact RTC amounts.
+//!
+//! Read as `f64`, a balance loses digits once it passes 2^53 or carries many
+//! decimal places. Node responses therefore hold balances and amounts as
+//! [`Decimal`], parsed from the number's source text. Signed transfers still
+//! carry `f64` amounts, since that is what the node signs and verifies, so an
+//! amount is only signed if [`to_wire`] converts it without rounding.
+
+use rust_decimal::prelude::ToPrimitive;
+use std::str::FromStr;
+
+pub use rust_decimal::Decimal;
+
+/// Parse an amount such as `"1.5"`, `"9007199254740993"` or `"1e-7"` exactly.
+pub fn parse(text: &str) -> Result<Decimal, rust_decimal::Error> {
+    let text = text.trim();
+    Decimal::from_str(text).or_else(|_| Decimal::from_scientific(text))
+}
+
+/// Nearest `f64` to `amount`.
+pub fn to_f64(amount: Decimal) -> f64 {
+    amount.to_f64().unwrap_or_default()
+}
+
+/// `amount` as the `f64` a signed transfer carries, or `None` if the `f64`
+/// would not read back as exactly `amount`.
+pub fn to_wire(amount: Decimal) -> Option<f64> {
+    let value = to_f64(amount);
+    (from_f64(value) == Some(amount)).then_some(value)
+}
+
+/// `value` as a [`Decimal`], taking its shortest decimal form so `0.1`
+/// becomes exactly `0.1`. `None` for NaN, infinities, and values outside
+/// `Decimal`'s range.
+pub fn from_f64(value: f64) -> Option<Decimal> {
+    if !value.is_finite() {
+        return None;
+    }
+    parse(&value.to_string()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(parse(" 12.50 ").unwrap().to_string(), "12.50");
+        assert_eq!(parse("1e-7").unwrap().to_string(), "0.0000001");
+        assert!(parse("lots").is_err());
+
+        assert_eq!(from_f64(0.1).unwrap().to_string(), "0.1");
+        assert_eq!(from_f64(-2.5), parse("-2.5").ok());
+        assert_eq!(from_f64(f64::NAN), None);
+        assert_eq!(from_f64(1e300), None);
+
+        assert_eq!(to_f64(parse("1.25").unwrap()), 1.25);
+        assert_eq!(to_f64(from_f64(0.3).unwrap()), 0.3);
+
+        assert_eq!(to_wire(parse("10.490").unwrap()), Some(10.49));
+        assert_eq!(to_wire(parse("9007199254740993").unwrap()), None);
+        assert_eq!(to_wire(parse("0.123456789012345678901").unwrap()), None);
+    }
+}
@@ -1,59 +1,338 @@
 //! BoTTube video platform client.
 //!
-//! Provides search, trending, commenting, and voting for the BoTTube
-//! AI video platform at bottube.ai.
+//! Provides search, trending, commenting, voting, reporting, and resumable
+//! video uploads for the BoTTube AI video platform at bottube.ai.
 
+use crate::clock::{self, Clock};
 use crate::error::{ClawRtcError, ClawRtcResult};
+use crate::http::{
+    read_json, send_with_retry, urlencoded, HttpOptions, RequestLimit, RetryPolicy, TlsVersion,
+    DEFAULT_MAX_BODY_BYTES,
+};
+use crate::op_result::OpResult;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
 use tracing::debug;
 
 const BOTTUBE_BASE: &str = "https://bottube.ai";
 
+/// Header the API key is sent in.
+const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Default pause between pages in [`BoTTubeClient::search_all`].
+const SEARCH_PAGE_DELAY: Duration = Duration::from_millis(250);
+
+/// Default part size for [`BoTTubeClient::upload_parts`]: 4 MiB.
+const UPLOAD_PART_SIZE: u64 = 4 * 1024 * 1024;
+
+/// A video from search or trending results.
+///
+/// Fields the API leaves out are zero or empty.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Video {
+    #[serde(alias = "video_id")]
+    pub id: String,
+    pub title: String,
+    /// Name of the agent that uploaded the video.
+    #[serde(alias = "agent", alias = "agent_name")]
+    pub channel: String,
+    #[serde(alias = "view_count")]
+    pub views: u64,
+    #[serde(alias = "like_count")]
+    pub likes: u64,
+    #[serde(alias = "duration")]
+    pub duration_s: f64,
+    #[serde(alias = "watch_url")]
+    pub url: String,
+}
+
+/// A channel's profile, from [`BoTTubeClient::get_channel`].
+///
+/// Fields the API leaves out are zero or empty.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Channel {
+    #[serde(alias = "channel_id", alias = "agent_name")]
+    pub id: String,
+    #[serde(alias = "display_name", alias = "title")]
+    pub name: String,
+    #[serde(alias = "bio")]
+    pub description: String,
+    #[serde(alias = "subscriber_count", alias = "followers")]
+    pub subscribers: u64,
+    #[serde(alias = "videos_count")]
+    pub video_count: u64,
+    pub url: String,
+}
+
+/// A video upload in progress, from [`BoTTubeClient::start_upload`].
+///
+/// Serializable, so an interrupted upload can be saved and resumed later,
+/// even by another process.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkedUpload {
+    /// Server-assigned upload ID.
+    pub upload_id: String,
+    /// The file being uploaded.
+    pub path: PathBuf,
+    /// File size in bytes.
+    pub size: u64,
+    /// Bytes per part; the last part may be shorter.
+    pub part_size: u64,
+    /// Parts the server has accepted, counting from the first.
+    pub parts_done: u64,
+}
+
+impl ChunkedUpload {
+    /// Number of parts the file is split into.
+    pub fn part_count(&self) -> u64 {
+        self.size.div_ceil(self.part_size)
+    }
+
+    /// Byte offset of the next part to send.
+    pub fn offset(&self) -> u64 {
+        (self.parts_done * self.part_size).min(self.size)
+    }
+
+    /// Whether every part has been accepted.
+    pub fn is_complete(&self) -> bool {
+        self.parts_done >= self.part_count()
+    }
+}
+
+/// A page of [`BoTTubeClient::search_typed`] results.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchResults {
+    #[serde(alias = "results")]
+    pub videos: Vec<Video>,
+    /// Total matches across all pages, if the API reports it.
+    pub total: Option<u64>,
+    pub page: Option<u32>,
+}
+
+impl SearchResults {
+    /// Read a search or trending body: an object holding a `videos` (or
+    /// `results`) list, or a bare list of videos.
+    pub fn from_value(body: serde_json::Value) -> ClawRtcResult<Self> {
+        if body.is_array() {
+            return Ok(Self {
+                videos: serde_json::from_value(body)?,
+                ..Self::default()
+            });
+        }
+        Ok(serde_json::from_value(body)?)
+    }
+}
+
+/// One page of search results with where it sits among the others.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchPage {
+    pub results: Vec<Video>,
+    pub page: u32,
+    /// Number of pages, if the API reports it or reports enough to work it out.
+    pub total_pages: Option<u32>,
+    pub has_next: bool,
+}
+
+impl SearchPage {
+    /// Read the search body for `requested_page`.
+    ///
+    /// The page count comes from `total_pages` (or `pages`), else from
+    /// `total` and `per_page`. `has_next` comes from `has_next` (or
+    /// `has_more`), else from the page count; with neither, a non-empty page
+    /// is assumed to have a successor.
+    pub fn from_value(body: serde_json::Value, requested_page: u32) -> ClawRtcResult<Self> {
+        let total_pages = ["total_pages", "pages"]
+            .iter()
+            .find_map(|key| body[*key].as_u64())
+            .or_else(|| {
+                let total = body["total"].as_u64()?;
+                let per_page = body["per_page"].as_u64().filter(|n| *n > 0)?;
+                Some(total.div_ceil(per_page))
+            })
+            .map(|pages| u32::try_from(pages).unwrap_or(u32::MAX));
+        let has_next = ["has_next", "has_more"]
+            .iter()
+            .find_map(|key| body[*key].as_bool());
+
+        let listing = SearchResults::from_value(body)?;
+        let page = listing.page.unwrap_or(requested_page);
+        Ok(Self {
+            has_next: has_next
+                .or(total_pages.map(|pages| page < pages))
+                .unwrap_or(!listing.videos.is_empty()),
+            results: listing.videos,
+            page,
+            total_pages,
+        })
+    }
+}
+
 /// BoTTube API client.
 pub struct BoTTubeClient {
     http: reqwest::Client,
+    http_options: HttpOptions,
     api_key: Option<String>,
+    base_url: String,
+    retry: RetryPolicy,
+    max_body_bytes: usize,
+    page_delay: Duration,
+    upload_part_size: u64,
+    clock: Arc<dyn Clock>,
 }
 
 impl BoTTubeClient {
     /// Create a new client, optionally with an API key for authenticated operations.
     pub fn new(api_key: Option<&str>) -> Self {
-        let http = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(15))
-            .build()
-            .expect("Failed to build HTTP client");
+        let mut http_options = HttpOptions::new(std::time::Duration::from_secs(15));
+        http_options.register_credential_header(API_KEY_HEADER);
         Self {
-            http,
+            http: http_options.build(),
+            http_options,
             api_key: api_key.map(|s| s.to_string()),
+            base_url: BOTTUBE_BASE.to_string(),
+            retry: RetryPolicy::default(),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            page_delay: SEARCH_PAGE_DELAY,
+            upload_part_size: UPLOAD_PART_SIZE,
+            clock: clock::system(),
         }
     }
 
+    /// Cap the size of response bodies the client will buffer.
+    pub fn with_max_body_bytes(mut self, limit: usize) -> Self {
+        self.max_body_bytes = limit;
+        self
+    }
+
+    /// Give up on a request after `timeout` (default 15 seconds).
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.http_options.timeout = timeout;
+        self.http = self.http_options.build();
+        self
+    }
+
+    /// Refuse TLS versions older than `version` (e.g. require TLS 1.3).
+    pub fn with_min_tls_version(mut self, version: TlsVersion) -> Self {
+        self.http_options.min_tls_version = Some(version);
+        self.http = self.http_options.build();
+        self
+    }
+
+    /// Resolve `host` to `addr` instead of asking system DNS.
+    ///
+    /// The URL still names `host`, so TLS is negotiated for it; the port comes
+    /// from the URL, not from `addr`.
+    pub fn with_resolve(mut self, host: &str, addr: SocketAddr) -> Self {
+        self.http_options.resolve.push((host.to_string(), addr));
+        self.http = self.http_options.build();
+        self
+    }
+
+    /// Share `limit` with other clients to bound their combined in-flight
+    /// requests.
+    pub fn with_request_limit(mut self, limit: RequestLimit) -> Self {
+        self.http_options.request_limit = Some(limit);
+        self
+    }
+
+    /// Override the API base URL (e.g. a staging server).
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        self.base_url = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set the retry policy for comment and vote operations.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Pause `delay` between page requests in [`BoTTubeClient::search_all`]
+    /// (default 250 ms).
+    pub fn with_page_delay(mut self, delay: Duration) -> Self {
+        self.page_delay = delay;
+        self
+    }
+
+    /// Wait out the page delay on `clock`.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Split uploads into parts of `bytes` (default 4 MiB). Smaller parts
+    /// lose less progress when a slow connection drops.
+    pub fn with_upload_part_size(mut self, bytes: u64) -> Self {
+        self.upload_part_size = bytes.max(1);
+        self
+    }
+
     /// Search videos by query string.
     pub async fn search(&self, query: &str, page: u32) -> ClawRtcResult<serde_json::Value> {
         let url = format!(
             "{}/api/search?q={}&page={}",
-            BOTTUBE_BASE,
+            self.base_url,
             urlencoded(query),
             page
         );
         debug!(url, "Searching BoTTube");
-        let resp = self.http.get(&url).send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        read_json(resp, self.max_body_bytes).await
+    }
+
+    /// Search videos by query string, parsed into [`SearchResults`].
+    pub async fn search_typed(&self, query: &str, page: u32) -> ClawRtcResult<SearchResults> {
+        SearchResults::from_value(self.search(query, page).await?)
+    }
+
+    /// Fetch one page of search results with its pagination details.
+    pub async fn search_page(&self, query: &str, page: u32) -> ClawRtcResult<SearchPage> {
+        SearchPage::from_value(self.search(query, page).await?, page)
+    }
+
+    /// Search every page from the first, stopping when a page has no
+    /// successor or after `max_pages`, and return all the results in order.
+    pub async fn search_all(&self, query: &str, max_pages: u32) -> ClawRtcResult<Vec<Video>> {
+        let mut videos = Vec::new();
+        for page in 1..=max_pages {
+            if page > 1 {
+                self.clock.sleep(self.page_delay).await;
+            }
+            let result = self.search_page(query, page).await?;
+            videos.extend(result.results);
+            if !result.has_next {
+                break;
+            }
+        }
+        Ok(videos)
     }
 
     /// Get trending videos.
     pub async fn trending(&self) -> ClawRtcResult<serde_json::Value> {
-        let url = format!("{}/api/trending", BOTTUBE_BASE);
+        let url = format!("{}/api/trending", self.base_url);
         debug!(url, "Getting BoTTube trending");
-        let resp = self.http.get(&url).send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        read_json(resp, self.max_body_bytes).await
+    }
+
+    /// Get trending videos, parsed into [`Video`]s.
+    pub async fn trending_typed(&self) -> ClawRtcResult<Vec<Video>> {
+        Ok(SearchResults::from_value(self.trending().await?)?.videos)
     }
 
     /// Get platform statistics.
     pub async fn stats(&self) -> ClawRtcResult<serde_json::Value> {
-        let url = format!("{}/api/stats", BOTTUBE_BASE);
+        let url = format!("{}/api/stats", self.base_url);
         debug!(url, "Getting BoTTube stats");
-        let resp = self.http.get(&url).send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        read_json(resp, self.max_body_bytes).await
     }
 
     /// Comment on a video.
@@ -67,7 +346,7 @@ impl BoTTubeClient {
             .api_key
             .as_deref()
             .ok_or_else(|| ClawRtcError::MissingApiKey("bottube".into()))?;
-        let url = format!("{}/api/videos/{}/comment", BOTTUBE_BASE, video_id);
+        let url = format!("{}/api/videos/{}/comment", self.base_url, video_id);
         debug!(url, video_id, "Commenting on BoTTube video");
 
         let mut body = serde_json::json!({
@@ -78,15 +357,15 @@ impl BoTTubeClient {
             body["parent_id"] = serde_json::json!(pid);
         }
 
-        let resp = self
-            .http
-            .post(&url)
-            .header("X-API-Key", key)
-            .json(&body)
-            .send()
-            .await?;
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            self.http
+                .post(&url)
+                .header(API_KEY_HEADER, key)
+                .json(&body)
+        })
+        .await?;
         let status = resp.status();
-        let result: serde_json::Value = resp.json().await?;
+        let result: serde_json::Value = read_json(resp, self.max_body_bytes).await?;
         if !status.is_success() {
             return Err(ClawRtcError::BoTTube(format!(
                 "Comment failed ({}): {}",
@@ -96,6 +375,23 @@ impl BoTTubeClient {
         Ok(result)
     }
 
+    /// Comment on a video and normalize the response into an [`OpResult`].
+    pub async fn comment_op(
+        &self,
+        video_id: &str,
+        content: &str,
+        parent_id: Option<&str>,
+    ) -> ClawRtcResult<OpResult> {
+        let raw = self.comment(video_id, content, parent_id).await?;
+        Ok(OpResult::from_response("bottube", &self.base_url, raw))
+    }
+
+    /// Vote on a video and normalize the response into an [`OpResult`].
+    pub async fn vote_op(&self, video_id: &str, vote: i8) -> ClawRtcResult<OpResult> {
+        let raw = self.vote(video_id, vote).await?;
+        Ok(OpResult::from_response("bottube", &self.base_url, raw))
+    }
+
     /// Vote on a video (1 = like, -1 = dislike, 0 = remove vote).
     pub async fn vote(
         &self,
@@ -106,7 +402,7 @@ impl BoTTubeClient {
             .api_key
             .as_deref()
             .ok_or_else(|| ClawRtcError::MissingApiKey("bottube".into()))?;
-        let url = format!("{}/api/videos/{}/vote", BOTTUBE_BASE, video_id);
+        let url = format!("{}/api/videos/{}/vote", self.base_url, video_id);
         let action = match vote {
             1 => "like",
             -1 => "dislike",
@@ -114,15 +410,15 @@ impl BoTTubeClient {
         };
         debug!(url, video_id, action, "Voting on BoTTube video");
 
-        let resp = self
-            .http
-            .post(&url)
-            .header("X-API-Key", key)
-            .json(&serde_json::json!({ "vote": vote }))
-            .send()
-            .await?;
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            self.http
+                .post(&url)
+                .header(API_KEY_HEADER, key)
+                .json(&serde_json::json!({ "vote": vote }))
+        })
+        .await?;
         let status = resp.status();
-        let result: serde_json::Value = resp.json().await?;
+        let result: serde_json::Value = read_json(resp, self.max_body_bytes).await?;
         if !status.is_success() {
             return Err(ClawRtcError::BoTTube(format!(
                 "Vote failed ({}): {}",
@@ -132,13 +428,44 @@ impl BoTTubeClient {
         Ok(result)
     }
 
+    /// Report a video for moderation with a short description of the problem.
+    pub async fn report(&self, video_id: &str, reason: &str) -> ClawRtcResult<serde_json::Value> {
+        let key = self
+            .api_key
+            .as_deref()
+            .ok_or_else(|| ClawRtcError::MissingApiKey("bottube".into()))?;
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(ClawRtcError::BoTTube("Report reason must not be empty".into()));
+        }
+        let url = format!("{}/api/videos/{}/report", self.base_url, video_id);
+        debug!(url, video_id, "Reporting BoTTube video");
+
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            self.http
+                .post(&url)
+                .header(API_KEY_HEADER, key)
+                .json(&serde_json::json!({ "reason": reason }))
+        })
+        .await?;
+        let status = resp.status();
+        let result: serde_json::Value = read_json(resp, self.max_body_bytes).await?;
+        if !status.is_success() {
+            return Err(ClawRtcError::BoTTube(format!(
+                "Report failed ({}): {}",
+                status, result
+            )));
+        }
+        Ok(result)
+    }
+
     /// Get video details.
     pub async fn get_video(&self, video_id: &str) -> ClawRtcResult<serde_json::Value> {
-        let url = format!("{}/api/videos/{}", BOTTUBE_BASE, video_id);
+        let url = format!("{}/api/videos/{}", self.base_url, video_id);
         debug!(url, "Getting BoTTube video");
-        let resp = self.http.get(&url).send().await?;
+        let resp = self.http_options.send(self.http.get(&url)).await?;
         let status = resp.status();
-        let result: serde_json::Value = resp.json().await?;
+        let result: serde_json::Value = read_json(resp, self.max_body_bytes).await?;
         if !status.is_success() {
             return Err(ClawRtcError::BoTTube(format!(
                 "Video not found ({}): {}",
@@ -148,27 +475,241 @@ impl BoTTubeClient {
         Ok(result)
     }
 
+    /// Whether a video exists, without downloading it.
+    ///
+    /// Sends a HEAD request, falling back to GET (whose body is not read) if
+    /// the server answers 405 or 501. A 404 is `false`; any other non-success
+    /// status is an error.
+    pub async fn video_exists(&self, video_id: &str) -> ClawRtcResult<bool> {
+        let url = format!("{}/api/videos/{}", self.base_url, video_id);
+        debug!(url, "Checking BoTTube video exists");
+        let mut resp = self.http_options.send(self.http.head(&url)).await?;
+        if matches!(
+            resp.status(),
+            reqwest::StatusCode::METHOD_NOT_ALLOWED | reqwest::StatusCode::NOT_IMPLEMENTED
+        ) {
+            debug!(url, "HEAD unsupported; falling back to GET");
+            resp = self.http_options.send(self.http.get(&url)).await?;
+        }
+        let status = resp.status();
+        if status.is_success() {
+            Ok(true)
+        } else if status == reqwest::StatusCode::NOT_FOUND {
+            Ok(false)
+        } else {
+            Err(ClawRtcError::BoTTube(format!(
+                "Video lookup failed: HTTP {status}"
+            )))
+        }
+    }
+
+    /// Get a channel's profile.
+    pub async fn get_channel(&self, channel_id: &str) -> ClawRtcResult<Channel> {
+        let url = format!("{}/api/channels/{}", self.base_url, urlencoded(channel_id));
+        debug!(url, "Getting BoTTube channel");
+        let body = self.get_channel_json(&url, channel_id).await?;
+        let body = match body {
+            serde_json::Value::Object(mut map) if map.contains_key("channel") => {
+                map.remove("channel").unwrap_or_default()
+            }
+            other => other,
+        };
+        Ok(serde_json::from_value(body)?)
+    }
+
+    /// Get one page of a channel's uploads.
+    pub async fn channel_videos(&self, channel_id: &str, page: u32) -> ClawRtcResult<SearchPage> {
+        let url = format!(
+            "{}/api/channels/{}/videos?page={}",
+            self.base_url,
+            urlencoded(channel_id),
+            page
+        );
+        debug!(url, "Getting BoTTube channel videos");
+        SearchPage::from_value(self.get_channel_json(&url, channel_id).await?, page)
+    }
+
+    async fn get_channel_json(
+        &self,
+        url: &str,
+        channel_id: &str,
+    ) -> ClawRtcResult<serde_json::Value> {
+        let resp = self.http_options.send(self.http.get(url)).await?;
+        let status = resp.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(ClawRtcError::BoTTube(format!(
+                "Channel not found: {channel_id}"
+            )));
+        }
+        let result: serde_json::Value = read_json(resp, self.max_body_bytes).await?;
+        if !status.is_success() {
+            return Err(ClawRtcError::BoTTube(format!(
+                "Channel {channel_id} lookup failed ({status}): {result}"
+            )));
+        }
+        Ok(result)
+    }
+
     /// Get comments on a video.
     pub async fn get_comments(&self, video_id: &str) -> ClawRtcResult<serde_json::Value> {
-        let url = format!("{}/api/videos/{}/comments", BOTTUBE_BASE, video_id);
+        let url = format!("{}/api/videos/{}/comments", self.base_url, video_id);
         debug!(url, "Getting BoTTube comments");
-        let resp = self.http.get(&url).send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        read_json(resp, self.max_body_bytes).await
     }
-}
 
-fn urlencoded(s: &str) -> String {
-    s.replace('%', "%25")
-        .replace(' ', "%20")
-        .replace('&', "%26")
-        .replace('=', "%3D")
-        .replace('+', "%2B")
-        .replace('#', "%23")
+    /// Open a chunked upload of the video at `path`.
+    ///
+    /// Nothing is sent but the metadata; pass the returned [`ChunkedUpload`]
+    /// to [`BoTTubeClient::upload_parts`]. Assumes BoTTube's resumable
+    /// protocol: `POST /api/uploads` opens the upload and returns its
+    /// `upload_id` (and optionally the `part_size` the server wants), each
+    /// part is `PUT` to `/api/uploads/{id}/parts/{index}`, and
+    /// `POST /api/uploads/{id}/complete` publishes the video.
+    pub async fn start_upload(
+        &self,
+        path: &Path,
+        title: &str,
+        description: &str,
+    ) -> ClawRtcResult<ChunkedUpload> {
+        let key = self.upload_key()?;
+        let size = tokio::fs::metadata(path).await?.len();
+        let url = format!("{}/api/uploads", self.base_url);
+        debug!(url, size, "Starting BoTTube upload");
+        let body = serde_json::json!({
+            "filename": path.file_name().map(|n| n.to_string_lossy()),
+            "size": size,
+            "part_size": self.upload_part_size,
+            "title": title,
+            "description": description,
+        });
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            self.http
+                .post(&url)
+                .header(API_KEY_HEADER, key)
+                .json(&body)
+        })
+        .await?;
+        let status = resp.status();
+        let result: serde_json::Value = read_json(resp, self.max_body_bytes).await?;
+        if !status.is_success() {
+            return Err(ClawRtcError::BoTTube(format!(
+                "Upload start failed ({}): {}",
+                status, result
+            )));
+        }
+        let upload_id = result["upload_id"]
+            .as_str()
+            .or_else(|| result["id"].as_str())
+            .ok_or_else(|| {
+                ClawRtcError::BoTTube(format!("Upload start returned no ID: {result}"))
+            })?;
+        Ok(ChunkedUpload {
+            upload_id: upload_id.to_string(),
+            path: path.to_path_buf(),
+            size,
+            part_size: result["part_size"]
+                .as_u64()
+                .filter(|&n| n > 0)
+                .unwrap_or(self.upload_part_size),
+            parts_done: 0,
+        })
+    }
+
+    /// Send the parts of `upload` not yet accepted, then complete it and
+    /// return the server's response (normally the new video).
+    ///
+    /// `upload.parts_done` advances as each part is accepted, so after an
+    /// error calling this again resumes from the first part that failed.
+    /// Parts are addressed by index, so resending one the server already
+    /// has is harmless. An upload with a zero `part_size` (e.g. a hand-edited
+    /// saved copy) is rejected.
+    pub async fn upload_parts(
+        &self,
+        upload: &mut ChunkedUpload,
+    ) -> ClawRtcResult<serde_json::Value> {
+        if upload.part_size == 0 {
+            return Err(ClawRtcError::BoTTube(format!(
+                "Upload {} has a part_size of 0",
+                upload.upload_id
+            )));
+        }
+        let key = self.upload_key()?;
+        let mut file = tokio::fs::File::open(&upload.path).await?;
+        if file.metadata().await?.len() != upload.size {
+            return Err(ClawRtcError::BoTTube(format!(
+                "{} changed since the upload started",
+                upload.path.display()
+            )));
+        }
+        let base = format!(
+            "{}/api/uploads/{}",
+            self.base_url,
+            urlencoded(&upload.upload_id)
+        );
+
+        while !upload.is_complete() {
+            let index = upload.parts_done;
+            let start = upload.offset();
+            let len = upload.part_size.min(upload.size - start);
+            let mut part = vec![0; len as usize];
+            file.seek(std::io::SeekFrom::Start(start)).await?;
+            file.read_exact(&mut part).await?;
+
+            let url = format!("{base}/parts/{index}");
+            let range = format!("bytes {}-{}/{}", start, start + len - 1, upload.size);
+            debug!(url, range, "Uploading BoTTube part");
+            let resp = send_with_retry(&self.retry, &self.http_options, || {
+                self.http
+                    .put(&url)
+                    .header(API_KEY_HEADER, key)
+                    .header("Content-Range", &range)
+                    .header("Content-Type", "application/octet-stream")
+                    .body(part.clone())
+            })
+            .await?;
+            let status = resp.status();
+            if !status.is_success() {
+                let result: serde_json::Value = read_json(resp, self.max_body_bytes)
+                    .await
+                    .unwrap_or_default();
+                return Err(ClawRtcError::BoTTube(format!(
+                    "Upload part {index} failed ({status}): {result}"
+                )));
+            }
+            upload.parts_done += 1;
+        }
+
+        let url = format!("{base}/complete");
+        debug!(url, "Completing BoTTube upload");
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            self.http.post(&url).header(API_KEY_HEADER, key)
+        })
+        .await?;
+        let status = resp.status();
+        let result: serde_json::Value = read_json(resp, self.max_body_bytes).await?;
+        if !status.is_success() {
+            return Err(ClawRtcError::BoTTube(format!(
+                "Upload completion failed ({}): {}",
+                status, result
+            )));
+        }
+        Ok(result)
+    }
+
+    fn upload_key(&self) -> ClawRtcResult<&str> {
+        self.api_key
+            .as_deref()
+            .ok_or_else(|| ClawRtcError::MissingApiKey("bottube".into()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::MockClock;
+    use crate::test_support::{MockResponse, MockServer};
+    use std::time::Duration;
 
     #[test]
     fn test_client_creation() {
@@ -181,4 +722,342 @@ mod tests {
         let c = BoTTubeClient::new(Some("bottube_sk_test123"));
         assert_eq!(c.api_key.as_deref(), Some("bottube_sk_test123"));
     }
+
+    fn mock_client(server: &MockServer) -> BoTTubeClient {
+        BoTTubeClient::new(Some("bottube_sk_test123"))
+            .with_base_url(&server.url())
+            .with_retry_policy(RetryPolicy {
+                max_retries: 2,
+                initial_backoff: Duration::from_millis(5),
+                max_backoff: Duration::from_millis(20),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_comment_retries_on_429() {
+        let server = MockServer::sequence(vec![
+            MockResponse::json(429, serde_json::json!({})).with_header("Retry-After", "0"),
+            MockResponse::json(200, serde_json::json!({ "ok": true })),
+        ])
+        .await;
+        let result = mock_client(&server)
+            .comment("vid1", "Nice video", None)
+            .await
+            .unwrap();
+        assert_eq!(result["ok"], true);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].path, "/api/videos/vid1/comment");
+        assert_eq!(requests[1].header("X-API-Key"), Some("bottube_sk_test123"));
+    }
+
+    #[test]
+    fn test_search_results_from_sample_body() {
+        let body = serde_json::json!({
+            "results": [
+                {
+                    "video_id": "v8Kd2",
+                    "title": "Sorting 1M keys on a PowerBook G4",
+                    "agent": "retro-bench",
+                    "view_count": 1523,
+                    "like_count": 87,
+                    "duration": 94.5,
+                    "url": "https://bottube.ai/watch/v8Kd2",
+                    "category": "tech"
+                },
+                { "id": "x1", "title": "Untitled" }
+            ],
+            "total": 41,
+            "page": 2
+        });
+        let results = SearchResults::from_value(body).unwrap();
+        assert_eq!(results.total, Some(41));
+        assert_eq!(results.page, Some(2));
+        assert_eq!(
+            results.videos[0],
+            Video {
+                id: "v8Kd2".into(),
+                title: "Sorting 1M keys on a PowerBook G4".into(),
+                channel: "retro-bench".into(),
+                views: 1523,
+                likes: 87,
+                duration_s: 94.5,
+                url: "https://bottube.ai/watch/v8Kd2".into(),
+            }
+        );
+        assert_eq!(results.videos[1].id, "x1");
+        assert_eq!(results.videos[1].views, 0);
+
+        // Trending returns a bare list
+        let trending = SearchResults::from_value(serde_json::json!([{ "id": "t1" }])).unwrap();
+        assert_eq!(trending.videos.len(), 1);
+        assert_eq!(trending.total, None);
+    }
+
+    #[test]
+    fn test_search_page_metadata() {
+        let page = |body| SearchPage::from_value(body, 1).unwrap();
+
+        let explicit = page(serde_json::json!({ "videos": [{}], "page": 2, "total_pages": 2 }));
+        assert_eq!((explicit.page, explicit.total_pages), (2, Some(2)));
+        assert!(!explicit.has_next);
+
+        let counted = page(serde_json::json!({ "videos": [{}], "total": 41, "per_page": 20 }));
+        assert_eq!((counted.page, counted.total_pages), (1, Some(3)));
+        assert!(counted.has_next);
+
+        let flagged = page(serde_json::json!({ "videos": [{}], "has_more": false }));
+        assert_eq!(flagged.total_pages, None);
+        assert!(!flagged.has_next);
+
+        // No pagination fields: keep going until a page comes back empty
+        assert!(page(serde_json::json!({ "videos": [{}] })).has_next);
+        assert!(!page(serde_json::json!({ "videos": [] })).has_next);
+    }
+
+    #[tokio::test]
+    async fn test_search_all_walks_pages() {
+        let server = MockServer::start(|req| {
+            let page: u32 = req
+                .path
+                .split("page=")
+                .nth(1)
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(0);
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "videos": [{ "id": format!("p{page}a") }, { "id": format!("p{page}b") }],
+                    "page": page,
+                    "total_pages": 3,
+                }),
+            )
+        })
+        .await;
+        let clock = MockClock::at_unix(0);
+        let client = mock_client(&server)
+            .with_page_delay(Duration::from_secs(2))
+            .with_clock(Arc::new(clock.clone()));
+
+        let videos = client.search_all("rust", 10).await.unwrap();
+        let ids: Vec<&str> = videos.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, ["p1a", "p1b", "p2a", "p2b", "p3a", "p3b"]);
+        assert_eq!(server.requests().len(), 3);
+        assert_eq!(clock.now().timestamp(), 4);
+
+        // The cap wins over has_next
+        let videos = client.search_all("rust", 2).await.unwrap();
+        assert_eq!(videos.len(), 4);
+        assert_eq!(server.requests().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_response_is_rejected() {
+        let items: Vec<u32> = (0..1000).collect();
+        let server =
+            MockServer::sequence(vec![MockResponse::json(200, serde_json::json!(items))]).await;
+        let client = mock_client(&server).with_max_body_bytes(256);
+        let err = client.trending().await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::ResponseTooLarge(256)));
+    }
+
+    #[tokio::test]
+    async fn test_report_request() {
+        let server =
+            MockServer::sequence(vec![MockResponse::json(200, serde_json::json!({ "ok": true }))])
+                .await;
+        let client = mock_client(&server);
+        let result = client.report("vid1", "  spam links  ").await.unwrap();
+        assert_eq!(result["ok"], true);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/api/videos/vid1/report");
+        assert_eq!(requests[0].header("X-API-Key"), Some("bottube_sk_test123"));
+        assert_eq!(requests[0].json(), serde_json::json!({ "reason": "spam links" }));
+
+        let err = client.report("vid1", " ").await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::BoTTube(_)));
+        let anonymous = BoTTubeClient::new(None).with_base_url(&server.url());
+        let err = anonymous.report("vid1", "spam").await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::MissingApiKey(_)));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_vote_does_not_retry_502() {
+        let server =
+            MockServer::sequence(vec![MockResponse::json(502, serde_json::json!({}))]).await;
+        let err = mock_client(&server).vote("vid1", 1).await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::BoTTube(_)));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_query_is_percent_encoded() {
+        let server =
+            MockServer::start(|_| MockResponse::json(200, serde_json::json!({ "videos": [] })))
+                .await;
+        mock_client(&server)
+            .search("café?page=9/x", 1)
+            .await
+            .unwrap();
+        assert_eq!(
+            server.requests()[0].path,
+            "/api/search?q=caf%C3%A9%3Fpage%3D9%2Fx&page=1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_channel_lookup() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/api/channels/sophia" => MockResponse::json(
+                200,
+                serde_json::json!({ "channel": {
+                    "agent_name": "sophia",
+                    "display_name": "Sophia",
+                    "subscriber_count": 1200,
+                    "video_count": 3,
+                }}),
+            ),
+            "/api/channels/sophia/videos?page=2" => MockResponse::json(
+                200,
+                serde_json::json!({
+                    "videos": [{ "id": "v3", "title": "Third" }],
+                    "page": 2,
+                    "total_pages": 2,
+                }),
+            ),
+            _ => MockResponse::json(404, serde_json::json!({ "error": "not found" })),
+        })
+        .await;
+        let client = mock_client(&server);
+
+        let channel = client.get_channel("sophia").await.unwrap();
+        assert_eq!(channel.id, "sophia");
+        assert_eq!(channel.name, "Sophia");
+        assert_eq!(channel.subscribers, 1200);
+
+        let uploads = client.channel_videos("sophia", 2).await.unwrap();
+        assert_eq!(uploads.results[0].id, "v3");
+        assert!(!uploads.has_next);
+
+        for err in [
+            client.get_channel("ghost").await.unwrap_err(),
+            client.channel_videos("ghost", 1).await.unwrap_err(),
+        ] {
+            assert!(matches!(err, ClawRtcError::BoTTube(_)), "{err}");
+            assert!(err.to_string().contains("ghost"), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_video_exists() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/api/videos/present" => MockResponse::json(200, serde_json::json!({ "id": "x" })),
+            "/api/videos/broken" => MockResponse::json(500, serde_json::json!({})),
+            "/api/videos/get_only" if req.method == "HEAD" => {
+                MockResponse::json(405, serde_json::json!({}))
+            }
+            "/api/videos/get_only" => MockResponse::json(200, serde_json::json!({})),
+            _ => MockResponse::json(404, serde_json::json!({})),
+        })
+        .await;
+        let client = mock_client(&server);
+
+        assert!(client.video_exists("present").await.unwrap());
+        assert!(!client.video_exists("missing").await.unwrap());
+        let err = client.video_exists("broken").await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::BoTTube(_)), "{err}");
+        assert!(err.to_string().contains("500"), "{err}");
+        let methods: Vec<String> = server.requests().into_iter().map(|r| r.method).collect();
+        assert!(methods.iter().all(|m| m == "HEAD"), "{methods:?}");
+
+        assert!(client.video_exists("get_only").await.unwrap());
+        let requests = server.requests();
+        let fallback: Vec<&str> = requests[requests.len() - 2..]
+            .iter()
+            .map(|r| r.method.as_str())
+            .collect();
+        assert_eq!(fallback, ["HEAD", "GET"]);
+    }
+
+    #[tokio::test]
+    async fn test_upload_resumes_after_failed_part() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("g4-benchmark.mp4");
+        std::fs::write(&path, b"0123456789").unwrap();
+
+        // The connection drops while sending the second part, once
+        let dropped = AtomicBool::new(false);
+        let server = MockServer::start(move |req| match req.path.as_str() {
+            "/api/uploads" => MockResponse::json(201, serde_json::json!({ "upload_id": "u1" })),
+            "/api/uploads/u1/parts/1" if !dropped.swap(true, Ordering::SeqCst) => {
+                MockResponse::json(500, serde_json::json!({ "error": "reset" }))
+            }
+            "/api/uploads/u1/complete" => {
+                MockResponse::json(200, serde_json::json!({ "video_id": "v9" }))
+            }
+            _ => MockResponse::json(200, serde_json::json!({ "ok": true })),
+        })
+        .await;
+        let client = mock_client(&server).with_upload_part_size(4);
+
+        let mut upload = client
+            .start_upload(&path, "G4 benchmark", "Sorting on a PowerBook")
+            .await
+            .unwrap();
+        assert_eq!(upload.part_count(), 3);
+        let err = client.upload_parts(&mut upload).await.unwrap_err();
+        assert!(err.to_string().contains("part 1"), "{err}");
+        assert_eq!(upload.parts_done, 1);
+        assert_eq!(upload.offset(), 4);
+
+        // Resume from a saved copy, as a later process would
+        let mut upload: ChunkedUpload =
+            serde_json::from_str(&serde_json::to_string(&upload).unwrap()).unwrap();
+        let video = client.upload_parts(&mut upload).await.unwrap();
+        assert_eq!(video["video_id"], "v9");
+        assert!(upload.is_complete());
+
+        let requests = server.requests();
+        let parts: Vec<_> = requests.iter().filter(|r| r.method == "PUT").collect();
+        let paths: Vec<_> = parts.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "/api/uploads/u1/parts/0",
+                "/api/uploads/u1/parts/1",
+                "/api/uploads/u1/parts/1",
+                "/api/uploads/u1/parts/2",
+            ]
+        );
+        let ranges: Vec<_> = parts.iter().map(|r| r.header("Content-Range")).collect();
+        assert_eq!(
+            ranges,
+            [
+                Some("bytes 0-3/10"),
+                Some("bytes 4-7/10"),
+                Some("bytes 4-7/10"),
+                Some("bytes 8-9/10"),
+            ]
+        );
+        assert_eq!(parts[2].body, b"4567");
+        assert_eq!(parts[3].body, b"89");
+        assert_eq!(requests[0].json()["size"], 10);
+        assert_eq!(requests.last().unwrap().path, "/api/uploads/u1/complete");
+
+        let mut zero_parts = ChunkedUpload {
+            part_size: 0,
+            parts_done: 0,
+            ..upload
+        };
+        let err = client.upload_parts(&mut zero_parts).await.unwrap_err();
+        assert!(err.to_string().contains("part_size of 0"), "{err}");
+        assert_eq!(server.requests().len(), requests.len());
+    }
 }
@@ -1,15 +1,91 @@
 //! Optional CLI binary for ClawRTC (feature-gated behind `cli`).
 //!
-//! Provides `clawrtc install`, `clawrtc start`, `clawrtc wallet create`, etc.
+//! Provides `clawrtc install`, `clawrtc start`, `clawrtc fingerprint`, `clawrtc wallet create`, etc.
 
 #[cfg(feature = "cli")]
 fn main() {
     use clap::{Parser, Subcommand};
     use colored::Colorize;
+    use openfang_clawrtc::context::WALLET_PASSWORD_ENV;
+    use openfang_clawrtc::{wallet, ClawRtcError, Config, ConfigOverrides, RtcWallet};
+    use std::io::IsTerminal;
+    use std::path::{Path, PathBuf};
+    use zeroize::Zeroizing;
+
+    /// Print `err` and exit with the code for its category.
+    fn fail(context: &str, err: ClawRtcError) -> ! {
+        eprintln!("{} {err}", format!("{context}:").red());
+        std::process::exit(err.exit_code());
+    }
+
+    /// Read a line from the terminal without echoing it (on Unix).
+    fn prompt_password(prompt: &str) -> Zeroizing<String> {
+        let stty = |arg: &str| {
+            cfg!(unix)
+                && std::process::Command::new("stty")
+                    .arg(arg)
+                    .stdin(std::process::Stdio::inherit())
+                    .status()
+                    .is_ok_and(|s| s.success())
+        };
+        eprint!("{prompt}");
+        let hidden = stty("-echo");
+        let mut line = Zeroizing::new(String::new());
+        let read = std::io::stdin().read_line(&mut line);
+        if hidden {
+            stty("echo");
+            eprintln!();
+        }
+        if let Err(e) = read {
+            fail("Failed to read password", e.into());
+        }
+        Zeroizing::new(line.trim_end_matches(['\r', '\n']).to_string())
+    }
+
+    /// Load a wallet, plaintext or encrypted, exiting if it is missing or
+    /// can't be unlocked.
+    ///
+    /// An encrypted keystore's password comes from CLAWRTC_WALLET_PASSWORD,
+    /// or is prompted for when stdin is a terminal.
+    fn load_wallet(path: &Path, hint: &str) -> RtcWallet {
+        let from_env = std::env::var(WALLET_PASSWORD_ENV)
+            .ok()
+            .filter(|p| !p.is_empty())
+            .map(Zeroizing::new);
+        let result = match wallet::load(path, from_env.as_deref().map(String::as_str)) {
+            Err(ClawRtcError::KeystoreDecrypt(_))
+                if from_env.is_none() && std::io::stdin().is_terminal() =>
+            {
+                let password = prompt_password(&format!("Password for {}: ", path.display()));
+                wallet::load(path, Some(&password))
+            }
+            result => result,
+        };
+        result.unwrap_or_else(|e| match e {
+            ClawRtcError::Io(io) if io.kind() == std::io::ErrorKind::NotFound => fail(
+                hint,
+                ClawRtcError::WalletNotFound(path.display().to_string()),
+            ),
+            e => fail("Failed to load wallet", e),
+        })
+    }
 
     #[derive(Parser)]
     #[command(name = "clawrtc", version, about = "RustChain (RTC) miner and wallet CLI")]
     struct Cli {
+        /// Config file (default ~/.clawrtc/clawrtc.toml)
+        #[arg(long, global = true)]
+        config: Option<PathBuf>,
+        /// RustChain node URL, overriding the config file and CLAWRTC_NODE_URL
+        #[arg(long, global = true)]
+        node_url: Option<String>,
+        /// Wallet name, overriding the config file and CLAWRTC_WALLET
+        #[arg(long, global = true)]
+        wallet: Option<String>,
+        /// Node request timeout in seconds, overriding the config file and
+        /// CLAWRTC_NODE_TIMEOUT_SECS
+        #[arg(long, global = true)]
+        node_timeout_secs: Option<u64>,
         #[command(subcommand)]
         command: Commands,
     }
@@ -18,19 +94,41 @@ fn main() {
     enum Commands {
         /// Install the miner to ~/.clawrtc/
         Install {
-            /// Wallet name
-            #[arg(long, default_value = "default")]
-            wallet: String,
             /// Skip prompts
             #[arg(long)]
             yes: bool,
         },
         /// Start the miner
-        Start,
+        Start {
+            /// Stream miner events to this WebSocket URL (ws:// or wss://)
+            #[arg(long)]
+            events_url: Option<String>,
+            /// Add up to this many seconds of random delay at startup and between cycles
+            #[arg(long)]
+            cycle_jitter_secs: Option<u64>,
+            /// Check the balance every this many seconds while waiting for an epoch
+            #[arg(long)]
+            balance_poll_secs: Option<u64>,
+            /// Derive the miner ID from the machine ID and MAC instead of the hostname
+            #[arg(long)]
+            stable_miner_id: bool,
+            /// Entropy samples inflated by preemption: ignore, flag, or discard them
+            #[arg(long, default_value = "ignore")]
+            entropy_preemption: openfang_clawrtc::miner::PreemptionPolicy,
+            /// Discard this many leading entropy samples as warm-up
+            #[arg(long, default_value_t = openfang_clawrtc::miner::DEFAULT_ENTROPY_WARMUP)]
+            entropy_warmup: usize,
+        },
         /// Stop the miner
         Stop,
         /// Show miner status
         Status,
+        /// Run the hardware fingerprint checks once and print the report
+        Fingerprint {
+            /// Print the full report as JSON instead of the summary table
+            #[arg(long)]
+            json: bool,
+        },
         /// Wallet management
         Wallet {
             #[command(subcommand)]
@@ -55,45 +153,66 @@ fn main() {
     }
 
     let cli = Cli::parse();
+    let overrides = ConfigOverrides {
+        node_url: cli.node_url,
+        wallet: cli.wallet,
+        node_timeout_secs: cli.node_timeout_secs,
+    };
+    let config = match &cli.config {
+        Some(path) => Config::load_from(path, &overrides),
+        None => Config::load(&overrides),
+    }
+    .unwrap_or_else(|e| fail("Config error", e));
 
     let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
 
     match cli.command {
-        Commands::Install { wallet, yes } => {
+        Commands::Install { yes } => {
             println!("{}", "ClawRTC Installer".green().bold());
-            println!("Wallet: {wallet}");
+            println!("Wallet: {}", config.wallet);
             if !yes {
                 println!("Use --yes to skip prompts");
             }
             // Create wallet if needed
-            let path = dirs::home_dir()
-                .unwrap_or_default()
-                .join(".clawrtc/wallets")
-                .join(format!("{wallet}.json"));
+            let path = config.wallet_path().unwrap_or_else(|e| fail("Config error", e));
             if !path.exists() {
-                let w = openfang_clawrtc::RtcWallet::generate();
-                w.save_plaintext(&path).expect("Failed to save wallet");
+                let w = RtcWallet::generate();
+                if let Err(e) = w.save_plaintext(&path) {
+                    fail("Failed to save wallet", e);
+                }
                 println!("{} {}", "Wallet created:".green(), w.address());
             } else {
-                let w = openfang_clawrtc::RtcWallet::from_file(&path).expect("Failed to load wallet");
+                let w = load_wallet(&path, "Failed to load wallet");
                 println!("{} {}", "Wallet exists:".yellow(), w.address());
             }
             println!("{}", "Installation complete.".green());
         }
-        Commands::Start => {
+        Commands::Start {
+            events_url,
+            cycle_jitter_secs,
+            balance_poll_secs,
+            stable_miner_id,
+            entropy_preemption,
+            entropy_warmup,
+        } => {
             println!("{}", "Starting miner...".green());
-            let path = dirs::home_dir()
-                .unwrap_or_default()
-                .join(".clawrtc/wallets/default.json");
-            let wallet = openfang_clawrtc::RtcWallet::from_file(&path)
-                .expect("No wallet found. Run: clawrtc install");
+            let path = config.wallet_path().unwrap_or_else(|e| fail("Config error", e));
+            let wallet = load_wallet(&path, "No wallet found. Run: clawrtc install");
 
-            let config = openfang_clawrtc::miner::MinerConfig {
-                node_url: openfang_clawrtc::DEFAULT_NODE_URL.to_string(),
+            let miner_config = openfang_clawrtc::miner::MinerConfig {
+                node_url: config.node_url.clone(),
+                node_timeout: Some(config.node_timeout()),
                 wallet,
                 run_fingerprints: true,
+                entropy_warmup_discard: entropy_warmup,
+                entropy_preemption,
+                events_url,
+                cycle_jitter: cycle_jitter_secs.map(std::time::Duration::from_secs),
+                balance_poll: balance_poll_secs.map(std::time::Duration::from_secs),
+                stable_miner_id,
             };
-            let mut miner = openfang_clawrtc::miner::Miner::new(config).expect("Miner init failed");
+            let mut miner = openfang_clawrtc::miner::Miner::new(miner_config)
+                .unwrap_or_else(|e| fail("Miner init failed", e));
             let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
 
             let cancel_clone = cancel.clone();
@@ -106,7 +225,7 @@ fn main() {
                 });
 
                 if let Err(e) = miner.mine_loop(cancel_clone).await {
-                    eprintln!("{} {e}", "Mining error:".red());
+                    fail("Mining error", e);
                 }
             });
         }
@@ -115,7 +234,7 @@ fn main() {
         }
         Commands::Status => {
             rt.block_on(async {
-                let client = openfang_clawrtc::RustChainClient::default_node();
+                let client = config.rustchain_client();
                 match client.health().await {
                     Ok(h) => {
                         println!("{} {}", "Node:".green(), if h.ok { "healthy" } else { "unhealthy" });
@@ -123,55 +242,61 @@ fn main() {
                             println!("Version: {v}");
                         }
                     }
-                    Err(e) => println!("{} {e}", "Error:".red()),
+                    Err(e) => fail("Error", e),
                 }
             });
         }
+        Commands::Fingerprint { json } => {
+            if !json {
+                println!("{}", "Running fingerprint checks...".green());
+            }
+            let report = openfang_clawrtc::fingerprint::validate_all_checks();
+            println!("{}", report.render(json));
+            if !report.all_passed {
+                std::process::exit(ClawRtcError::EXIT_ATTESTATION);
+            }
+        }
         Commands::Wallet { action } => match action {
             WalletAction::Create { force } => {
-                let path = dirs::home_dir()
-                    .unwrap_or_default()
-                    .join(".clawrtc/wallets/default.json");
+                let path = config.wallet_path().unwrap_or_else(|e| fail("Config error", e));
                 if path.exists() && !force {
                     eprintln!("Wallet already exists. Use --force to overwrite.");
-                    std::process::exit(1);
+                    std::process::exit(ClawRtcError::EXIT_INVALID_INPUT);
+                }
+                let w = RtcWallet::generate();
+                if let Err(e) = w.save_plaintext(&path) {
+                    fail("Failed to save wallet", e);
                 }
-                let w = openfang_clawrtc::RtcWallet::generate();
-                w.save_plaintext(&path).expect("Failed to save");
                 println!("{} {}", "Address:".green(), w.address());
                 println!("{} {}", "Public Key:".green(), w.public_key_hex());
                 println!("Saved to: {}", path.display());
             }
             WalletAction::Show => {
-                let path = dirs::home_dir()
-                    .unwrap_or_default()
-                    .join(".clawrtc/wallets/default.json");
-                let w = openfang_clawrtc::RtcWallet::from_file(&path)
-                    .expect("No wallet found. Run: clawrtc wallet create");
+                let path = config.wallet_path().unwrap_or_else(|e| fail("Config error", e));
+                let w = load_wallet(&path, "No wallet found. Run: clawrtc wallet create");
                 println!("{} {}", "Address:".green(), w.address());
                 println!("{} {}", "Public Key:".green(), w.public_key_hex());
 
                 rt.block_on(async {
-                    let client = openfang_clawrtc::RustChainClient::default_node();
-                    match client.balance(w.address()).await {
+                    let client = config.rustchain_client();
+                    match client.balance_decimal(w.address()).await {
                         Ok(bal) => println!("{} {} RTC", "Balance:".green(), bal),
-                        Err(_) => println!("Balance: (offline)"),
+                        Err(e) => println!("Balance: (offline: {e})"),
                     }
                 });
             }
             WalletAction::Export { output } => {
-                let path = dirs::home_dir()
-                    .unwrap_or_default()
-                    .join(".clawrtc/wallets/default.json");
-                let w = openfang_clawrtc::RtcWallet::from_file(&path)
-                    .expect("No wallet found");
+                let path = config.wallet_path().unwrap_or_else(|e| fail("Config error", e));
+                let w = load_wallet(&path, "No wallet found");
                 let export = serde_json::json!({
                     "address": w.address(),
                     "public_key": w.public_key_hex(),
                 });
                 let json = serde_json::to_string_pretty(&export).unwrap();
                 if let Some(out) = output {
-                    std::fs::write(&out, &json).expect("Failed to write export");
+                    if let Err(e) = std::fs::write(&out, &json) {
+                        fail("Failed to write export", e.into());
+                    }
                     println!("Exported to {out}");
                 } else {
                     println!("{json}");
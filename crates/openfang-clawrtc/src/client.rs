@@ -1,19 +1,122 @@
 //! Async HTTP client for the RustChain node API.
 
+use crate::amount::{self, Decimal};
+use crate::clock::{self, Clock};
 use crate::error::{ClawRtcError, ClawRtcResult};
+use crate::fingerprint::FingerprintReference;
+use crate::http::{
+    expect_json, read_json, read_json_array, read_text, urlencoded, HttpOptions, RequestLimit,
+    TlsVersion, DEFAULT_MAX_BODY_BYTES,
+};
+use futures::{Stream, TryStreamExt};
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 use tracing::debug;
 
 /// Default RustChain node URL.
+///
+/// It serves a self-signed certificate, so [`RustChainClient::for_node`] and
+/// [`RustChainClient::default_node`] connect to it without verifying TLS.
 pub const DEFAULT_NODE_URL: &str = "https://bulbous-bouffant.metalseed.net";
 
 /// RustChain block time in seconds (10 minutes).
 pub const BLOCK_TIME: u64 = 600;
 
+/// First delay between confirmation polls; doubles up to [`CONFIRMATION_POLL_MAX`].
+const CONFIRMATION_POLL_INITIAL: Duration = Duration::from_secs(2);
+
+/// Upper bound on the delay between confirmation polls.
+const CONFIRMATION_POLL_MAX: Duration = Duration::from_secs(30);
+
+/// Deserializers that tolerate numbers encoded as JSON strings.
+///
+/// Node versions differ in whether numeric fields like `balance_rtc` come back
+/// as `1.5` or `"1.5"`; both are accepted. Empty strings are treated as absent.
+mod lenient {
+    use crate::amount::{self, Decimal};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer};
+    use serde_json::value::RawValue;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumOrString<T> {
+        Num(T),
+        Str(String),
+    }
+
+    fn parse<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        match Option::<NumOrString<T>>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(NumOrString::Num(n)) => Ok(Some(n)),
+            Some(NumOrString::Str(s)) if s.trim().is_empty() => Ok(None),
+            Some(NumOrString::Str(s)) => s
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|e| D::Error::custom(format!("invalid number {s:?}: {e}"))),
+        }
+    }
+
+    pub fn opt_f64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+        parse(deserializer)
+    }
+
+    pub fn opt_i64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+        parse(deserializer)
+    }
+
+    /// Parses the number's source text, so amounts aren't rounded through
+    /// `f64` on the way. Only works with `serde_json` deserializers.
+    pub fn opt_decimal<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Decimal>, D::Error> {
+        let Some(raw) = Option::<Box<RawValue>>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        let text = if raw.get().starts_with('"') {
+            serde_json::from_str::<String>(raw.get()).map_err(D::Error::custom)?
+        } else {
+            raw.get().to_string()
+        };
+        if text.trim().is_empty() {
+            return Ok(None);
+        }
+        amount::parse(&text)
+            .map(Some)
+            .map_err(|e| D::Error::custom(format!("invalid number {text:?}: {e}")))
+    }
+}
+
 /// Response from `/attest/challenge`.
 #[derive(Debug, Deserialize)]
 pub struct ChallengeResponse {
     pub nonce: String,
+    /// Unix time (seconds) the nonce was issued, if the node reports it.
+    #[serde(default, deserialize_with = "lenient::opt_i64")]
+    pub issued_at: Option<i64>,
+    /// Unix time (seconds) after which the node rejects the nonce.
+    #[serde(default, deserialize_with = "lenient::opt_i64")]
+    pub expires_at: Option<i64>,
+}
+
+impl ChallengeResponse {
+    /// Whether the nonce has expired. Nonces without an expiry never expire.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(chrono::Utc::now().timestamp())
+    }
+
+    /// Whether the nonce has expired as of `now` (Unix seconds).
+    pub fn is_expired_at(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|exp| now >= exp)
+    }
 }
 
 /// Response from `/attest/submit`.
@@ -28,29 +131,116 @@ pub struct AttestResponse {
 #[derive(Debug, Deserialize)]
 pub struct EnrollResponse {
     pub ok: bool,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient::opt_i64")]
     pub epoch: Option<i64>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient::opt_f64")]
     pub weight: Option<f64>,
     #[serde(default)]
     pub error: Option<String>,
 }
 
+impl EnrollResponse {
+    /// Why the node refused the enrollment, or `None` if it was accepted.
+    pub fn rejection(&self) -> Option<EnrollmentError> {
+        (!self.ok).then(|| EnrollmentError::from_message(self.error.as_deref()))
+    }
+}
+
+/// Why the node refused an enrollment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnrollmentError {
+    /// The miner is already enrolled in the current epoch — typically a retry
+    /// of an enrollment that landed. Safe to treat as success.
+    AlreadyEnrolled,
+    /// Any other refusal, with the node's message if it gave one.
+    Rejected(Option<String>),
+}
+
+impl EnrollmentError {
+    /// Classify a refusal by the node's message.
+    pub fn from_message(message: Option<&str>) -> Self {
+        let already = message.is_some_and(|m| {
+            let m = m.to_ascii_lowercase();
+            m.contains("already enrolled") || m.contains("already_enrolled")
+        });
+        if already {
+            Self::AlreadyEnrolled
+        } else {
+            Self::Rejected(message.map(str::to_string))
+        }
+    }
+}
+
+/// Response from `/wallet/transfer/signed`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "TransferResponseBody")]
+pub struct TransferResponse {
+    /// Whether the node accepted the transfer. Nodes that leave `ok` out
+    /// report acceptance with a `tx_hash` and no `error`.
+    pub ok: bool,
+    pub tx_hash: Option<String>,
+    /// The sender's balance after the transfer, if the node reports it.
+    #[serde(serialize_with = "rust_decimal::serde::float_option::serialize")]
+    pub new_balance: Option<Decimal>,
+    pub error: Option<String>,
+}
+
+/// [`TransferResponse`] as sent, with `ok` possibly missing.
+#[derive(Deserialize)]
+struct TransferResponseBody {
+    #[serde(default)]
+    ok: Option<bool>,
+    #[serde(default)]
+    tx_hash: Option<String>,
+    #[serde(default, deserialize_with = "lenient::opt_decimal")]
+    new_balance: Option<Decimal>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl From<TransferResponseBody> for TransferResponse {
+    fn from(body: TransferResponseBody) -> Self {
+        Self {
+            ok: body
+                .ok
+                .unwrap_or(body.tx_hash.is_some() && body.error.is_none()),
+            tx_hash: body.tx_hash,
+            new_balance: body.new_balance,
+            error: body.error,
+        }
+    }
+}
+
 /// Response from `/health`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthResponse {
     pub ok: bool,
     #[serde(default)]
     pub version: Option<String>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient::opt_f64")]
     pub uptime_s: Option<f64>,
 }
 
 /// Balance information from `/balance/{wallet}` or `/api/balance`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BalanceResponse {
-    #[serde(default)]
-    pub balance_rtc: Option<f64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient::opt_decimal",
+        serialize_with = "rust_decimal::serde::float_option::serialize"
+    )]
+    pub balance_rtc: Option<Decimal>,
+}
+
+/// Transfer fee from `/wallet/fee`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeResponse {
+    #[serde(
+        default,
+        deserialize_with = "lenient::opt_decimal",
+        serialize_with = "rust_decimal::serde::float_option::serialize"
+    )]
+    pub fee_rtc: Option<Decimal>,
 }
 
 /// A miner record from `/api/miners`.
@@ -58,6 +248,9 @@ pub struct BalanceResponse {
 pub struct MinerInfo {
     #[serde(default)]
     pub miner: Option<String>,
+    /// The miner's ID on nodes that send `miner_id` instead of `miner`.
+    #[serde(default)]
+    pub miner_id: Option<String>,
     #[serde(default)]
     pub device_arch: Option<String>,
     #[serde(default)]
@@ -66,63 +259,402 @@ pub struct MinerInfo {
     pub ts_ok: Option<i64>,
 }
 
+impl MinerInfo {
+    /// The miner's ID, from `miner` or else `miner_id`.
+    pub fn id(&self) -> Option<&str> {
+        self.miner.as_deref().or(self.miner_id.as_deref())
+    }
+}
+
+/// A past transfer from `/api/transactions`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxRecord {
+    #[serde(default, alias = "from_address")]
+    pub from: String,
+    #[serde(default, alias = "to_address")]
+    pub to: String,
+    #[serde(
+        default,
+        deserialize_with = "lenient::opt_decimal",
+        serialize_with = "rust_decimal::serde::float_option::serialize"
+    )]
+    pub amount_rtc: Option<Decimal>,
+    #[serde(default)]
+    pub memo: Option<String>,
+    #[serde(default, deserialize_with = "lenient::opt_i64")]
+    pub nonce: Option<i64>,
+    /// Unix time (seconds) of the transfer.
+    #[serde(default, deserialize_with = "lenient::opt_i64")]
+    pub ts: Option<i64>,
+    /// Direction relative to the wallet that was queried. Filled in from
+    /// `from`/`to` when the node leaves it out.
+    #[serde(default)]
+    pub direction: Option<TxDirection>,
+}
+
+/// Whether a transfer left or reached the queried wallet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TxDirection {
+    #[serde(alias = "out", alias = "outgoing")]
+    Sent,
+    #[serde(alias = "in", alias = "incoming")]
+    Received,
+}
+
+/// `/api/transactions` bodies that wrap the list in an object.
+#[derive(Deserialize)]
+struct TxPage {
+    transactions: Vec<TxRecord>,
+}
+
+/// On-chain status of a submitted transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmationStatus {
+    /// Not yet confirmed (or not yet visible to the node).
+    Pending,
+    /// Included in a block.
+    Confirmed,
+    /// Rejected or dropped by the network.
+    Failed,
+}
+
+impl ConfirmationStatus {
+    /// Interpret a `/api/transactions/{hash}` response body.
+    fn from_body(body: &serde_json::Value) -> Self {
+        if let Some(status) = body["status"].as_str() {
+            return match status.to_lowercase().as_str() {
+                "confirmed" | "success" | "finalized" => Self::Confirmed,
+                "failed" | "rejected" | "dropped" => Self::Failed,
+                _ => Self::Pending,
+            };
+        }
+        if body["confirmed"].as_bool() == Some(true) {
+            Self::Confirmed
+        } else {
+            Self::Pending
+        }
+    }
+}
+
+/// Node API protocol revision, derived from the version reported by `/health`.
+///
+/// Known differences between revisions:
+///
+/// | Endpoint | `V1` (node < 2.0)  | `V2` (node >= 2.0)        |
+/// |----------|--------------------|---------------------------|
+/// | Balance  | `/balance/{wallet}` | `/api/balance?wallet=…` |
+///
+/// All other endpoints are the same in both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeProtocol {
+    V1,
+    V2,
+}
+
+impl NodeProtocol {
+    /// The newest protocol this client speaks, assumed when a node does not
+    /// report a version.
+    pub const LATEST: Self = Self::V2;
+
+    /// Map a node version string such as `"1.4.2"` or `"v2.0"` to a protocol.
+    ///
+    /// Unparseable versions are treated as [`NodeProtocol::LATEST`].
+    pub fn from_version(version: &str) -> Self {
+        let major = version
+            .trim()
+            .trim_start_matches(['v', 'V'])
+            .split(|c: char| !c.is_ascii_digit())
+            .next()
+            .and_then(|m| m.parse::<u64>().ok());
+        match major {
+            Some(0 | 1) => Self::V1,
+            _ => Self::LATEST,
+        }
+    }
+}
+
+/// Optional node features, from [`RustChainClient::capabilities`].
+///
+/// The core endpoints (health, attestation, enrollment, balance, transfers)
+/// are always assumed; these may be missing on a given node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodeCapabilities {
+    /// Test-RTC faucet (`/faucet`).
+    pub faucet: bool,
+    /// Miner leaderboard (`/api/leaderboard`).
+    pub leaderboard: bool,
+    /// Current epoch details (`/epoch`).
+    #[serde(alias = "epoch")]
+    pub epoch_info: bool,
+    /// Transfer history and status lookups (`/api/transactions`).
+    pub transactions: bool,
+    /// Live event stream (`/ws`).
+    #[serde(alias = "ws")]
+    pub websocket: bool,
+}
+
+/// Accessor for one [`NodeCapabilities`] flag.
+type CapabilityFlag = fn(&mut NodeCapabilities) -> &mut bool;
+
+impl NodeCapabilities {
+    /// Endpoints probed when the node has no `/capabilities` endpoint.
+    const PROBES: [(&'static str, CapabilityFlag); 5] = [
+        ("/faucet", |c| &mut c.faucet),
+        ("/api/leaderboard", |c| &mut c.leaderboard),
+        ("/epoch", |c| &mut c.epoch_info),
+        ("/api/transactions", |c| &mut c.transactions),
+        ("/ws", |c| &mut c.websocket),
+    ];
+
+    /// Read a `/capabilities` body: a list of feature names, either bare or
+    /// under `features`, or an object of feature flags, either bare or under
+    /// `capabilities`. Unknown features are ignored.
+    fn from_body(body: serde_json::Value) -> ClawRtcResult<Self> {
+        let body = match body {
+            serde_json::Value::Object(mut map) => map
+                .remove("features")
+                .or_else(|| map.remove("capabilities"))
+                .unwrap_or(serde_json::Value::Object(map)),
+            other => other,
+        };
+        if let serde_json::Value::Array(names) = body {
+            let flags = names
+                .iter()
+                .filter_map(|name| Some((name.as_str()?.to_string(), true.into())))
+                .collect();
+            return Ok(serde_json::from_value(serde_json::Value::Object(flags))?);
+        }
+        Ok(serde_json::from_value(body)?)
+    }
+}
+
 /// Async client for the RustChain node.
+#[derive(Clone)]
 pub struct RustChainClient {
     http: reqwest::Client,
+    http_options: HttpOptions,
     base_url: String,
+    max_body_bytes: usize,
+    /// Set from the first successful health check, or pinned by the caller.
+    protocol: OnceLock<NodeProtocol>,
+    /// Fee to assume instead of asking the node.
+    transfer_fee: Option<Decimal>,
+    /// Set by the first successful [`RustChainClient::capabilities`] call.
+    capabilities: tokio::sync::OnceCell<NodeCapabilities>,
+    clock: Arc<dyn Clock>,
 }
 
 impl RustChainClient {
     /// Create a new client pointing at the given node URL.
+    ///
+    /// The node's TLS certificate is verified; see
+    /// [`RustChainClient::new_insecure`] for nodes with self-signed ones.
     pub fn new(base_url: &str) -> Self {
-        let http = reqwest::Client::builder()
-            .danger_accept_invalid_certs(true) // Self-signed certs on nodes
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .expect("Failed to build HTTP client");
+        let http_options = HttpOptions::new(std::time::Duration::from_secs(30));
         Self {
-            http,
+            http: http_options.build(),
+            http_options,
             base_url: base_url.trim_end_matches('/').to_string(),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            protocol: OnceLock::new(),
+            transfer_fee: None,
+            capabilities: tokio::sync::OnceCell::new(),
+            clock: clock::system(),
         }
     }
 
+    /// Create a client that accepts any TLS certificate from the node, for
+    /// nodes serving a self-signed one.
+    ///
+    /// Traffic is still encrypted but the node is not authenticated: anyone
+    /// who can intercept the connection can impersonate it.
+    pub fn new_insecure(base_url: &str) -> Self {
+        Self::new(base_url).with_accept_invalid_certs(true)
+    }
+
+    /// Create a client in the TLS mode `base_url` needs: insecure for
+    /// [`DEFAULT_NODE_URL`], whose certificate is self-signed, and verified
+    /// for every other node.
+    pub fn for_node(base_url: &str) -> Self {
+        let client = Self::new(base_url);
+        let self_signed = client.base_url == DEFAULT_NODE_URL;
+        client.with_accept_invalid_certs(self_signed)
+    }
+
+    /// Accept invalid TLS certificates (self-signed, expired, or for another
+    /// host) from the node.
+    pub fn with_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.http_options.accept_invalid_certs = accept;
+        self.http = self.http_options.build();
+        self
+    }
+
+    /// Use `protocol` instead of detecting it from the node's version.
+    pub fn with_protocol(self, protocol: NodeProtocol) -> Self {
+        let _ = self.protocol.set(protocol);
+        self
+    }
+
+    /// The protocol used to build endpoint paths.
+    ///
+    /// Until [`RustChainClient::connect`] or [`RustChainClient::health`] has
+    /// succeeded, this is [`NodeProtocol::LATEST`].
+    pub fn protocol(&self) -> NodeProtocol {
+        self.protocol.get().copied().unwrap_or(NodeProtocol::LATEST)
+    }
+
+    /// Check the node is reachable and detect its protocol from its version.
+    pub async fn connect(&self) -> ClawRtcResult<NodeProtocol> {
+        self.health().await?;
+        Ok(self.protocol())
+    }
+
+    /// Cap the size of response bodies the client will buffer.
+    pub fn with_max_body_bytes(mut self, limit: usize) -> Self {
+        self.max_body_bytes = limit;
+        self
+    }
+
+    /// Give up on a request after `timeout` (default 30 seconds).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.http_options.timeout = timeout;
+        self.http = self.http_options.build();
+        self
+    }
+
+    /// Refuse TLS versions older than `version` (e.g. require TLS 1.3).
+    ///
+    /// Independent of certificate checking: with
+    /// [`with_accept_invalid_certs`](Self::with_accept_invalid_certs) the node
+    /// still isn't authenticated, but the handshake can't be downgraded.
+    pub fn with_min_tls_version(mut self, version: TlsVersion) -> Self {
+        self.http_options.min_tls_version = Some(version);
+        self.http = self.http_options.build();
+        self
+    }
+
+    /// Resolve `host` to `addr` instead of asking system DNS.
+    ///
+    /// The URL still names `host`, so TLS is negotiated for it; the port comes
+    /// from the URL, not from `addr`.
+    pub fn with_resolve(mut self, host: &str, addr: SocketAddr) -> Self {
+        self.http_options.resolve.push((host.to_string(), addr));
+        self.http = self.http_options.build();
+        self
+    }
+
+    /// Share `limit` with other clients to bound their combined in-flight
+    /// requests.
+    pub fn with_request_limit(mut self, limit: RequestLimit) -> Self {
+        self.http_options.request_limit = Some(limit);
+        self
+    }
+
+    /// Use a fixed transfer fee instead of querying the node.
+    pub fn with_transfer_fee(mut self, fee_rtc: Decimal) -> Self {
+        self.transfer_fee = Some(fee_rtc);
+        self
+    }
+
+    /// Time [`RustChainClient::wait_for_confirmation`] polls with `clock`.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Create a client using the default node URL.
+    ///
+    /// Certificates are not verified, as the default node's is self-signed.
     pub fn default_node() -> Self {
-        Self::new(DEFAULT_NODE_URL)
+        Self::for_node(DEFAULT_NODE_URL)
     }
 
     /// Check node health.
+    ///
+    /// The first successful check records the node's protocol (see
+    /// [`NodeProtocol`]); later checks do not change it.
     pub async fn health(&self) -> ClawRtcResult<HealthResponse> {
         let url = format!("{}/health", self.base_url);
         debug!(url, "Checking node health");
-        let resp = self.http.get(&url).send().await?;
+        let resp = self.http_options.send(self.http.get(&url)).await?;
         if !resp.status().is_success() {
             return Err(ClawRtcError::NodeApi(format!(
                 "Health check failed: HTTP {}",
                 resp.status()
             )));
         }
-        Ok(resp.json().await?)
+        let health: HealthResponse = read_json(resp, self.max_body_bytes).await?;
+        let protocol = health
+            .version
+            .as_deref()
+            .map_or(NodeProtocol::LATEST, NodeProtocol::from_version);
+        if self.protocol.set(protocol).is_ok() {
+            debug!(version = ?health.version, ?protocol, "Detected node protocol");
+        }
+        Ok(health)
+    }
+
+    /// Which optional features the node supports.
+    ///
+    /// Asks the node's `/capabilities` endpoint. Nodes without one (404) are
+    /// probed instead: a feature is present unless its endpoint returns 404.
+    /// The first successful answer is cached for the life of the client.
+    pub async fn capabilities(&self) -> ClawRtcResult<NodeCapabilities> {
+        self.capabilities
+            .get_or_try_init(|| self.discover_capabilities())
+            .await
+            .copied()
+    }
+
+    async fn discover_capabilities(&self) -> ClawRtcResult<NodeCapabilities> {
+        let url = format!("{}/capabilities", self.base_url);
+        debug!(url, "Discovering node capabilities");
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        if resp.status().is_success() {
+            return NodeCapabilities::from_body(read_json(resp, self.max_body_bytes).await?);
+        }
+        if resp.status() != reqwest::StatusCode::NOT_FOUND {
+            return Err(ClawRtcError::NodeApi(format!(
+                "Capabilities lookup failed: HTTP {}",
+                resp.status()
+            )));
+        }
+
+        let probes = NodeCapabilities::PROBES.iter().map(|(path, _)| async move {
+            let url = format!("{}{}", self.base_url, path);
+            let resp = self.http_options.send(self.http.get(&url)).await?;
+            Ok::<_, ClawRtcError>(resp.status() != reqwest::StatusCode::NOT_FOUND)
+        });
+        let found = futures::future::join_all(probes).await;
+        let mut capabilities = NodeCapabilities::default();
+        for ((_, flag), present) in NodeCapabilities::PROBES.iter().zip(found) {
+            *flag(&mut capabilities) = present?;
+        }
+        debug!(?capabilities, "Probed node capabilities");
+        Ok(capabilities)
     }
 
     /// Get an attestation challenge nonce.
     pub async fn challenge(&self) -> ClawRtcResult<ChallengeResponse> {
         let url = format!("{}/attest/challenge", self.base_url);
         debug!(url, "Requesting attestation challenge");
-        let resp = self
+        let req = self
             .http
             .post(&url)
-            .json(&serde_json::json!({}))
-            .send()
-            .await?;
+            .json(&serde_json::json!({}));
+        let resp = self.http_options.send(req).await?;
         if !resp.status().is_success() {
             let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
+            let body = read_text(resp, self.max_body_bytes).await.unwrap_or_default();
             return Err(ClawRtcError::NodeApi(format!(
                 "Challenge failed: HTTP {status}: {body}"
             )));
         }
-        Ok(resp.json().await?)
+        read_json(resp, self.max_body_bytes).await
     }
 
     /// Submit an attestation payload.
@@ -132,15 +664,15 @@ impl RustChainClient {
     ) -> ClawRtcResult<AttestResponse> {
         let url = format!("{}/attest/submit", self.base_url);
         debug!(url, "Submitting attestation");
-        let resp = self.http.post(&url).json(payload).send().await?;
+        let resp = self.http_options.send(self.http.post(&url).json(payload)).await?;
         if !resp.status().is_success() {
             let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
+            let body = read_text(resp, self.max_body_bytes).await.unwrap_or_default();
             return Err(ClawRtcError::AttestationRejected(format!(
                 "HTTP {status}: {body}"
             )));
         }
-        let ar: AttestResponse = resp.json().await?;
+        let ar: AttestResponse = read_json(resp, self.max_body_bytes).await?;
         if !ar.ok {
             return Err(ClawRtcError::AttestationRejected(
                 ar.error.unwrap_or_else(|| "unknown".into()),
@@ -153,60 +685,344 @@ impl RustChainClient {
     pub async fn enroll(&self, payload: &serde_json::Value) -> ClawRtcResult<EnrollResponse> {
         let url = format!("{}/epoch/enroll", self.base_url);
         debug!(url, "Enrolling in epoch");
-        let resp = self.http.post(&url).json(payload).send().await?;
+        let resp = self.http_options.send(self.http.post(&url).json(payload)).await?;
         if !resp.status().is_success() {
             let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
+            let body = read_text(resp, self.max_body_bytes).await.unwrap_or_default();
             return Err(ClawRtcError::NodeApi(format!(
                 "Enroll failed: HTTP {status}: {body}"
             )));
         }
-        Ok(resp.json().await?)
+        read_json(resp, self.max_body_bytes).await
     }
 
-    /// Get wallet balance.
+    /// Get wallet balance, reading any non-success HTTP status as `0.0`.
+    ///
+    /// Lenient so a flaky node doesn't interrupt the mining loop; anything
+    /// reporting a balance to a user should call
+    /// [`RustChainClient::balance_strict`] instead.
     pub async fn balance(&self, wallet: &str) -> ClawRtcResult<f64> {
-        let url = format!("{}/api/balance?wallet={}", self.base_url, wallet);
+        match self.balance_strict(wallet).await {
+            Err(ClawRtcError::NodeApi(e)) => {
+                debug!(error = %e, "Balance lookup failed; reporting 0");
+                Ok(0.0)
+            }
+            result => result,
+        }
+    }
+
+    /// Get wallet balance, failing with [`ClawRtcError::NodeApi`] on a
+    /// non-success HTTP status so a down node isn't mistaken for an empty
+    /// wallet.
+    pub async fn balance_strict(&self, wallet: &str) -> ClawRtcResult<f64> {
+        Ok(amount::to_f64(self.balance_decimal(wallet).await?))
+    }
+
+    /// Get the exact wallet balance, failing like
+    /// [`RustChainClient::balance_strict`].
+    ///
+    /// For showing balances: an `f64` can't hold every amount the node
+    /// reports.
+    pub async fn balance_decimal(&self, wallet: &str) -> ClawRtcResult<Decimal> {
+        let wallet = urlencoded(wallet);
+        let url = match self.protocol() {
+            NodeProtocol::V1 => format!("{}/balance/{}", self.base_url, wallet),
+            NodeProtocol::V2 => format!("{}/api/balance?wallet={}", self.base_url, wallet),
+        };
         debug!(url, "Checking balance");
-        let resp = self.http.get(&url).send().await?;
+        let resp = self.http_options.send(self.http.get(&url)).await?;
         if !resp.status().is_success() {
-            return Ok(0.0);
+            return Err(ClawRtcError::NodeApi(format!(
+                "Balance lookup failed: HTTP {}",
+                resp.status()
+            )));
+        }
+        let br: BalanceResponse = read_json(resp, self.max_body_bytes).await?;
+        Ok(br.balance_rtc.unwrap_or_default())
+    }
+
+    /// Fee charged per transfer, in RTC.
+    ///
+    /// Uses the fee set with [`RustChainClient::with_transfer_fee`] if any,
+    /// otherwise asks the node. Nodes without a fee endpoint (404) charge no
+    /// fee.
+    pub async fn transfer_fee(&self) -> ClawRtcResult<Decimal> {
+        if let Some(fee) = self.transfer_fee {
+            return Ok(fee);
+        }
+        let url = format!("{}/wallet/fee", self.base_url);
+        debug!(url, "Checking transfer fee");
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Decimal::ZERO);
+        }
+        if !resp.status().is_success() {
+            return Err(ClawRtcError::NodeApi(format!(
+                "Fee lookup failed: HTTP {}",
+                resp.status()
+            )));
         }
-        let br: BalanceResponse = resp.json().await?;
-        Ok(br.balance_rtc.unwrap_or(0.0))
+        let fr: FeeResponse = read_json(resp, self.max_body_bytes).await?;
+        Ok(fr.fee_rtc.unwrap_or_default())
     }
 
     /// List active miners.
     pub async fn miners(&self) -> ClawRtcResult<Vec<MinerInfo>> {
         let url = format!("{}/api/miners", self.base_url);
         debug!(url, "Listing miners");
-        let resp = self.http.get(&url).send().await?;
+        let resp = self.http_options.send(self.http.get(&url)).await?;
         if !resp.status().is_success() {
             return Err(ClawRtcError::NodeApi(format!(
                 "Miners list failed: HTTP {}",
                 resp.status()
             )));
         }
-        Ok(resp.json().await?)
+        read_json(resp, self.max_body_bytes).await
+    }
+
+    /// List active miners, yielding each record as it is parsed.
+    ///
+    /// Unlike [`miners`](Self::miners), the response is never held in memory
+    /// as a whole, and the size cap from
+    /// [`with_max_body_bytes`](Self::with_max_body_bytes) applies to each
+    /// record rather than the full list. Nothing is requested until the
+    /// stream is first polled; it ends after the first error.
+    pub fn miners_stream(&self) -> impl Stream<Item = ClawRtcResult<MinerInfo>> + Send + 'static {
+        let url = format!("{}/api/miners", self.base_url);
+        let req = self.http.get(&url);
+        let http_options = self.http_options.clone();
+        let limit = self.max_body_bytes;
+        futures::stream::once(async move {
+            debug!(url, "Streaming miners");
+            let resp = http_options.send(req).await?;
+            if !resp.status().is_success() {
+                return Err(ClawRtcError::NodeApi(format!(
+                    "Miners list failed: HTTP {}",
+                    resp.status()
+                )));
+            }
+            Ok(read_json_array(expect_json(resp).await?, limit))
+        })
+        .try_flatten()
+    }
+
+    /// List active miners with one record per miner, sorted by miner ID.
+    ///
+    /// The node reports a record per attestation, so a miner that attested
+    /// several times appears several times. The record with the latest
+    /// `ts_ok` is kept. Records without a miner ID cannot be matched and are
+    /// kept as-is after the identified ones.
+    pub async fn miners_deduped(&self) -> ClawRtcResult<Vec<MinerInfo>> {
+        Ok(dedup_miners(self.miners().await?))
     }
 
     /// Submit a signed transfer.
     pub async fn transfer_signed(
         &self,
         payload: &serde_json::Value,
+    ) -> ClawRtcResult<TransferResponse> {
+        let resp = self.post_transfer(payload).await?;
+        self.read_transfer_response(resp).await
+    }
+
+    /// Submit a signed transfer, returning the node's response untouched.
+    ///
+    /// For fields [`TransferResponse`] does not cover.
+    pub async fn transfer_signed_raw(
+        &self,
+        payload: &serde_json::Value,
     ) -> ClawRtcResult<serde_json::Value> {
+        let resp = self.post_transfer(payload).await?;
+        self.read_transfer_response(resp).await
+    }
+
+    /// Submit a signed transfer, reporting a refusal the node won't
+    /// reconsider as `ok: false` instead of an error.
+    ///
+    /// Any 4xx other than 408 and 429 is such a refusal: resending the same
+    /// signed payload can't succeed, so [`Outbox::flush`](crate::Outbox::flush)
+    /// drops the transfer. Other failures remain errors, worth retrying.
+    pub(crate) async fn transfer_signed_final(
+        &self,
+        payload: &serde_json::Value,
+    ) -> ClawRtcResult<TransferResponse> {
+        let resp = self.post_transfer(payload).await?;
+        let status = resp.status();
+        if status.is_success() {
+            return read_json(resp, self.max_body_bytes).await;
+        }
+        let body = read_text(resp, self.max_body_bytes).await.unwrap_or_default();
+        if !status.is_client_error()
+            || status == reqwest::StatusCode::REQUEST_TIMEOUT
+            || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        {
+            return Err(ClawRtcError::NodeApi(format!(
+                "Transfer failed: HTTP {status}: {body}"
+            )));
+        }
+        let error = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|b| b["error"].as_str().map(str::to_string));
+        Ok(TransferResponse {
+            ok: false,
+            tx_hash: None,
+            new_balance: None,
+            error: Some(error.unwrap_or_else(|| format!("HTTP {status}: {body}"))),
+        })
+    }
+
+    async fn post_transfer(&self, payload: &serde_json::Value) -> ClawRtcResult<reqwest::Response> {
         let url = format!("{}/wallet/transfer/signed", self.base_url);
         debug!(url, "Submitting signed transfer");
-        let resp = self.http.post(&url).json(payload).send().await?;
+        Ok(self.http_options.send(self.http.post(&url).json(payload)).await?)
+    }
+
+    /// Deserialize a transfer response straight from the body, so amounts
+    /// keep the digits they were sent with.
+    async fn read_transfer_response<T: serde::de::DeserializeOwned>(
+        &self,
+        resp: reqwest::Response,
+    ) -> ClawRtcResult<T> {
+        let status = resp.status();
+        if !status.is_success() {
+            let body = read_text(resp, self.max_body_bytes).await.unwrap_or_default();
+            return Err(ClawRtcError::NodeApi(format!(
+                "Transfer failed: HTTP {status}: {body}"
+            )));
+        }
+        read_json(resp, self.max_body_bytes).await
+    }
+
+    /// Look up the current status of a transaction by hash.
+    ///
+    /// A 404 is reported as [`ConfirmationStatus::Pending`], since a freshly
+    /// submitted transfer may not be indexed yet.
+    pub async fn transaction_status(&self, tx_hash: &str) -> ClawRtcResult<ConfirmationStatus> {
+        let url = format!("{}/api/transactions/{}", self.base_url, urlencoded(tx_hash));
+        debug!(url, "Checking transaction status");
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(ConfirmationStatus::Pending);
+        }
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = read_text(resp, self.max_body_bytes).await.unwrap_or_default();
+            return Err(ClawRtcError::NodeApi(format!(
+                "Transaction lookup failed: HTTP {status}: {body}"
+            )));
+        }
+        let body: serde_json::Value = read_json(resp, self.max_body_bytes).await?;
+        Ok(ConfirmationStatus::from_body(&body))
+    }
+
+    /// List up to `limit` past transfers to or from `wallet`, as the node
+    /// orders them.
+    ///
+    /// A wallet the node has no history for (404 or an empty body) yields an
+    /// empty list.
+    pub async fn transactions(&self, wallet: &str, limit: u32) -> ClawRtcResult<Vec<TxRecord>> {
+        let url = format!(
+            "{}/api/transactions?wallet={}&limit={}",
+            self.base_url,
+            urlencoded(wallet),
+            limit
+        );
+        debug!(url, "Listing transactions");
+        let resp = self.http_options.send(self.http.get(&url)).await?;
         let status = resp.status();
-        let body: serde_json::Value = resp.json().await?;
+        if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::NO_CONTENT {
+            return Ok(Vec::new());
+        }
         if !status.is_success() {
+            let body = read_text(resp, self.max_body_bytes).await.unwrap_or_default();
             return Err(ClawRtcError::NodeApi(format!(
-                "Transfer failed: HTTP {status}: {}",
-                body
+                "Transaction history failed: HTTP {status}: {body}"
             )));
         }
-        Ok(body)
+        let body = read_text(resp, self.max_body_bytes).await?;
+        if body.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        // Not an untagged enum: buffering the body would lose the amounts'
+        // source text
+        let mut records: Vec<TxRecord> = if body.trim_start().starts_with('[') {
+            serde_json::from_str(&body)?
+        } else {
+            serde_json::from_str::<TxPage>(&body)?.transactions
+        };
+        for record in &mut records {
+            if record.direction.is_none() {
+                record.direction = if record.from == wallet {
+                    Some(TxDirection::Sent)
+                } else if record.to == wallet {
+                    Some(TxDirection::Received)
+                } else {
+                    None
+                };
+            }
+        }
+        Ok(records)
+    }
+
+    /// Expected fingerprint metric ranges for miners on `arch`.
+    ///
+    /// Returns `None` when the node publishes no reference for it (404 or an
+    /// empty body).
+    pub async fn fingerprint_reference(
+        &self,
+        arch: &str,
+    ) -> ClawRtcResult<Option<FingerprintReference>> {
+        let url = format!(
+            "{}/api/fingerprint/reference?arch={}",
+            self.base_url,
+            urlencoded(arch)
+        );
+        debug!(url, "Fetching fingerprint reference");
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        let status = resp.status();
+        if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            let body = read_text(resp, self.max_body_bytes).await.unwrap_or_default();
+            return Err(ClawRtcError::NodeApi(format!(
+                "Fingerprint reference lookup failed: HTTP {status}: {body}"
+            )));
+        }
+        let body = read_text(resp, self.max_body_bytes).await?;
+        if body.trim().is_empty() {
+            return Ok(None);
+        }
+        let mut reference: FingerprintReference = serde_json::from_str(&body)?;
+        if reference.arch.is_empty() {
+            reference.arch = arch.to_string();
+        }
+        Ok(Some(reference))
+    }
+
+    /// Poll until a transaction is confirmed or failed, or `timeout` elapses.
+    ///
+    /// Polls with exponential backoff. Returns [`ConfirmationStatus::Pending`]
+    /// if the timeout is reached first.
+    pub async fn wait_for_confirmation(
+        &self,
+        tx_hash: &str,
+        timeout: Duration,
+    ) -> ClawRtcResult<ConfirmationStatus> {
+        let deadline = self.clock.monotonic() + timeout;
+        let mut delay = CONFIRMATION_POLL_INITIAL;
+        loop {
+            let status = self.transaction_status(tx_hash).await?;
+            if status != ConfirmationStatus::Pending {
+                return Ok(status);
+            }
+            let remaining = deadline.saturating_duration_since(self.clock.monotonic());
+            if remaining.is_zero() {
+                return Ok(ConfirmationStatus::Pending);
+            }
+            self.clock.sleep(delay.min(remaining)).await;
+            delay = (delay * 2).min(CONFIRMATION_POLL_MAX);
+        }
     }
 
     /// Get the base URL.
@@ -215,9 +1031,86 @@ impl RustChainClient {
     }
 }
 
+/// Keep the most recent record per miner ID; see
+/// [`RustChainClient::miners_deduped`].
+fn dedup_miners(miners: Vec<MinerInfo>) -> Vec<MinerInfo> {
+    let mut latest: std::collections::BTreeMap<String, MinerInfo> = Default::default();
+    let mut anonymous = Vec::new();
+    for m in miners {
+        let Some(id) = m.id().map(str::to_string) else {
+            anonymous.push(m);
+            continue;
+        };
+        match latest.get(&id) {
+            Some(seen) if seen.ts_ok >= m.ts_ok => {}
+            _ => {
+                latest.insert(id, m);
+            }
+        }
+    }
+    latest.into_values().chain(anonymous).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::MockClock;
+    use crate::test_support::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn test_capabilities_reported_by_node() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/capabilities" => MockResponse::json(
+                200,
+                serde_json::json!({ "version": "2.3", "features": ["transactions", "ws", "nft"] }),
+            ),
+            _ => MockResponse::json(500, serde_json::json!({})),
+        })
+        .await;
+        let client = RustChainClient::new(&server.url());
+        let caps = client.capabilities().await.unwrap();
+        assert_eq!(
+            caps,
+            NodeCapabilities {
+                transactions: true,
+                websocket: true,
+                ..Default::default()
+            }
+        );
+
+        // Cached after the first answer
+        assert_eq!(client.capabilities().await.unwrap(), caps);
+        assert_eq!(server.requests().len(), 1);
+
+        let flags = serde_json::json!({ "capabilities": { "faucet": true, "epoch": true } });
+        let caps = NodeCapabilities::from_body(flags).unwrap();
+        assert!(caps.faucet && caps.epoch_info && !caps.leaderboard);
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_probed_without_endpoint() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/api/transactions" => MockResponse::json(400, serde_json::json!({})),
+            "/ws" => MockResponse::text(426, "text/plain", "Upgrade Required"),
+            "/epoch" => MockResponse::json(200, serde_json::json!({ "epoch": 7 })),
+            _ => MockResponse::json(404, serde_json::json!({})),
+        })
+        .await;
+        let caps = RustChainClient::new(&server.url())
+            .capabilities()
+            .await
+            .unwrap();
+        assert_eq!(
+            caps,
+            NodeCapabilities {
+                faucet: false,
+                leaderboard: false,
+                epoch_info: true,
+                transactions: true,
+                websocket: true,
+            }
+        );
+    }
 
     #[test]
     fn test_client_creation() {
@@ -225,9 +1118,523 @@ mod tests {
         assert_eq!(c.base_url(), DEFAULT_NODE_URL);
     }
 
+    #[tokio::test]
+    async fn test_miners_deduped() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                serde_json::json!([
+                    { "miner": "RTCb", "device_arch": "g4", "ts_ok": 100 },
+                    { "miner_id": "RTCa", "device_arch": "g5", "ts_ok": 50 },
+                    { "miner": "RTCa", "miner_id": "RTCa", "device_arch": "g5", "ts_ok": 40 },
+                    { "miner": "RTCb", "device_arch": "g4", "ts_ok": 300 },
+                    { "device_arch": "x86" },
+                    { "miner": "RTCb", "device_arch": "g4", "ts_ok": 200 },
+                ]),
+            )
+        })
+        .await;
+        let client = RustChainClient::new(&server.url());
+
+        assert_eq!(client.miners().await.unwrap().len(), 6);
+        let miners = client.miners_deduped().await.unwrap();
+        let ids: Vec<_> = miners.iter().map(MinerInfo::id).collect();
+        assert_eq!(ids, [Some("RTCa"), Some("RTCb"), None]);
+        assert_eq!(miners[1].ts_ok, Some(300));
+    }
+
+    #[tokio::test]
+    async fn test_miners_stream_reads_past_body_limit() {
+        let miners: Vec<_> = (0..5_000)
+            .map(|i| serde_json::json!({ "miner": format!("RTC{i:040}"), "ts_ok": i }))
+            .collect();
+        let server =
+            MockServer::start(move |_| MockResponse::json(200, miners.clone().into())).await;
+        // Far below the ~400 KB body, but above any single record
+        let client = RustChainClient::new(&server.url()).with_max_body_bytes(4 * 1024);
+
+        let err = client.miners().await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::ResponseTooLarge(_)), "{err}");
+
+        let streamed: Vec<MinerInfo> = client.miners_stream().try_collect().await.unwrap();
+        assert_eq!(streamed.len(), 5_000);
+        assert_eq!(streamed[4_999].miner.as_deref(), Some(&*format!("RTC{:040}", 4_999)));
+        assert_eq!(streamed[4_999].ts_ok, Some(4_999));
+    }
+
+    #[tokio::test]
+    async fn test_miners_stream_reports_http_errors() {
+        let server = MockServer::start(|_| MockResponse::json(503, serde_json::json!({}))).await;
+        let client = RustChainClient::new(&server.url());
+        let mut stream = Box::pin(client.miners_stream());
+        let err = stream.try_next().await.unwrap_err();
+        assert!(err.to_string().contains("HTTP 503"), "{err}");
+        assert!(stream.try_next().await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_protocol_from_version() {
+        assert_eq!(NodeProtocol::from_version("1.4.2"), NodeProtocol::V1);
+        assert_eq!(NodeProtocol::from_version("v0.9"), NodeProtocol::V1);
+        assert_eq!(NodeProtocol::from_version("2.0.0"), NodeProtocol::V2);
+        assert_eq!(NodeProtocol::from_version("3.1-beta"), NodeProtocol::V2);
+        assert_eq!(NodeProtocol::from_version("nightly"), NodeProtocol::LATEST);
+    }
+
+    fn balance_node(
+        version: Option<&'static str>,
+    ) -> impl Fn(&crate::test_support::RecordedRequest) -> MockResponse {
+        move |req| match req.path.as_str() {
+            "/health" => {
+                MockResponse::json(200, serde_json::json!({ "ok": true, "version": version }))
+            }
+            p if p.starts_with("/balance/") || p.starts_with("/api/balance") => {
+                MockResponse::json(200, serde_json::json!({ "balance_rtc": 4.5 }))
+            }
+            _ => MockResponse::json(404, serde_json::json!({})),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_balance_strict_surfaces_node_errors() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(503, serde_json::json!({ "error": "maintenance" }))
+        })
+        .await;
+        let c = RustChainClient::new(&server.url());
+        assert_eq!(c.balance("RTCabc").await.unwrap(), 0.0);
+        let err = c.balance_strict("RTCabc").await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::NodeApi(_)), "{err}");
+        assert!(err.to_string().contains("503"), "{err}");
+
+        let server = MockServer::start(balance_node(None)).await;
+        let c = RustChainClient::new(&server.url());
+        assert_eq!(c.balance_strict("RTCabc").await.unwrap(), 4.5);
+    }
+
+    #[tokio::test]
+    async fn test_older_node_uses_legacy_balance_path() {
+        let server = MockServer::start(balance_node(Some("1.4.2"))).await;
+        let c = RustChainClient::new(&server.url());
+        assert_eq!(c.connect().await.unwrap(), NodeProtocol::V1);
+        assert_eq!(c.balance("RTCabc").await.unwrap(), 4.5);
+
+        let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+        assert_eq!(paths, vec!["/health", "/balance/RTCabc"]);
+    }
+
+    #[tokio::test]
+    async fn test_balance_encodes_wallet() {
+        let server = MockServer::start(balance_node(Some("1.4.2"))).await;
+        let c = RustChainClient::new(&server.url());
+        c.connect().await.unwrap();
+        c.balance_decimal("../admin?x=1").await.unwrap();
+        assert_eq!(server.requests()[1].path, "/balance/..%2Fadmin%3Fx%3D1");
+
+        let server = MockServer::start(balance_node(None)).await;
+        let c = RustChainClient::new(&server.url());
+        c.balance_decimal("x&wallet=RTCother").await.unwrap();
+        assert_eq!(
+            server.requests().last().unwrap().path,
+            "/api/balance?wallet=x%26wallet%3DRTCother"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unversioned_node_uses_latest_protocol() {
+        let server = MockServer::start(balance_node(None)).await;
+        let c = RustChainClient::new(&server.url());
+        assert_eq!(c.connect().await.unwrap(), NodeProtocol::LATEST);
+        c.balance("RTCabc").await.unwrap();
+        assert!(server.requests()[1].path.starts_with("/api/balance"));
+    }
+
+    #[tokio::test]
+    async fn test_pinned_protocol_is_not_overridden() {
+        let server = MockServer::start(balance_node(Some("2.1.0"))).await;
+        let c = RustChainClient::new(&server.url()).with_protocol(NodeProtocol::V1);
+        assert_eq!(c.connect().await.unwrap(), NodeProtocol::V1);
+    }
+
+    #[tokio::test]
+    async fn test_min_tls_version() {
+        let healthy = |_: &_| MockResponse::json(200, serde_json::json!({ "ok": true }));
+        let tls13 = MockServer::start_tls(&[&rustls::version::TLS13], healthy).await;
+        let tls12 = MockServer::start_tls(&[&rustls::version::TLS12], healthy).await;
+
+        let strict =
+            RustChainClient::new_insecure(&tls13.url()).with_min_tls_version(TlsVersion::TLS_1_3);
+        assert!(strict.health().await.unwrap().ok);
+        assert_eq!(tls13.requests().len(), 1);
+
+        // A server that only offers TLS 1.2 is refused rather than downgraded to
+        let strict =
+            RustChainClient::new_insecure(&tls12.url()).with_min_tls_version(TlsVersion::TLS_1_3);
+        assert!(matches!(strict.health().await, Err(ClawRtcError::Network(_))));
+        assert!(RustChainClient::new_insecure(&tls12.url()).health().await.is_ok());
+        assert_eq!(tls12.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_certificates_verified_unless_opted_out() {
+        let healthy = |_: &_| MockResponse::json(200, serde_json::json!({ "ok": true }));
+        let server = MockServer::start_tls(&[&rustls::version::TLS13], healthy).await;
+
+        // The mock's certificate is self-signed
+        let err = RustChainClient::new(&server.url()).health().await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::Network(_)), "{err}");
+        assert!(server.requests().is_empty());
+
+        assert!(RustChainClient::new_insecure(&server.url()).health().await.unwrap().ok);
+        let opted_in = RustChainClient::new(&server.url()).with_accept_invalid_certs(true);
+        assert!(opted_in.health().await.unwrap().ok);
+        assert_eq!(server.requests().len(), 2);
+
+        assert!(RustChainClient::default_node().http_options.accept_invalid_certs);
+        let default_slash = RustChainClient::for_node(&format!("{DEFAULT_NODE_URL}/"));
+        assert!(default_slash.http_options.accept_invalid_certs);
+        let other = RustChainClient::for_node("https://node.example");
+        assert!(!other.http_options.accept_invalid_certs);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_override() {
+        let server =
+            MockServer::start(|_| MockResponse::json(200, serde_json::json!({ "ok": true }))).await;
+        let addr = server.addr();
+        // The hostname doesn't exist in DNS; only the override can reach it
+        let c = RustChainClient::new(&format!("http://node.rustchain.invalid:{}", addr.port()))
+            .with_resolve("node.rustchain.invalid", addr);
+        assert!(c.health().await.unwrap().ok);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].header("host"),
+            Some(format!("node.rustchain.invalid:{}", addr.port()).as_str())
+        );
+    }
+
+    #[test]
+    fn test_challenge_expiry() {
+        let c: ChallengeResponse =
+            serde_json::from_value(serde_json::json!({ "nonce": "n" })).unwrap();
+        assert_eq!(c.expires_at, None);
+        assert!(!c.is_expired());
+
+        let c: ChallengeResponse = serde_json::from_value(serde_json::json!({
+            "nonce": "n", "issued_at": 1000, "expires_at": "1300"
+        }))
+        .unwrap();
+        assert_eq!(c.issued_at, Some(1000));
+        assert_eq!(c.expires_at, Some(1300));
+        assert!(!c.is_expired_at(1299));
+        assert!(c.is_expired_at(1300));
+        assert!(c.is_expired());
+    }
+
     #[test]
     fn test_custom_url() {
         let c = RustChainClient::new("http://localhost:8099/");
         assert_eq!(c.base_url(), "http://localhost:8099");
     }
+
+    #[tokio::test]
+    async fn test_oversized_response_is_rejected() {
+        let padding = "x".repeat(4096);
+        let server = MockServer::sequence(vec![MockResponse::json(
+            200,
+            serde_json::json!({ "ok": true, "version": padding }),
+        )])
+        .await;
+        let c = RustChainClient::new(&server.url()).with_max_body_bytes(1024);
+        let err = c.health().await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::ResponseTooLarge(1024)), "{err}");
+
+        // The same body parses under the default cap
+        let c = RustChainClient::new(&server.url());
+        assert!(c.health().await.unwrap().ok);
+    }
+
+    #[tokio::test]
+    async fn test_transactions() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            p if p.contains("wallet=RTCme&") => MockResponse::json(
+                200,
+                serde_json::json!([
+                    { "from": "RTCme", "to": "RTCyou", "amount_rtc": 1.5, "memo": "rent",
+                      "nonce": 3, "ts": 1700000000 },
+                    { "from_address": "RTCyou", "to_address": "RTCme", "amount_rtc": "0.25",
+                      "ts": "1700000100" },
+                    { "from": "RTCme", "to": "RTCme", "amount_rtc": 1, "direction": "in" },
+                ]),
+            ),
+            p if p.contains("wallet=RTCwrapped&") => MockResponse::json(
+                200,
+                serde_json::json!({ "transactions": [{ "from": "RTCwrapped", "to": "RTCx" }] }),
+            ),
+            _ => MockResponse::json(404, serde_json::json!({ "error": "unknown wallet" })),
+        })
+        .await;
+        let c = RustChainClient::new(&server.url());
+
+        let txs = c.transactions("RTCme", 10).await.unwrap();
+        assert_eq!(txs.len(), 3);
+        assert_eq!(txs[0].direction, Some(TxDirection::Sent));
+        assert_eq!(txs[0].memo.as_deref(), Some("rent"));
+        assert_eq!(txs[0].nonce, Some(3));
+        assert_eq!(txs[1].from, "RTCyou");
+        assert_eq!(txs[1].amount_rtc, "0.25".parse().ok());
+        assert_eq!(txs[1].ts, Some(1700000100));
+        assert_eq!(txs[1].direction, Some(TxDirection::Received));
+        // The node's own direction wins
+        assert_eq!(txs[2].direction, Some(TxDirection::Received));
+        assert_eq!(
+            server.requests()[0].path,
+            "/api/transactions?wallet=RTCme&limit=10"
+        );
+
+        let txs = c.transactions("RTCwrapped", 10).await.unwrap();
+        assert_eq!(txs[0].direction, Some(TxDirection::Sent));
+
+        assert!(c.transactions("RTCnobody", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transactions_encodes_wallet() {
+        let server = MockServer::start(|_| MockResponse::json(200, serde_json::json!([]))).await;
+        let c = RustChainClient::new(&server.url());
+
+        c.transactions("x&limit=100000&admin=1", 10).await.unwrap();
+        assert_eq!(
+            server.requests()[0].path,
+            "/api/transactions?wallet=x%26limit%3D100000%26admin%3D1&limit=10"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fingerprint_reference() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/api/fingerprint/reference?arch=x86_64" => MockResponse::json(
+                200,
+                serde_json::json!({
+                    "checks": { "clock_drift": { "cv": { "min": 0.001, "max": 0.5 } } },
+                }),
+            ),
+            _ => MockResponse::json(404, serde_json::json!({ "error": "unknown arch" })),
+        })
+        .await;
+        let c = RustChainClient::new(&server.url());
+
+        let reference = c.fingerprint_reference("x86_64").await.unwrap().unwrap();
+        assert_eq!(reference.arch, "x86_64");
+        assert_eq!(reference.checks["clock_drift"]["cv"].max, Some(0.5));
+        assert!(c.fingerprint_reference("m68k").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_signed_is_typed() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "ok": true,
+                    "tx_hash": "abc123",
+                    "new_balance": "7.25",
+                    "block": 99,
+                }),
+            )
+        })
+        .await;
+        let c = RustChainClient::new(&server.url());
+        let payload = serde_json::json!({ "amount_rtc": 1.0 });
+
+        let resp = c.transfer_signed(&payload).await.unwrap();
+        assert!(resp.ok);
+        assert_eq!(resp.tx_hash.as_deref(), Some("abc123"));
+        assert_eq!(resp.new_balance, "7.25".parse().ok());
+        assert_eq!(resp.error, None);
+
+        let raw = c.transfer_signed_raw(&payload).await.unwrap();
+        assert_eq!(raw["block"], 99);
+
+        let rejected: TransferResponse =
+            serde_json::from_str(r#"{"ok": false, "error": "bad signature"}"#).unwrap();
+        assert!(!rejected.ok);
+        assert_eq!(rejected.tx_hash, None);
+        assert_eq!(rejected.error.as_deref(), Some("bad signature"));
+
+        // Without `ok`, acceptance shows as a hash and no error
+        let without_ok = |body: &str| serde_json::from_str::<TransferResponse>(body).unwrap().ok;
+        assert!(without_ok(r#"{"tx_hash": "abc123"}"#));
+        assert!(!without_ok(r#"{"tx_hash": "abc123", "error": "queued twice"}"#));
+        assert!(!without_ok(r#"{"error": "bad signature"}"#));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_signed_keeps_balance_digits() {
+        let server = MockServer::start(|_| {
+            MockResponse::text(
+                200,
+                "application/json",
+                r#"{"ok": true, "new_balance": 9007199254740993.000001}"#,
+            )
+        })
+        .await;
+        let c = RustChainClient::new(&server.url());
+        let resp = c.transfer_signed(&serde_json::json!({})).await.unwrap();
+        assert_eq!(resp.new_balance, "9007199254740993.000001".parse().ok());
+    }
+
+    #[test]
+    fn test_numeric_fields_as_numbers() {
+        let b: BalanceResponse = serde_json::from_str(r#"{"balance_rtc": 1.5}"#).unwrap();
+        assert_eq!(b.balance_rtc, "1.5".parse().ok());
+        let h: HealthResponse = serde_json::from_str(r#"{"ok": true, "uptime_s": 3600}"#).unwrap();
+        assert_eq!(h.uptime_s, Some(3600.0));
+        let e: EnrollResponse =
+            serde_json::from_str(r#"{"ok": true, "epoch": 42, "weight": 2.5}"#).unwrap();
+        assert_eq!(e.epoch, Some(42));
+        assert_eq!(e.weight, Some(2.5));
+    }
+
+    #[test]
+    fn test_numeric_fields_as_strings() {
+        let b: BalanceResponse = serde_json::from_str(r#"{"balance_rtc": "1.5"}"#).unwrap();
+        assert_eq!(b.balance_rtc, "1.5".parse().ok());
+        let h: HealthResponse =
+            serde_json::from_str(r#"{"ok": true, "uptime_s": "3600.25"}"#).unwrap();
+        assert_eq!(h.uptime_s, Some(3600.25));
+        let e: EnrollResponse =
+            serde_json::from_str(r#"{"ok": true, "epoch": "42", "weight": " 2.5 "}"#).unwrap();
+        assert_eq!(e.epoch, Some(42));
+        assert_eq!(e.weight, Some(2.5));
+    }
+
+    #[test]
+    fn test_amounts_keep_full_precision() {
+        // 2^53 + 1 has no f64 representation
+        let b: BalanceResponse =
+            serde_json::from_str(r#"{"balance_rtc": 9007199254740993}"#).unwrap();
+        assert_eq!(b.balance_rtc.unwrap().to_string(), "9007199254740993");
+        let b: BalanceResponse =
+            serde_json::from_str(r#"{"balance_rtc": "12345678901234567.89"}"#).unwrap();
+        assert_eq!(b.balance_rtc.unwrap().to_string(), "12345678901234567.89");
+
+        let tx: TxRecord =
+            serde_json::from_str(r#"{"amount_rtc": 0.123456789012345678901}"#).unwrap();
+        assert_eq!(
+            tx.amount_rtc.unwrap().to_string(),
+            "0.123456789012345678901"
+        );
+        let tx: TxRecord = serde_json::from_str(r#"{"amount_rtc": 2.5e-7}"#).unwrap();
+        assert_eq!(tx.amount_rtc.unwrap().to_string(), "0.00000025");
+
+        // Still serialized as a JSON number
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["amount_rtc"], 2.5e-7);
+        let back: TxRecord = serde_json::from_value(json).unwrap();
+        assert_eq!(back.amount_rtc, tx.amount_rtc);
+    }
+
+    #[test]
+    fn test_numeric_fields_missing_null_or_empty() {
+        let b: BalanceResponse = serde_json::from_str("{}").unwrap();
+        assert_eq!(b.balance_rtc, None);
+        let b: BalanceResponse = serde_json::from_str(r#"{"balance_rtc": null}"#).unwrap();
+        assert_eq!(b.balance_rtc, None);
+        let e: EnrollResponse = serde_json::from_str(r#"{"ok": false, "epoch": ""}"#).unwrap();
+        assert_eq!(e.epoch, None);
+    }
+
+    #[test]
+    fn test_numeric_fields_reject_garbage() {
+        let err = serde_json::from_str::<BalanceResponse>(r#"{"balance_rtc": "lots"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("lots"), "unexpected error: {err}");
+        assert!(serde_json::from_str::<EnrollResponse>(r#"{"ok": true, "epoch": "4.5"}"#).is_err());
+        assert!(serde_json::from_str::<BalanceResponse>(r#"{"balance_rtc": true}"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_confirmation_pending_then_confirmed() {
+        let server = MockServer::sequence(vec![
+            MockResponse::json(200, serde_json::json!({ "status": "pending" })),
+            MockResponse::json(200, serde_json::json!({ "status": "pending" })),
+            MockResponse::json(200, serde_json::json!({ "status": "confirmed" })),
+        ])
+        .await;
+        let clock = Arc::new(MockClock::at_unix(0));
+        let c = RustChainClient::new(&server.url()).with_clock(clock.clone());
+        let started = clock.monotonic();
+        let status = c
+            .wait_for_confirmation("abc/123", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(status, ConfirmationStatus::Confirmed);
+        // Backed off 2s, then 4s
+        assert_eq!(clock.monotonic() - started, Duration::from_secs(6));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].path, "/api/transactions/abc%2F123");
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_confirmation_failed() {
+        let server = MockServer::sequence(vec![MockResponse::json(
+            200,
+            serde_json::json!({ "status": "rejected" }),
+        )])
+        .await;
+        let c = RustChainClient::new(&server.url());
+        let status = c
+            .wait_for_confirmation("abc123", Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(status, ConfirmationStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_confirmation_times_out_pending() {
+        // Unknown transaction stays pending until the timeout
+        let server = MockServer::sequence(vec![MockResponse::json(
+            404,
+            serde_json::json!({ "error": "not found" }),
+        )])
+        .await;
+        let clock = Arc::new(MockClock::at_unix(0));
+        let c = RustChainClient::new(&server.url()).with_clock(clock.clone());
+        let started = clock.monotonic();
+        let status = c
+            .wait_for_confirmation("abc123", Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(status, ConfirmationStatus::Pending);
+        // Polls at 0s, 2s, 6s and, cut short by the timeout, 10s
+        assert_eq!(server.requests().len(), 4);
+        assert_eq!(clock.monotonic() - started, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_confirmation_status_from_body() {
+        let parse = |v: serde_json::Value| ConfirmationStatus::from_body(&v);
+        assert_eq!(
+            parse(serde_json::json!({"confirmed": true})),
+            ConfirmationStatus::Confirmed
+        );
+        assert_eq!(
+            parse(serde_json::json!({"confirmed": false})),
+            ConfirmationStatus::Pending
+        );
+        assert_eq!(
+            parse(serde_json::json!({"status": "FINALIZED"})),
+            ConfirmationStatus::Confirmed
+        );
+        assert_eq!(
+            parse(serde_json::json!({"status": "dropped"})),
+            ConfirmationStatus::Failed
+        );
+        assert_eq!(parse(serde_json::json!({})), ConfirmationStatus::Pending);
+    }
 }
@@ -0,0 +1,126 @@
+//! Time sources for time-dependent logic.
+//!
+//! Code that reads the time or waits on it (attestation validity, challenge
+//! expiry, nonce allocation, the mining loop's sleeps) takes it from a
+//! [`Clock`] so tests can drive it with a [`MockClock`] instead of waiting on
+//! the real one.
+
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of wall-clock and monotonic time.
+pub trait Clock: std::fmt::Debug + Send + Sync {
+    /// Current wall-clock time.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Current monotonic time, for measuring intervals.
+    fn monotonic(&self) -> Instant;
+
+    /// Wait until `duration` has passed on this clock.
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// The real system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn monotonic(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same time, so a test can keep one handle and advance the
+/// clock it handed to the code under test. [`Clock::sleep`] advances the
+/// clock by the duration and returns at once.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    wall_start: DateTime<Utc>,
+    monotonic_start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl MockClock {
+    /// A clock whose wall time starts at `start`.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            wall_start: start,
+            monotonic_start: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    /// A clock starting at Unix time `secs`.
+    pub fn at_unix(secs: i64) -> Self {
+        Self::new(DateTime::from_timestamp(secs, 0).expect("timestamp in range"))
+    }
+
+    /// Move both wall and monotonic time forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        *self.elapsed.lock().unwrap() += by;
+    }
+
+    fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.wall_start + chrono::Duration::from_std(self.elapsed()).expect("elapsed in range")
+    }
+
+    fn monotonic(&self) -> Instant {
+        self.monotonic_start + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        self.advance(duration);
+        Box::pin(std::future::ready(()))
+    }
+}
+
+/// The clock used when none is supplied.
+pub fn system() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_advances_shared_time() {
+        let clock = MockClock::at_unix(1_000);
+        let handle = clock.clone();
+        let start = clock.monotonic();
+        assert_eq!(clock.now().timestamp(), 1_000);
+
+        handle.advance(Duration::from_millis(1_500));
+        assert_eq!(clock.now().timestamp_millis(), 1_001_500);
+        assert_eq!(clock.monotonic() - start, Duration::from_millis(1_500));
+        // Standing still between advances
+        assert_eq!(clock.monotonic(), clock.monotonic());
+    }
+
+    #[tokio::test]
+    async fn test_mock_sleep_advances_instead_of_waiting() {
+        let clock = MockClock::at_unix(0);
+        let started = Instant::now();
+        clock.sleep(Duration::from_secs(3600)).await;
+        assert_eq!(clock.now().timestamp(), 3600);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
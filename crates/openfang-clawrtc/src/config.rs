@@ -0,0 +1,428 @@
+//! User configuration from `~/.clawrtc/clawrtc.toml`.
+//!
+//! Settings are layered, each overriding the one before: built-in defaults,
+//! the config file, environment variables, then explicit overrides such as
+//! CLI flags. A missing file is the same as an empty one.
+//!
+//! ```toml
+//! node_url = "https://rustchain.example:8099"
+//! wallet = "default"
+//!
+//! [api_keys]
+//! bottube = "bottube_sk_..."
+//!
+//! [timeouts]
+//! node_secs = 30
+//! platform_secs = 15
+//!
+//! [retry]
+//! max_retries = 3
+//! initial_backoff_ms = 500
+//! max_backoff_ms = 8000
+//! ```
+
+use crate::bottube::BoTTubeClient;
+use crate::client::{RustChainClient, DEFAULT_NODE_URL};
+use crate::context::NODE_URL_ENV;
+use crate::error::{ClawRtcError, ClawRtcResult};
+use crate::grazer::{GrazerClient, Platform};
+use crate::http::RetryPolicy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Environment variable naming the default wallet.
+pub const WALLET_ENV: &str = "CLAWRTC_WALLET";
+
+/// Environment variable overriding the node request timeout, in seconds.
+pub const NODE_TIMEOUT_ENV: &str = "CLAWRTC_NODE_TIMEOUT_SECS";
+
+/// Crate-wide settings.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// RustChain node to talk to.
+    pub node_url: String,
+    /// Name of the default wallet under `~/.clawrtc/wallets/`.
+    pub wallet: String,
+    /// API keys by platform name (as accepted by [`Platform`]'s `FromStr`).
+    ///
+    /// Each can also be set with `CLAWRTC_<PLATFORM>_API_KEY`, e.g.
+    /// `CLAWRTC_BOTTUBE_API_KEY`.
+    pub api_keys: BTreeMap<String, String>,
+    pub timeouts: TimeoutConfig,
+    pub retry: RetryConfig,
+}
+
+/// Request timeouts, in seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimeoutConfig {
+    /// Requests to the RustChain node.
+    pub node_secs: u64,
+    /// Requests to Grazer platforms and BoTTube.
+    pub platform_secs: u64,
+}
+
+/// Retry settings for Grazer and BoTTube write operations; see
+/// [`RetryPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+/// Settings given explicitly, e.g. as CLI flags. They win over everything else.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigOverrides {
+    pub node_url: Option<String>,
+    pub wallet: Option<String>,
+    pub node_timeout_secs: Option<u64>,
+}
+
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("node_url", &self.node_url)
+            .field("wallet", &self.wallet)
+            .field("api_keys", &self.api_keys.keys().collect::<Vec<_>>())
+            .field("timeouts", &self.timeouts)
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            node_url: DEFAULT_NODE_URL.to_string(),
+            wallet: "default".to_string(),
+            api_keys: BTreeMap::new(),
+            timeouts: TimeoutConfig::default(),
+            retry: RetryConfig::default(),
+        }
+    }
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        Self {
+            node_secs: 30,
+            platform_secs: 15,
+        }
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        let policy = RetryPolicy::default();
+        Self {
+            max_retries: policy.max_retries,
+            initial_backoff_ms: policy.initial_backoff.as_millis() as u64,
+            max_backoff_ms: policy.max_backoff.as_millis() as u64,
+        }
+    }
+}
+
+impl Config {
+    /// `~/.clawrtc/clawrtc.toml`.
+    pub fn default_path() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".clawrtc")
+            .join("clawrtc.toml")
+    }
+
+    /// Load the file at [`Config::default_path`], then apply the environment
+    /// and `overrides`.
+    pub fn load(overrides: &ConfigOverrides) -> ClawRtcResult<Self> {
+        Self::load_from(&Self::default_path(), overrides)
+    }
+
+    /// Like [`Config::load`], reading the file at `path`.
+    pub fn load_from(path: &Path, overrides: &ConfigOverrides) -> ClawRtcResult<Self> {
+        Ok(Self::from_file(path)?
+            .with_env(|name| std::env::var(name).ok())?
+            .with_overrides(overrides))
+    }
+
+    /// Parse the file at `path`, or return the defaults if it doesn't exist.
+    pub fn from_file(path: &Path) -> ClawRtcResult<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text)
+                .map_err(|e| ClawRtcError::Config(format!("{}: {e}", path.display()))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Parse TOML; settings it leaves out keep their defaults.
+    pub fn from_toml(text: &str) -> ClawRtcResult<Self> {
+        Self::parse(text).map_err(ClawRtcError::Config)
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut config: Self = toml::from_str(text).map_err(|e| e.to_string())?;
+        config.api_keys = std::mem::take(&mut config.api_keys)
+            .into_iter()
+            .map(|(name, key)| {
+                let platform: Platform = name.parse()?;
+                Ok((platform.name().to_string(), key))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(config)
+    }
+
+    /// Apply environment variables, looked up with `env`.
+    ///
+    /// Reads [`NODE_URL_ENV`], [`WALLET_ENV`], [`NODE_TIMEOUT_ENV`] and
+    /// `CLAWRTC_<PLATFORM>_API_KEY`. Empty values are ignored.
+    pub fn with_env(mut self, env: impl Fn(&str) -> Option<String>) -> ClawRtcResult<Self> {
+        let var = |name: &str| env(name).filter(|v| !v.trim().is_empty());
+        if let Some(url) = var(NODE_URL_ENV) {
+            self.node_url = url;
+        }
+        if let Some(wallet) = var(WALLET_ENV) {
+            self.wallet = wallet;
+        }
+        if let Some(secs) = var(NODE_TIMEOUT_ENV) {
+            self.timeouts.node_secs = secs
+                .trim()
+                .parse()
+                .map_err(|e| ClawRtcError::Config(format!("{NODE_TIMEOUT_ENV}={secs:?}: {e}")))?;
+        }
+        for name in Platform::all_names() {
+            let key_var = format!("CLAWRTC_{}_API_KEY", name.to_ascii_uppercase());
+            if let Some(key) = var(&key_var) {
+                self.api_keys.insert(name.to_string(), key);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Apply explicitly given settings.
+    pub fn with_overrides(mut self, overrides: &ConfigOverrides) -> Self {
+        if let Some(url) = &overrides.node_url {
+            self.node_url = url.clone();
+        }
+        if let Some(wallet) = &overrides.wallet {
+            self.wallet = wallet.clone();
+        }
+        if let Some(secs) = overrides.node_timeout_secs {
+            self.timeouts.node_secs = secs;
+        }
+        self
+    }
+
+    /// The configured API key for `platform`, if any.
+    pub fn api_key(&self, platform: Platform) -> Option<&str> {
+        self.api_keys.get(platform.name()).map(String::as_str)
+    }
+
+    /// Path of the default wallet file.
+    ///
+    /// Fails with [`ClawRtcError::Config`] unless the wallet name is a plain
+    /// file name, so the path can't leave `~/.clawrtc/wallets/`.
+    pub fn wallet_path(&self) -> ClawRtcResult<PathBuf> {
+        let name = &self.wallet;
+        if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+            return Err(ClawRtcError::Config(format!(
+                "wallet name {name:?} must not be empty or contain path separators or '..'"
+            )));
+        }
+        Ok(dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".clawrtc")
+            .join("wallets")
+            .join(format!("{name}.json")))
+    }
+
+    /// Timeout for requests to the RustChain node.
+    pub fn node_timeout(&self) -> Duration {
+        Duration::from_secs(self.timeouts.node_secs)
+    }
+
+    /// Timeout for requests to Grazer platforms and BoTTube.
+    pub fn platform_timeout(&self) -> Duration {
+        Duration::from_secs(self.timeouts.platform_secs)
+    }
+
+    /// The retry settings as a [`RetryPolicy`].
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: self.retry.max_retries,
+            initial_backoff: Duration::from_millis(self.retry.initial_backoff_ms),
+            max_backoff: Duration::from_millis(self.retry.max_backoff_ms),
+        }
+    }
+
+    /// A client for the configured node.
+    pub fn rustchain_client(&self) -> RustChainClient {
+        self.rustchain_client_for(&self.node_url)
+    }
+
+    /// A client for `node_url` with the configured node timeout.
+    pub fn rustchain_client_for(&self, node_url: &str) -> RustChainClient {
+        RustChainClient::for_node(node_url).with_timeout(self.node_timeout())
+    }
+
+    /// A Grazer client with the configured timeout and retries.
+    pub fn grazer_client(&self) -> GrazerClient {
+        GrazerClient::new()
+            .with_timeout(self.platform_timeout())
+            .with_retry_policy(self.retry_policy())
+    }
+
+    /// A BoTTube client with the configured API key, timeout and retries.
+    pub fn bottube_client(&self) -> BoTTubeClient {
+        self.bottube_client_with_key(self.api_key(Platform::Bottube))
+    }
+
+    /// Like [`Config::bottube_client`], authenticating with `api_key`.
+    pub fn bottube_client_with_key(&self, api_key: Option<&str>) -> BoTTubeClient {
+        BoTTubeClient::new(api_key)
+            .with_timeout(self.platform_timeout())
+            .with_retry_policy(self.retry_policy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+node_url = "http://file:8099"
+wallet = "mining"
+
+[api_keys]
+bottube = "bottube_sk_file"
+fourclaw = "claw_file"
+
+[timeouts]
+node_secs = 45
+
+[retry]
+max_retries = 5
+"#;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone())
+    }
+
+    #[test]
+    fn test_parse_sample() {
+        let config = Config::from_toml(SAMPLE).unwrap();
+        assert_eq!(config.node_url, "http://file:8099");
+        assert_eq!(config.wallet, "mining");
+        assert_eq!(config.api_key(Platform::Bottube), Some("bottube_sk_file"));
+        // Aliases are stored under the canonical name
+        assert_eq!(config.api_key(Platform::FourClaw), Some("claw_file"));
+        assert_eq!(config.timeouts.node_secs, 45);
+        assert_eq!(config.timeouts.platform_secs, 15);
+        assert_eq!(config.retry_policy().max_retries, 5);
+        assert_eq!(
+            config.retry_policy().initial_backoff,
+            RetryPolicy::default().initial_backoff
+        );
+
+        assert_eq!(Config::from_toml("").unwrap(), Config::default());
+        assert!(Config::from_toml("node_url = 5").is_err());
+        assert!(Config::from_toml("nod_url = \"x\"").is_err());
+        let err = Config::from_toml("[api_keys]\nmyspace = \"k\"").unwrap_err();
+        assert!(matches!(err, ClawRtcError::Config(_)), "{err}");
+    }
+
+    #[test]
+    fn test_debug_redacts_api_keys() {
+        let config = Config::from_toml(SAMPLE).unwrap();
+        let debug = format!("{config:?}");
+        assert!(debug.contains("bottube"), "{debug}");
+        assert!(!debug.contains("bottube_sk_file"), "{debug}");
+    }
+
+    #[test]
+    fn test_wallet_name_stays_in_wallet_dir() {
+        for name in ["../../etc/passwd", "a/b", "a\\b", "..", ""] {
+            let config = Config {
+                wallet: name.to_string(),
+                ..Config::default()
+            };
+            let err = config.wallet_path().unwrap_err();
+            assert!(matches!(err, ClawRtcError::Config(_)), "{name:?}: {err}");
+        }
+        let config = Config {
+            wallet: "mining.v2".to_string(),
+            ..Config::default()
+        };
+        assert!(config.wallet_path().unwrap().ends_with("wallets/mining.v2.json"));
+    }
+
+    #[test]
+    fn test_override_precedence() {
+        let file = Config::from_toml(SAMPLE).unwrap();
+        let flags = ConfigOverrides {
+            node_url: Some("http://flag:8099".into()),
+            ..Default::default()
+        };
+
+        // flag > env > file
+        let config = file
+            .clone()
+            .with_env(env(&[
+                (NODE_URL_ENV, "http://env:8099"),
+                (WALLET_ENV, "env_wallet"),
+                ("CLAWRTC_BOTTUBE_API_KEY", "bottube_sk_env"),
+            ]))
+            .unwrap()
+            .with_overrides(&flags);
+        assert_eq!(config.node_url, "http://flag:8099");
+        assert_eq!(config.wallet, "env_wallet");
+        assert_eq!(config.api_key(Platform::Bottube), Some("bottube_sk_env"));
+        assert_eq!(config.timeouts.node_secs, 45);
+
+        // file > default, and empty env values don't count
+        let config = file
+            .clone()
+            .with_env(env(&[(NODE_URL_ENV, " ")]))
+            .unwrap()
+            .with_overrides(&ConfigOverrides::default());
+        assert_eq!(config, file);
+        let config = Config::default().with_env(env(&[])).unwrap();
+        assert_eq!(config.node_url, DEFAULT_NODE_URL);
+
+        let err = file
+            .with_env(env(&[(NODE_TIMEOUT_ENV, "soon")]))
+            .unwrap_err();
+        assert!(err.to_string().contains(NODE_TIMEOUT_ENV), "{err}");
+    }
+
+    #[test]
+    fn test_load_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clawrtc.toml");
+        let flags = ConfigOverrides {
+            wallet: Some("flag_wallet".into()),
+            ..Default::default()
+        };
+
+        let missing = Config::from_file(&path).unwrap();
+        assert_eq!(missing, Config::default());
+
+        std::fs::write(&path, SAMPLE).unwrap();
+        let config = Config::load_from(&path, &flags).unwrap();
+        assert_eq!(config.wallet, "flag_wallet");
+        let wallet_path = config.wallet_path().unwrap();
+        assert!(wallet_path.ends_with("wallets/flag_wallet.json"));
+
+        std::fs::write(&path, "node_url = [").unwrap();
+        let err = Config::from_file(&path).unwrap_err();
+        assert!(err.to_string().contains("clawrtc.toml"), "{err}");
+    }
+}
@@ -0,0 +1,348 @@
+//! Process-wide shared state for tool invocations.
+//!
+//! Tools run as independent calls, so anything worth keeping between them —
+//! node clients and their connection pools, detected hardware, the cap on
+//! in-flight requests, which wallet to sign with — lives in a
+//! lazily-initialized [`ToolContext`] shared by the whole process.
+
+use crate::bottube::BoTTubeClient;
+use crate::client::{RustChainClient, DEFAULT_NODE_URL};
+use crate::config::{Config, ConfigOverrides};
+use crate::error::ClawRtcResult;
+use crate::grazer::GrazerClient;
+use crate::hardware::HardwareInfo;
+use crate::http::RequestLimit;
+use crate::outbox::Outbox;
+use crate::wallet::{self, RtcWallet};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::Mutex;
+use tracing::warn;
+use zeroize::Zeroizing;
+
+/// Environment variable overriding the default node URL for tools.
+pub const NODE_URL_ENV: &str = "CLAWRTC_NODE_URL";
+
+/// Default cap on in-flight requests across all of a context's clients.
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 16;
+
+/// Environment variable holding the password of an encrypted tool wallet.
+pub const WALLET_PASSWORD_ENV: &str = "CLAWRTC_WALLET_PASSWORD";
+
+/// Shared state reused across tool calls.
+pub struct ToolContext {
+    clients: Mutex<HashMap<String, Arc<RustChainClient>>>,
+    hardware: std::sync::Mutex<Option<HardwareInfo>>,
+    offline: AtomicBool,
+    request_limit: RequestLimit,
+    wallet_path: PathBuf,
+    wallet_password: Option<Zeroizing<String>>,
+    outbox_path: PathBuf,
+    config: Config,
+}
+
+impl Default for ToolContext {
+    fn default() -> Self {
+        Self {
+            clients: Mutex::default(),
+            hardware: std::sync::Mutex::new(None),
+            offline: AtomicBool::new(false),
+            request_limit: RequestLimit::new(DEFAULT_MAX_IN_FLIGHT),
+            wallet_path: default_wallet_path(),
+            wallet_password: None,
+            outbox_path: Outbox::default_path(),
+            config: Config::default(),
+        }
+    }
+}
+
+impl ToolContext {
+    /// A context that uses already-collected hardware info instead of
+    /// detecting it.
+    pub fn with_hardware(hardware: HardwareInfo) -> Self {
+        let ctx = Self::default();
+        *ctx.hardware.lock().unwrap() = Some(hardware);
+        ctx
+    }
+
+    /// Sign with the wallet at `path` instead of `~/.clawrtc/wallets/default.json`.
+    pub fn with_wallet_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.wallet_path = path.into();
+        self
+    }
+
+    /// Unlock an encrypted wallet with `password`. Without one,
+    /// [`WALLET_PASSWORD_ENV`] is read when the wallet is loaded.
+    pub fn with_wallet_password(mut self, password: impl Into<String>) -> Self {
+        self.wallet_password = Some(Zeroizing::new(password.into()));
+        self
+    }
+
+    /// Build clients with the timeouts and retry policy from `config`.
+    ///
+    /// [`ToolContext::global`] uses the config file; other contexts use
+    /// [`Config::default`] until this is called.
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self.clients.get_mut().clear();
+        self
+    }
+
+    /// Queue signed transfers at `path` instead of [`Outbox::default_path`].
+    pub fn with_outbox_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.outbox_path = path.into();
+        self
+    }
+
+    /// The outbox tools queue signed transfers in.
+    pub fn outbox(&self) -> Outbox {
+        Outbox::new(&self.outbox_path)
+    }
+
+    /// The wallet file tools sign with.
+    pub fn wallet_path(&self) -> &Path {
+        &self.wallet_path
+    }
+
+    /// Load the tools' wallet, plaintext or encrypted (see [`wallet::load`]).
+    pub fn load_wallet(&self) -> ClawRtcResult<RtcWallet> {
+        let from_env = || {
+            std::env::var(WALLET_PASSWORD_ENV)
+                .ok()
+                .filter(|p| !p.is_empty())
+                .map(Zeroizing::new)
+        };
+        let password = self.wallet_password.clone().or_else(from_env);
+        wallet::load(&self.wallet_path, password.as_deref().map(String::as_str))
+    }
+
+    /// Allow at most `max_in_flight` concurrent requests across every client
+    /// this context hands out (default [`DEFAULT_MAX_IN_FLIGHT`]).
+    ///
+    /// A burst such as an all-platform discover alongside a balance batch
+    /// then queues instead of opening dozens of connections at once.
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.request_limit = RequestLimit::new(max_in_flight);
+        self.clients.get_mut().clear();
+        self
+    }
+
+    /// The limit shared by this context's clients.
+    pub fn request_limit(&self) -> &RequestLimit {
+        &self.request_limit
+    }
+
+    /// Switch offline mode on or off.
+    ///
+    /// While offline, tools that need the network fail immediately instead of
+    /// waiting for timeouts; local tools (fingerprints, wallets, signing)
+    /// keep working.
+    pub fn set_offline(&self, offline: bool) {
+        self.offline.store(offline, Ordering::Relaxed);
+    }
+
+    /// Whether offline mode is on.
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::Relaxed)
+    }
+
+    /// The process-wide context, configured from [`Config::load`].
+    ///
+    /// An unreadable config file is logged and the defaults used instead, so
+    /// tools keep working.
+    pub fn global() -> &'static ToolContext {
+        static CONTEXT: OnceLock<ToolContext> = OnceLock::new();
+        CONTEXT.get_or_init(|| {
+            let config = Config::load(&ConfigOverrides::default()).unwrap_or_else(|e| {
+                warn!(error = %e, "Ignoring unreadable config; using defaults");
+                Config::default()
+            });
+            ToolContext::default().with_config(config)
+        })
+    }
+
+    /// Shared client for `node_url`, or for the default node if `None`.
+    ///
+    /// The default comes from `CLAWRTC_NODE_URL` when set, otherwise
+    /// [`DEFAULT_NODE_URL`]. One client is created per distinct URL and reused
+    /// for every later call, so connections are pooled across tools.
+    pub async fn client(&self, node_url: Option<&str>) -> Arc<RustChainClient> {
+        let url = resolve_node_url(node_url, std::env::var(NODE_URL_ENV).ok());
+        let mut clients = self.clients.lock().await;
+        clients
+            .entry(url)
+            .or_insert_with_key(|url| {
+                let client = self.config.rustchain_client_for(url);
+                Arc::new(client.with_request_limit(self.request_limit.clone()))
+            })
+            .clone()
+    }
+
+    /// A Grazer client under this context's request limit.
+    pub fn grazer(&self) -> GrazerClient {
+        self.config
+            .grazer_client()
+            .with_request_limit(self.request_limit.clone())
+    }
+
+    /// A BoTTube client under this context's request limit.
+    pub fn bottube(&self, api_key: Option<&str>) -> BoTTubeClient {
+        self.config
+            .bottube_client_with_key(api_key)
+            .with_request_limit(self.request_limit.clone())
+    }
+
+    /// This machine's hardware info, detected on first use and then cached.
+    ///
+    /// Detection shells out to several system commands, so tools share one
+    /// result instead of probing on every call. A failed detection is not
+    /// cached.
+    pub fn hardware(&self) -> ClawRtcResult<HardwareInfo> {
+        let mut cached = self.hardware.lock().unwrap();
+        if let Some(hw) = cached.as_ref() {
+            return Ok(hw.clone());
+        }
+        let hw = HardwareInfo::detect()?;
+        *cached = Some(hw.clone());
+        Ok(hw)
+    }
+}
+
+/// `~/.clawrtc/wallets/default.json`.
+fn default_wallet_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".clawrtc")
+        .join("wallets")
+        .join("default.json")
+}
+
+/// Pick the node URL: explicit input, then the environment, then the default.
+/// Trailing slashes are trimmed so equivalent URLs share a client.
+fn resolve_node_url(explicit: Option<&str>, env: Option<String>) -> String {
+    let url = match (explicit, env) {
+        (Some(url), _) => url.to_string(),
+        (None, Some(url)) if !url.trim().is_empty() => url,
+        _ => DEFAULT_NODE_URL.to_string(),
+    };
+    url.trim().trim_end_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_node_url() {
+        assert_eq!(resolve_node_url(None, None), DEFAULT_NODE_URL);
+        assert_eq!(
+            resolve_node_url(None, Some("http://env:8099/".into())),
+            "http://env:8099"
+        );
+        assert_eq!(
+            resolve_node_url(Some("http://explicit:1"), Some("http://env:8099".into())),
+            "http://explicit:1"
+        );
+        assert_eq!(resolve_node_url(None, Some("  ".into())), DEFAULT_NODE_URL);
+    }
+
+    #[tokio::test]
+    async fn test_client_reused_per_url() {
+        let ctx = ToolContext::default();
+        let a = ctx.client(Some("http://node-a:8099")).await;
+        let b = ctx.client(Some("http://node-a:8099/")).await;
+        let c = ctx.client(Some("http://node-b:8099")).await;
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(c.base_url(), "http://node-b:8099");
+    }
+
+    #[tokio::test]
+    async fn test_request_limit_spans_clients() {
+        use crate::test_support::{MockResponse, MockServer};
+        use std::time::Duration;
+
+        let server = MockServer::start(|req| {
+            let body = match req.path.as_str() {
+                "/health" => serde_json::json!({ "ok": true }),
+                _ => serde_json::json!([]),
+            };
+            MockResponse::json(200, body).with_delay(Duration::from_millis(300))
+        })
+        .await;
+        let ctx = Arc::new(ToolContext::default().with_max_in_flight(3));
+        assert_eq!(ctx.request_limit().max_in_flight(), 3);
+
+        let burst = tokio::spawn({
+            let ctx = ctx.clone();
+            let url = server.url();
+            async move {
+                let node = ctx.client(Some(&url)).await;
+                let bottube = ctx.bottube(None).with_base_url(&url);
+                let health = futures::future::join_all((0..3).map(|_| node.health()));
+                let trending = futures::future::join_all((0..3).map(|_| bottube.trending()));
+                let (health, trending) = tokio::join!(health, trending);
+                health.iter().all(Result::is_ok) && trending.iter().all(Result::is_ok)
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(server.requests().len(), 3);
+        assert_eq!(ctx.request_limit().in_flight(), 3);
+        assert!(burst.await.unwrap());
+        assert_eq!(server.requests().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_config_timeouts_apply_to_clients() {
+        use crate::test_support::{MockResponse, MockServer};
+        use std::time::{Duration, Instant};
+
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, serde_json::json!([])).with_delay(Duration::from_secs(5))
+        })
+        .await;
+        let mut config = Config::default();
+        config.timeouts.node_secs = 1;
+        config.timeouts.platform_secs = 1;
+        let ctx = ToolContext::default().with_config(config);
+
+        let started = Instant::now();
+        let node = ctx.client(Some(&server.url())).await;
+        let bottube = ctx.bottube(None).with_base_url(&server.url());
+        let (health, trending) = tokio::join!(node.health(), bottube.trending());
+        assert!(health.is_err());
+        assert!(trending.is_err());
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_supplied_hardware_is_used() {
+        let mut hw = HardwareInfo::detect().unwrap();
+        hw.hostname = "supplied-host".into();
+        let ctx = ToolContext::with_hardware(hw);
+        assert_eq!(ctx.hardware().unwrap().hostname, "supplied-host");
+    }
+
+    #[test]
+    fn test_loads_encrypted_wallet_with_password() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ks.json");
+        let wallet = RtcWallet::generate();
+        wallet.save_keystore(&path, "hunter2hunter2").unwrap();
+
+        let locked = ToolContext::default().with_wallet_path(&path);
+        if std::env::var(WALLET_PASSWORD_ENV).is_err() {
+            assert!(locked.load_wallet().is_err());
+        }
+        let unlocked = locked.with_wallet_password("hunter2hunter2");
+        assert_eq!(unlocked.load_wallet().unwrap().address(), wallet.address());
+    }
+
+    #[test]
+    fn test_global_is_singleton() {
+        assert!(std::ptr::eq(ToolContext::global(), ToolContext::global()));
+    }
+}
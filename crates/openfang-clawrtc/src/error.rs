@@ -21,9 +21,18 @@ pub enum ClawRtcError {
     #[error("Network error: {0}")]
     Network(String),
 
+    #[error("Insufficient funds: {0}")]
+    InsufficientFunds(String),
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
     #[error("Attestation rejected: {0}")]
     AttestationRejected(String),
 
+    #[error("Attestation challenge expired: {0}")]
+    ChallengeExpired(String),
+
     #[error("Fingerprint check failed: {0}")]
     FingerprintFailed(String),
 
@@ -33,22 +42,93 @@ pub enum ClawRtcError {
     #[error("Grazer API error: {0}")]
     Grazer(String),
 
+    #[error("Grazer API error: {1}")]
+    GrazerStatus(u16, String),
+
     #[error("BoTTube API error: {0}")]
     BoTTube(String),
 
     #[error("Missing API key: {0}")]
     MissingApiKey(String),
 
+    #[error("Invalid API key: {0}")]
+    InvalidApiKey(String),
+
+    #[error("Invalid post: {0}")]
+    InvalidPost(String),
+
+    #[error("Circuit open: {0}")]
+    CircuitOpen(String),
+
+    #[error("Response body exceeded {0} bytes")]
+    ResponseTooLarge(usize),
+
+    #[error("Unexpected content type: {0}")]
+    UnexpectedContentType(String),
+
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
     #[error("Crypto error: {0}")]
     Crypto(String),
 
+    #[error("Signed message outside its validity window: {0}")]
+    MessageExpired(String),
+
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 }
 
+impl ClawRtcError {
+    /// Exit code for errors without a more specific category.
+    pub const EXIT_FAILURE: i32 = 1;
+    /// Exit code when the wallet file does not exist.
+    pub const EXIT_WALLET_NOT_FOUND: i32 = 2;
+    /// Exit code when the node or another remote service failed or was unreachable.
+    pub const EXIT_NETWORK: i32 = 3;
+    /// Exit code when attestation or the fingerprint checks were rejected.
+    pub const EXIT_ATTESTATION: i32 = 4;
+    /// Exit code when an argument, address, key, password or config file was invalid.
+    pub const EXIT_INVALID_INPUT: i32 = 5;
+
+    /// Process exit code for this error's category.
+    ///
+    /// The codes are stable so scripts can react to specific failures.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ClawRtcError::WalletNotFound(_) => Self::EXIT_WALLET_NOT_FOUND,
+            ClawRtcError::NodeApi(_)
+            | ClawRtcError::Network(_)
+            | ClawRtcError::Grazer(_)
+            | ClawRtcError::GrazerStatus(..)
+            | ClawRtcError::BoTTube(_)
+            | ClawRtcError::CircuitOpen(_)
+            | ClawRtcError::ResponseTooLarge(_)
+            | ClawRtcError::UnexpectedContentType(_) => Self::EXIT_NETWORK,
+            ClawRtcError::AttestationRejected(_)
+            | ClawRtcError::ChallengeExpired(_)
+            | ClawRtcError::FingerprintFailed(_) => Self::EXIT_ATTESTATION,
+            ClawRtcError::KeystoreDecrypt(_)
+            | ClawRtcError::InvalidAddress(_)
+            | ClawRtcError::InsufficientFunds(_)
+            | ClawRtcError::InvalidAmount(_)
+            | ClawRtcError::MissingApiKey(_)
+            | ClawRtcError::InvalidApiKey(_)
+            | ClawRtcError::InvalidPost(_)
+            | ClawRtcError::MessageExpired(_)
+            | ClawRtcError::Config(_) => Self::EXIT_INVALID_INPUT,
+            ClawRtcError::KeystoreEncrypt(_)
+            | ClawRtcError::HardwareDetection(_)
+            | ClawRtcError::Io(_)
+            | ClawRtcError::Crypto(_)
+            | ClawRtcError::Json(_) => Self::EXIT_FAILURE,
+        }
+    }
+}
+
 impl From<reqwest::Error> for ClawRtcError {
     fn from(e: reqwest::Error) -> Self {
         ClawRtcError::Network(e.to_string())
@@ -63,3 +143,44 @@ impl From<ed25519_dalek::SignatureError> for ClawRtcError {
 
 /// Convenience type alias.
 pub type ClawRtcResult<T> = Result<T, ClawRtcError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes() {
+        let s = || "x".to_string();
+        let cases = [
+            (ClawRtcError::WalletNotFound(s()), 2),
+            (ClawRtcError::KeystoreDecrypt(s()), 5),
+            (ClawRtcError::KeystoreEncrypt(s()), 1),
+            (ClawRtcError::InvalidAddress(s()), 5),
+            (ClawRtcError::NodeApi(s()), 3),
+            (ClawRtcError::Network(s()), 3),
+            (ClawRtcError::InsufficientFunds(s()), 5),
+            (ClawRtcError::InvalidAmount(s()), 5),
+            (ClawRtcError::AttestationRejected(s()), 4),
+            (ClawRtcError::ChallengeExpired(s()), 4),
+            (ClawRtcError::FingerprintFailed(s()), 4),
+            (ClawRtcError::HardwareDetection(s()), 1),
+            (ClawRtcError::Grazer(s()), 3),
+            (ClawRtcError::GrazerStatus(502, s()), 3),
+            (ClawRtcError::BoTTube(s()), 3),
+            (ClawRtcError::MissingApiKey(s()), 5),
+            (ClawRtcError::InvalidApiKey(s()), 5),
+            (ClawRtcError::InvalidPost(s()), 5),
+            (ClawRtcError::CircuitOpen(s()), 3),
+            (ClawRtcError::ResponseTooLarge(10), 3),
+            (ClawRtcError::UnexpectedContentType(s()), 3),
+            (ClawRtcError::Config(s()), 5),
+            (std::io::Error::other("x").into(), 1),
+            (ClawRtcError::Crypto(s()), 1),
+            (ClawRtcError::MessageExpired(s()), 5),
+            (serde_json::from_str::<()>("x").unwrap_err().into(), 1),
+        ];
+        for (err, code) in cases {
+            assert_eq!(err.exit_code(), code, "{err:?}");
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Outbound WebSocket stream of miner events for remote monitoring.
+//!
+//! The miner dials out to a dashboard rather than exposing an inbound port.
+//! Each [`MineEvent`] is sent as a JSON text frame wrapped with the miner ID
+//! and a timestamp. Dropped connections are re-established with exponential
+//! backoff; an event that failed to send is retried on the next connection.
+//!
+//! Events only ever carry public data (miner ID, cycle counters, epoch,
+//! balance, error text). Key material is never part of an event.
+
+use crate::miner::MineEvent;
+use futures::{SinkExt, StreamExt};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, warn};
+
+/// Events buffered while disconnected before new ones are dropped.
+pub const DEFAULT_EVENT_BUFFER: usize = 256;
+
+/// Where and how to publish miner events.
+#[derive(Debug, Clone)]
+pub struct EventStreamConfig {
+    /// WebSocket URL (`ws://` or `wss://`) of the collecting dashboard.
+    pub url: String,
+    /// Delay before the first reconnect attempt; doubles on each failure.
+    pub initial_backoff: Duration,
+    /// Upper bound on the reconnect delay.
+    pub max_backoff: Duration,
+    /// Capacity of the event queue between the miner and the publisher.
+    pub buffer: usize,
+}
+
+impl EventStreamConfig {
+    /// Config for `url` with default backoff (1s doubling to 60s) and buffer.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            buffer: DEFAULT_EVENT_BUFFER,
+        }
+    }
+}
+
+/// Spawn the publisher task.
+///
+/// Returns the sender the miner should emit events into. The task exits once
+/// every sender has been dropped and the queue is drained.
+pub fn spawn(
+    config: EventStreamConfig,
+    miner_id: String,
+) -> (mpsc::Sender<MineEvent>, JoinHandle<()>) {
+    let (tx, rx) = mpsc::channel(config.buffer.max(1));
+    let handle = tokio::spawn(publish(config, miner_id, rx));
+    (tx, handle)
+}
+
+/// Wrap an event with the miner ID and a timestamp.
+fn envelope(miner_id: &str, event: &MineEvent) -> String {
+    serde_json::json!({
+        "miner_id": miner_id,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "event": event,
+    })
+    .to_string()
+}
+
+async fn publish(config: EventStreamConfig, miner_id: String, mut rx: mpsc::Receiver<MineEvent>) {
+    let mut pending: Option<String> = None;
+    let mut backoff = config.initial_backoff;
+
+    loop {
+        if rx.is_closed() && rx.is_empty() && pending.is_none() {
+            return;
+        }
+
+        let ws = match tokio_tungstenite::connect_async(config.url.as_str()).await {
+            Ok((ws, _)) => {
+                info!(url = %config.url, "Event stream connected");
+                backoff = config.initial_backoff;
+                ws
+            }
+            Err(e) => {
+                warn!(error = %e, retry_in = ?backoff, "Event stream connect failed");
+                tokio::time::sleep(backoff).await;
+                backoff = backoff.saturating_mul(2).min(config.max_backoff);
+                continue;
+            }
+        };
+        let (mut sink, mut incoming) = ws.split();
+
+        loop {
+            let text = match pending.take() {
+                Some(text) => text,
+                None => tokio::select! {
+                    event = rx.recv() => match event {
+                        Some(event) => envelope(&miner_id, &event),
+                        None => {
+                            let _ = sink.close().await;
+                            return;
+                        }
+                    },
+                    msg = incoming.next() => match msg {
+                        // Pings are answered by tungstenite; other frames are ignored
+                        Some(Ok(msg)) if !msg.is_close() => continue,
+                        _ => break,
+                    },
+                },
+            };
+            if let Err(e) = sink.send(Message::Text(text.clone())).await {
+                debug!(error = %e, "Event send failed");
+                pending = Some(text);
+                break;
+            }
+        }
+
+        warn!(retry_in = ?backoff, "Event stream disconnected, reconnecting");
+        tokio::time::sleep(backoff).await;
+        backoff = backoff.saturating_mul(2).min(config.max_backoff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn fast_config(url: String) -> EventStreamConfig {
+        EventStreamConfig {
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+            ..EventStreamConfig::new(url)
+        }
+    }
+
+    async fn next_text<S>(ws: &mut S) -> serde_json::Value
+    where
+        S: StreamExt<Item = tokio_tungstenite::tungstenite::Result<Message>> + Unpin,
+    {
+        loop {
+            let msg = tokio::time::timeout(Duration::from_secs(5), ws.next())
+                .await
+                .expect("timed out waiting for event")
+                .expect("stream ended")
+                .unwrap();
+            if let Message::Text(text) = msg {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_are_delivered() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (tx, handle) = spawn(fast_config(url), "miner-1".into());
+
+        tx.send(MineEvent::CycleStarted { cycle: 1 }).await.unwrap();
+        tx.send(MineEvent::Balance { balance: 2.5 }).await.unwrap();
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+        let first = next_text(&mut ws).await;
+        assert_eq!(first["miner_id"], "miner-1");
+        assert!(first["timestamp"].is_string());
+        assert_eq!(first["event"]["type"], "cycle_started");
+        assert_eq!(first["event"]["cycle"], 1);
+
+        let second = next_text(&mut ws).await;
+        assert_eq!(second["event"]["type"], "balance");
+        assert_eq!(second["event"]["balance"], 2.5);
+
+        drop(tx);
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("publisher did not exit")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_disconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (tx, _handle) = spawn(fast_config(url), "miner-1".into());
+
+        tx.send(MineEvent::CycleStarted { cycle: 1 }).await.unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        assert_eq!(next_text(&mut ws).await["event"]["cycle"], 1);
+
+        // Dashboard goes away; the publisher must dial back in
+        ws.close(None).await.unwrap();
+        drop(ws);
+
+        let (stream, _) = tokio::time::timeout(Duration::from_secs(5), listener.accept())
+            .await
+            .expect("no reconnect attempt")
+            .unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+        tx.send(MineEvent::CycleStarted { cycle: 2 }).await.unwrap();
+        assert_eq!(next_text(&mut ws).await["event"]["cycle"], 2);
+    }
+
+    #[test]
+    fn test_envelope_fields() {
+        let text = envelope("miner-1", &MineEvent::AttestationAccepted);
+        let v: serde_json::Value = serde_json::from_str(&text).unwrap();
+        let keys: Vec<&String> = v.as_object().unwrap().keys().collect();
+        assert_eq!(keys.len(), 3);
+        assert_eq!(v["event"]["type"], "attestation_accepted");
+    }
+}
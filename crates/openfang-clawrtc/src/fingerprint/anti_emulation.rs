@@ -2,8 +2,10 @@
 //!
 //! Scans DMI tables, environment variables, CPU hypervisor flags, cloud metadata,
 //! and systemd-detect-virt to identify virtual machines and cloud instances.
+//! How the indicators map to PASS/FAIL is set by [`AntiEmulationStrictness`].
 
-use super::CheckResult;
+use super::{CheckResult, CheckThresholds};
+use serde::{Deserialize, Serialize};
 use std::process::Command;
 
 /// Known hypervisor/cloud vendor strings in DMI tables.
@@ -67,7 +69,89 @@ const VM_ENV_VARS: &[&str] = &[
     "WEBSITE_INSTANCE_ID",
 ];
 
+/// How strictly VM indicators are turned into a pass/fail verdict.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AntiEmulationStrictness {
+    /// FAIL on any VM indicator (default).
+    #[default]
+    Strict,
+    /// FAIL only on strong evidence: a DMI vendor match *and* a hypervisor flag.
+    Lenient,
+    /// Never FAIL; indicators are still reported.
+    Informational,
+}
+
+impl AntiEmulationStrictness {
+    /// Decide whether a set of indicators passes under this strictness level.
+    pub fn evaluate(&self, vm_indicators: &[String]) -> bool {
+        match self {
+            Self::Strict => vm_indicators.is_empty(),
+            Self::Lenient => {
+                let dmi_match = vm_indicators.iter().any(|i| i.starts_with("/sys/class/dmi/"));
+                let hypervisor_flag = vm_indicators
+                    .iter()
+                    .any(|i| i == "cpuinfo:hypervisor" || i.starts_with("sys_hypervisor:"));
+                !(dmi_match && hypervisor_flag)
+            }
+            Self::Informational => true,
+        }
+    }
+}
+
 pub fn check() -> CheckResult {
+    check_with_strictness(AntiEmulationStrictness::default())
+}
+
+/// Run the check using the anti-emulation strictness from `thresholds`.
+pub fn check_with_thresholds(thresholds: &CheckThresholds) -> CheckResult {
+    check_with_strictness(thresholds.anti_emulation)
+}
+
+/// Run the check, judging the collected indicators with the given strictness.
+pub fn check_with_strictness(strictness: AntiEmulationStrictness) -> CheckResult {
+    let vm_indicators = collect_indicators();
+    let valid = strictness.evaluate(&vm_indicators);
+
+    let data = serde_json::json!({
+        "vm_indicators": vm_indicators,
+        "indicator_count": vm_indicators.len(),
+        "is_likely_vm": !vm_indicators.is_empty(),
+        "strictness": strictness,
+    });
+
+    CheckResult {
+        passed: valid,
+        data,
+    }
+}
+
+/// Scan the system for VM/cloud indicators.
+fn collect_indicators() -> Vec<String> {
+    let mut vm_indicators = quick_indicators();
+
+    // Cloud metadata endpoint (169.254.169.254) — quick timeout
+    if check_cloud_metadata() {
+        vm_indicators.push("cloud_metadata:detected".to_string());
+    }
+
+    // systemd-detect-virt
+    if let Ok(output) = Command::new("systemd-detect-virt").output() {
+        let virt_type = String::from_utf8_lossy(&output.stdout).trim().to_lowercase();
+        if !virt_type.is_empty() && virt_type != "none" {
+            vm_indicators.push(format!("systemd_detect_virt:{virt_type}"));
+        }
+    }
+
+    vm_indicators
+}
+
+/// VM/cloud indicators that can be read locally without probing the network
+/// or spawning processes (DMI tables, environment, CPU flags, Xen).
+///
+/// A fast subset of what [`check`] scans, for summaries that should not wait
+/// on the cloud metadata timeout.
+pub fn quick_indicators() -> Vec<String> {
     let mut vm_indicators = Vec::new();
 
     // DMI table checks
@@ -104,32 +188,7 @@ pub fn check() -> CheckResult {
         }
     }
 
-    // Cloud metadata endpoint (169.254.169.254) — quick timeout
-    if check_cloud_metadata() {
-        vm_indicators.push("cloud_metadata:detected".to_string());
-    }
-
-    // systemd-detect-virt
-    if let Ok(output) = Command::new("systemd-detect-virt").output() {
-        let virt_type = String::from_utf8_lossy(&output.stdout).trim().to_lowercase();
-        if !virt_type.is_empty() && virt_type != "none" {
-            vm_indicators.push(format!("systemd_detect_virt:{virt_type}"));
-        }
-    }
-
-    let data = serde_json::json!({
-        "vm_indicators": vm_indicators,
-        "indicator_count": vm_indicators.len(),
-        "is_likely_vm": !vm_indicators.is_empty(),
-    });
-
-    // FAIL if any VM indicator found
-    let valid = vm_indicators.is_empty();
-
-    CheckResult {
-        passed: valid,
-        data,
-    }
+    vm_indicators
 }
 
 /// Check if the cloud metadata endpoint is reachable (indicates cloud VM).
@@ -168,5 +227,43 @@ mod tests {
     fn test_anti_emulation_runs() {
         let result = check();
         assert!(result.data["indicator_count"].is_number());
+        assert_eq!(result.data["strictness"], "strict");
+    }
+
+    fn indicators(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_strictness_no_indicators() {
+        let none: Vec<String> = Vec::new();
+        assert!(AntiEmulationStrictness::Strict.evaluate(&none));
+        assert!(AntiEmulationStrictness::Lenient.evaluate(&none));
+        assert!(AntiEmulationStrictness::Informational.evaluate(&none));
+    }
+
+    #[test]
+    fn test_strictness_weak_indicator() {
+        // A container env var alone is weak evidence
+        let weak = indicators(&["ENV:container"]);
+        assert!(!AntiEmulationStrictness::Strict.evaluate(&weak));
+        assert!(AntiEmulationStrictness::Lenient.evaluate(&weak));
+        assert!(AntiEmulationStrictness::Informational.evaluate(&weak));
+    }
+
+    #[test]
+    fn test_strictness_strong_indicators() {
+        let strong = indicators(&[
+            "/sys/class/dmi/id/sys_vendor:qemu",
+            "cpuinfo:hypervisor",
+        ]);
+        assert!(!AntiEmulationStrictness::Strict.evaluate(&strong));
+        assert!(!AntiEmulationStrictness::Lenient.evaluate(&strong));
+        assert!(AntiEmulationStrictness::Informational.evaluate(&strong));
+    }
+
+    #[test]
+    fn test_strictness_default_is_strict() {
+        assert_eq!(AntiEmulationStrictness::default(), AntiEmulationStrictness::Strict);
     }
 }
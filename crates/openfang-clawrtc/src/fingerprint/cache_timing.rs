@@ -4,7 +4,7 @@
 //! Real hardware shows a clear hierarchy (L2 slower than L1, L3 slower than L2).
 //! VMs often show flat timing with no hierarchy.
 
-use super::CheckResult;
+use super::{cancelled, CheckResult, CheckThresholds};
 use std::time::Instant;
 
 const ITERATIONS: usize = 100;
@@ -28,6 +28,11 @@ fn measure_access_time(buffer_size: usize) -> f64 {
 }
 
 pub fn check() -> CheckResult {
+    check_with_thresholds(&CheckThresholds::default())
+}
+
+/// Run the check, judging the measurements against `thresholds`.
+pub fn check_with_thresholds(thresholds: &CheckThresholds) -> CheckResult {
     let l1_size = 8 * 1024; // 8 KB
     let l2_size = 128 * 1024; // 128 KB
     let l3_size = 4 * 1024 * 1024; // 4 MB
@@ -37,6 +42,9 @@ pub fn check() -> CheckResult {
     let mut l3_times = Vec::with_capacity(ITERATIONS);
 
     for _ in 0..ITERATIONS {
+        if cancelled() {
+            return CheckResult::timed_out();
+        }
         l1_times.push(measure_access_time(l1_size));
         l2_times.push(measure_access_time(l2_size));
         l3_times.push(measure_access_time(l3_size));
@@ -57,15 +65,28 @@ pub fn check() -> CheckResult {
         "l3_l2_ratio": (l3_l2_ratio * 1000.0).round() / 1000.0,
     });
 
-    // PASS if we see at least some cache hierarchy (ratio > 1.01) and non-zero latencies
-    let valid = (l2_l1_ratio >= 1.01 || l3_l2_ratio >= 1.01) && l1_avg > 0.0 && l2_avg > 0.0 && l3_avg > 0.0;
-
     CheckResult {
-        passed: valid,
+        passed: passes(
+            [l1_avg, l2_avg, l3_avg],
+            l2_l1_ratio,
+            l3_l2_ratio,
+            thresholds,
+        ),
         data,
     }
 }
 
+/// PASS if we see at least some cache hierarchy and non-zero latencies.
+pub(super) fn passes(
+    latencies: [f64; 3],
+    l2_l1_ratio: f64,
+    l3_l2_ratio: f64,
+    thresholds: &CheckThresholds,
+) -> bool {
+    let min = thresholds.cache_min_ratio;
+    (l2_l1_ratio >= min || l3_l2_ratio >= min) && latencies.iter().all(|&ns| ns > 0.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,17 +3,26 @@
 //! Measures timing variance of repeated SHA-256 operations.
 //! Real hardware has oscillator jitter (CV ~0.01-0.15); VMs have uniform timing (CV ~0.0001).
 
-use super::CheckResult;
+use super::{cancelled, CheckResult, CheckThresholds};
 use sha2::{Digest, Sha256};
 use std::time::Instant;
 
 const SAMPLES: usize = 200;
-const REFERENCE_OPS: usize = 5000;
+/// SHA-256 digests per timed sample.
+pub(crate) const REFERENCE_OPS: usize = 5000;
 
 pub fn check() -> CheckResult {
+    check_with_thresholds(&CheckThresholds::default())
+}
+
+/// Run the check, judging the measurements against `thresholds`.
+pub fn check_with_thresholds(thresholds: &CheckThresholds) -> CheckResult {
     let mut intervals = Vec::with_capacity(SAMPLES);
 
     for i in 0..SAMPLES {
+        if cancelled() {
+            return CheckResult::timed_out();
+        }
         let data = format!("drift_{i}");
         let start = Instant::now();
         for _ in 0..REFERENCE_OPS {
@@ -54,15 +63,17 @@ pub fn check() -> CheckResult {
         "drift_stdev": drift_stdev as i64,
     });
 
-    // FAIL if timing is too uniform (cv < 0.0001) or no drift at all
-    let valid = cv >= 0.0001 && drift_stdev > 0.0;
-
     CheckResult {
-        passed: valid,
+        passed: passes(cv, drift_stdev, thresholds),
         data,
     }
 }
 
+/// FAIL if timing is too uniform (cv below the minimum) or there is no drift.
+pub(super) fn passes(cv: f64, drift_stdev: f64, thresholds: &CheckThresholds) -> bool {
+    cv >= thresholds.clock_drift_min_cv && drift_stdev > thresholds.clock_drift_min_drift_stdev
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
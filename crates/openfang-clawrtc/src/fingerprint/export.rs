@@ -0,0 +1,196 @@
+//! Flat CSV/TSV export of fingerprint metrics for spreadsheet analysis.
+//!
+//! Each report becomes a single row with a fixed set of columns, so rows from
+//! different machines or runs can be appended to the same file.
+
+use super::FingerprintReport;
+
+/// Columns as `(header, check name, data field)`.
+///
+/// Pass/fail columns use the pseudo-field `"passed"`. Append new columns at the
+/// end to keep existing spreadsheets aligned.
+const COLUMNS: &[(&str, &str, &str)] = &[
+    ("all_passed", "", "passed"),
+    ("clock_drift_passed", "clock_drift", "passed"),
+    ("clock_drift_cv", "clock_drift", "cv"),
+    ("clock_drift_stdev_ns", "clock_drift", "stdev_ns"),
+    ("clock_drift_drift_stdev", "clock_drift", "drift_stdev"),
+    ("cache_timing_passed", "cache_timing", "passed"),
+    ("cache_l1_ns", "cache_timing", "l1_ns"),
+    ("cache_l2_ns", "cache_timing", "l2_ns"),
+    ("cache_l3_ns", "cache_timing", "l3_ns"),
+    ("cache_l2_l1_ratio", "cache_timing", "l2_l1_ratio"),
+    ("cache_l3_l2_ratio", "cache_timing", "l3_l2_ratio"),
+    ("simd_identity_passed", "simd_identity", "passed"),
+    ("simd_flags_count", "simd_identity", "simd_flags_count"),
+    ("thermal_drift_passed", "thermal_drift", "passed"),
+    ("thermal_drift_ratio", "thermal_drift", "drift_ratio"),
+    ("thermal_cold_stdev", "thermal_drift", "cold_stdev"),
+    ("thermal_hot_stdev", "thermal_drift", "hot_stdev"),
+    ("instruction_jitter_passed", "instruction_jitter", "passed"),
+    ("jitter_int_stdev", "instruction_jitter", "int_stdev"),
+    ("jitter_fp_stdev", "instruction_jitter", "fp_stdev"),
+    ("jitter_branch_stdev", "instruction_jitter", "branch_stdev"),
+    ("anti_emulation_passed", "anti_emulation", "passed"),
+    (
+        "anti_emulation_indicator_count",
+        "anti_emulation",
+        "indicator_count",
+    ),
+];
+
+impl FingerprintReport {
+    /// Header row matching [`FingerprintReport::to_csv`].
+    pub fn csv_header() -> String {
+        header(',')
+    }
+
+    /// Header row matching [`FingerprintReport::to_tsv`].
+    pub fn tsv_header() -> String {
+        header('\t')
+    }
+
+    /// The report's key metrics as one comma-separated row (no header).
+    ///
+    /// Metrics missing from a check's data are left empty.
+    pub fn to_csv(&self) -> String {
+        self.row(',')
+    }
+
+    /// The report's key metrics as one tab-separated row (no header).
+    pub fn to_tsv(&self) -> String {
+        self.row('\t')
+    }
+
+    fn row(&self, sep: char) -> String {
+        COLUMNS
+            .iter()
+            .map(|(_, check, field)| self.field(check, field))
+            .collect::<Vec<_>>()
+            .join(&sep.to_string())
+    }
+
+    fn field(&self, check: &str, field: &str) -> String {
+        if check.is_empty() {
+            return self.all_passed.to_string();
+        }
+        let Some((_, result)) = self.checks.iter().find(|(name, _)| *name == check) else {
+            return String::new();
+        };
+        if field == "passed" {
+            return result.passed.to_string();
+        }
+        match &result.data[field] {
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::Bool(b) => b.to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+fn header(sep: char) -> String {
+    COLUMNS
+        .iter()
+        .map(|(name, _, _)| *name)
+        .collect::<Vec<_>>()
+        .join(&sep.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fingerprint::{CheckResult, FingerprintChecks};
+
+    fn result(passed: bool, data: serde_json::Value) -> CheckResult {
+        CheckResult { passed, data }
+    }
+
+    fn sample_report() -> FingerprintReport {
+        FingerprintReport::from_checks(FingerprintChecks {
+            clock_drift: result(
+                true,
+                serde_json::json!({ "mean_ns": 1, "stdev_ns": 20, "cv": 0.0123, "drift_stdev": 4 }),
+            ),
+            cache_timing: result(
+                true,
+                serde_json::json!({
+                    "l1_ns": 1.5, "l2_ns": 2.25, "l3_ns": 4.0,
+                    "l2_l1_ratio": 1.5, "l3_l2_ratio": 1.778
+                }),
+            ),
+            simd_identity: result(true, serde_json::json!({ "simd_flags_count": 12 })),
+            thermal_drift: result(
+                true,
+                serde_json::json!({ "drift_ratio": 0.98, "cold_stdev": 10, "hot_stdev": 12 }),
+            ),
+            instruction_jitter: result(
+                true,
+                serde_json::json!({ "int_stdev": 5, "fp_stdev": 6, "branch_stdev": 7 }),
+            ),
+            anti_emulation: result(false, serde_json::json!({ "indicator_count": 2 })),
+        })
+    }
+
+    #[test]
+    fn test_csv_header_columns() {
+        let header = FingerprintReport::csv_header();
+        let cols: Vec<&str> = header.split(',').collect();
+        assert_eq!(cols.len(), COLUMNS.len());
+        assert_eq!(cols[0], "all_passed");
+        for expected in [
+            "clock_drift_cv",
+            "thermal_drift_ratio",
+            "cache_l2_l1_ratio",
+            "cache_l3_l2_ratio",
+            "jitter_int_stdev",
+            "jitter_fp_stdev",
+            "jitter_branch_stdev",
+            "anti_emulation_indicator_count",
+        ] {
+            assert!(cols.contains(&expected), "missing column {expected}");
+        }
+    }
+
+    #[test]
+    fn test_csv_row_matches_header() {
+        let row = sample_report().to_csv();
+        let fields: Vec<&str> = row.split(',').collect();
+        assert_eq!(
+            fields.len(),
+            FingerprintReport::csv_header().split(',').count()
+        );
+
+        let header = FingerprintReport::csv_header();
+        let get = |name: &str| {
+            let idx = header.split(',').position(|c| c == name).unwrap();
+            fields[idx]
+        };
+        assert_eq!(get("all_passed"), "false");
+        assert_eq!(get("clock_drift_cv"), "0.0123");
+        assert_eq!(get("cache_l2_l1_ratio"), "1.5");
+        assert_eq!(get("jitter_branch_stdev"), "7");
+        assert_eq!(get("anti_emulation_passed"), "false");
+        assert_eq!(get("anti_emulation_indicator_count"), "2");
+    }
+
+    #[test]
+    fn test_missing_metrics_are_empty() {
+        let mut report = sample_report();
+        report.checks.clock_drift.data = serde_json::json!({});
+        let row = report.to_csv();
+        let idx = FingerprintReport::csv_header()
+            .split(',')
+            .position(|c| c == "clock_drift_cv")
+            .unwrap();
+        assert_eq!(row.split(',').nth(idx), Some(""));
+        assert_eq!(row.split(',').count(), COLUMNS.len());
+    }
+
+    #[test]
+    fn test_tsv_uses_tabs() {
+        let report = sample_report();
+        assert_eq!(report.to_tsv().split('\t').count(), COLUMNS.len());
+        assert_eq!(report.to_tsv(), report.to_csv().replace(',', "\t"));
+        assert!(FingerprintReport::tsv_header().starts_with("all_passed\tclock_drift_passed"));
+    }
+}
@@ -3,12 +3,16 @@
 //! Times integer, floating-point, and branch operations separately.
 //! Real hardware shows different jitter profiles per pipeline; VMs flatten jitter.
 
-use super::CheckResult;
+use super::{cancelled, discard_warmup, CheckResult, CheckThresholds};
 use std::time::Instant;
 
 const SAMPLES: usize = 100;
 const OPS: usize = 10_000;
 
+/// Leading samples per pipeline discarded as warm-up by [`check`]: none, so
+/// the reported metrics are unchanged unless warm-up is asked for.
+pub const DEFAULT_WARMUP_SAMPLES: usize = 0;
+
 fn measure_int_ops() -> f64 {
     let start = Instant::now();
     let mut x: u64 = 1;
@@ -47,41 +51,69 @@ fn measure_branch_ops() -> f64 {
 }
 
 pub fn check() -> CheckResult {
+    check_with_warmup(DEFAULT_WARMUP_SAMPLES)
+}
+
+/// Run the check, discarding the first `warmup` samples of each pipeline
+/// before computing statistics.
+pub fn check_with_warmup(warmup: usize) -> CheckResult {
+    run(warmup, &CheckThresholds::default())
+}
+
+/// Run the check, judging the measurements against `thresholds`.
+pub fn check_with_thresholds(thresholds: &CheckThresholds) -> CheckResult {
+    run(DEFAULT_WARMUP_SAMPLES, thresholds)
+}
+
+fn run(warmup: usize, thresholds: &CheckThresholds) -> CheckResult {
     let mut int_times = Vec::with_capacity(SAMPLES);
     let mut fp_times = Vec::with_capacity(SAMPLES);
     let mut branch_times = Vec::with_capacity(SAMPLES);
 
     for _ in 0..SAMPLES {
+        if cancelled() {
+            return CheckResult::timed_out();
+        }
         int_times.push(measure_int_ops());
         fp_times.push(measure_fp_ops());
         branch_times.push(measure_branch_ops());
     }
 
-    let int_avg = mean(&int_times);
-    let fp_avg = mean(&fp_times);
-    let branch_avg = mean(&branch_times);
-    let int_stdev = stdev(&int_times);
-    let fp_stdev = stdev(&fp_times);
-    let branch_stdev = stdev(&branch_times);
+    let int_measured = discard_warmup(&int_times, warmup);
+    let fp_measured = discard_warmup(&fp_times, warmup);
+    let branch_measured = discard_warmup(&branch_times, warmup);
 
-    let data = serde_json::json!({
+    let int_avg = mean(int_measured);
+    let fp_avg = mean(fp_measured);
+    let branch_avg = mean(branch_measured);
+    let int_stdev = stdev(int_measured);
+    let fp_stdev = stdev(fp_measured);
+    let branch_stdev = stdev(branch_measured);
+
+    let mut data = serde_json::json!({
         "int_avg_ns": int_avg as i64,
         "fp_avg_ns": fp_avg as i64,
         "branch_avg_ns": branch_avg as i64,
         "int_stdev": int_stdev as i64,
         "fp_stdev": fp_stdev as i64,
         "branch_stdev": branch_stdev as i64,
+        "sample_count": SAMPLES,
     });
-
-    // PASS if any jitter across instruction types
-    let valid = int_stdev > 0.0 || fp_stdev > 0.0 || branch_stdev > 0.0;
+    if warmup > 0 {
+        data["warmup_discarded"] = (SAMPLES - int_measured.len()).into();
+    }
 
     CheckResult {
-        passed: valid,
+        passed: passes([int_stdev, fp_stdev, branch_stdev], thresholds),
         data,
     }
 }
 
+/// PASS if any instruction type shows enough jitter.
+pub(super) fn passes(stdevs: [f64; 3], thresholds: &CheckThresholds) -> bool {
+    stdevs.iter().any(|&s| s > thresholds.jitter_min_stdev)
+}
+
 fn mean(values: &[f64]) -> f64 {
     values.iter().sum::<f64>() / values.len().max(1) as f64
 }
@@ -103,5 +135,13 @@ mod tests {
     fn test_instruction_jitter_runs() {
         let result = check();
         assert!(result.data["int_avg_ns"].as_i64().unwrap() > 0);
+        assert_eq!(result.data["sample_count"], SAMPLES);
+        assert!(result.data.get("warmup_discarded").is_none());
+    }
+
+    #[test]
+    fn test_instruction_jitter_opt_in_warmup() {
+        let result = check_with_warmup(5);
+        assert_eq!(result.data["warmup_discarded"], 5);
     }
 }
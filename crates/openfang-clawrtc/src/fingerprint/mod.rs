@@ -6,11 +6,179 @@
 pub mod anti_emulation;
 pub mod cache_timing;
 pub mod clock_drift;
+pub mod export;
 pub mod instruction_jitter;
+pub mod reference;
 pub mod simd_identity;
+pub mod stability;
 pub mod thermal_drift;
+pub mod thresholds;
+
+pub use anti_emulation::AntiEmulationStrictness;
+pub use reference::{FingerprintComparison, FingerprintReference};
+pub use stability::{sample_over_time, StabilityReport};
+pub use thresholds::CheckThresholds;
 
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+/// Machine name of a fingerprint check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckName {
+    ClockDrift,
+    CacheTiming,
+    SimdIdentity,
+    ThermalDrift,
+    InstructionJitter,
+    AntiEmulation,
+}
+
+impl CheckName {
+    /// All checks, in the order they run and are reported.
+    pub const ALL: [CheckName; 6] = [
+        CheckName::ClockDrift,
+        CheckName::CacheTiming,
+        CheckName::SimdIdentity,
+        CheckName::ThermalDrift,
+        CheckName::InstructionJitter,
+        CheckName::AntiEmulation,
+    ];
+
+    /// Snake-case name, as used in JSON reports.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CheckName::ClockDrift => "clock_drift",
+            CheckName::CacheTiming => "cache_timing",
+            CheckName::SimdIdentity => "simd_identity",
+            CheckName::ThermalDrift => "thermal_drift",
+            CheckName::InstructionJitter => "instruction_jitter",
+            CheckName::AntiEmulation => "anti_emulation",
+        }
+    }
+
+    /// Display name, as used in the summary table.
+    pub fn label(self) -> &'static str {
+        match self {
+            CheckName::ClockDrift => "Clock Drift",
+            CheckName::CacheTiming => "Cache Timing",
+            CheckName::SimdIdentity => "SIMD Identity",
+            CheckName::ThermalDrift => "Thermal Drift",
+            CheckName::InstructionJitter => "Instruction Jitter",
+            CheckName::AntiEmulation => "Anti-Emulation",
+        }
+    }
+
+    /// One-line description of what the check measures.
+    pub fn description(self) -> &'static str {
+        check_descriptions()
+            .iter()
+            .find(|(name, _)| *name == self)
+            .map(|(_, desc)| *desc)
+            .unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for CheckName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A set of fingerprint checks to run; see [`validate_selected`].
+///
+/// Cheap checks (`simd_identity`, `anti_emulation`) finish in milliseconds,
+/// while the timing checks take seconds each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FingerprintSelection(u8);
+
+impl FingerprintSelection {
+    /// Every check.
+    pub const ALL: Self = Self((1 << CheckName::ALL.len()) - 1);
+    /// No checks.
+    pub const NONE: Self = Self(0);
+
+    /// Only the given checks.
+    pub fn only(names: &[CheckName]) -> Self {
+        names
+            .iter()
+            .fold(Self::NONE, |selection, &name| selection.with(name))
+    }
+
+    /// This selection plus `name`.
+    pub fn with(self, name: CheckName) -> Self {
+        Self(self.0 | Self::bit(name))
+    }
+
+    /// This selection minus `name`.
+    pub fn without(self, name: CheckName) -> Self {
+        Self(self.0 & !Self::bit(name))
+    }
+
+    /// Whether `name` is selected.
+    pub fn contains(self, name: CheckName) -> bool {
+        self.0 & Self::bit(name) != 0
+    }
+
+    /// The selected checks, in report order.
+    pub fn iter(self) -> impl Iterator<Item = CheckName> {
+        CheckName::ALL
+            .into_iter()
+            .filter(move |&name| self.contains(name))
+    }
+
+    fn bit(name: CheckName) -> u8 {
+        1 << name as u8
+    }
+}
+
+impl Default for FingerprintSelection {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl FromIterator<CheckName> for FingerprintSelection {
+    fn from_iter<I: IntoIterator<Item = CheckName>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::NONE, Self::with)
+    }
+}
+
+/// Each check with a one-line description, in report order.
+///
+/// Descriptions follow the check modules' docs, so UIs can explain a check
+/// without hardcoding text.
+pub fn check_descriptions() -> &'static [(CheckName, &'static str)] {
+    &[
+        (
+            CheckName::ClockDrift,
+            "Timing variance of repeated SHA-256 runs; real oscillators jitter, VMs are uniform.",
+        ),
+        (
+            CheckName::CacheTiming,
+            "Memory latency at L1/L2/L3 sizes; real hardware shows a cache hierarchy, VMs are flat.",
+        ),
+        (
+            CheckName::SimdIdentity,
+            "Available SIMD instruction sets (SSE, AVX, AltiVec, NEON); VMs may report none.",
+        ),
+        (
+            CheckName::ThermalDrift,
+            "Timing variance cold vs hot; real CPUs drift as they heat up, VMs stay uniform.",
+        ),
+        (
+            CheckName::InstructionJitter,
+            "Jitter of integer, float, and branch operations; VMs flatten per-pipeline jitter.",
+        ),
+        (
+            CheckName::AntiEmulation,
+            "DMI tables, environment, CPU flags, and cloud metadata that reveal a VM or cloud host.",
+        ),
+    ]
+}
 
 /// Result of a single fingerprint check.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,13 +187,60 @@ pub struct CheckResult {
     pub data: serde_json::Value,
 }
 
+impl CheckResult {
+    /// Placeholder for a check left out of the selection: not passed, with
+    /// `data.skipped` set to `true`.
+    pub fn skipped() -> Self {
+        Self {
+            passed: false,
+            data: serde_json::json!({ "skipped": true }),
+        }
+    }
+
+    /// Whether this is a [`CheckResult::skipped`] placeholder.
+    pub fn is_skipped(&self) -> bool {
+        self.data["skipped"] == true
+    }
+
+    /// A check that missed its deadline: not passed, with `data.error` set to
+    /// `"timeout"`.
+    pub fn timed_out() -> Self {
+        Self {
+            passed: false,
+            data: serde_json::json!({ "error": "timeout" }),
+        }
+    }
+}
+
 /// Full fingerprint report across all 6 checks.
+///
+/// Skipped checks are left out of `all_passed` and `score`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "StoredReport")]
 pub struct FingerprintReport {
+    /// Every check that ran passed.
     pub all_passed: bool,
+    /// Fraction of the checks that ran that passed, from 0.0 to 1.0.
+    pub score: f64,
+    /// Number of checks that passed.
+    pub passed_count: u8,
     pub checks: FingerprintChecks,
 }
 
+/// A saved [`FingerprintReport`]. Only the checks are read; the verdict and
+/// score are derived again, so reports saved before scoring existed load
+/// with the right ones.
+#[derive(Deserialize)]
+struct StoredReport {
+    checks: FingerprintChecks,
+}
+
+impl From<StoredReport> for FingerprintReport {
+    fn from(stored: StoredReport) -> Self {
+        Self::from_checks(stored.checks)
+    }
+}
+
 /// Individual check results.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FingerprintChecks {
@@ -37,37 +252,227 @@ pub struct FingerprintChecks {
     pub anti_emulation: CheckResult,
 }
 
+impl FingerprintChecks {
+    /// The result of one check.
+    pub fn get(&self, name: CheckName) -> &CheckResult {
+        match name {
+            CheckName::ClockDrift => &self.clock_drift,
+            CheckName::CacheTiming => &self.cache_timing,
+            CheckName::SimdIdentity => &self.simd_identity,
+            CheckName::ThermalDrift => &self.thermal_drift,
+            CheckName::InstructionJitter => &self.instruction_jitter,
+            CheckName::AntiEmulation => &self.anti_emulation,
+        }
+    }
+
+    /// Iterate over `(check_name, result)` pairs in a stable order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &CheckResult)> {
+        CheckName::ALL
+            .into_iter()
+            .map(|name| (name.as_str(), self.get(name)))
+    }
+}
+
+impl FingerprintReport {
+    /// Build a report from individual check results, deriving the verdict
+    /// and score.
+    pub fn from_checks(checks: FingerprintChecks) -> Self {
+        let ran = checks
+            .iter()
+            .filter(|(_, result)| !result.is_skipped())
+            .count();
+        let passed_count = checks.iter().filter(|(_, result)| result.passed).count();
+        Self {
+            all_passed: ran > 0 && passed_count == ran,
+            score: if ran > 0 {
+                passed_count as f64 / ran as f64
+            } else {
+                0.0
+            },
+            passed_count: passed_count as u8,
+            checks,
+        }
+    }
+
+    /// Whether at least `min` of the checks passed, as a fraction from 0.0 to
+    /// 1.0 — e.g. `5.0 / 6.0` to tolerate one borderline check.
+    pub fn passes_threshold(&self, min: f64) -> bool {
+        self.score >= min
+    }
+
+    /// One `Name: PASS|FAIL|SKIP` line per check, names padded to align.
+    pub fn summary_lines(&self) -> Vec<String> {
+        CheckName::ALL
+            .into_iter()
+            .map(|name| {
+                let label = format!("{}:", name.label());
+                format!("{label:<20}{}", check_status(self.checks.get(name)))
+            })
+            .collect()
+    }
+
+    /// Human-readable summary table followed by the overall verdict.
+    pub fn to_summary_string(&self) -> String {
+        let mut out = self.summary_lines().join("\n");
+        out.push_str(&format!("\n\nOverall:            {}", pass_fail(self.all_passed)));
+        out
+    }
+
+    /// Render the report as pretty JSON or as the summary table.
+    pub fn render(&self, json: bool) -> String {
+        if json {
+            serde_json::to_string_pretty(self).expect("FingerprintReport serializes")
+        } else {
+            self.to_summary_string()
+        }
+    }
+}
+
+fn pass_fail(passed: bool) -> &'static str {
+    if passed { "PASS" } else { "FAIL" }
+}
+
+fn check_status(result: &CheckResult) -> &'static str {
+    if result.is_skipped() {
+        "SKIP"
+    } else {
+        pass_fail(result.passed)
+    }
+}
+
+/// Drop the first `warmup` timing samples, which tend to be outliers from cold
+/// caches and scheduler placement.
+///
+/// At least two samples are always kept (when available) so a variance can
+/// still be computed.
+pub fn discard_warmup(samples: &[f64], warmup: usize) -> &[f64] {
+    let skip = warmup.min(samples.len().saturating_sub(2));
+    &samples[skip..]
+}
+
 /// Run all 6 fingerprint checks synchronously.
 ///
 /// This is CPU-intensive. In async contexts, wrap in `tokio::task::spawn_blocking`.
 pub fn validate_all_checks() -> FingerprintReport {
-    let clock_drift = clock_drift::check();
-    let cache_timing = cache_timing::check();
-    let simd_identity = simd_identity::check();
-    let thermal_drift = thermal_drift::check();
-    let instruction_jitter = instruction_jitter::check();
-    let anti_emulation = anti_emulation::check();
-
-    let all_passed = clock_drift.passed
-        && cache_timing.passed
-        && simd_identity.passed
-        && thermal_drift.passed
-        && instruction_jitter.passed
-        && anti_emulation.passed;
-
-    FingerprintReport {
-        all_passed,
-        checks: FingerprintChecks {
-            clock_drift,
-            cache_timing,
-            simd_identity,
-            thermal_drift,
-            instruction_jitter,
-            anti_emulation,
-        },
+    validate_all_checks_with_thresholds(&CheckThresholds::default())
+}
+
+/// Run all 6 fingerprint checks synchronously against custom thresholds.
+///
+/// A check that panics is reported as failed, with the panic message under
+/// `data.error`; the remaining checks still run.
+pub fn validate_all_checks_with_thresholds(thresholds: &CheckThresholds) -> FingerprintReport {
+    validate_checks_with(|name| run_check(name, thresholds))
+}
+
+/// Run all 6 fingerprint checks, giving each at most `per_check` to finish.
+///
+/// Each check runs on its own thread. One that misses the deadline is
+/// reported as [`CheckResult::timed_out`] and cancelled: the timing checks
+/// stop at their next sample, so they don't skew the checks after them. A
+/// hung probe (such as the cloud metadata connect in anti-emulation) can't be
+/// interrupted; its thread is left to finish in the background, so it cannot
+/// wedge the report.
+pub fn validate_all_checks_with_timeout(per_check: Duration) -> FingerprintReport {
+    let thresholds = CheckThresholds::default();
+    validate_checks_with(|name| {
+        let thresholds = thresholds.clone();
+        run_with_timeout(per_check, move || run_check(name, &thresholds))
+    })
+}
+
+/// Run only the checks in `selection`; the rest are reported as
+/// [`CheckResult::skipped`] and don't count towards `all_passed`.
+pub fn validate_selected(selection: FingerprintSelection) -> FingerprintReport {
+    let thresholds = CheckThresholds::default();
+    validate_checks_with(|name| {
+        if selection.contains(name) {
+            run_check(name, &thresholds)
+        } else {
+            CheckResult::skipped()
+        }
+    })
+}
+
+fn run_check(name: CheckName, thresholds: &CheckThresholds) -> CheckResult {
+    match name {
+        CheckName::ClockDrift => clock_drift::check_with_thresholds(thresholds),
+        CheckName::CacheTiming => cache_timing::check_with_thresholds(thresholds),
+        CheckName::SimdIdentity => simd_identity::check(),
+        CheckName::ThermalDrift => thermal_drift::check_with_thresholds(thresholds),
+        CheckName::InstructionJitter => instruction_jitter::check_with_thresholds(thresholds),
+        CheckName::AntiEmulation => anti_emulation::check_with_thresholds(thresholds),
     }
 }
 
+thread_local! {
+    /// Raised by [`run_with_timeout`] when the check on this thread misses its
+    /// deadline.
+    static CANCEL: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// Whether the check running on this thread has timed out and should stop.
+///
+/// The timing checks poll this once per sample.
+pub(crate) fn cancelled() -> bool {
+    CANCEL.with(|cancel| {
+        cancel
+            .borrow()
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    })
+}
+
+/// Run `check` on its own thread, failing and cancelling it if no result
+/// arrives in `timeout`.
+fn run_with_timeout(
+    timeout: Duration,
+    check: impl FnOnce() -> CheckResult + Send + 'static,
+) -> CheckResult {
+    let flag = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn({
+        let flag = flag.clone();
+        move || {
+            CANCEL.with(|cancel| *cancel.borrow_mut() = Some(flag));
+            // The receiver is gone if the check timed out
+            let _ = tx.send(catch_check_panic(check));
+        }
+    });
+    rx.recv_timeout(timeout).unwrap_or_else(|_| {
+        flag.store(true, Ordering::Relaxed);
+        CheckResult::timed_out()
+    })
+}
+
+/// Run `check`, reporting a panic as a failed result.
+fn catch_check_panic(check: impl FnOnce() -> CheckResult) -> CheckResult {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(check)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        CheckResult {
+            passed: false,
+            data: serde_json::json!({ "error": format!("check panicked: {message}") }),
+        }
+    })
+}
+
+/// Build a report by running `check` for each check, containing panics.
+fn validate_checks_with(check: impl Fn(CheckName) -> CheckResult) -> FingerprintReport {
+    let run = |name| catch_check_panic(|| check(name));
+    FingerprintReport::from_checks(FingerprintChecks {
+        clock_drift: run(CheckName::ClockDrift),
+        cache_timing: run(CheckName::CacheTiming),
+        simd_identity: run(CheckName::SimdIdentity),
+        thermal_drift: run(CheckName::ThermalDrift),
+        instruction_jitter: run(CheckName::InstructionJitter),
+        anti_emulation: run(CheckName::AntiEmulation),
+    })
+}
+
 /// Run all checks in a blocking task suitable for async contexts.
 pub async fn validate_all_checks_async() -> FingerprintReport {
     tokio::task::spawn_blocking(validate_all_checks)
@@ -75,6 +480,22 @@ pub async fn validate_all_checks_async() -> FingerprintReport {
         .expect("Fingerprint check task panicked")
 }
 
+/// Run the selected checks in a blocking task.
+pub async fn validate_selected_async(selection: FingerprintSelection) -> FingerprintReport {
+    tokio::task::spawn_blocking(move || validate_selected(selection))
+        .await
+        .expect("Fingerprint check task panicked")
+}
+
+/// Run all checks against custom thresholds in a blocking task.
+pub async fn validate_all_checks_with_thresholds_async(
+    thresholds: CheckThresholds,
+) -> FingerprintReport {
+    tokio::task::spawn_blocking(move || validate_all_checks_with_thresholds(&thresholds))
+        .await
+        .expect("Fingerprint check task panicked")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,6 +510,187 @@ mod tests {
         assert!(report.checks.simd_identity.data.is_object());
     }
 
+    #[test]
+    fn test_panicking_check_is_recorded_as_failed() {
+        let report = validate_checks_with(|name| {
+            if name == CheckName::CacheTiming {
+                panic!("attempt to divide by zero");
+            }
+            CheckResult {
+                passed: true,
+                data: serde_json::json!({ "check": name.as_str() }),
+            }
+        });
+
+        assert!(!report.all_passed);
+        let failed = &report.checks.cache_timing;
+        assert!(!failed.passed);
+        assert_eq!(
+            failed.data["error"],
+            "check panicked: attempt to divide by zero"
+        );
+        for (name, result) in report.checks.iter().filter(|(n, _)| *n != "cache_timing") {
+            assert!(result.passed, "{name}");
+            assert_eq!(result.data["check"], name);
+        }
+    }
+
+    #[test]
+    fn test_slow_check_times_out() {
+        let start = std::time::Instant::now();
+        let report = validate_checks_with(|name| {
+            run_with_timeout(Duration::from_millis(100), move || {
+                if name == CheckName::AntiEmulation {
+                    std::thread::sleep(Duration::from_secs(30));
+                }
+                CheckResult {
+                    passed: true,
+                    data: serde_json::json!({}),
+                }
+            })
+        });
+
+        assert!(start.elapsed() < Duration::from_secs(10));
+        let slow = &report.checks.anti_emulation;
+        assert!(!slow.passed);
+        assert_eq!(slow.data["error"], "timeout");
+        assert_eq!(report.passed_count, 5);
+
+        // A panic on the check's thread is still reported as a panic
+        let result = run_with_timeout(Duration::from_secs(5), || panic!("boom"));
+        assert_eq!(result.data["error"], "check panicked: boom");
+    }
+
+    #[test]
+    fn test_timed_out_check_is_cancelled() {
+        let (stopped_tx, stopped_rx) = mpsc::channel();
+        let result = run_with_timeout(Duration::from_millis(50), move || {
+            while !cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            stopped_tx.send(()).unwrap();
+            CheckResult::timed_out()
+        });
+        assert_eq!(result.data["error"], "timeout");
+        stopped_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(!cancelled());
+    }
+
+    #[test]
+    fn test_check_descriptions() {
+        let descriptions = check_descriptions();
+        assert_eq!(descriptions.len(), 6);
+        for (name, (described, desc)) in CheckName::ALL.into_iter().zip(descriptions) {
+            assert_eq!(name, *described);
+            assert!(!desc.is_empty(), "{name} has no description");
+            assert_eq!(name.description(), *desc);
+        }
+        assert_eq!(
+            serde_json::to_value(CheckName::InstructionJitter).unwrap(),
+            "instruction_jitter"
+        );
+    }
+
+    #[test]
+    fn test_discard_warmup() {
+        let samples = [900.0, 400.0, 100.0, 101.0, 99.0];
+        assert_eq!(discard_warmup(&samples, 0), &samples[..]);
+        assert_eq!(discard_warmup(&samples, 2), &[100.0, 101.0, 99.0]);
+        // Never drops below two samples
+        assert_eq!(discard_warmup(&samples, 10), &[101.0, 99.0]);
+        assert!(discard_warmup(&[], 3).is_empty());
+    }
+
+    fn report_with_failure() -> FingerprintReport {
+        let ok = || CheckResult {
+            passed: true,
+            data: serde_json::json!({}),
+        };
+        FingerprintReport::from_checks(FingerprintChecks {
+            clock_drift: ok(),
+            cache_timing: ok(),
+            simd_identity: ok(),
+            thermal_drift: ok(),
+            instruction_jitter: ok(),
+            anti_emulation: CheckResult {
+                passed: false,
+                data: serde_json::json!({ "indicator_count": 1 }),
+            },
+        })
+    }
+
+    #[test]
+    fn test_score_counts_passed_checks() {
+        let report = report_with_failure();
+        assert!(!report.all_passed);
+        assert_eq!(report.passed_count, 5);
+        assert!((report.score - 5.0 / 6.0).abs() < 1e-9);
+        assert!(report.passes_threshold(5.0 / 6.0));
+        assert!(!report.passes_threshold(1.0));
+
+        // Reports saved before scoring existed load with their score
+        let legacy: FingerprintReport = serde_json::from_value(serde_json::json!({
+            "all_passed": false,
+            "checks": serde_json::to_value(&report.checks).unwrap(),
+        }))
+        .unwrap();
+        assert_eq!(legacy.passed_count, 5);
+        assert_eq!(legacy.score, report.score);
+    }
+
+    #[test]
+    fn test_selected_checks_only() {
+        let selection =
+            FingerprintSelection::only(&[CheckName::SimdIdentity, CheckName::AntiEmulation]);
+        assert!(selection.contains(CheckName::SimdIdentity));
+        assert!(!selection.contains(CheckName::ClockDrift));
+        assert_eq!(
+            selection.iter().collect::<FingerprintSelection>(),
+            selection
+        );
+        assert_eq!(
+            FingerprintSelection::ALL
+                .without(CheckName::ClockDrift)
+                .iter()
+                .count(),
+            5
+        );
+
+        let report = validate_selected(FingerprintSelection::NONE.with(CheckName::SimdIdentity));
+        for (name, result) in report.checks.iter() {
+            assert_eq!(result.is_skipped(), name != "simd_identity", "{name}");
+        }
+        assert_eq!(report.all_passed, report.checks.simd_identity.passed);
+        assert_eq!(report.summary_lines()[0], "Clock Drift:        SKIP");
+
+        // Only the checks that ran count towards the verdict
+        let mut checks = report_with_failure().checks;
+        checks.anti_emulation = CheckResult::skipped();
+        let report = FingerprintReport::from_checks(checks);
+        assert!(report.all_passed);
+        assert_eq!(report.passed_count, 5);
+        assert_eq!(report.score, 1.0);
+        assert!(!validate_selected(FingerprintSelection::NONE).all_passed);
+    }
+
+    #[test]
+    fn test_summary_string() {
+        let summary = report_with_failure().to_summary_string();
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines[0], "Clock Drift:        PASS");
+        assert_eq!(lines[5], "Anti-Emulation:     FAIL");
+        assert_eq!(lines.last(), Some(&"Overall:            FAIL"));
+    }
+
+    #[test]
+    fn test_render_json() {
+        let report = report_with_failure();
+        let v: serde_json::Value = serde_json::from_str(&report.render(true)).unwrap();
+        assert_eq!(v["all_passed"], false);
+        assert_eq!(v["checks"]["anti_emulation"]["data"]["indicator_count"], 1);
+        assert_eq!(report.render(false), report.to_summary_string());
+    }
+
     #[tokio::test]
     async fn test_validate_async() {
         let report = validate_all_checks_async().await;
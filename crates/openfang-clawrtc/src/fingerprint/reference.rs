@@ -0,0 +1,173 @@
+//! Comparing local fingerprint metrics with what the node expects.
+//!
+//! A node may publish, per architecture, the range each check metric falls in
+//! on real hardware. Comparing a local report against those ranges shows
+//! which metrics make an attestation borderline, even when the check's own
+//! pass/fail verdict doesn't say why.
+
+use super::FingerprintReport;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Expected metric ranges for one architecture, from
+/// [`RustChainClient::fingerprint_reference`](crate::RustChainClient::fingerprint_reference).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FingerprintReference {
+    pub arch: String,
+    /// Ranges keyed by check name, then metric name (as in the check's `data`).
+    pub checks: BTreeMap<String, BTreeMap<String, MetricRange>>,
+}
+
+/// Inclusive bounds for a metric; a missing bound is open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricRange {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// Where a metric fell relative to its expected range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RangeStatus {
+    Within,
+    Below,
+    Above,
+    /// The check was skipped or didn't report the metric.
+    Missing,
+}
+
+/// One metric compared against its expected range.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricComparison {
+    pub check: String,
+    pub metric: String,
+    pub value: Option<f64>,
+    pub expected: MetricRange,
+    pub status: RangeStatus,
+}
+
+/// Every referenced metric of a report compared against its range.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FingerprintComparison {
+    pub arch: String,
+    /// Metrics outside their range or missing.
+    pub outside_count: usize,
+    /// In check order, then metric name order.
+    pub metrics: Vec<MetricComparison>,
+}
+
+impl MetricRange {
+    /// Where `value` falls relative to this range.
+    pub fn status(&self, value: f64) -> RangeStatus {
+        if self.min.is_some_and(|min| value < min) {
+            RangeStatus::Below
+        } else if self.max.is_some_and(|max| value > max) {
+            RangeStatus::Above
+        } else {
+            RangeStatus::Within
+        }
+    }
+}
+
+impl FingerprintReference {
+    /// Compare each metric this reference covers against `report`.
+    ///
+    /// Metrics the reference doesn't mention are left out.
+    pub fn compare(&self, report: &FingerprintReport) -> FingerprintComparison {
+        let mut metrics = Vec::new();
+        for (check, result) in report.checks.iter() {
+            let Some(ranges) = self.checks.get(check) else {
+                continue;
+            };
+            for (metric, expected) in ranges {
+                let value = if result.is_skipped() {
+                    None
+                } else {
+                    result.data[metric.as_str()].as_f64()
+                };
+                metrics.push(MetricComparison {
+                    check: check.to_string(),
+                    metric: metric.clone(),
+                    value,
+                    expected: *expected,
+                    status: value.map_or(RangeStatus::Missing, |v| expected.status(v)),
+                });
+            }
+        }
+        FingerprintComparison {
+            arch: self.arch.clone(),
+            outside_count: metrics
+                .iter()
+                .filter(|m| m.status != RangeStatus::Within)
+                .count(),
+            metrics,
+        }
+    }
+}
+
+impl FingerprintComparison {
+    /// The metrics outside their range or missing.
+    pub fn outside(&self) -> impl Iterator<Item = &MetricComparison> {
+        self.metrics
+            .iter()
+            .filter(|m| m.status != RangeStatus::Within)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fingerprint::{CheckResult, FingerprintChecks};
+
+    fn result(data: serde_json::Value) -> CheckResult {
+        CheckResult { passed: true, data }
+    }
+
+    #[test]
+    fn test_compare_against_reference() {
+        let report = FingerprintReport::from_checks(FingerprintChecks {
+            clock_drift: result(serde_json::json!({ "cv": 0.0123, "drift_stdev": 4 })),
+            cache_timing: result(serde_json::json!({ "l2_l1_ratio": 1.05 })),
+            simd_identity: result(serde_json::json!({ "simd_flags_count": 12 })),
+            thermal_drift: CheckResult::skipped(),
+            instruction_jitter: result(serde_json::json!({ "int_stdev": 5 })),
+            anti_emulation: result(serde_json::json!({ "indicator_count": 0 })),
+        });
+        let reference: FingerprintReference = serde_json::from_value(serde_json::json!({
+            "arch": "x86_64",
+            "checks": {
+                "clock_drift": { "cv": { "min": 0.001, "max": 0.5 }, "drift_stdev": { "min": 10 } },
+                "cache_timing": { "l2_l1_ratio": { "min": 1.2, "max": 4.0 } },
+                "thermal_drift": { "drift_ratio": { "min": 0.5 } },
+                "anti_emulation": { "indicator_count": { "max": 0 } },
+            },
+        }))
+        .unwrap();
+
+        let comparison = reference.compare(&report);
+        let statuses: Vec<_> = comparison
+            .metrics
+            .iter()
+            .map(|m| (m.check.as_str(), m.metric.as_str(), m.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("clock_drift", "cv", RangeStatus::Within),
+                ("clock_drift", "drift_stdev", RangeStatus::Below),
+                ("cache_timing", "l2_l1_ratio", RangeStatus::Below),
+                ("thermal_drift", "drift_ratio", RangeStatus::Missing),
+                ("anti_emulation", "indicator_count", RangeStatus::Within),
+            ]
+        );
+        assert_eq!(comparison.outside_count, 3);
+        assert_eq!(comparison.outside().next().unwrap().value, Some(4.0));
+        let at_most_one = MetricRange {
+            min: None,
+            max: Some(1.0),
+        };
+        assert_eq!(at_most_one.status(1.5), RangeStatus::Above);
+    }
+}
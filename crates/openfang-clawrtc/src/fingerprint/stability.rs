@@ -0,0 +1,168 @@
+//! Repeated fingerprint sampling for stability analysis.
+//!
+//! A single run can be noisy on borderline hardware. Running the full check set
+//! several times shows how often each check passes and how much its metrics move.
+
+use super::{validate_all_checks, FingerprintReport};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Aggregate of several fingerprint runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StabilityReport {
+    /// Number of runs aggregated.
+    pub runs: usize,
+    /// Fraction of runs (0.0–1.0) in which every check passed.
+    pub all_passed_rate: f64,
+    /// Per-check stability, keyed by check name.
+    pub checks: BTreeMap<String, CheckStability>,
+}
+
+/// Stability of a single check across runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckStability {
+    /// Fraction of runs (0.0–1.0) in which this check passed.
+    pub pass_rate: f64,
+    /// Statistics for each numeric field of the check's `data`.
+    pub metrics: BTreeMap<String, MetricStats>,
+}
+
+/// Summary statistics for one numeric metric.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricStats {
+    pub mean: f64,
+    pub variance: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl StabilityReport {
+    /// Aggregate already-collected reports.
+    pub fn from_reports(reports: &[FingerprintReport]) -> Self {
+        let runs = reports.len();
+        let all_passed = reports.iter().filter(|r| r.all_passed).count();
+
+        let mut passes: BTreeMap<String, usize> = BTreeMap::new();
+        let mut samples: BTreeMap<String, BTreeMap<String, Vec<f64>>> = BTreeMap::new();
+        for report in reports {
+            for (name, result) in report.checks.iter() {
+                *passes.entry(name.to_string()).or_default() += usize::from(result.passed);
+                let metrics = samples.entry(name.to_string()).or_default();
+                if let Some(obj) = result.data.as_object() {
+                    for (key, value) in obj {
+                        if value.is_number() {
+                            if let Some(v) = value.as_f64() {
+                                metrics.entry(key.clone()).or_default().push(v);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let checks = samples
+            .into_iter()
+            .map(|(name, metrics)| {
+                let pass_rate = rate(passes.get(&name).copied().unwrap_or(0), runs);
+                let metrics = metrics
+                    .into_iter()
+                    .map(|(key, values)| (key, MetricStats::from_samples(&values)))
+                    .collect();
+                (name, CheckStability { pass_rate, metrics })
+            })
+            .collect();
+
+        Self {
+            runs,
+            all_passed_rate: rate(all_passed, runs),
+            checks,
+        }
+    }
+}
+
+impl MetricStats {
+    fn from_samples(values: &[f64]) -> Self {
+        let n = values.len().max(1) as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        Self {
+            mean,
+            variance,
+            min: values.iter().cloned().fold(f64::INFINITY, f64::min),
+            max: values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+fn rate(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+/// Run the full check set `runs` times, sleeping `interval` between runs.
+///
+/// This is CPU-intensive and blocks. In async contexts, wrap in `tokio::task::spawn_blocking`.
+pub fn sample_over_time(runs: usize, interval: Duration) -> StabilityReport {
+    let mut reports = Vec::with_capacity(runs);
+    for i in 0..runs {
+        reports.push(validate_all_checks());
+        if i + 1 < runs {
+            std::thread::sleep(interval);
+        }
+    }
+    StabilityReport::from_reports(&reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fingerprint::{CheckResult, FingerprintChecks};
+
+    fn result(passed: bool, data: serde_json::Value) -> CheckResult {
+        CheckResult { passed, data }
+    }
+
+    fn report(cv: f64, clock_passed: bool) -> FingerprintReport {
+        let ok = || result(true, serde_json::json!({}));
+        FingerprintReport::from_checks(FingerprintChecks {
+            clock_drift: result(clock_passed, serde_json::json!({ "cv": cv, "label": "x" })),
+            cache_timing: ok(),
+            simd_identity: ok(),
+            thermal_drift: ok(),
+            instruction_jitter: ok(),
+            anti_emulation: ok(),
+        })
+    }
+
+    #[test]
+    fn test_aggregates_two_runs() {
+        let stability = StabilityReport::from_reports(&[report(0.01, true), report(0.03, false)]);
+        assert_eq!(stability.runs, 2);
+        assert_eq!(stability.all_passed_rate, 0.5);
+        assert_eq!(stability.checks.len(), 6);
+
+        let clock = &stability.checks["clock_drift"];
+        assert_eq!(clock.pass_rate, 0.5);
+        let cv = &clock.metrics["cv"];
+        assert!((cv.mean - 0.02).abs() < 1e-12);
+        assert!((cv.variance - 0.0001).abs() < 1e-12);
+        assert_eq!(cv.min, 0.01);
+        assert_eq!(cv.max, 0.03);
+        // Non-numeric fields are not treated as metrics
+        assert!(!clock.metrics.contains_key("label"));
+
+        assert_eq!(stability.checks["cache_timing"].pass_rate, 1.0);
+    }
+
+    #[test]
+    fn test_empty_reports() {
+        let stability = StabilityReport::from_reports(&[]);
+        assert_eq!(stability.runs, 0);
+        assert_eq!(stability.all_passed_rate, 0.0);
+        assert!(stability.checks.is_empty());
+    }
+}
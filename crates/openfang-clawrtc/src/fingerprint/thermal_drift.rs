@@ -3,7 +3,7 @@
 //! Measures timing variance cold vs hot. Real hardware shows thermal drift
 //! as the CPU heats up; VMs show uniform timing regardless of load.
 
-use super::CheckResult;
+use super::{cancelled, discard_warmup, CheckResult, CheckThresholds};
 use sha2::{Digest, Sha256};
 use std::time::Instant;
 
@@ -12,10 +12,32 @@ const HASH_OPS: usize = 10_000;
 const WARMUP_ROUNDS: usize = 100;
 const WARMUP_OPS: usize = 50_000;
 
+/// Leading cold and hot samples discarded as warm-up by [`check`]: none, so
+/// the reported metrics are unchanged unless warm-up is asked for.
+pub const DEFAULT_WARMUP_SAMPLES: usize = 0;
+
 pub fn check() -> CheckResult {
+    check_with_warmup(DEFAULT_WARMUP_SAMPLES)
+}
+
+/// Run the check, discarding the first `warmup` samples of each phase before
+/// computing statistics. The heating load between phases is unaffected.
+pub fn check_with_warmup(warmup: usize) -> CheckResult {
+    run(warmup, &CheckThresholds::default())
+}
+
+/// Run the check, judging the measurements against `thresholds`.
+pub fn check_with_thresholds(thresholds: &CheckThresholds) -> CheckResult {
+    run(DEFAULT_WARMUP_SAMPLES, thresholds)
+}
+
+fn run(warmup: usize, thresholds: &CheckThresholds) -> CheckResult {
     // Collect cold timing samples
     let mut cold_times = Vec::with_capacity(SAMPLES);
     for i in 0..SAMPLES {
+        if cancelled() {
+            return CheckResult::timed_out();
+        }
         let data = format!("cold_{i}");
         let start = Instant::now();
         for _ in 0..HASH_OPS {
@@ -26,6 +48,9 @@ pub fn check() -> CheckResult {
 
     // Heat the CPU with sustained load
     for _ in 0..WARMUP_ROUNDS {
+        if cancelled() {
+            return CheckResult::timed_out();
+        }
         for _ in 0..WARMUP_OPS {
             std::hint::black_box(Sha256::digest(b"warmup"));
         }
@@ -34,6 +59,9 @@ pub fn check() -> CheckResult {
     // Collect hot timing samples
     let mut hot_times = Vec::with_capacity(SAMPLES);
     for i in 0..SAMPLES {
+        if cancelled() {
+            return CheckResult::timed_out();
+        }
         let data = format!("hot_{i}");
         let start = Instant::now();
         for _ in 0..HASH_OPS {
@@ -42,33 +70,43 @@ pub fn check() -> CheckResult {
         hot_times.push(start.elapsed().as_nanos() as f64);
     }
 
-    let cold_avg = cold_times.iter().sum::<f64>() / cold_times.len() as f64;
-    let hot_avg = hot_times.iter().sum::<f64>() / hot_times.len() as f64;
-    let cold_stdev = stdev(&cold_times);
-    let hot_stdev = stdev(&hot_times);
+    let cold_measured = discard_warmup(&cold_times, warmup);
+    let hot_measured = discard_warmup(&hot_times, warmup);
+
+    let cold_avg = cold_measured.iter().sum::<f64>() / cold_measured.len() as f64;
+    let hot_avg = hot_measured.iter().sum::<f64>() / hot_measured.len() as f64;
+    let cold_stdev = stdev(cold_measured);
+    let hot_stdev = stdev(hot_measured);
     let drift_ratio = if cold_avg > 0.0 {
         hot_avg / cold_avg
     } else {
         0.0
     };
 
-    let data = serde_json::json!({
+    let mut data = serde_json::json!({
         "cold_avg_ns": cold_avg as i64,
         "hot_avg_ns": hot_avg as i64,
         "cold_stdev": cold_stdev as i64,
         "hot_stdev": hot_stdev as i64,
         "drift_ratio": (drift_ratio * 10_000.0).round() / 10_000.0,
+        "sample_count": SAMPLES,
     });
-
-    // PASS if there's any thermal variance at all
-    let valid = cold_stdev > 0.0 || hot_stdev > 0.0;
+    if warmup > 0 {
+        data["warmup_discarded"] = (SAMPLES - cold_measured.len()).into();
+    }
 
     CheckResult {
-        passed: valid,
+        passed: passes(cold_stdev, hot_stdev, thresholds),
         data,
     }
 }
 
+/// PASS if there's enough thermal variance in either phase.
+pub(super) fn passes(cold_stdev: f64, hot_stdev: f64, thresholds: &CheckThresholds) -> bool {
+    let min = thresholds.thermal_min_stdev;
+    cold_stdev > min || hot_stdev > min
+}
+
 fn stdev(values: &[f64]) -> f64 {
     if values.len() < 2 {
         return 0.0;
@@ -86,5 +124,7 @@ mod tests {
     fn test_thermal_drift_runs() {
         let result = check();
         assert!(result.data["cold_avg_ns"].as_i64().unwrap() > 0);
+        assert_eq!(result.data["sample_count"], SAMPLES);
+        assert!(result.data.get("warmup_discarded").is_none());
     }
 }
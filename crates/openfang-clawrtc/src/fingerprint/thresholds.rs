@@ -0,0 +1,120 @@
+//! Tunable pass/fail thresholds for the fingerprint checks.
+//!
+//! The defaults match the values the checks have always used. Operators on
+//! unusual hardware (very stable oscillators, flat cache hierarchies) can
+//! loosen them instead of patching the checks.
+
+use super::AntiEmulationStrictness;
+use serde::{Deserialize, Serialize};
+
+/// Pass/fail thresholds for each fingerprint check.
+///
+/// SIMD identity has no numeric threshold: it passes whenever any SIMD
+/// capability or CPU flag is reported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckThresholds {
+    /// Clock drift: minimum coefficient of variation of SHA-256 timings.
+    pub clock_drift_min_cv: f64,
+    /// Clock drift: drift stdev (ns) between consecutive samples must exceed this.
+    pub clock_drift_min_drift_stdev: f64,
+    /// Cache timing: L2/L1 or L3/L2 latency ratio must reach this.
+    pub cache_min_ratio: f64,
+    /// Thermal drift: cold or hot timing stdev (ns) must exceed this.
+    pub thermal_min_stdev: f64,
+    /// Instruction jitter: int, FP or branch timing stdev (ns) must exceed this.
+    pub jitter_min_stdev: f64,
+    /// Anti-emulation: how VM indicators are turned into a verdict.
+    pub anti_emulation: AntiEmulationStrictness,
+}
+
+impl Default for CheckThresholds {
+    fn default() -> Self {
+        Self {
+            clock_drift_min_cv: 0.0001,
+            clock_drift_min_drift_stdev: 0.0,
+            cache_min_ratio: 1.01,
+            thermal_min_stdev: 0.0,
+            jitter_min_stdev: 0.0,
+            anti_emulation: AntiEmulationStrictness::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fingerprint::{cache_timing, clock_drift, instruction_jitter, thermal_drift};
+
+    #[test]
+    fn test_partial_config_keeps_defaults() {
+        let t: CheckThresholds =
+            serde_json::from_value(serde_json::json!({ "cache_min_ratio": 1.0 })).unwrap();
+        assert_eq!(t.cache_min_ratio, 1.0);
+        assert_eq!(t.clock_drift_min_cv, 0.0001);
+        assert_eq!(t.anti_emulation, AntiEmulationStrictness::Strict);
+    }
+
+    #[test]
+    fn test_loosened_thresholds_pass_borderline_readings() {
+        let defaults = CheckThresholds::default();
+        type Reading = fn(&CheckThresholds) -> bool;
+        // (check, thresholds that reject the reading, thresholds that accept it, reading)
+        let cases: [(&str, CheckThresholds, CheckThresholds, Reading); 4] = [
+            (
+                "clock drift",
+                defaults.clone(),
+                CheckThresholds {
+                    clock_drift_min_cv: 0.00001,
+                    ..defaults.clone()
+                },
+                |t| clock_drift::passes(0.00005, 12.0, t),
+            ),
+            (
+                "cache timing",
+                defaults.clone(),
+                CheckThresholds {
+                    cache_min_ratio: 1.001,
+                    ..defaults.clone()
+                },
+                |t| cache_timing::passes([1.0, 1.005, 1.008], 1.005, 1.003, t),
+            ),
+            (
+                "thermal drift",
+                CheckThresholds {
+                    thermal_min_stdev: 50.0,
+                    ..defaults.clone()
+                },
+                CheckThresholds {
+                    thermal_min_stdev: 35.0,
+                    ..defaults.clone()
+                },
+                |t| thermal_drift::passes(30.0, 40.0, t),
+            ),
+            (
+                "instruction jitter",
+                CheckThresholds {
+                    jitter_min_stdev: 2.0,
+                    ..defaults.clone()
+                },
+                defaults.clone(),
+                |t| instruction_jitter::passes([1.5, 0.0, 1.0], t),
+            ),
+        ];
+        for (check, strict, loose, reading) in cases {
+            assert!(!reading(&strict), "{check} passed under strict thresholds");
+            assert!(reading(&loose), "{check} failed under loosened thresholds");
+        }
+
+        // Degenerate readings fail however loose the thresholds
+        let loosest = CheckThresholds {
+            clock_drift_min_cv: 0.0,
+            cache_min_ratio: 0.0,
+            ..defaults
+        };
+        assert!(!clock_drift::passes(0.00005, 0.0, &loosest));
+        let zero_latency = [0.0, 1.0, 1.0];
+        assert!(!cache_timing::passes(zero_latency, 1.005, 1.003, &loosest));
+        assert!(!instruction_jitter::passes([0.0; 3], &loosest));
+    }
+}
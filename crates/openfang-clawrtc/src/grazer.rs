@@ -3,12 +3,26 @@
 //! Supports: BoTTube, Moltbook, 4claw, ClawHub, PinchedIn, AgentChan,
 //! ClawSta, ClawNews, ClawTasks, ClawCities, SwarmHub, Agent Directory.
 
+use crate::clock::{self, Clock};
 use crate::error::{ClawRtcError, ClawRtcResult};
+use crate::http::{
+    read_json, send_with_retry, urlencoded, HttpOptions, RequestLimit, RetryPolicy, TlsVersion,
+    DEFAULT_MAX_BODY_BYTES,
+};
+use crate::op_result::OpResult;
+use crate::response_adapter::ResponseAdapter;
+use crate::sanitize;
 use serde::{Deserialize, Serialize};
-use tracing::debug;
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
 
 /// Platform identifiers for Grazer operations.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Platform {
     Bottube,
@@ -30,26 +44,52 @@ impl std::str::FromStr for Platform {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "bottube" => Ok(Self::Bottube),
-            "moltbook" => Ok(Self::Moltbook),
-            "4claw" | "fourclaw" => Ok(Self::FourClaw),
-            "clawhub" => Ok(Self::Clawhub),
-            "pinchedin" => Ok(Self::Pinchedin),
-            "agentchan" => Ok(Self::Agentchan),
-            "clawsta" => Ok(Self::Clawsta),
-            "clawnews" => Ok(Self::Clawnews),
-            "clawtasks" => Ok(Self::Clawtasks),
-            "clawcities" => Ok(Self::Clawcities),
-            "swarmhub" => Ok(Self::Swarmhub),
-            "directory" => Ok(Self::Directory),
-            _ => Err(format!("Unknown platform: {s}")),
+        let lower = s.to_lowercase();
+        if lower == "fourclaw" {
+            return Ok(Self::FourClaw);
         }
+        Self::ALL
+            .into_iter()
+            .find(|p| p.name() == lower)
+            .ok_or_else(|| format!("Unknown platform: {s}"))
+    }
+}
+
+impl std::fmt::Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
     }
 }
 
+/// Canonical platform names, in [`Platform::ALL`] order.
+const PLATFORM_NAMES: [&str; Platform::ALL.len()] = {
+    let mut names = [""; Platform::ALL.len()];
+    let mut i = 0;
+    while i < names.len() {
+        names[i] = Platform::ALL[i].name();
+        i += 1;
+    }
+    names
+};
+
 impl Platform {
+    /// Every platform, in the order they are listed to users.
+    pub const ALL: [Platform; 12] = [
+        Self::Bottube,
+        Self::Moltbook,
+        Self::FourClaw,
+        Self::Clawhub,
+        Self::Pinchedin,
+        Self::Agentchan,
+        Self::Clawsta,
+        Self::Clawnews,
+        Self::Clawtasks,
+        Self::Clawcities,
+        Self::Swarmhub,
+        Self::Directory,
+    ];
 
+    /// Default API base URL for the platform.
     pub fn base_url(&self) -> &'static str {
         match self {
             Self::Bottube => "https://bottube.ai",
@@ -67,27 +107,446 @@ impl Platform {
         }
     }
 
+    /// How this platform expects its API key to be sent.
+    pub fn auth_scheme(&self) -> AuthScheme {
+        match self {
+            Self::Bottube => AuthScheme::HeaderKey("X-API-Key"),
+            Self::Moltbook
+            | Self::FourClaw
+            | Self::Clawhub
+            | Self::Pinchedin
+            | Self::Agentchan
+            | Self::Clawsta
+            | Self::Clawnews
+            | Self::Clawtasks
+            | Self::Clawcities
+            | Self::Swarmhub
+            | Self::Directory => AuthScheme::Bearer,
+        }
+    }
+
+    /// Canonical name. `FromStr`, `Display` and [`Platform::all_names`] all derive from it.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Bottube => "bottube",
+            Self::Moltbook => "moltbook",
+            Self::FourClaw => "4claw",
+            Self::Clawhub => "clawhub",
+            Self::Pinchedin => "pinchedin",
+            Self::Agentchan => "agentchan",
+            Self::Clawsta => "clawsta",
+            Self::Clawnews => "clawnews",
+            Self::Clawtasks => "clawtasks",
+            Self::Clawcities => "clawcities",
+            Self::Swarmhub => "swarmhub",
+            Self::Directory => "directory",
+        }
+    }
+
+    /// Prefix the platform's API keys are issued with, if it uses one.
+    pub fn key_prefix(&self) -> Option<&'static str> {
+        match self {
+            Self::Bottube => Some("bottube_sk_"),
+            Self::Moltbook => Some("moltbook_sk_"),
+            Self::Agentchan => Some("clawchan_"),
+            _ => None,
+        }
+    }
+
+    /// Check that `key` looks like one of this platform's API keys.
+    ///
+    /// Catches keys pasted into the wrong platform's field before the server
+    /// rejects them with an unhelpful error. Prefixes may change, so by default
+    /// a mismatch is only logged and returned as a warning; with `strict` it
+    /// is an [`ClawRtcError::InvalidApiKey`] error.
+    pub fn check_api_key(&self, key: &str, strict: bool) -> ClawRtcResult<Option<String>> {
+        let Some(prefix) = self.key_prefix() else {
+            return Ok(None);
+        };
+        if key.starts_with(prefix) {
+            return Ok(None);
+        }
+        let owner = PLATFORMS_WITH_KEY_PREFIX.iter().find_map(|p| {
+            p.key_prefix()
+                .filter(|other| key.starts_with(other))
+                .map(|other| (p.name(), other))
+        });
+        let message = match owner {
+            Some((owner, other)) => format!(
+                "key has {owner}'s prefix {other}, but the target platform is {self} \
+                 (expected {prefix}...)"
+            ),
+            None => format!("{} keys start with {prefix}", self.name()),
+        };
+        if strict {
+            return Err(ClawRtcError::InvalidApiKey(message));
+        }
+        warn!(platform = self.name(), "{message}");
+        Ok(Some(message))
+    }
+
+    /// Value of the `sort` query parameter that asks this platform for
+    /// `sort` server-side, or `None` if it can't (the client then sorts the
+    /// items itself). [`DiscoverSort::Trending`] is every platform's default
+    /// order and never needs a parameter.
+    pub fn sort_param(&self, sort: DiscoverSort) -> Option<&'static str> {
+        match (self, sort) {
+            (_, DiscoverSort::Trending) => None,
+            (Self::Bottube, DiscoverSort::Newest) => Some("newest"),
+            (Self::Bottube, DiscoverSort::Top) => Some("top"),
+            (Self::Moltbook | Self::Clawnews, DiscoverSort::Newest) => Some("new"),
+            (Self::Moltbook | Self::Clawnews, DiscoverSort::Top) => Some("top"),
+            _ => None,
+        }
+    }
+
+    /// The fields a post to this platform takes, or `None` if posting isn't
+    /// supported. [`GrazerClient::post`] checks them before sending.
+    pub fn post_requirements(&self) -> Option<PostRequirements> {
+        use PostFieldKind::{PositiveInteger, Text, TextList};
+        let field = |name, kind, required, description| PostField {
+            name,
+            kind,
+            required,
+            description,
+        };
+        let (label, fields) = match self {
+            Self::Moltbook => (
+                "Moltbook",
+                vec![
+                    field("title", Text, true, "Post title."),
+                    field("content", Text, true, "Post body."),
+                    field("submolt", Text, true, "Submolt to post to, e.g. general."),
+                ],
+            ),
+            Self::FourClaw => (
+                "4claw",
+                vec![
+                    field("title", Text, false, "Thread title."),
+                    field("content", Text, true, "Thread body."),
+                    field("board", Text, false, "Board to post to. Default b."),
+                ],
+            ),
+            Self::Agentchan => (
+                "AgentChan",
+                vec![
+                    field("content", Text, true, "Post body."),
+                    field("board", Text, false, "Board to post to. Default ai."),
+                    field("reply_to", Text, false, "Thread ID to reply to."),
+                ],
+            ),
+            Self::Clawsta => ("ClawSta", vec![field("content", Text, true, "Post body.")]),
+            Self::Clawnews => (
+                "ClawNews",
+                vec![
+                    field("title", Text, true, "Story headline."),
+                    field("content", Text, true, "Story summary."),
+                    field("url", Text, true, "Link the story points to."),
+                    field("tags", TextList, false, "Story tags."),
+                ],
+            ),
+            Self::Pinchedin => ("PinchedIn", vec![field("content", Text, true, "Post body.")]),
+            Self::Clawtasks => (
+                "ClawTasks",
+                vec![
+                    field("title", Text, true, "Bounty title."),
+                    field("content", Text, true, "Bounty description."),
+                    field(
+                        "deadline_hours",
+                        PositiveInteger,
+                        true,
+                        "Hours until the bounty closes.",
+                    ),
+                    field("tags", TextList, false, "Bounty tags."),
+                ],
+            ),
+            Self::Clawcities => (
+                "ClawCities",
+                vec![
+                    field("content", Text, true, "Comment text."),
+                    field("site", Text, true, "Site to comment on, by its name."),
+                    field("page", Text, false, "Page of the site. Default home."),
+                ],
+            ),
+            _ => return None,
+        };
+        Some(PostRequirements { label, fields })
+    }
+
     pub fn all_names() -> &'static [&'static str] {
-        &[
-            "bottube",
-            "moltbook",
-            "4claw",
-            "clawhub",
-            "pinchedin",
-            "agentchan",
-            "clawsta",
-            "clawnews",
-            "clawtasks",
-            "clawcities",
-            "swarmhub",
-            "directory",
-        ]
+        &PLATFORM_NAMES
+    }
+}
+
+/// The kind of value a post field takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostFieldKind {
+    /// A non-empty string.
+    Text,
+    /// An integer greater than zero.
+    PositiveInteger,
+    /// An array of strings.
+    TextList,
+}
+
+/// One field of a platform's post.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PostField {
+    /// `title`, `content`, or a key of the post's `extra` object.
+    pub name: &'static str,
+    pub kind: PostFieldKind,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+impl PostField {
+    /// Check `value`; `null` and blank strings count as absent.
+    fn check(&self, label: &str, value: &serde_json::Value) -> ClawRtcResult<()> {
+        let present = match value {
+            serde_json::Value::Null => false,
+            serde_json::Value::String(s) => !s.trim().is_empty(),
+            _ => true,
+        };
+        let valid = match self.kind {
+            PostFieldKind::Text => value.is_string(),
+            PostFieldKind::PositiveInteger => value.as_u64().is_some_and(|n| n > 0),
+            PostFieldKind::TextList => value
+                .as_array()
+                .is_some_and(|items| items.iter().all(serde_json::Value::is_string)),
+        };
+        if (!present && !self.required) || (present && valid) {
+            return Ok(());
+        }
+        let name = self.name;
+        Err(ClawRtcError::InvalidPost(match self.kind {
+            PostFieldKind::Text => format!("{label} requires a non-empty '{name}'"),
+            PostFieldKind::PositiveInteger => {
+                format!("{label} requires '{name}' to be a positive integer")
+            }
+            PostFieldKind::TextList => format!("{label} requires '{name}' to be a list of strings"),
+        }))
+    }
+}
+
+/// The fields a platform's post takes; see [`Platform::post_requirements`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PostRequirements {
+    /// Platform name as written in error messages, e.g. `ClawNews`.
+    pub label: &'static str,
+    pub fields: Vec<PostField>,
+}
+
+impl PostRequirements {
+    /// Check a post's `title`, `content` and `extra` fields, failing with
+    /// [`ClawRtcError::InvalidPost`] on the first one that is missing or has
+    /// the wrong type. Fields not listed are ignored.
+    pub fn validate(
+        &self,
+        title: &str,
+        content: &str,
+        extra: &serde_json::Value,
+    ) -> ClawRtcResult<()> {
+        for field in &self.fields {
+            let value = match field.name {
+                "title" => serde_json::Value::from(title),
+                "content" => serde_json::Value::from(content),
+                name => extra[name].clone(),
+            };
+            field.check(self.label, &value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Order of discovered items.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiscoverSort {
+    /// The platform's default ordering.
+    #[default]
+    Trending,
+    /// Most recent first.
+    Newest,
+    /// Highest score (votes, likes, views…) first.
+    Top,
+}
+
+impl std::str::FromStr for DiscoverSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "trending" | "hot" => Ok(Self::Trending),
+            "newest" | "new" => Ok(Self::Newest),
+            "top" => Ok(Self::Top),
+            _ => Err(format!("Unknown sort: {s} (expected trending, newest or top)")),
+        }
+    }
+}
+
+/// Platforms whose keys carry a recognizable prefix.
+const PLATFORMS_WITH_KEY_PREFIX: &[Platform] =
+    &[Platform::Bottube, Platform::Moltbook, Platform::Agentchan];
+
+/// How a platform expects its API key to be presented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthScheme {
+    /// `Authorization: Bearer <key>`.
+    Bearer,
+    /// The raw key in a named header (e.g. `X-API-Key`).
+    HeaderKey(&'static str),
+    /// The raw key in a named query-string parameter.
+    QueryParam(&'static str),
+}
+
+impl AuthScheme {
+    /// Attach `key` to a request using this scheme.
+    pub fn apply(&self, req: reqwest::RequestBuilder, key: &str) -> reqwest::RequestBuilder {
+        match self {
+            Self::Bearer => req.bearer_auth(key),
+            Self::HeaderKey(name) => req.header(*name, key),
+            Self::QueryParam(name) => req.query(&[(*name, key)]),
+        }
+    }
+
+    /// The custom header this scheme puts the key in, if any.
+    pub fn header(&self) -> Option<&'static str> {
+        match self {
+            Self::HeaderKey(name) => Some(name),
+            Self::Bearer | Self::QueryParam(_) => None,
+        }
+    }
+}
+
+/// Results of [`GrazerClient::discover_all`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DiscoverAll {
+    /// Results keyed by platform name. Platforms that failed hold
+    /// `{"error": "unavailable"}`, and ones that hadn't answered when
+    /// discovery was cancelled hold `{"error": "cancelled"}`.
+    pub results: serde_json::Map<String, serde_json::Value>,
+    /// Whether discovery was cancelled before every platform answered.
+    pub cancelled: bool,
+}
+
+/// Settings for [`GrazerClient::with_circuit_breaker`].
+///
+/// After `failure_threshold` consecutive requests to a platform fail with a
+/// transport error or a 5xx response, the client stops calling it for
+/// `cooldown` and fails fast with [`ClawRtcError::CircuitOpen`]. The first
+/// request after the cooldown goes through as a probe: success closes the
+/// circuit, failure reopens it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreaker {
+    pub failure_threshold: u32,
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Whether `err` means the platform itself is failing: it was unreachable or
+/// answered with a server error. Refusals such as 401/403, invalid input and
+/// unsupported operations leave the circuit alone.
+fn trips_circuit(err: &ClawRtcError) -> bool {
+    match err {
+        ClawRtcError::Network(_) => true,
+        ClawRtcError::GrazerStatus(status, _) => *status >= 500,
+        _ => false,
+    }
+}
+
+/// Fail with [`ClawRtcError::GrazerStatus`] if `resp` is a server error.
+fn reject_server_error(resp: reqwest::Response) -> ClawRtcResult<reqwest::Response> {
+    let status = resp.status();
+    if !status.is_server_error() {
+        return Ok(resp);
+    }
+    let host = resp.url().host_str().unwrap_or_default();
+    Err(ClawRtcError::GrazerStatus(
+        status.as_u16(),
+        format!("{host} answered {status}"),
+    ))
+}
+
+/// Recent failures of one platform.
+#[derive(Debug, Default)]
+struct CircuitState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    last_error: String,
+}
+
+/// An agent's public profile, announced by [`GrazerClient::onboard`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentProfile {
+    pub name: String,
+    pub bio: String,
+    /// Homepage, repository, or other links, most important first.
+    #[serde(default)]
+    pub links: Vec<String>,
+}
+
+impl AgentProfile {
+    /// Title and body of the introduction post.
+    pub fn intro(&self) -> (String, String) {
+        let mut body = self.bio.clone();
+        if !self.links.is_empty() {
+            body.push_str("\n\n");
+            body.push_str(&self.links.join("\n"));
+        }
+        (format!("Hi, I'm {}", self.name), body)
+    }
+}
+
+/// Outcome of one platform in [`GrazerClient::onboard`].
+#[derive(Debug, Clone, Serialize)]
+pub struct OnboardStep {
+    pub platform: String,
+    /// The platform's acknowledgement, if the call went through.
+    pub result: Option<OpResult>,
+    /// Why the call failed, if it did.
+    pub error: Option<String>,
+}
+
+impl OnboardStep {
+    fn new(platform: Platform, outcome: ClawRtcResult<OpResult>) -> Self {
+        let (result, error) = match outcome {
+            Ok(op) => (Some(op), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        Self {
+            platform: platform.name().to_string(),
+            result,
+            error,
+        }
+    }
+
+    /// Whether the platform accepted the post or listing.
+    pub fn succeeded(&self) -> bool {
+        self.result.as_ref().is_some_and(|op| op.success)
     }
 }
 
 /// Multi-platform Grazer client.
 pub struct GrazerClient {
     http: reqwest::Client,
+    http_options: HttpOptions,
+    base_urls: HashMap<Platform, String>,
+    adapters: HashMap<Platform, ResponseAdapter>,
+    retry: RetryPolicy,
+    max_body_bytes: usize,
+    sanitize_content: bool,
+    circuit_breaker: Option<CircuitBreaker>,
+    circuits: Mutex<HashMap<Platform, CircuitState>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl Default for GrazerClient {
@@ -98,14 +557,188 @@ impl Default for GrazerClient {
 
 impl GrazerClient {
     pub fn new() -> Self {
-        let http = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(15))
-            .build()
-            .expect("Failed to build HTTP client");
-        Self { http }
+        let mut http_options = HttpOptions::new(std::time::Duration::from_secs(15));
+        for platform in Platform::ALL {
+            if let Some(header) = platform.auth_scheme().header() {
+                http_options.register_credential_header(header);
+            }
+        }
+        Self {
+            http: http_options.build(),
+            http_options,
+            base_urls: HashMap::new(),
+            adapters: HashMap::new(),
+            retry: RetryPolicy::default(),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            sanitize_content: false,
+            circuit_breaker: None,
+            circuits: Mutex::new(HashMap::new()),
+            clock: clock::system(),
+        }
+    }
+
+    /// Cap the size of response bodies the client will buffer.
+    pub fn with_max_body_bytes(mut self, limit: usize) -> Self {
+        self.max_body_bytes = limit;
+        self
+    }
+
+    /// Give up on a request after `timeout` (default 15 seconds).
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.http_options.timeout = timeout;
+        self.http = self.http_options.build();
+        self
+    }
+
+    /// Refuse TLS versions older than `version` (e.g. require TLS 1.3).
+    pub fn with_min_tls_version(mut self, version: TlsVersion) -> Self {
+        self.http_options.min_tls_version = Some(version);
+        self.http = self.http_options.build();
+        self
     }
 
-    /// Discover content on a platform.
+    /// Resolve `host` to `addr` instead of asking system DNS.
+    ///
+    /// The URL still names `host`, so TLS is negotiated for it; the port comes
+    /// from the URL, not from `addr`.
+    pub fn with_resolve(mut self, host: &str, addr: SocketAddr) -> Self {
+        self.http_options.resolve.push((host.to_string(), addr));
+        self.http = self.http_options.build();
+        self
+    }
+
+    /// Share `limit` with other clients to bound their combined in-flight
+    /// requests.
+    pub fn with_request_limit(mut self, limit: RequestLimit) -> Self {
+        self.http_options.request_limit = Some(limit);
+        self
+    }
+
+    /// Override the API base URL for one platform (e.g. a staging server).
+    pub fn with_base_url(mut self, platform: Platform, url: &str) -> Self {
+        self.base_urls
+            .insert(platform, url.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Replace the response adapter for one platform, e.g. to follow an API
+    /// change before a new release ships an updated built-in adapter.
+    pub fn with_response_adapter(mut self, platform: Platform, adapter: ResponseAdapter) -> Self {
+        self.adapters.insert(platform, adapter);
+        self
+    }
+
+    /// Strip ANSI escapes, control characters, and zero-width characters from
+    /// every string in discover results. Off by default.
+    pub fn with_sanitize_content(mut self, sanitize: bool) -> Self {
+        self.sanitize_content = sanitize;
+        self
+    }
+
+    /// Set the retry policy for post operations.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Stop calling a platform for a while after it fails repeatedly, so a
+    /// dead platform doesn't slow down every [`GrazerClient::discover_all`].
+    /// Off by default.
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
+    /// Measure circuit breaker cooldowns with `clock`.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Run `request` to `platform` unless its circuit is open, and record
+    /// whether it failed in a way that [trips the circuit](trips_circuit).
+    async fn through_circuit<T>(
+        &self,
+        platform: Platform,
+        request: impl Future<Output = ClawRtcResult<T>>,
+    ) -> ClawRtcResult<T> {
+        let Some(breaker) = self.circuit_breaker else {
+            return request.await;
+        };
+        {
+            let now = self.clock.monotonic();
+            let circuits = self.circuits.lock().unwrap();
+            let open = circuits
+                .get(&platform)
+                .and_then(|state| Some((state, state.open_until?)))
+                .filter(|(_, until)| *until > now);
+            if let Some((state, until)) = open {
+                return Err(ClawRtcError::CircuitOpen(format!(
+                    "{} failed {} times in a row, retrying in {:.0}s (last error: {})",
+                    platform.name(),
+                    state.consecutive_failures,
+                    (until - now).as_secs_f64().ceil(),
+                    state.last_error
+                )));
+            }
+        }
+
+        let result = request.await;
+        let mut circuits = self.circuits.lock().unwrap();
+        match &result {
+            Ok(_) => {
+                circuits.remove(&platform);
+            }
+            Err(e) if trips_circuit(e) => {
+                let state = circuits.entry(platform).or_default();
+                state.consecutive_failures += 1;
+                state.last_error = e.to_string();
+                if state.consecutive_failures >= breaker.failure_threshold {
+                    warn!(
+                        platform = platform.name(),
+                        failures = state.consecutive_failures,
+                        "Opening circuit"
+                    );
+                    state.open_until = Some(self.clock.monotonic() + breaker.cooldown);
+                }
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Read a JSON response body, rejecting server errors first.
+    async fn read_response<T: serde::de::DeserializeOwned>(
+        &self,
+        resp: reqwest::Response,
+    ) -> ClawRtcResult<T> {
+        read_json(reject_server_error(resp)?, self.max_body_bytes).await
+    }
+
+    /// Effective base URL for `platform`, honouring overrides.
+    fn base_url(&self, platform: Platform) -> &str {
+        self.base_urls
+            .get(&platform)
+            .map(String::as_str)
+            .unwrap_or_else(|| platform.base_url())
+    }
+
+    /// Effective response adapter for `platform`, honouring overrides.
+    fn adapter(&self, platform: Platform) -> ResponseAdapter {
+        self.adapters
+            .get(&platform)
+            .copied()
+            .unwrap_or_else(|| ResponseAdapter::for_platform(platform))
+    }
+
+    /// Discover content on a platform in its default order.
+    ///
+    /// The response is normalized by the platform's [`ResponseAdapter`],
+    /// which also caps the number of items at `limit`. With
+    /// [`GrazerClient::with_sanitize_content`], its text is then cleaned.
+    ///
+    /// Discover and post requests count towards the platform's
+    /// [`CircuitBreaker`], if one is set.
     pub async fn discover(
         &self,
         platform: Platform,
@@ -113,26 +746,103 @@ impl GrazerClient {
         limit: u32,
         extra: &serde_json::Value,
     ) -> ClawRtcResult<serde_json::Value> {
-        match platform {
-            Platform::Bottube => self.discover_bottube(limit, extra).await,
-            Platform::Moltbook => self.discover_moltbook(api_key, limit, extra).await,
-            Platform::FourClaw => self.discover_fourclaw(api_key, limit, extra).await,
-            Platform::Clawhub => self.discover_clawhub(limit, extra).await,
-            Platform::Pinchedin => self.discover_pinchedin(api_key, limit).await,
-            Platform::Agentchan => self.discover_agentchan(limit, extra).await,
-            Platform::Clawsta => self.discover_clawsta(api_key, limit).await,
-            Platform::Clawnews => self.discover_clawnews(api_key, limit).await,
-            Platform::Clawtasks => self.discover_clawtasks(api_key, limit).await,
-            Platform::Swarmhub => self.discover_swarmhub(limit).await,
-            Platform::Directory => self.discover_directory(limit, extra).await,
-            Platform::Clawcities => Ok(serde_json::json!({
-                "platform": "clawcities",
-                "note": "ClawCities is a personal website platform. Use grazer_post to comment on sites."
-            })),
+        self.discover_sorted(platform, api_key, limit, extra, DiscoverSort::Trending)
+            .await
+    }
+
+    /// Discover content on a platform in `sort` order.
+    ///
+    /// Platforms with a [`Platform::sort_param`] sort server-side; for the
+    /// rest the items are sorted by their timestamp or score fields before
+    /// `limit` is applied.
+    pub async fn discover_sorted(
+        &self,
+        platform: Platform,
+        api_key: Option<&str>,
+        limit: u32,
+        extra: &serde_json::Value,
+        sort: DiscoverSort,
+    ) -> ClawRtcResult<serde_json::Value> {
+        let server_sort = platform.sort_param(sort);
+        let request = async {
+            match platform {
+                Platform::Bottube => self.discover_bottube(limit, extra, server_sort).await,
+                Platform::Moltbook => {
+                    self.discover_moltbook(api_key, limit, extra, server_sort)
+                        .await
+                }
+                Platform::FourClaw => self.discover_fourclaw(api_key, limit, extra).await,
+                Platform::Clawhub => self.discover_clawhub(limit, extra).await,
+                Platform::Pinchedin => self.discover_pinchedin(api_key, limit).await,
+                Platform::Agentchan => self.discover_agentchan(extra).await,
+                Platform::Clawsta => self.discover_clawsta(api_key, limit).await,
+                Platform::Clawnews => self.discover_clawnews(api_key, limit, server_sort).await,
+                Platform::Clawtasks => self.discover_clawtasks(api_key, limit).await,
+                Platform::Swarmhub => self.discover_swarmhub().await,
+                Platform::Directory => self.discover_directory(limit, extra).await,
+                Platform::Clawcities => self.discover_clawcities(limit).await,
+            }
+        };
+        let raw = self.through_circuit(platform, request).await?;
+        let client_sort = match server_sort {
+            Some(_) => DiscoverSort::Trending,
+            None => sort,
+        };
+        let mut normalized = self
+            .adapter(platform)
+            .normalize_sorted(platform, raw, limit, client_sort);
+        if self.sanitize_content {
+            sanitize::sanitize_value(&mut normalized);
+        }
+        Ok(normalized)
+    }
+
+    /// Discover content on several platforms at once, without API keys, in
+    /// `sort` order.
+    ///
+    /// The platforms are queried concurrently, so this takes as long as the
+    /// slowest of them. If `cancel` fires, requests still in flight are
+    /// abandoned and the results gathered so far are returned with
+    /// `cancelled` set.
+    pub async fn discover_all(
+        &self,
+        platforms: &[Platform],
+        limit: u32,
+        extra: &serde_json::Value,
+        sort: DiscoverSort,
+        cancel: &CancellationToken,
+    ) -> DiscoverAll {
+        let requests = platforms.iter().map(|&platform| async move {
+            let result = tokio::select! {
+                biased;
+                _ = cancel.cancelled() => None,
+                result = self.discover_sorted(platform, None, limit, extra, sort) => Some(result),
+            };
+            (platform, result)
+        });
+        let mut out = DiscoverAll::default();
+        for (platform, result) in futures::future::join_all(requests).await {
+            let data = match result {
+                Some(Ok(data)) => data,
+                Some(Err(_)) => serde_json::json!({"error": "unavailable"}),
+                None => {
+                    out.cancelled = true;
+                    serde_json::json!({"error": "cancelled"})
+                }
+            };
+            out.results.insert(platform.name().to_string(), data);
+        }
+        if out.cancelled {
+            debug!(platforms = platforms.len(), "Discover cancelled");
         }
+        out
     }
 
     /// Post content to a platform.
+    ///
+    /// The post is checked against [`Platform::post_requirements`] first, so
+    /// a missing field fails with [`ClawRtcError::InvalidPost`] before any
+    /// request is sent.
     pub async fn post(
         &self,
         platform: Platform,
@@ -141,19 +851,160 @@ impl GrazerClient {
         content: &str,
         extra: &serde_json::Value,
     ) -> ClawRtcResult<serde_json::Value> {
-        match platform {
-            Platform::Moltbook => self.post_moltbook(api_key, title, content, extra).await,
-            Platform::FourClaw => self.post_fourclaw(api_key, title, content, extra).await,
-            Platform::Agentchan => self.post_agentchan(api_key, content, extra).await,
-            Platform::Clawsta => self.post_clawsta(api_key, content).await,
-            Platform::Clawnews => self.post_clawnews(api_key, title, content, extra).await,
-            Platform::Pinchedin => self.post_pinchedin(api_key, content).await,
-            Platform::Clawtasks => self.post_clawtask(api_key, title, content, extra).await,
-            _ => Err(ClawRtcError::Grazer(format!(
-                "Posting not supported for platform: {:?}",
+        if let Some(requirements) = platform.post_requirements() {
+            requirements.validate(title, content, extra)?;
+        }
+        let request = async {
+            match platform {
+                Platform::Moltbook => self.post_moltbook(api_key, title, content, extra).await,
+                Platform::FourClaw => self.post_fourclaw(api_key, title, content, extra).await,
+                Platform::Agentchan => self.post_agentchan(api_key, content, extra).await,
+                Platform::Clawsta => self.post_clawsta(api_key, content).await,
+                Platform::Clawnews => self.post_clawnews(api_key, title, content, extra).await,
+                Platform::Pinchedin => self.post_pinchedin(api_key, content).await,
+                Platform::Clawtasks => self.post_clawtask(api_key, title, content, extra).await,
+                Platform::Clawcities => self.post_clawcities(api_key, content, extra).await,
+                _ => Err(ClawRtcError::Grazer(format!(
+                    "Posting not supported for platform: {:?}",
+                    platform
+                ))),
+            }
+        };
+        self.through_circuit(platform, request).await
+    }
+
+    /// Post content and normalize the response into an [`OpResult`].
+    pub async fn post_op(
+        &self,
+        platform: Platform,
+        api_key: &str,
+        title: &str,
+        content: &str,
+        extra: &serde_json::Value,
+    ) -> ClawRtcResult<OpResult> {
+        let raw = self.post(platform, api_key, title, content, extra).await?;
+        Ok(OpResult::from_response(platform.name(), self.base_url(platform), raw))
+    }
+
+    /// Comment on the post `parent_id`.
+    ///
+    /// Only Moltbook supports comments so far; there `parent_id` is the post
+    /// ID and is required, and `extra["reply_to"]` optionally names a comment
+    /// to reply to.
+    pub async fn comment(
+        &self,
+        platform: Platform,
+        api_key: &str,
+        parent_id: Option<&str>,
+        content: &str,
+        extra: &serde_json::Value,
+    ) -> ClawRtcResult<serde_json::Value> {
+        if content.trim().is_empty() {
+            return Err(ClawRtcError::InvalidPost(
+                "Comment content must not be empty".to_string(),
+            ));
+        }
+        if platform != Platform::Moltbook {
+            return Err(ClawRtcError::Grazer(format!(
+                "Commenting not supported for platform: {:?}",
                 platform
-            ))),
+            )));
         }
+        let post_id = parent_id.ok_or_else(|| {
+            ClawRtcError::InvalidPost(
+                "Moltbook comments require parent_id (the post to comment on)".to_string(),
+            )
+        })?;
+        let request = self.comment_moltbook(api_key, post_id, content, extra);
+        self.through_circuit(platform, request).await
+    }
+
+    /// Comment and normalize the response into an [`OpResult`].
+    pub async fn comment_op(
+        &self,
+        platform: Platform,
+        api_key: &str,
+        parent_id: Option<&str>,
+        content: &str,
+        extra: &serde_json::Value,
+    ) -> ClawRtcResult<OpResult> {
+        let raw = self
+            .comment(platform, api_key, parent_id, content, extra)
+            .await?;
+        Ok(OpResult::from_response(platform.name(), self.base_url(platform), raw))
+    }
+
+    /// List `profile` in the Agent Directory.
+    ///
+    /// The directory accepts anonymous listings; `api_key` ties the listing
+    /// to an existing directory account.
+    pub async fn register_directory(
+        &self,
+        api_key: Option<&str>,
+        profile: &AgentProfile,
+    ) -> ClawRtcResult<OpResult> {
+        let url = format!("{}/api/services", self.base_url(Platform::Directory));
+        debug!(url, name = profile.name, "Registering in Agent Directory");
+        let body = serde_json::json!({
+            "name": profile.name,
+            "description": profile.bio,
+            "url": profile.links.first(),
+            "links": profile.links,
+            "category": "agent",
+        });
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            let req = self.http.post(&url).json(&body);
+            match api_key {
+                Some(key) => Platform::Directory.auth_scheme().apply(req, key),
+                None => req,
+            }
+        })
+        .await?;
+        let status = resp.status();
+        let raw: serde_json::Value = self.read_response(resp).await?;
+        if !status.is_success() {
+            return Err(ClawRtcError::Grazer(format!(
+                "Agent Directory registration failed ({}): {}",
+                status, raw
+            )));
+        }
+        Ok(OpResult::from_response(
+            Platform::Directory.name(),
+            self.base_url(Platform::Directory),
+            raw,
+        ))
+    }
+
+    /// Announce an agent: post an introduction to every platform in
+    /// `api_keys`, then list it in the Agent Directory.
+    ///
+    /// A failure on one platform doesn't stop the others; each gets its own
+    /// [`OnboardStep`], the directory's last. A key for
+    /// [`Platform::Directory`] is used for the listing rather than a post.
+    pub async fn onboard(
+        &self,
+        profile: &AgentProfile,
+        api_keys: &[(Platform, String)],
+    ) -> Vec<OnboardStep> {
+        let (title, content) = profile.intro();
+        // ClawNews stories link somewhere and Moltbook posts need a submolt;
+        // other platforms ignore the fields
+        let extra = serde_json::json!({ "url": profile.links.first(), "submolt": "general" });
+        let mut steps = Vec::with_capacity(api_keys.len() + 1);
+        for (platform, key) in api_keys {
+            if *platform == Platform::Directory {
+                continue;
+            }
+            let outcome = self.post_op(*platform, key, &title, &content, &extra).await;
+            steps.push(OnboardStep::new(*platform, outcome));
+        }
+        let directory_key = api_keys
+            .iter()
+            .find(|(platform, _)| *platform == Platform::Directory)
+            .map(|(_, key)| key.as_str());
+        let outcome = self.register_directory(directory_key, profile).await;
+        steps.push(OnboardStep::new(Platform::Directory, outcome));
+        steps
     }
 
     /// Search ClawHub skills.
@@ -164,13 +1015,13 @@ impl GrazerClient {
     ) -> ClawRtcResult<serde_json::Value> {
         let url = format!(
             "{}/api/v1/skills?search={}&limit={}",
-            Platform::Clawhub.base_url(),
+            self.base_url(Platform::Clawhub),
             urlencoded(query),
             limit
         );
         debug!(url, "Searching ClawHub");
-        let resp = self.http.get(&url).send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        self.read_response(resp).await
     }
 
     // ─── Platform-specific discover implementations ─────────────────────
@@ -179,19 +1030,23 @@ impl GrazerClient {
         &self,
         limit: u32,
         extra: &serde_json::Value,
+        sort: Option<&str>,
     ) -> ClawRtcResult<serde_json::Value> {
         let category = extra["category"].as_str().unwrap_or("");
         let agent = extra["agent"].as_str().unwrap_or("");
-        let mut url = format!("{}/api/videos?limit={}", Platform::Bottube.base_url(), limit);
+        let mut url = format!("{}/api/videos?limit={}", self.base_url(Platform::Bottube), limit);
         if !category.is_empty() {
             url.push_str(&format!("&category={}", urlencoded(category)));
         }
         if !agent.is_empty() {
             url.push_str(&format!("&agent={}", urlencoded(agent)));
         }
+        if let Some(sort) = sort {
+            url.push_str(&format!("&sort={sort}"));
+        }
         debug!(url, "Discovering BoTTube");
-        let resp = self.http.get(&url).send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        self.read_response(resp).await
     }
 
     async fn discover_moltbook(
@@ -199,21 +1054,25 @@ impl GrazerClient {
         api_key: Option<&str>,
         limit: u32,
         extra: &serde_json::Value,
+        sort: Option<&str>,
     ) -> ClawRtcResult<serde_json::Value> {
         let submolt = extra["submolt"].as_str().unwrap_or("tech");
-        let url = format!(
+        let mut url = format!(
             "{}/api/v1/posts?submolt={}&limit={}",
-            Platform::Moltbook.base_url(),
+            self.base_url(Platform::Moltbook),
             urlencoded(submolt),
             limit
         );
+        if let Some(sort) = sort {
+            url.push_str(&format!("&sort={sort}"));
+        }
         debug!(url, "Discovering Moltbook");
         let mut req = self.http.get(&url);
         if let Some(key) = api_key {
-            req = req.bearer_auth(key);
+            req = Platform::Moltbook.auth_scheme().apply(req, key);
         }
-        let resp = req.send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(req).await?;
+        self.read_response(resp).await
     }
 
     async fn discover_fourclaw(
@@ -225,17 +1084,17 @@ impl GrazerClient {
         let board = extra["board"].as_str().unwrap_or("b");
         let url = format!(
             "{}/api/v1/boards/{}/threads?limit={}",
-            Platform::FourClaw.base_url(),
+            self.base_url(Platform::FourClaw),
             urlencoded(board),
             limit.min(20)
         );
         debug!(url, "Discovering 4claw");
         let mut req = self.http.get(&url);
         if let Some(key) = api_key {
-            req = req.bearer_auth(key);
+            req = Platform::FourClaw.auth_scheme().apply(req, key);
         }
-        let resp = req.send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(req).await?;
+        self.read_response(resp).await
     }
 
     async fn discover_clawhub(
@@ -246,15 +1105,15 @@ impl GrazerClient {
         let search = extra["search"].as_str().unwrap_or("");
         let mut url = format!(
             "{}/api/v1/skills?limit={}",
-            Platform::Clawhub.base_url(),
+            self.base_url(Platform::Clawhub),
             limit
         );
         if !search.is_empty() {
             url.push_str(&format!("&search={}", urlencoded(search)));
         }
         debug!(url, "Discovering ClawHub");
-        let resp = self.http.get(&url).send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        self.read_response(resp).await
     }
 
     async fn discover_pinchedin(
@@ -265,39 +1124,31 @@ impl GrazerClient {
         let key = api_key.ok_or_else(|| ClawRtcError::MissingApiKey("pinchedin".into()))?;
         let url = format!(
             "{}/api/feed?limit={}",
-            Platform::Pinchedin.base_url(),
+            self.base_url(Platform::Pinchedin),
             limit
         );
         debug!(url, "Discovering PinchedIn");
-        let resp = self
-            .http
-            .get(&url)
-            .bearer_auth(key)
-            .header("Content-Type", "application/json")
-            .send()
-            .await?;
-        Ok(resp.json().await?)
+        let req = Platform::Pinchedin
+            .auth_scheme()
+            .apply(self.http.get(&url), key)
+            .header("Content-Type", "application/json");
+        let resp = self.http_options.send(req).await?;
+        self.read_response(resp).await
     }
 
     async fn discover_agentchan(
         &self,
-        limit: u32,
         extra: &serde_json::Value,
     ) -> ClawRtcResult<serde_json::Value> {
         let board = extra["board"].as_str().unwrap_or("ai");
         let url = format!(
             "{}/api/boards/{}/catalog",
-            Platform::Agentchan.base_url(),
+            self.base_url(Platform::Agentchan),
             urlencoded(board)
         );
         debug!(url, "Discovering AgentChan");
-        let resp = self.http.get(&url).send().await?;
-        let mut data: serde_json::Value = resp.json().await?;
-        // Trim to limit
-        if let Some(arr) = data.get_mut("data").and_then(|d| d.as_array_mut()) {
-            arr.truncate(limit as usize);
-        }
-        Ok(data)
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        self.read_response(resp).await
     }
 
     async fn discover_clawsta(
@@ -305,33 +1156,37 @@ impl GrazerClient {
         api_key: Option<&str>,
         limit: u32,
     ) -> ClawRtcResult<serde_json::Value> {
-        let url = format!("{}/v1/posts?limit={}", Platform::Clawsta.base_url(), limit);
+        let url = format!("{}/v1/posts?limit={}", self.base_url(Platform::Clawsta), limit);
         debug!(url, "Discovering ClawSta");
         let mut req = self.http.get(&url);
         if let Some(key) = api_key {
-            req = req.bearer_auth(key);
+            req = Platform::Clawsta.auth_scheme().apply(req, key);
         }
-        let resp = req.send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(req).await?;
+        self.read_response(resp).await
     }
 
     async fn discover_clawnews(
         &self,
         api_key: Option<&str>,
         limit: u32,
+        sort: Option<&str>,
     ) -> ClawRtcResult<serde_json::Value> {
-        let url = format!(
+        let mut url = format!(
             "{}/api/stories?limit={}",
-            Platform::Clawnews.base_url(),
+            self.base_url(Platform::Clawnews),
             limit
         );
+        if let Some(sort) = sort {
+            url.push_str(&format!("&sort={sort}"));
+        }
         debug!(url, "Discovering ClawNews");
         let mut req = self.http.get(&url);
         if let Some(key) = api_key {
-            req = req.bearer_auth(key);
+            req = Platform::Clawnews.auth_scheme().apply(req, key);
         }
-        let resp = req.send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(req).await?;
+        self.read_response(resp).await
     }
 
     async fn discover_clawtasks(
@@ -342,29 +1197,23 @@ impl GrazerClient {
         let key = api_key.ok_or_else(|| ClawRtcError::MissingApiKey("clawtasks".into()))?;
         let url = format!(
             "{}/api/bounties?status=open&limit={}",
-            Platform::Clawtasks.base_url(),
+            self.base_url(Platform::Clawtasks),
             limit
         );
         debug!(url, "Discovering ClawTasks");
-        let resp = self
-            .http
-            .get(&url)
-            .bearer_auth(key)
-            .header("Content-Type", "application/json")
-            .send()
-            .await?;
-        Ok(resp.json().await?)
+        let req = Platform::Clawtasks
+            .auth_scheme()
+            .apply(self.http.get(&url), key)
+            .header("Content-Type", "application/json");
+        let resp = self.http_options.send(req).await?;
+        self.read_response(resp).await
     }
 
-    async fn discover_swarmhub(&self, limit: u32) -> ClawRtcResult<serde_json::Value> {
-        let url = format!("{}/api/v1/agents", Platform::Swarmhub.base_url());
+    async fn discover_swarmhub(&self) -> ClawRtcResult<serde_json::Value> {
+        let url = format!("{}/api/v1/agents", self.base_url(Platform::Swarmhub));
         debug!(url, "Discovering SwarmHub");
-        let resp = self.http.get(&url).send().await?;
-        let mut data: serde_json::Value = resp.json().await?;
-        if let Some(arr) = data.get_mut("agents").and_then(|a| a.as_array_mut()) {
-            arr.truncate(limit as usize);
-        }
-        Ok(data)
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        self.read_response(resp).await
     }
 
     async fn discover_directory(
@@ -375,15 +1224,30 @@ impl GrazerClient {
         let category = extra["category"].as_str().unwrap_or("");
         let mut url = format!(
             "{}/api/services?limit={}",
-            Platform::Directory.base_url(),
+            self.base_url(Platform::Directory),
             limit
         );
         if !category.is_empty() {
             url.push_str(&format!("&category={}", urlencoded(category)));
         }
         debug!(url, "Discovering Agent Directory");
-        let resp = self.http.get(&url).send().await?;
-        Ok(resp.json().await?)
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        self.read_response(resp).await
+    }
+
+    /// Recently updated sites.
+    ///
+    /// ClawCities doesn't document its API; `/api/sites` is assumed to list
+    /// sites as `{"sites": [...]}`, like the other platforms' feeds.
+    async fn discover_clawcities(&self, limit: u32) -> ClawRtcResult<serde_json::Value> {
+        let url = format!(
+            "{}/api/sites?sort=recent&limit={}",
+            self.base_url(Platform::Clawcities),
+            limit
+        );
+        debug!(url, "Discovering ClawCities");
+        let resp = self.http_options.send(self.http.get(&url)).await?;
+        self.read_response(resp).await
     }
 
     // ─── Platform-specific post implementations ─────────────────────────
@@ -396,21 +1260,21 @@ impl GrazerClient {
         extra: &serde_json::Value,
     ) -> ClawRtcResult<serde_json::Value> {
         let submolt = extra["submolt"].as_str().unwrap_or("general");
-        let url = format!("{}/api/v1/posts", Platform::Moltbook.base_url());
+        let url = format!("{}/api/v1/posts", self.base_url(Platform::Moltbook));
         debug!(url, submolt, "Posting to Moltbook");
-        let resp = self
-            .http
-            .post(&url)
-            .bearer_auth(api_key)
-            .json(&serde_json::json!({
-                "title": title,
-                "content": content,
-                "submolt_name": submolt,
-            }))
-            .send()
-            .await?;
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            Platform::Moltbook
+                .auth_scheme()
+                .apply(self.http.post(&url), api_key)
+                .json(&serde_json::json!({
+                    "title": title,
+                    "content": content,
+                    "submolt_name": submolt,
+                }))
+        })
+        .await?;
         let status = resp.status();
-        let body: serde_json::Value = resp.json().await?;
+        let body: serde_json::Value = self.read_response(resp).await?;
         if !status.is_success() {
             return Err(ClawRtcError::Grazer(format!(
                 "Moltbook post failed ({}): {}",
@@ -420,6 +1284,41 @@ impl GrazerClient {
         Ok(body)
     }
 
+    async fn comment_moltbook(
+        &self,
+        api_key: &str,
+        post_id: &str,
+        content: &str,
+        extra: &serde_json::Value,
+    ) -> ClawRtcResult<serde_json::Value> {
+        let url = format!(
+            "{}/api/v1/posts/{}/comments",
+            self.base_url(Platform::Moltbook),
+            urlencoded(post_id)
+        );
+        debug!(url, "Commenting on Moltbook");
+        let mut body = serde_json::json!({ "content": content });
+        if let Some(reply_to) = extra["reply_to"].as_str() {
+            body["parent_id"] = reply_to.into();
+        }
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            Platform::Moltbook
+                .auth_scheme()
+                .apply(self.http.post(&url), api_key)
+                .json(&body)
+        })
+        .await?;
+        let status = resp.status();
+        let body: serde_json::Value = self.read_response(resp).await?;
+        if !status.is_success() {
+            return Err(ClawRtcError::Grazer(format!(
+                "Moltbook comment failed ({}): {}",
+                status, body
+            )));
+        }
+        Ok(body)
+    }
+
     async fn post_fourclaw(
         &self,
         api_key: &str,
@@ -430,23 +1329,23 @@ impl GrazerClient {
         let board = extra["board"].as_str().unwrap_or("b");
         let url = format!(
             "{}/api/v1/boards/{}/threads",
-            Platform::FourClaw.base_url(),
+            self.base_url(Platform::FourClaw),
             urlencoded(board)
         );
         debug!(url, board, "Posting to 4claw");
-        let resp = self
-            .http
-            .post(&url)
-            .bearer_auth(api_key)
-            .json(&serde_json::json!({
-                "title": title,
-                "content": content,
-                "anon": false,
-            }))
-            .send()
-            .await?;
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            Platform::FourClaw
+                .auth_scheme()
+                .apply(self.http.post(&url), api_key)
+                .json(&serde_json::json!({
+                    "title": title,
+                    "content": content,
+                    "anon": false,
+                }))
+        })
+        .await?;
         let status = resp.status();
-        let body: serde_json::Value = resp.json().await?;
+        let body: serde_json::Value = self.read_response(resp).await?;
         if !status.is_success() {
             return Err(ClawRtcError::Grazer(format!(
                 "4claw post failed ({}): {}",
@@ -468,28 +1367,30 @@ impl GrazerClient {
         let url = if let Some(thread_id) = reply_to {
             format!(
                 "{}/api/boards/{}/threads/{}/posts",
-                Platform::Agentchan.base_url(),
+                self.base_url(Platform::Agentchan),
                 urlencoded(board),
                 urlencoded(thread_id)
             )
         } else {
             format!(
                 "{}/api/boards/{}/threads",
-                Platform::Agentchan.base_url(),
+                self.base_url(Platform::Agentchan),
                 urlencoded(board)
             )
         };
 
         debug!(url, board, "Posting to AgentChan");
-        let resp = self
-            .http
-            .post(&url)
-            .bearer_auth(api_key)
-            .json(&serde_json::json!({ "content": content }))
-            .send()
-            .await?;
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            Platform::Agentchan
+                .auth_scheme()
+                .apply(self.http.post(&url), api_key)
+                .json(&serde_json::json!({ "content": content }))
+        })
+        .await?;
         let status = resp.status();
-        let body: serde_json::Value = resp.json().await.unwrap_or(serde_json::json!({}));
+        let body: serde_json::Value = read_json(reject_server_error(resp)?, self.max_body_bytes)
+            .await
+            .unwrap_or(serde_json::json!({}));
         if !status.is_success() {
             return Err(ClawRtcError::Grazer(format!(
                 "AgentChan post failed ({}): {}",
@@ -504,17 +1405,17 @@ impl GrazerClient {
         api_key: &str,
         content: &str,
     ) -> ClawRtcResult<serde_json::Value> {
-        let url = format!("{}/v1/posts", Platform::Clawsta.base_url());
+        let url = format!("{}/v1/posts", self.base_url(Platform::Clawsta));
         debug!(url, "Posting to ClawSta");
-        let resp = self
-            .http
-            .post(&url)
-            .bearer_auth(api_key)
-            .json(&serde_json::json!({ "content": content }))
-            .send()
-            .await?;
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            Platform::Clawsta
+                .auth_scheme()
+                .apply(self.http.post(&url), api_key)
+                .json(&serde_json::json!({ "content": content }))
+        })
+        .await?;
         let status = resp.status();
-        let body: serde_json::Value = resp.json().await?;
+        let body: serde_json::Value = self.read_response(resp).await?;
         if !status.is_success() {
             return Err(ClawRtcError::Grazer(format!(
                 "ClawSta post failed ({}): {}",
@@ -535,7 +1436,7 @@ impl GrazerClient {
         let tags: Option<Vec<&str>> = extra["tags"]
             .as_array()
             .map(|a| a.iter().filter_map(|v| v.as_str()).collect());
-        let url = format!("{}/api/stories", Platform::Clawnews.base_url());
+        let url = format!("{}/api/stories", self.base_url(Platform::Clawnews));
         debug!(url, "Posting to ClawNews");
         let mut body = serde_json::json!({
             "headline": headline,
@@ -545,15 +1446,17 @@ impl GrazerClient {
         if let Some(t) = tags {
             body["tags"] = serde_json::json!(t);
         }
-        let resp = self
-            .http
-            .post(&url)
-            .bearer_auth(api_key)
-            .json(&body)
-            .send()
-            .await?;
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            Platform::Clawnews
+                .auth_scheme()
+                .apply(self.http.post(&url), api_key)
+                .json(&body)
+        })
+        .await?;
         let status = resp.status();
-        let result: serde_json::Value = resp.json().await.unwrap_or(serde_json::json!({}));
+        let result: serde_json::Value = read_json(reject_server_error(resp)?, self.max_body_bytes)
+            .await
+            .unwrap_or(serde_json::json!({}));
         if !status.is_success() {
             return Err(ClawRtcError::Grazer(format!(
                 "ClawNews post failed ({}): {}",
@@ -563,23 +1466,62 @@ impl GrazerClient {
         Ok(result)
     }
 
+    /// Comment on a ClawCities site's guestbook.
+    ///
+    /// Assumes `POST /api/sites/{site}/comments`, taking the page in the body
+    /// as Moltbook comments take their parent.
+    async fn post_clawcities(
+        &self,
+        api_key: &str,
+        content: &str,
+        extra: &serde_json::Value,
+    ) -> ClawRtcResult<serde_json::Value> {
+        let site = extra["site"].as_str().unwrap_or_default();
+        let url = format!(
+            "{}/api/sites/{}/comments",
+            self.base_url(Platform::Clawcities),
+            urlencoded(site)
+        );
+        debug!(url, site, "Commenting on ClawCities");
+        let mut body = serde_json::json!({ "content": content });
+        if let Some(page) = extra["page"].as_str() {
+            body["page"] = page.into();
+        }
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            Platform::Clawcities
+                .auth_scheme()
+                .apply(self.http.post(&url), api_key)
+                .json(&body)
+        })
+        .await?;
+        let status = resp.status();
+        let body: serde_json::Value = self.read_response(resp).await?;
+        if !status.is_success() {
+            return Err(ClawRtcError::Grazer(format!(
+                "ClawCities comment failed ({}): {}",
+                status, body
+            )));
+        }
+        Ok(body)
+    }
+
     async fn post_pinchedin(
         &self,
         api_key: &str,
         content: &str,
     ) -> ClawRtcResult<serde_json::Value> {
-        let url = format!("{}/api/posts", Platform::Pinchedin.base_url());
+        let url = format!("{}/api/posts", self.base_url(Platform::Pinchedin));
         debug!(url, "Posting to PinchedIn");
-        let resp = self
-            .http
-            .post(&url)
-            .bearer_auth(api_key)
-            .header("Content-Type", "application/json")
-            .json(&serde_json::json!({ "content": content }))
-            .send()
-            .await?;
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            Platform::Pinchedin
+                .auth_scheme()
+                .apply(self.http.post(&url), api_key)
+                .header("Content-Type", "application/json")
+                .json(&serde_json::json!({ "content": content }))
+        })
+        .await?;
         let status = resp.status();
-        let body: serde_json::Value = resp.json().await?;
+        let body: serde_json::Value = self.read_response(resp).await?;
         if !status.is_success() {
             return Err(ClawRtcError::Grazer(format!(
                 "PinchedIn post failed ({}): {}",
@@ -600,7 +1542,7 @@ impl GrazerClient {
         let tags: Option<Vec<&str>> = extra["tags"]
             .as_array()
             .map(|a| a.iter().filter_map(|v| v.as_str()).collect());
-        let url = format!("{}/api/bounties", Platform::Clawtasks.base_url());
+        let url = format!("{}/api/bounties", self.base_url(Platform::Clawtasks));
         debug!(url, "Posting to ClawTasks");
         let mut body = serde_json::json!({
             "title": title,
@@ -610,16 +1552,16 @@ impl GrazerClient {
         if let Some(t) = tags {
             body["tags"] = serde_json::json!(t);
         }
-        let resp = self
-            .http
-            .post(&url)
-            .bearer_auth(api_key)
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
+        let resp = send_with_retry(&self.retry, &self.http_options, || {
+            Platform::Clawtasks
+                .auth_scheme()
+                .apply(self.http.post(&url), api_key)
+                .header("Content-Type", "application/json")
+                .json(&body)
+        })
+        .await?;
         let status = resp.status();
-        let result: serde_json::Value = resp.json().await?;
+        let result: serde_json::Value = self.read_response(resp).await?;
         if !status.is_success() {
             return Err(ClawRtcError::Grazer(format!(
                 "ClawTasks post failed ({}): {}",
@@ -630,19 +1572,12 @@ impl GrazerClient {
     }
 }
 
-/// Minimal percent-encoding for URL query parameters.
-fn urlencoded(s: &str) -> String {
-    s.replace('%', "%25")
-        .replace(' ', "%20")
-        .replace('&', "%26")
-        .replace('=', "%3D")
-        .replace('+', "%2B")
-        .replace('#', "%23")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::MockClock;
+    use crate::test_support::{MockResponse, MockServer};
+    use std::time::Duration;
 
     #[test]
     fn test_platform_from_str() {
@@ -653,6 +1588,29 @@ mod tests {
         assert!("unknown".parse::<Platform>().is_err());
     }
 
+    #[test]
+    fn test_api_key_prefix_mismatch() {
+        assert_eq!(Platform::Moltbook.check_api_key("moltbook_sk_abc", true).unwrap(), None);
+        // Platforms without a known prefix accept anything
+        assert_eq!(Platform::Clawnews.check_api_key("whatever", true).unwrap(), None);
+
+        let warning = Platform::Bottube
+            .check_api_key("moltbook_sk_abc", false)
+            .unwrap()
+            .unwrap();
+        assert!(warning.contains("moltbook's prefix"), "{warning}");
+        assert!(warning.contains("bottube_sk_"), "{warning}");
+
+        let err = Platform::Agentchan
+            .check_api_key("bottube_sk_abc", true)
+            .unwrap_err();
+        assert!(matches!(err, ClawRtcError::InvalidApiKey(_)));
+        assert!(err.to_string().contains("bottube's prefix"), "{err}");
+
+        let err = Platform::Moltbook.check_api_key("sk-abc", true).unwrap_err();
+        assert!(err.to_string().contains("moltbook keys start with moltbook_sk_"), "{err}");
+    }
+
     #[test]
     fn test_platform_base_urls() {
         assert_eq!(Platform::Bottube.base_url(), "https://bottube.ai");
@@ -660,14 +1618,623 @@ mod tests {
         assert_eq!(Platform::FourClaw.base_url(), "https://www.4claw.org");
     }
 
+    #[test]
+    fn test_platform_name_round_trips() {
+        for name in Platform::all_names() {
+            assert_eq!(name.parse::<Platform>().unwrap().name(), *name);
+        }
+        for platform in Platform::ALL {
+            assert_eq!(platform.to_string().parse::<Platform>().unwrap(), platform);
+        }
+        assert_eq!("FourClaw".parse::<Platform>().unwrap(), Platform::FourClaw);
+    }
+
+    #[tokio::test]
+    async fn test_discover_all_returns_partial_results_on_cancel() {
+        let cancel = CancellationToken::new();
+        let ok = || {
+            let agents = MockResponse::json(200, serde_json::json!({ "agents": [1] }));
+            MockServer::sequence(vec![agents])
+        };
+        let first = ok().await;
+        let second = ok().await;
+        // The third platform hangs; the agent gives up while waiting on it
+        let trigger = cancel.clone();
+        let third = MockServer::start(move |_| {
+            let trigger = trigger.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                trigger.cancel();
+            });
+            MockResponse::json(200, serde_json::json!({})).with_delay(Duration::from_secs(30))
+        })
+        .await;
+        let fourth = ok().await;
+
+        let client = GrazerClient::new()
+            .with_base_url(Platform::Swarmhub, &first.url())
+            .with_base_url(Platform::Clawhub, &second.url())
+            .with_base_url(Platform::Directory, &third.url())
+            .with_base_url(Platform::Bottube, &fourth.url());
+        let platforms = [
+            Platform::Swarmhub,
+            Platform::Clawhub,
+            Platform::Directory,
+            Platform::Bottube,
+        ];
+
+        let started = std::time::Instant::now();
+        let out = client
+            .discover_all(
+                &platforms,
+                10,
+                &serde_json::json!({}),
+                DiscoverSort::Trending,
+                &cancel,
+            )
+            .await;
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(out.cancelled);
+        assert_eq!(out.results.len(), 4);
+        assert_eq!(out.results["swarmhub"]["agents"], serde_json::json!([1]));
+        assert_eq!(out.results["clawhub"]["agents"], serde_json::json!([1]));
+        assert_eq!(out.results["directory"]["error"], "cancelled");
+        // Queried alongside the hanging platform, not after it
+        assert_eq!(fourth.requests().len(), 1);
+        assert!(out.results["bottube"].get("error").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_discover_all_queries_platforms_concurrently() {
+        let slow = || {
+            let response = MockResponse::json(200, serde_json::json!({ "agents": [] }));
+            MockServer::sequence(vec![response.with_delay(Duration::from_millis(800))])
+        };
+        let servers = [slow().await, slow().await, slow().await, slow().await];
+        let platforms = [
+            Platform::Swarmhub,
+            Platform::Clawhub,
+            Platform::Directory,
+            Platform::Clawsta,
+        ];
+        let client = platforms
+            .iter()
+            .zip(&servers)
+            .fold(GrazerClient::new(), |client, (&platform, server)| {
+                client.with_base_url(platform, &server.url())
+            });
+
+        let started = std::time::Instant::now();
+        let out = client
+            .discover_all(
+                &platforms,
+                10,
+                &serde_json::json!({}),
+                DiscoverSort::Trending,
+                &CancellationToken::new(),
+            )
+            .await;
+        // Bounded by the slowest platform, not the sum of all four
+        assert!(started.elapsed() < Duration::from_millis(2000));
+        assert!(!out.cancelled);
+        assert!(out.results.values().all(|r| r.get("error").is_none()));
+        assert!(servers.iter().all(|s| s.requests().len() == 1));
+    }
+
+    #[tokio::test]
+    async fn test_discover_all_completes_without_cancel() {
+        let server =
+            MockServer::sequence(vec![MockResponse::json(200, serde_json::json!({ "agents": [] }))])
+                .await;
+        let client = GrazerClient::new().with_base_url(Platform::Swarmhub, &server.url());
+        let out = client
+            .discover_all(
+                &[Platform::Swarmhub],
+                10,
+                &serde_json::json!({}),
+                DiscoverSort::Trending,
+                &CancellationToken::new(),
+            )
+            .await;
+        assert!(!out.cancelled);
+        assert_eq!(out.results.len(), 1);
+    }
+
     #[test]
     fn test_all_platform_names() {
         assert_eq!(Platform::all_names().len(), 12);
     }
 
+    fn built(scheme: AuthScheme) -> reqwest::Request {
+        let req = reqwest::Client::new().get("https://example.com/api/feed?limit=5");
+        scheme.apply(req, "sk_test").build().unwrap()
+    }
+
+    #[test]
+    fn test_auth_scheme_table() {
+        assert_eq!(Platform::Moltbook.auth_scheme(), AuthScheme::Bearer);
+        assert_eq!(Platform::Clawtasks.auth_scheme(), AuthScheme::Bearer);
+        assert_eq!(
+            Platform::Bottube.auth_scheme(),
+            AuthScheme::HeaderKey("X-API-Key")
+        );
+        let client = GrazerClient::new();
+        assert_eq!(client.http_options.credential_headers, ["X-API-Key"]);
+    }
+
+    #[test]
+    fn test_auth_scheme_bearer() {
+        let req = built(Platform::Moltbook.auth_scheme());
+        assert_eq!(req.headers()["authorization"], "Bearer sk_test");
+        assert!(req.headers().get("x-api-key").is_none());
+    }
+
     #[test]
-    fn test_urlencoded() {
-        assert_eq!(urlencoded("hello world"), "hello%20world");
-        assert_eq!(urlencoded("a&b=c"), "a%26b%3Dc");
+    fn test_auth_scheme_header_key() {
+        let req = built(Platform::Bottube.auth_scheme());
+        assert_eq!(req.headers()["x-api-key"], "sk_test");
+        assert!(req.headers().get("authorization").is_none());
+    }
+
+    #[test]
+    fn test_auth_scheme_query_param() {
+        let req = built(AuthScheme::QueryParam("api_key"));
+        assert_eq!(req.url().query(), Some("limit=5&api_key=sk_test"));
+        assert!(req.headers().get("authorization").is_none());
+    }
+
+    fn mock_client(server: &MockServer) -> GrazerClient {
+        GrazerClient::new()
+            .with_base_url(Platform::Moltbook, &server.url())
+            .with_retry_policy(RetryPolicy {
+                max_retries: 2,
+                initial_backoff: Duration::from_millis(5),
+                max_backoff: Duration::from_millis(20),
+            })
+    }
+
+    #[test]
+    fn test_base_url_override() {
+        let c = GrazerClient::new().with_base_url(Platform::Moltbook, "http://localhost:9000/");
+        assert_eq!(c.base_url(Platform::Moltbook), "http://localhost:9000");
+        assert_eq!(c.base_url(Platform::FourClaw), "https://www.4claw.org");
+    }
+
+    #[tokio::test]
+    async fn test_post_retries_on_503() {
+        let server = MockServer::sequence(vec![
+            MockResponse::json(503, serde_json::json!({ "error": "busy" })),
+            MockResponse::json(201, serde_json::json!({ "id": "p1" })),
+        ])
+        .await;
+        let extra = serde_json::json!({ "submolt": "general" });
+        let result = mock_client(&server)
+            .post(Platform::Moltbook, "sk", "Title", "Body", &extra)
+            .await
+            .unwrap();
+        assert_eq!(result["id"], "p1");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        // Every attempt carries the same payload
+        assert_eq!(requests[0].body, requests[1].body);
+        assert_eq!(requests[1].json()["content"], "Body");
+    }
+
+    #[tokio::test]
+    async fn test_post_does_not_retry_500() {
+        let server = MockServer::sequence(vec![MockResponse::json(
+            500,
+            serde_json::json!({ "error": "boom" }),
+        )])
+        .await;
+        let extra = serde_json::json!({ "submolt": "general" });
+        let err = mock_client(&server)
+            .post(Platform::Moltbook, "sk", "Title", "Body", &extra)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("500"));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_discover_follows_updated_adapter() {
+        // AgentChan renamed `data` to `threads`
+        let server = MockServer::sequence(vec![MockResponse::json(
+            200,
+            serde_json::json!({ "threads": [1, 2, 3, 4] }),
+        )])
+        .await;
+        let client = GrazerClient::new().with_base_url(Platform::Agentchan, &server.url());
+        let stale = client
+            .discover(Platform::Agentchan, None, 2, &serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(stale["threads"].as_array().unwrap().len(), 4);
+
+        let client = client.with_response_adapter(
+            Platform::Agentchan,
+            ResponseAdapter {
+                version: 2,
+                items_key: Some("data"),
+                aliases: &["threads"],
+            },
+        );
+        let fixed = client
+            .discover(Platform::Agentchan, None, 2, &serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(fixed, serde_json::json!({ "data": [1, 2] }));
+    }
+
+    #[tokio::test]
+    async fn test_discover_sanitizes_content_when_enabled() {
+        let title = "\u{1B}[31mHot\u{1B}[0m\u{200B} take";
+        let server = MockServer::start(move |_| {
+            MockResponse::json(200, serde_json::json!({ "posts": [{ "title": title }] }))
+        })
+        .await;
+        let client = GrazerClient::new().with_base_url(Platform::Clawsta, &server.url());
+        let raw = client
+            .discover(Platform::Clawsta, None, 5, &serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(raw["posts"][0]["title"], title);
+
+        let client = client.with_sanitize_content(true);
+        let clean = client
+            .discover(Platform::Clawsta, None, 5, &serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(clean["posts"][0]["title"], "Hot take");
+    }
+
+    #[tokio::test]
+    async fn test_discover_sort_query_params() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, serde_json::json!({ "posts": [] }))
+        })
+        .await;
+        let client = GrazerClient::new()
+            .with_base_url(Platform::Bottube, &server.url())
+            .with_base_url(Platform::Moltbook, &server.url())
+            .with_base_url(Platform::Clawnews, &server.url());
+        let extra = serde_json::json!({});
+        for (platform, sort) in [
+            (Platform::Bottube, DiscoverSort::Newest),
+            (Platform::Moltbook, DiscoverSort::Top),
+            (Platform::Clawnews, DiscoverSort::Newest),
+            (Platform::Moltbook, DiscoverSort::Trending),
+        ] {
+            client
+                .discover_sorted(platform, None, 5, &extra, sort)
+                .await
+                .unwrap();
+        }
+        let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+        assert!(paths[0].ends_with("&sort=newest"), "{}", paths[0]);
+        assert!(paths[1].ends_with("&sort=top"), "{}", paths[1]);
+        assert!(paths[2].ends_with("&sort=new"), "{}", paths[2]);
+        assert!(!paths[3].contains("sort="), "{}", paths[3]);
+
+        assert_eq!(Platform::Swarmhub.sort_param(DiscoverSort::Top), None);
+        assert_eq!("new".parse::<DiscoverSort>(), Ok(DiscoverSort::Newest));
+        assert!("random".parse::<DiscoverSort>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_discover_sorts_client_side_without_server_sort() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                serde_json::json!({ "posts": [
+                    { "id": 1, "likes": 5, "created_at": "2026-01-02T00:00:00Z" },
+                    { "id": 2, "likes": 50, "created_at": "2026-01-01T00:00:00Z" },
+                    { "id": 3, "created_at": "2026-01-03T00:00:00Z" },
+                ] }),
+            )
+        })
+        .await;
+        let client = GrazerClient::new().with_base_url(Platform::Clawsta, &server.url());
+        let ids = |out: serde_json::Value| -> Vec<i64> {
+            out["posts"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|p| p["id"].as_i64().unwrap())
+                .collect()
+        };
+        let extra = serde_json::json!({});
+        let discover = |sort| client.discover_sorted(Platform::Clawsta, None, 2, &extra, sort);
+
+        assert_eq!(ids(discover(DiscoverSort::Newest).await.unwrap()), [3, 1]);
+        // Items without a score sort last
+        assert_eq!(ids(discover(DiscoverSort::Top).await.unwrap()), [2, 1]);
+        assert_eq!(ids(discover(DiscoverSort::Trending).await.unwrap()), [1, 2]);
+        assert!(server.requests().iter().all(|r| !r.path.contains("sort=")));
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_skips_platform_until_cooldown() {
+        // A CDN error page in place of the API for the first five requests
+        let served = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let count = served.clone();
+        let server = MockServer::start(move |_| {
+            if count.fetch_add(1, std::sync::atomic::Ordering::SeqCst) < 5 {
+                MockResponse::text(503, "text/html", "<h1>Bad gateway</h1>")
+            } else {
+                MockResponse::json(200, serde_json::json!({ "posts": [] }))
+            }
+        })
+        .await;
+        let clock = MockClock::at_unix(0);
+        let client = GrazerClient::new()
+            .with_base_url(Platform::Clawsta, &server.url())
+            .with_base_url(Platform::Clawnews, &server.url())
+            .with_clock(Arc::new(clock.clone()))
+            .with_circuit_breaker(CircuitBreaker {
+                failure_threshold: 3,
+                cooldown: Duration::from_secs(60),
+            });
+        let discover = |platform| client.discover(platform, None, 5, &serde_json::Value::Null);
+
+        for _ in 0..3 {
+            let err = discover(Platform::Clawsta).await.unwrap_err();
+            assert!(
+                matches!(err, ClawRtcError::GrazerStatus(503, _)),
+                "{err}"
+            );
+        }
+        let err = discover(Platform::Clawsta).await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::CircuitOpen(_)), "{err}");
+        assert!(err.to_string().contains("clawsta failed 3 times"), "{err}");
+        assert_eq!(server.requests().len(), 3);
+
+        // Other platforms are still called
+        let err = discover(Platform::Clawnews).await.unwrap_err();
+        assert!(
+            matches!(err, ClawRtcError::GrazerStatus(503, _)),
+            "{err}"
+        );
+        assert_eq!(server.requests().len(), 4);
+
+        clock.advance(Duration::from_secs(59));
+        let err = discover(Platform::Clawsta).await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::CircuitOpen(_)), "{err}");
+        assert_eq!(server.requests().len(), 4);
+
+        // After the cooldown one probe goes through; it fails and reopens
+        clock.advance(Duration::from_secs(1));
+        let err = discover(Platform::Clawsta).await.unwrap_err();
+        assert!(
+            matches!(err, ClawRtcError::GrazerStatus(503, _)),
+            "{err}"
+        );
+        let err = discover(Platform::Clawsta).await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::CircuitOpen(_)), "{err}");
+        assert_eq!(server.requests().len(), 5);
+
+        // A successful probe closes it
+        clock.advance(Duration::from_secs(60));
+        discover(Platform::Clawsta).await.unwrap();
+        discover(Platform::Clawsta).await.unwrap();
+        assert_eq!(server.requests().len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_ignores_refusals() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(401, serde_json::json!({ "error": "bad key" }))
+        })
+        .await;
+        let client = GrazerClient::new()
+            .with_base_url(Platform::Moltbook, &server.url())
+            .with_circuit_breaker(CircuitBreaker {
+                failure_threshold: 2,
+                cooldown: Duration::from_secs(60),
+            });
+        let extra = serde_json::json!({ "submolt": "tech" });
+
+        for _ in 0..3 {
+            let err = client
+                .post(Platform::Moltbook, "moltbook_sk_x", "Title", "Body", &extra)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("401"), "{err}");
+            let err = client
+                .post(Platform::Swarmhub, "key", "Title", "Body", &extra)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("not supported"), "{err}");
+        }
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[test]
+    fn test_post_requirements() {
+        let clawnews = Platform::Clawnews.post_requirements().unwrap();
+        let story = serde_json::json!({ "url": "https://example.com/a", "tags": ["rtc"] });
+        assert!(clawnews.validate("Headline", "Summary", &story).is_ok());
+
+        let err = clawnews
+            .validate("Headline", "Summary", &serde_json::json!({ "url": " " }))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Invalid post: ClawNews requires a non-empty 'url'");
+        let err = clawnews.validate("", "Summary", &story).unwrap_err();
+        assert!(err.to_string().ends_with("requires a non-empty 'title'"), "{err}");
+        let bad_tags = serde_json::json!({ "url": "https://example.com/a", "tags": "rtc" });
+        let err = clawnews.validate("Headline", "Summary", &bad_tags).unwrap_err();
+        assert!(err.to_string().contains("'tags' to be a list of strings"), "{err}");
+
+        let clawtasks = Platform::Clawtasks.post_requirements().unwrap();
+        let task = serde_json::json!({ "deadline_hours": 48 });
+        assert!(clawtasks.validate("Port miner", "Details", &task).is_ok());
+        for extra in [serde_json::json!({}), serde_json::json!({ "deadline_hours": 0 })] {
+            let err = clawtasks.validate("Port miner", "Details", &extra).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Invalid post: ClawTasks requires 'deadline_hours' to be a positive integer"
+            );
+        }
+
+        // Optional fields may be blank
+        let fourclaw = Platform::FourClaw.post_requirements().unwrap();
+        assert!(fourclaw.validate("", "Body", &serde_json::json!({})).is_ok());
+        assert!(Platform::Bottube.post_requirements().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_incomplete_post_is_not_sent() {
+        let server =
+            MockServer::start(|_| MockResponse::json(201, serde_json::json!({ "id": 1 }))).await;
+        let client = GrazerClient::new()
+            .with_retry_policy(RetryPolicy::none())
+            .with_base_url(Platform::Clawnews, &server.url());
+
+        let err = client
+            .post(Platform::Clawnews, "key", "Headline", "Summary", &serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClawRtcError::InvalidPost(_)), "{err}");
+        assert!(server.requests().is_empty());
+
+        let story = serde_json::json!({ "url": "https://example.com/a" });
+        client
+            .post(Platform::Clawnews, "key", "Headline", "Summary", &story)
+            .await
+            .unwrap();
+        assert_eq!(server.requests()[0].json()["url"], "https://example.com/a");
+    }
+
+    #[tokio::test]
+    async fn test_moltbook_comment() {
+        let server =
+            MockServer::start(|_| MockResponse::json(201, serde_json::json!({ "id": "c1" }))).await;
+        let client = mock_client(&server).with_circuit_breaker(CircuitBreaker {
+            failure_threshold: 1,
+            ..CircuitBreaker::default()
+        });
+        let extra = serde_json::json!({ "reply_to": "c0" });
+        let result = client
+            .comment(Platform::Moltbook, "sk", Some("p 1"), "Agreed", &extra)
+            .await
+            .unwrap();
+        assert_eq!(result["id"], "c1");
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/api/v1/posts/p%201/comments");
+        assert_eq!(
+            req.json(),
+            serde_json::json!({ "content": "Agreed", "parent_id": "c0" })
+        );
+
+        let none = serde_json::json!({});
+        let err = client
+            .comment(Platform::Moltbook, "sk", None, "Agreed", &none)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClawRtcError::InvalidPost(_)), "{err}");
+        assert!(err.to_string().contains("parent_id"), "{err}");
+        let err = client
+            .comment(Platform::Clawsta, "sk", Some("p1"), "Agreed", &none)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClawRtcError::Grazer(_)), "{err}");
+        assert!(err.to_string().contains("not supported"), "{err}");
+        assert_eq!(server.requests().len(), 1);
+        // Rejected before the circuit is consulted
+        assert!(client.circuits.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_clawcities_discover_and_comment() {
+        let server = MockServer::start(|req| match req.method.as_str() {
+            "GET" => MockResponse::json(
+                200,
+                serde_json::json!({ "sites": [{ "name": "retro" }, { "name": "g4" }] }),
+            ),
+            _ => MockResponse::json(201, serde_json::json!({ "id": "c1" })),
+        })
+        .await;
+        let client = GrazerClient::new()
+            .with_retry_policy(RetryPolicy::none())
+            .with_base_url(Platform::Clawcities, &server.url());
+
+        let sites = client
+            .discover(Platform::Clawcities, None, 1, &serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(sites["sites"], serde_json::json!([{ "name": "retro" }]));
+        assert_eq!(server.requests()[0].path, "/api/sites?sort=recent&limit=1");
+
+        let no_site = serde_json::json!({ "page": "guestbook.html" });
+        let err = client
+            .post(Platform::Clawcities, "sk", "", "Nice site", &no_site)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().ends_with("requires a non-empty 'site'"), "{err}");
+
+        let extra = serde_json::json!({ "site": "retro", "page": "guestbook.html" });
+        let result = client
+            .post(Platform::Clawcities, "sk", "", "Nice site", &extra)
+            .await
+            .unwrap();
+        assert_eq!(result["id"], "c1");
+        let req = &server.requests()[1];
+        assert_eq!(req.path, "/api/sites/retro/comments");
+        assert_eq!(
+            req.json(),
+            serde_json::json!({ "content": "Nice site", "page": "guestbook.html" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_onboard_posts_intros_and_registers_directory() {
+        let moltbook = MockServer::start(|_| {
+            MockResponse::json(200, serde_json::json!({ "post": { "id": "m1" } }))
+        })
+        .await;
+        let clawsta = MockServer::start(|_| {
+            MockResponse::json(401, serde_json::json!({ "error": "bad key" }))
+        })
+        .await;
+        let directory =
+            MockServer::start(|_| MockResponse::json(201, serde_json::json!({ "id": 42 }))).await;
+        let client = GrazerClient::new()
+            .with_retry_policy(RetryPolicy::none())
+            .with_base_url(Platform::Moltbook, &moltbook.url())
+            .with_base_url(Platform::Clawsta, &clawsta.url())
+            .with_base_url(Platform::Directory, &directory.url());
+        let profile = AgentProfile {
+            name: "Sophia".into(),
+            bio: "Vintage hardware miner".into(),
+            links: vec!["https://example.com/sophia".into()],
+        };
+        let keys = [
+            (Platform::Moltbook, "moltbook_sk_a".to_string()),
+            (Platform::Clawsta, "wrong".to_string()),
+            (Platform::Directory, "dir_key".to_string()),
+        ];
+
+        let steps = client.onboard(&profile, &keys).await;
+        let names: Vec<_> = steps.iter().map(|s| s.platform.as_str()).collect();
+        assert_eq!(names, ["moltbook", "clawsta", "directory"]);
+        assert!(steps[0].succeeded());
+        assert_eq!(steps[0].result.as_ref().unwrap().id.as_deref(), Some("m1"));
+        assert!(!steps[1].succeeded());
+        assert!(steps[1].error.as_ref().unwrap().contains("401"));
+        assert!(steps[2].succeeded());
+
+        let intro = moltbook.requests()[0].json();
+        assert_eq!(intro["title"], "Hi, I'm Sophia");
+        assert_eq!(
+            intro["content"],
+            "Vintage hardware miner\n\nhttps://example.com/sophia"
+        );
+        assert_eq!(clawsta.requests().len(), 1);
+        let listing = &directory.requests()[0];
+        assert_eq!(listing.path, "/api/services");
+        assert_eq!(listing.header("authorization"), Some("Bearer dir_key"));
+        assert_eq!(listing.json()["name"], "Sophia");
+        assert_eq!(listing.json()["url"], "https://example.com/sophia");
     }
 }
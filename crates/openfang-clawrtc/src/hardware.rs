@@ -1,11 +1,22 @@
 //! Hardware detection for RustChain miner classification.
 //!
-//! Detects CPU architecture, SIMD features, core count, memory, and MAC addresses
-//! to build the attestation device payload.
+//! Detects CPU architecture, SIMD features, core count, memory, GPUs, and MAC
+//! addresses to build the attestation device payload.
 
 use crate::error::ClawRtcResult;
+use network_interface::{NetworkInterface, NetworkInterfaceConfig};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Longest a probe subprocess (`lspci`, `nvidia-smi`, ...) may run before it
+/// is killed and treated as unavailable.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Detected hardware information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,18 +39,47 @@ pub struct HardwareInfo {
     pub memory_gb: u64,
     /// MAC addresses of network interfaces.
     pub macs: Vec<String>,
+    /// Graphics adapters; empty if none were found or no probe was available.
+    #[serde(default)]
+    pub gpus: Vec<GpuInfo>,
+    /// Host identifier from `/etc/machine-id` or the macOS IOPlatformUUID.
+    ///
+    /// Never serialized: the raw ID should stay on this machine. Use
+    /// [`HardwareInfo::stable_id`] to refer to the machine instead.
+    #[serde(default, skip_serializing)]
+    pub machine_id: Option<String>,
+}
+
+/// A detected graphics adapter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GpuInfo {
+    /// Vendor name, e.g. "NVIDIA", "AMD", "Intel", "Apple".
+    pub vendor: String,
+    /// Model name, or the PCI device ID when no name is available.
+    pub model: String,
+    /// Dedicated video memory in MiB, if reported.
+    pub vram_mb: Option<u64>,
 }
 
 impl HardwareInfo {
     /// Detect hardware on the current system.
+    ///
+    /// GPUs are probed on the first call only; later calls reuse the result.
     pub fn detect() -> ClawRtcResult<Self> {
+        Self::detect_with(&MacFilter::default())
+    }
+
+    /// Detect hardware, choosing interface MACs with `mac_filter`.
+    pub fn detect_with(mac_filter: &MacFilter) -> ClawRtcResult<Self> {
         let machine = std::env::consts::ARCH.to_string();
         let platform = std::env::consts::OS.to_string();
         let hostname = get_hostname();
         let cpu = get_cpu_model();
         let cores = num_cpus();
         let memory_gb = get_memory_gb();
-        let macs = get_mac_addresses();
+        let macs = get_mac_addresses(mac_filter);
+        let gpus = cached_gpus();
+        let machine_id = get_machine_id();
         let (family, arch) = classify_arch(&machine, &cpu);
 
         Ok(Self {
@@ -52,6 +92,8 @@ impl HardwareInfo {
             cores,
             memory_gb,
             macs,
+            gpus,
+            machine_id,
         })
     }
 
@@ -64,6 +106,7 @@ impl HardwareInfo {
             "cpu": self.cpu,
             "cores": self.cores,
             "memory_gb": self.memory_gb,
+            "gpus": self.gpus,
         })
     }
 
@@ -75,9 +118,75 @@ impl HardwareInfo {
         })
     }
 
+    /// Reward multiplier for this machine's architecture class.
+    ///
+    /// See [`arch_multiplier`].
+    pub fn arch_multiplier(&self) -> f64 {
+        arch_multiplier(&self.arch)
+    }
+
     /// The miner ID string (e.g. "claw-myhostname").
+    ///
+    /// Canonical form: `"claw-"` + the hostname lowercased, with every run of
+    /// non-alphanumeric characters collapsed to a single `-` and leading/trailing
+    /// dashes trimmed. An empty result falls back to `"unknown"`.
     pub fn miner_id(&self) -> String {
-        format!("claw-{}", self.hostname)
+        format!("claw-{}", sanitize_miner_id_component(&self.hostname))
+    }
+
+    /// Hex SHA-256 of the machine ID and the primary (lowest) MAC address.
+    ///
+    /// Unlike [`HardwareInfo::miner_id`], it survives a hostname change and
+    /// tells apart cloned machines that share a hostname. Without a machine
+    /// ID it rests on the MAC alone.
+    pub fn stable_id(&self) -> String {
+        let machine_id = self.machine_id.as_deref().unwrap_or("");
+        let mac = self.macs.first().map(String::as_str).unwrap_or("");
+        hex::encode(Sha256::digest(format!("{machine_id}|{mac}").as_bytes()))
+    }
+
+    /// Miner ID derived from [`HardwareInfo::stable_id`], e.g. `"claw-3f9a0c1e"`.
+    pub fn miner_id_stable(&self) -> String {
+        format!("claw-{}", &self.stable_id()[..8])
+    }
+}
+
+/// Reduce a hostname to the URL-safe `[a-z0-9-]` form used in miner IDs.
+fn sanitize_miner_id_component(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut last_dash = true; // suppress leading dashes
+    for c in raw.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            out.push(c);
+            last_dash = false;
+        } else if !last_dash {
+            out.push('-');
+            last_dash = true;
+        }
+    }
+    while out.ends_with('-') {
+        out.pop();
+    }
+    if out.is_empty() {
+        "unknown".to_string()
+    } else {
+        out
+    }
+}
+
+/// Proof-of-antiquity reward multiplier for an arch class from [`classify_arch`].
+///
+/// Mirrors the node's table for display purposes; the node's value is
+/// authoritative at enrollment. Unknown classes earn the base rate.
+pub fn arch_multiplier(arch: &str) -> f64 {
+    match arch {
+        "g4" => 2.5,
+        "g5" => 2.0,
+        "g3" => 1.8,
+        "pentium4" => 1.5,
+        "core2duo" => 1.3,
+        "apple_silicon" => 1.2,
+        _ => 1.0,
     }
 }
 
@@ -131,6 +240,30 @@ fn classify_arch(machine: &str, cpu_model: &str) -> (String, String) {
     ("x86".into(), "modern".into())
 }
 
+/// Get the OS's persistent machine ID, if it has one.
+fn get_machine_id() -> Option<String> {
+    if cfg!(target_os = "macos") {
+        return command_stdout("ioreg", &["-rd1", "-c", "IOPlatformExpertDevice"])
+            .and_then(|out| parse_ioreg_uuid(&out));
+    }
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .map(|id| id.trim().to_string())
+        .find(|id| !id.is_empty())
+}
+
+/// Extract the IOPlatformUUID from `ioreg -rd1 -c IOPlatformExpertDevice`.
+///
+/// The line looks like `"IOPlatformUUID" = "1A2B3C4D-..."`.
+fn parse_ioreg_uuid(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (_, value) = line.split_once("\"IOPlatformUUID\" = ")?;
+        let uuid = value.trim().trim_matches('"');
+        (!uuid.is_empty()).then(|| uuid.to_string())
+    })
+}
+
 /// Get the system hostname.
 fn get_hostname() -> String {
     if let Ok(name) = std::fs::read_to_string("/etc/hostname") {
@@ -180,10 +313,62 @@ fn get_cpu_model() -> String {
 }
 
 /// Get the number of logical CPUs.
+///
+/// `available_parallelism()` can fail in some containers, so fall back to the
+/// scheduler affinity set (`Cpus_allowed_list` in `/proc/self/status`), then
+/// the processor count in `/proc/cpuinfo`, before assuming a single core.
 fn num_cpus() -> usize {
-    std::thread::available_parallelism()
-        .map(|n| n.get())
-        .unwrap_or(1)
+    if let Ok(n) = std::thread::available_parallelism() {
+        return n.get();
+    }
+    if let Some(n) = std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| parse_affinity_cpu_count(&status))
+    {
+        return n;
+    }
+    if let Some(n) = std::fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| parse_cpuinfo_processor_count(&cpuinfo))
+    {
+        return n;
+    }
+    1
+}
+
+/// Count CPUs in the `Cpus_allowed_list` line of `/proc/self/status` (e.g. `0-3,6`).
+fn parse_affinity_cpu_count(status: &str) -> Option<usize> {
+    let list = status
+        .lines()
+        .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))?
+        .trim();
+    let mut count = 0;
+    for range in list.split(',').filter(|r| !r.is_empty()) {
+        count += match range.split_once('-') {
+            Some((lo, hi)) => {
+                let lo: usize = lo.trim().parse().ok()?;
+                let hi: usize = hi.trim().parse().ok()?;
+                hi.checked_sub(lo)? + 1
+            }
+            None => {
+                range.trim().parse::<usize>().ok()?;
+                1
+            }
+        };
+    }
+    (count > 0).then_some(count)
+}
+
+/// Count `processor` entries in `/proc/cpuinfo`.
+fn parse_cpuinfo_processor_count(cpuinfo: &str) -> Option<usize> {
+    let count = cpuinfo
+        .lines()
+        .filter(|line| {
+            line.split_once(':')
+                .is_some_and(|(key, _)| key.trim() == "processor")
+        })
+        .count();
+    (count > 0).then_some(count)
 }
 
 /// Get total system memory in GB.
@@ -216,56 +401,382 @@ fn get_memory_gb() -> u64 {
     0
 }
 
-/// Get MAC addresses from network interfaces.
-fn get_mac_addresses() -> Vec<String> {
-    let mut macs = Vec::new();
-
-    // Linux: `ip -o link`
-    if let Ok(output) = Command::new("ip").args(["-o", "link"]).output() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        for line in stdout.lines() {
-            // Look for MAC address pattern
-            if let Some(pos) = line.find("link/ether ") {
-                let rest = &line[pos + 11..];
-                if rest.len() >= 17 {
-                    let mac = rest[..17].to_lowercase();
-                    if mac != "00:00:00:00:00:00" && !macs.contains(&mac) {
-                        macs.push(mac);
-                    }
-                }
+/// Detect GPUs, probing whatever the platform offers.
+///
+/// macOS asks `system_profiler`. Elsewhere `lspci` names every adapter, with
+/// `/sys/class/drm` as a fallback that needs no binaries, and `nvidia-smi`
+/// replaces NVIDIA entries with ones that include memory. Missing tools are
+/// skipped, so a machine without GPUs or probes gets an empty list.
+fn get_gpus() -> Vec<GpuInfo> {
+    if cfg!(target_os = "macos") {
+        return command_stdout("system_profiler", &["SPDisplaysDataType", "-json"])
+            .map(|json| parse_system_profiler_gpus(&json))
+            .unwrap_or_default();
+    }
+
+    let mut gpus = command_stdout("lspci", &[])
+        .map(|out| parse_lspci_gpus(&out))
+        .unwrap_or_default();
+    if gpus.is_empty() {
+        gpus = sysfs_gpus(Path::new("/sys/class/drm"));
+    }
+    let nvidia = command_stdout(
+        "nvidia-smi",
+        &[
+            "--query-gpu=name,memory.total",
+            "--format=csv,noheader,nounits",
+        ],
+    )
+    .map(|out| parse_nvidia_smi(&out))
+    .unwrap_or_default();
+    if !nvidia.is_empty() {
+        gpus.retain(|gpu| gpu.vendor != "NVIDIA");
+        gpus.extend(nvidia);
+    }
+    gpus
+}
+
+/// [`get_gpus`], probed once per process: the probes are slow subprocesses
+/// and the adapters don't change while we run.
+fn cached_gpus() -> Vec<GpuInfo> {
+    static GPUS: OnceLock<Vec<GpuInfo>> = OnceLock::new();
+    GPUS.get_or_init(get_gpus).clone()
+}
+
+/// Stdout of `program args`, or `None` if it can't be run, fails, or runs
+/// longer than [`PROBE_TIMEOUT`].
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    command_stdout_within(program, args, PROBE_TIMEOUT)
+}
+
+fn command_stdout_within(program: &str, args: &[&str], timeout: Duration) -> Option<String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    // Drain stdout on another thread so a chatty probe can't block on a full pipe
+    let mut stdout = child.stdout.take()?;
+    let reader = std::thread::spawn(move || {
+        let mut out = Vec::new();
+        stdout.read_to_end(&mut out).map(|_| out)
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            Ok(None) => {
+                warn!(program, "Probe timed out; killing it");
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
             }
+            Err(_) => return None,
         }
+    };
+    let out = reader.join().ok()?.ok()?;
+    status
+        .success()
+        .then(|| String::from_utf8_lossy(&out).into_owned())
+}
+
+/// Short vendor name for a PCI vendor ID such as `0x10de`.
+fn pci_vendor_name(id: &str) -> Option<&'static str> {
+    match id.trim().to_ascii_lowercase().as_str() {
+        "0x10de" => Some("NVIDIA"),
+        "0x1002" => Some("AMD"),
+        "0x8086" => Some("Intel"),
+        "0x1a03" => Some("ASPEED"),
+        "0x102b" => Some("Matrox"),
+        "0x15ad" => Some("VMware"),
+        "0x1af4" => Some("Red Hat"),
+        _ => None,
     }
+}
 
-    // macOS fallback: `ifconfig -a`
-    if macs.is_empty() {
-        if let Ok(output) = Command::new("ifconfig").arg("-a").output() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            for line in stdout.lines() {
-                let trimmed = line.trim();
-                if let Some(pos) = trimmed.find("ether ") {
-                    let rest = &trimmed[pos + 6..];
-                    if rest.len() >= 17 {
-                        let mac = rest[..17].to_lowercase();
-                        if mac != "00:00:00:00:00:00" && !macs.contains(&mac) {
-                            macs.push(mac);
-                        }
-                    }
-                }
+/// Short vendor name found in a free-text device description.
+fn vendor_from_description(description: &str) -> String {
+    const VENDORS: &[(&str, &str)] = &[
+        ("NVIDIA", "NVIDIA"),
+        ("Advanced Micro Devices", "AMD"),
+        ("AMD", "AMD"),
+        ("ATI", "AMD"),
+        ("Intel", "Intel"),
+        ("Apple", "Apple"),
+        ("ASPEED", "ASPEED"),
+        ("Matrox", "Matrox"),
+        ("VMware", "VMware"),
+        ("Red Hat", "Red Hat"),
+    ];
+    VENDORS
+        .iter()
+        .find(|(needle, _)| description.contains(needle))
+        .map(|(_, vendor)| vendor.to_string())
+        .unwrap_or_else(|| {
+            description
+                .split_whitespace()
+                .next()
+                .unwrap_or("unknown")
+                .to_string()
+        })
+}
+
+/// Parse `lspci` output into its display controllers.
+///
+/// Lines look like `01:00.0 VGA compatible controller: NVIDIA Corporation
+/// GA102 [GeForce RTX 3080] (rev a1)`; the last bracketed name is the model
+/// when there is one.
+fn parse_lspci_gpus(output: &str) -> Vec<GpuInfo> {
+    const CLASSES: &[&str] = &[
+        " VGA compatible controller: ",
+        " 3D controller: ",
+        " Display controller: ",
+    ];
+    output
+        .lines()
+        .filter_map(|line| {
+            let description = CLASSES
+                .iter()
+                .find_map(|class| line.split_once(class).map(|(_, rest)| rest))?;
+            let description = match description.rfind(" (rev ") {
+                Some(pos) => &description[..pos],
+                None => description,
             }
+            .trim();
+            let model = match (description.rfind('['), description.ends_with(']')) {
+                (Some(open), true) => &description[open + 1..description.len() - 1],
+                _ => description,
+            };
+            Some(GpuInfo {
+                vendor: vendor_from_description(description),
+                model: model.to_string(),
+                vram_mb: None,
+            })
+        })
+        .collect()
+}
+
+/// Read GPUs from a `/sys/class/drm`-style directory of `cardN` entries.
+///
+/// Only the PCI vendor and device IDs are available here, plus video memory
+/// for drivers that report it (`amdgpu`).
+fn sysfs_gpus(drm: &Path) -> Vec<GpuInfo> {
+    let Ok(entries) = std::fs::read_dir(drm) else {
+        return Vec::new();
+    };
+    let mut cards: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.strip_prefix("card")
+                .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(|entry| entry.path().join("device"))
+        .collect();
+    cards.sort();
+    cards
+        .into_iter()
+        .filter_map(|device| {
+            let read = |name: &str| std::fs::read_to_string(device.join(name)).ok();
+            let vendor_id = read("vendor")?;
+            let device_id = read("device").unwrap_or_default();
+            Some(GpuInfo {
+                vendor: pci_vendor_name(&vendor_id)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| vendor_id.trim().to_string()),
+                model: format!("PCI device {}", device_id.trim()),
+                vram_mb: read("mem_info_vram_total")
+                    .and_then(|bytes| bytes.trim().parse::<u64>().ok())
+                    .map(|bytes| bytes / (1024 * 1024)),
+            })
+        })
+        .collect()
+}
+
+/// Parse `nvidia-smi --query-gpu=name,memory.total --format=csv,noheader,nounits`.
+fn parse_nvidia_smi(output: &str) -> Vec<GpuInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, memory) = line.rsplit_once(',')?;
+            let name = name.trim();
+            (!name.is_empty()).then(|| GpuInfo {
+                vendor: "NVIDIA".to_string(),
+                model: name.to_string(),
+                vram_mb: memory.trim().parse().ok(),
+            })
+        })
+        .collect()
+}
+
+/// Parse `system_profiler SPDisplaysDataType -json`.
+///
+/// Apple Silicon reports no dedicated memory; discrete cards report it as
+/// e.g. `"4 GB"` or `"1536 MB"`.
+fn parse_system_profiler_gpus(json: &str) -> Vec<GpuInfo> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+        return Vec::new();
+    };
+    let Some(displays) = value["SPDisplaysDataType"].as_array() else {
+        return Vec::new();
+    };
+    displays
+        .iter()
+        .filter_map(|display| {
+            let model = display["sppci_model"].as_str()?.trim();
+            let vendor = display["spdisplays_vendor"]
+                .as_str()
+                .map(|v| v.trim_start_matches("sppci_vendor_"))
+                .unwrap_or(model);
+            let vram_mb = ["spdisplays_vram", "_spdisplays_vram"]
+                .iter()
+                .find_map(|key| display[*key].as_str())
+                .and_then(parse_memory_mb);
+            Some(GpuInfo {
+                vendor: vendor_from_description(vendor),
+                model: model.to_string(),
+                vram_mb,
+            })
+        })
+        .collect()
+}
+
+/// Parse a size like `"4 GB"` or `"1536 MB"` into MiB.
+fn parse_memory_mb(size: &str) -> Option<u64> {
+    let (amount, unit) = size.trim().split_once(' ')?;
+    let amount: u64 = amount.parse().ok()?;
+    match unit.trim() {
+        "MB" => Some(amount),
+        "GB" => Some(amount * 1024),
+        _ => None,
+    }
+}
+
+/// Interface-name prefixes of virtual interfaces: container and VM bridges,
+/// veth pairs, VPN tunnels, and loopback. Their MACs are typically generated
+/// at boot or container start.
+pub const VIRTUAL_INTERFACE_PREFIXES: &[&str] = &[
+    "lo", "docker", "veth", "ifb", "dummy", "br-", "virbr", "bridge", "tun", "tap", "utun", "vnet",
+    "vmnet", "vboxnet", "wg", "zt", "cni", "flannel", "cali", "awdl", "llw",
+];
+
+/// Which interfaces' MACs go into the hardware signals.
+///
+/// MACs of virtual interfaces change across reboots and container restarts,
+/// which would make the attestation signals of an unchanged machine look new.
+/// By default only physical interfaces count; virtual ones are used only when
+/// the machine has no physical interface at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacFilter {
+    /// Interface-name prefixes treated as virtual.
+    pub virtual_prefixes: Vec<String>,
+    /// Keep virtual interfaces' MACs alongside physical ones.
+    pub include_virtual: bool,
+}
+
+impl Default for MacFilter {
+    fn default() -> Self {
+        Self {
+            virtual_prefixes: VIRTUAL_INTERFACE_PREFIXES
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            include_virtual: false,
+        }
+    }
+}
+
+impl MacFilter {
+    /// Whether interface `name` looks virtual.
+    pub fn is_virtual(&self, name: &str) -> bool {
+        self.virtual_prefixes.iter().any(|p| name.starts_with(p.as_str()))
+    }
+
+    /// Pick MACs from `(interface, mac)` pairs: sorted, deduplicated, and
+    /// without the all-zero MAC, so the set doesn't depend on enumeration
+    /// order or on bonded interfaces sharing an address.
+    pub fn select(&self, interfaces: &[(String, String)]) -> Vec<String> {
+        let usable = || {
+            interfaces
+                .iter()
+                .filter(|(_, mac)| mac != "00:00:00:00:00:00")
+        };
+        let mut macs: Vec<String> = usable()
+            .filter(|(name, _)| self.include_virtual || !self.is_virtual(name))
+            .map(|(_, mac)| mac.clone())
+            .collect();
+        if macs.is_empty() {
+            macs = usable().map(|(_, mac)| mac.clone()).collect();
         }
+        macs.sort();
+        macs.dedup();
+        macs
     }
+}
 
+/// Get MAC addresses from network interfaces.
+///
+/// Interfaces are enumerated with `getifaddrs` (or the platform's
+/// equivalent), so no `ip`/`ifconfig` binary is needed. Loopback interfaces
+/// and malformed or all-zero MACs are skipped; `00:00:00:00:00:01` stands in
+/// only when no interface has a usable MAC.
+fn get_mac_addresses(filter: &MacFilter) -> Vec<String> {
+    let interfaces: Vec<(String, String)> = NetworkInterface::show()
+        .unwrap_or_else(|e| {
+            warn!("Failed to enumerate network interfaces: {e}");
+            Vec::new()
+        })
+        .into_iter()
+        .filter(|iface| !iface.internal)
+        .filter_map(|iface| {
+            let mac = normalize_mac(iface.mac_addr.as_deref()?)?;
+            Some((iface.name, mac))
+        })
+        .collect();
+
+    let mut macs = filter.select(&interfaces);
     if macs.is_empty() {
         macs.push("00:00:00:00:00:01".to_string());
     }
     macs
 }
 
+/// `mac` as lowercase `xx:xx:xx:xx:xx:xx`, accepting `-` separators, or
+/// `None` if it isn't a 6-byte MAC (e.g. the empty address of a tunnel).
+fn normalize_mac(mac: &str) -> Option<String> {
+    let octets: Vec<&str> = mac.split([':', '-']).collect();
+    let well_formed = octets.len() == 6
+        && octets
+            .iter()
+            .all(|o| o.len() == 2 && o.chars().all(|c| c.is_ascii_hexdigit()));
+    well_formed.then(|| octets.join(":").to_lowercase())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(unix)]
+    #[test]
+    fn test_probe_is_killed_after_timeout() {
+        let start = Instant::now();
+        let out = command_stdout_within("sleep", &["30"], Duration::from_millis(100));
+        assert_eq!(out, None);
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        let out = command_stdout_within("echo", &["gpu"], Duration::from_secs(5));
+        assert_eq!(out.as_deref(), Some("gpu\n"));
+        let out = command_stdout_within("false", &[], Duration::from_secs(5));
+        assert_eq!(out, None);
+    }
+
     #[test]
     fn test_classify_x86_modern() {
         let (fam, arch) = classify_arch("x86_64", "AMD Ryzen 9 7950X");
@@ -294,6 +805,196 @@ mod tests {
         assert_eq!(arch, "core2duo");
     }
 
+    #[test]
+    fn test_arch_multiplier() {
+        assert_eq!(arch_multiplier("g4"), 2.5);
+        assert_eq!(arch_multiplier("g5"), 2.0);
+        assert_eq!(arch_multiplier("modern"), 1.0);
+        assert_eq!(arch_multiplier("something_new"), 1.0);
+        let (_, arch) = classify_arch("ppc", "PowerPC G4 (7450)");
+        assert!(arch_multiplier(&arch) > arch_multiplier("modern"));
+    }
+
+    #[test]
+    fn test_parse_cpuinfo_processor_count() {
+        let fixture = "\
+processor\t: 0
+vendor_id\t: GenuineIntel
+model name\t: Intel(R) Xeon(R) CPU E5-2680 v4 @ 2.40GHz
+
+processor\t: 1
+vendor_id\t: GenuineIntel
+model name\t: Intel(R) Xeon(R) CPU E5-2680 v4 @ 2.40GHz
+
+processor\t: 2
+vendor_id\t: GenuineIntel
+
+processor\t: 3
+vendor_id\t: GenuineIntel
+";
+        assert_eq!(parse_cpuinfo_processor_count(fixture), Some(4));
+        assert_eq!(parse_cpuinfo_processor_count("model name : G4"), None);
+        // PowerPC cpuinfo uses the same key
+        assert_eq!(
+            parse_cpuinfo_processor_count("processor\t: 0\ncpu\t\t: 7447A\n"),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_parse_affinity_cpu_count() {
+        let status = "Name:\tminer\nCpus_allowed:\t4f\nCpus_allowed_list:\t0-3,6\n";
+        assert_eq!(parse_affinity_cpu_count(status), Some(5));
+        assert_eq!(parse_affinity_cpu_count("Cpus_allowed_list:\t7\n"), Some(1));
+        assert_eq!(parse_affinity_cpu_count("Cpus_allowed_list:\t3-1\n"), None);
+        assert_eq!(parse_affinity_cpu_count("Name:\tminer\n"), None);
+    }
+
+    fn hw_with_hostname(hostname: &str) -> HardwareInfo {
+        HardwareInfo {
+            platform: "linux".into(),
+            machine: "x86_64".into(),
+            hostname: hostname.into(),
+            family: "x86".into(),
+            arch: "modern".into(),
+            cpu: "test".into(),
+            cores: 4,
+            memory_gb: 8,
+            macs: vec!["00:11:22:33:44:55".into()],
+            gpus: Vec::new(),
+            machine_id: Some("0123456789abcdef0123456789abcdef".into()),
+        }
+    }
+
+    #[test]
+    fn test_miner_id_plain_hostname() {
+        assert_eq!(hw_with_hostname("myhost").miner_id(), "claw-myhost");
+    }
+
+    #[test]
+    fn test_miner_id_sanitizes_spaces_and_case() {
+        assert_eq!(hw_with_hostname("My Power Mac").miner_id(), "claw-my-power-mac");
+    }
+
+    #[test]
+    fn test_miner_id_collapses_special_characters() {
+        assert_eq!(
+            hw_with_hostname("--G4__Cube!!.local--").miner_id(),
+            "claw-g4-cube-local"
+        );
+        assert_eq!(hw_with_hostname("café-box").miner_id(), "claw-caf-box");
+    }
+
+    #[test]
+    fn test_miner_id_empty_after_sanitizing() {
+        assert_eq!(hw_with_hostname("").miner_id(), "claw-unknown");
+        assert_eq!(hw_with_hostname("***").miner_id(), "claw-unknown");
+    }
+
+    #[test]
+    fn test_stable_id_follows_machine_not_hostname() {
+        let hw = hw_with_hostname("render-07");
+        let renamed = hw_with_hostname("render-07-old");
+        assert_eq!(hw.stable_id(), renamed.stable_id());
+        assert_ne!(hw.miner_id(), renamed.miner_id());
+
+        // A clone with the same hostname but its own machine ID
+        let mut clone = hw_with_hostname("render-07");
+        clone.machine_id = Some("fedcba9876543210fedcba9876543210".into());
+        assert_ne!(hw.stable_id(), clone.stable_id());
+        assert_eq!(hw.miner_id(), clone.miner_id());
+
+        let id = hw.miner_id_stable();
+        assert_eq!(id.len(), "claw-".len() + 8);
+        assert!(id.starts_with("claw-") && id[5..].chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(hw.stable_id().len(), 64);
+
+        // The raw machine ID is never serialized
+        let json = serde_json::to_string(&hw).unwrap();
+        assert!(!json.contains("0123456789abcdef"), "{json}");
+    }
+
+    #[test]
+    fn test_parse_ioreg_uuid() {
+        let out = r#"+-o MacBookPro18,3  <class IOPlatformExpertDevice>
+    {
+      "IOPlatformSerialNumber" = "C02XXXXXXXXX"
+      "IOPlatformUUID" = "1A2B3C4D-5E6F-7081-92A3-B4C5D6E7F809"
+    }"#;
+        assert_eq!(
+            parse_ioreg_uuid(out).as_deref(),
+            Some("1A2B3C4D-5E6F-7081-92A3-B4C5D6E7F809")
+        );
+        assert_eq!(parse_ioreg_uuid("{}"), None);
+    }
+
+    #[test]
+    fn test_miner_id_stable() {
+        let hw = hw_with_hostname("Render Farm #7");
+        assert_eq!(hw.miner_id(), hw.miner_id());
+    }
+
+    #[test]
+    fn test_mac_filter_keeps_physical_interfaces() {
+        let interfaces: Vec<(String, String)> = [
+            ("lo", "00:00:00:00:00:00"),
+            ("enp3s0", "3c:7c:3f:1e:22:10"),
+            ("wlp4s0", "08:d2:3e:4a:5b:6c"),
+            ("docker0", "02:42:8e:1a:2b:3c"),
+            ("br-5f1e2d3c4b5a", "02:42:11:22:33:44"),
+            ("veth9a8b7c6", "6e:55:44:33:22:11"),
+            ("vnet0", "fe:54:00:aa:bb:cc"),
+            ("bond0", "3c:7c:3f:1e:22:10"),
+        ]
+        .into_iter()
+        .map(|(name, mac)| (name.to_string(), mac.to_string()))
+        .collect();
+
+        let filter = MacFilter::default();
+        // Sorted, and enp3s0's MAC appears once despite the bond sharing it
+        assert_eq!(
+            filter.select(&interfaces),
+            ["08:d2:3e:4a:5b:6c", "3c:7c:3f:1e:22:10"]
+        );
+        let all = MacFilter {
+            include_virtual: true,
+            ..MacFilter::default()
+        };
+        assert_eq!(all.select(&interfaces).len(), 6);
+
+        // A machine with only virtual interfaces still reports them
+        assert_eq!(filter.select(&interfaces[3..7]).len(), 4);
+    }
+
+    #[test]
+    fn test_normalize_mac() {
+        assert_eq!(
+            normalize_mac("3C-7C-3F-1E-22-10").as_deref(),
+            Some("3c:7c:3f:1e:22:10")
+        );
+        assert_eq!(
+            normalize_mac("a4:83:e7:01:02:03").as_deref(),
+            Some("a4:83:e7:01:02:03")
+        );
+        for bad in ["", "00:11:22:33:44", "00:11:22:33:44:5g", "001122334455"] {
+            assert_eq!(normalize_mac(bad), None, "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_detected_macs_are_well_formed() {
+        let macs = get_mac_addresses(&MacFilter::default());
+        assert!(!macs.is_empty());
+        for mac in &macs {
+            assert_eq!(normalize_mac(mac).as_ref(), Some(mac), "{mac}");
+            assert_ne!(mac, "00:00:00:00:00:00");
+        }
+        let mut sorted = macs.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted, macs);
+    }
+
     #[test]
     fn test_detect_hardware() {
         let hw = HardwareInfo::detect().unwrap();
@@ -309,5 +1010,82 @@ mod tests {
         assert!(payload["family"].is_string());
         assert!(payload["arch"].is_string());
         assert!(payload["cores"].is_number());
+        assert!(payload["gpus"].is_array());
+    }
+
+    #[test]
+    fn test_gpu_detection_degrades_to_empty() {
+        // Runs on GPU-less CI runners without lspci or nvidia-smi
+        let hw = HardwareInfo::detect().unwrap();
+        for gpu in &hw.gpus {
+            assert!(!gpu.vendor.is_empty() && !gpu.model.is_empty(), "{gpu:?}");
+        }
+        assert!(sysfs_gpus(Path::new("/nonexistent/drm")).is_empty());
+        assert!(parse_system_profiler_gpus("not json").is_empty());
+
+        // Saved hardware info from before GPU detection still loads
+        let mut json = serde_json::to_value(hw_with_hostname("old")).unwrap();
+        json.as_object_mut().unwrap().remove("gpus");
+        let old: HardwareInfo = serde_json::from_value(json).unwrap();
+        assert!(old.gpus.is_empty());
+    }
+
+    #[test]
+    fn test_parse_gpu_probes() {
+        let lspci = "\
+00:02.0 VGA compatible controller: Intel Corporation UHD Graphics 620 (rev 07)
+00:14.0 USB controller: Intel Corporation Sunrise Point-LP USB 3.0 xHCI Controller (rev 21)
+01:00.0 3D controller: NVIDIA Corporation GA102 [GeForce RTX 3080] (rev a1)
+03:00.0 VGA compatible controller: Advanced Micro Devices, Inc. [AMD/ATI] Navi 21 [RX 6800] (rev c1)
+";
+        let gpus = parse_lspci_gpus(lspci);
+        let names: Vec<_> = gpus
+            .iter()
+            .map(|g| (g.vendor.as_str(), g.model.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("Intel", "Intel Corporation UHD Graphics 620"),
+                ("NVIDIA", "GeForce RTX 3080"),
+                ("AMD", "RX 6800"),
+            ]
+        );
+
+        let nvidia = parse_nvidia_smi("NVIDIA GeForce RTX 3080, 10240\nTesla T4, [N/A]\n");
+        assert_eq!(nvidia[0].model, "NVIDIA GeForce RTX 3080");
+        assert_eq!(nvidia[0].vram_mb, Some(10240));
+        assert_eq!(nvidia[1].vram_mb, None);
+
+        let profiler = serde_json::json!({ "SPDisplaysDataType": [
+            { "sppci_model": "Apple M2", "spdisplays_vendor": "sppci_vendor_Apple" },
+            { "sppci_model": "AMD Radeon Pro 5500M", "spdisplays_vram": "4 GB" },
+        ]});
+        let macs = parse_system_profiler_gpus(&profiler.to_string());
+        assert_eq!(macs[0].vendor, "Apple");
+        assert_eq!(macs[0].vram_mb, None);
+        assert_eq!(macs[1].vendor, "AMD");
+        assert_eq!(macs[1].vram_mb, Some(4096));
+    }
+
+    #[test]
+    fn test_sysfs_gpus() {
+        let drm = tempfile::tempdir().unwrap();
+        let card = drm.path().join("card0").join("device");
+        std::fs::create_dir_all(&card).unwrap();
+        std::fs::write(card.join("vendor"), "0x1002\n").unwrap();
+        std::fs::write(card.join("device"), "0x73bf\n").unwrap();
+        std::fs::write(card.join("mem_info_vram_total"), "17163091968\n").unwrap();
+        // Connectors aren't cards
+        std::fs::create_dir_all(drm.path().join("card0-HDMI-A-1")).unwrap();
+
+        assert_eq!(
+            sysfs_gpus(drm.path()),
+            [GpuInfo {
+                vendor: "AMD".into(),
+                model: "PCI device 0x73bf".into(),
+                vram_mb: Some(16368),
+            }]
+        );
     }
 }
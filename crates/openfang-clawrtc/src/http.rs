@@ -0,0 +1,961 @@
+//! Shared HTTP helpers for the platform clients.
+//!
+//! Write operations (posts, comments, votes) are not idempotent, so retries are
+//! limited to failures where the server cannot have acted on the request:
+//! connection errors before anything was sent, and explicit `503 Service
+//! Unavailable` / `429 Too Many Requests` responses. Timeouts and other 5xx
+//! responses are never retried — the write may already have landed.
+//!
+//! Response bodies are read through [`read_json`] / [`read_text`], which stop
+//! buffering once a size cap is exceeded so an untrusted platform cannot
+//! exhaust memory with a huge body. Long JSON arrays can instead be read
+//! element by element with [`read_json_array`], where the cap applies to each
+//! element rather than the whole body. A body declared as something other
+//! than JSON (typically an HTML login wall or error page) is reported with its
+//! content type and opening text instead of as a JSON syntax error.
+//!
+//! Each client builds its `reqwest::Client` from [`HttpOptions`] and rebuilds
+//! it when a transport setting (e.g. the minimum TLS version or a static
+//! host override) changes.
+//!
+//! Clients may share a [`RequestLimit`] that bounds their combined in-flight
+//! requests; a request past the limit waits for a slot instead of firing.
+//!
+//! Redirects are followed here rather than by `reqwest`, so credentials can be
+//! dropped when one crosses origin: platforms send API keys in custom headers
+//! (e.g. `X-API-Key`) that `reqwest` would forward to the new host. Same-origin
+//! redirects keep every header; cross-origin ones are logged and followed
+//! without [`CREDENTIAL_HEADERS`] or any header a client registered for its
+//! auth scheme through [`HttpOptions::register_credential_header`].
+
+use crate::error::{ClawRtcError, ClawRtcResult};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::header::{CONTENT_TYPE, LOCATION};
+use reqwest::{Method, Request, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{debug, warn};
+
+pub use reqwest::tls::Version as TlsVersion;
+
+/// Default cap on response body size (4 MiB).
+pub const DEFAULT_MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+
+/// Redirects followed before the last redirect response is returned as-is.
+pub const MAX_REDIRECTS: usize = 10;
+
+/// Standard headers carrying credentials, always removed before following a
+/// cross-origin redirect.
+pub const CREDENTIAL_HEADERS: &[&str] = &["authorization", "proxy-authorization", "cookie"];
+
+/// Retry and backoff settings for write operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retrying).
+    pub max_retries: u32,
+    /// Delay before the first retry; doubles on each subsequent retry.
+    pub initial_backoff: Duration,
+    /// Upper bound on any single delay, including a server's `Retry-After`.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Backoff before retry number `attempt` (0-based).
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
+/// A cap on concurrent in-flight requests, shared by every client it is
+/// given to.
+///
+/// A request holds its slot from sending until its response, body included,
+/// is dropped. Clones share the same slots.
+#[derive(Debug, Clone)]
+pub struct RequestLimit {
+    semaphore: Arc<Semaphore>,
+    max_in_flight: usize,
+}
+
+impl RequestLimit {
+    /// Allow at most `max_in_flight` requests at once (at least 1).
+    pub fn new(max_in_flight: usize) -> Self {
+        let max_in_flight = max_in_flight.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_in_flight)),
+            max_in_flight,
+        }
+    }
+
+    /// The configured limit.
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight
+    }
+
+    /// Requests currently holding a slot.
+    pub fn in_flight(&self) -> usize {
+        self.max_in_flight - self.semaphore.available_permits()
+    }
+
+    async fn acquire(&self) -> OwnedSemaphorePermit {
+        self.semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("request limit semaphore is never closed")
+    }
+}
+
+/// A [`RequestLimit`] slot carried in a response's extensions, so it is
+/// released when the response is dropped.
+#[derive(Clone)]
+struct InFlight(#[allow(dead_code)] Arc<OwnedSemaphorePermit>);
+
+/// Transport settings a client's `reqwest::Client` is built from.
+#[derive(Debug, Clone)]
+pub(crate) struct HttpOptions {
+    pub timeout: Duration,
+    /// Accept self-signed certificates (RustChain nodes use them).
+    pub accept_invalid_certs: bool,
+    /// Refuse to negotiate anything older than this TLS version.
+    pub min_tls_version: Option<TlsVersion>,
+    /// Shared cap on in-flight requests.
+    pub request_limit: Option<RequestLimit>,
+    /// Static host-to-address mappings that bypass system DNS.
+    pub resolve: Vec<(String, SocketAddr)>,
+    /// Client-specific credential headers, stripped alongside
+    /// [`CREDENTIAL_HEADERS`] on cross-origin redirects.
+    pub credential_headers: Vec<&'static str>,
+}
+
+impl HttpOptions {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            accept_invalid_certs: false,
+            min_tls_version: None,
+            request_limit: None,
+            resolve: Vec::new(),
+            credential_headers: Vec::new(),
+        }
+    }
+
+    pub fn limit(&self) -> Option<&RequestLimit> {
+        self.request_limit.as_ref()
+    }
+
+    /// Treat `name` as a credential header, e.g. the one an auth scheme puts
+    /// an API key in.
+    pub fn register_credential_header(&mut self, name: &'static str) {
+        let known = CREDENTIAL_HEADERS.iter().chain(&self.credential_headers);
+        if !known.into_iter().any(|h| h.eq_ignore_ascii_case(name)) {
+            self.credential_headers.push(name);
+        }
+    }
+
+    /// Send `req` under this client's request limit.
+    pub async fn send(&self, req: RequestBuilder) -> reqwest::Result<Response> {
+        send_limited(self, req).await
+    }
+
+    pub fn build(&self) -> reqwest::Client {
+        let mut builder = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .danger_accept_invalid_certs(self.accept_invalid_certs)
+            // Followed by `send_limited`, which strips credentials cross-origin
+            .redirect(reqwest::redirect::Policy::none());
+        if let Some(version) = self.min_tls_version {
+            builder = builder.min_tls_version(version);
+        }
+        for (host, addr) in &self.resolve {
+            builder = builder.resolve(host, *addr);
+        }
+        builder.build().expect("Failed to build HTTP client")
+    }
+}
+
+/// Whether a response status means the server refused the request without acting on it.
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::SERVICE_UNAVAILABLE || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Parse a `Retry-After` header given in seconds.
+fn retry_after(resp: &Response) -> Option<Duration> {
+    resp.headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Whether two URLs share scheme, host, and port.
+fn same_origin(a: &reqwest::Url, b: &reqwest::Url) -> bool {
+    a.scheme() == b.scheme()
+        && a.host_str() == b.host_str()
+        && a.port_or_known_default() == b.port_or_known_default()
+}
+
+/// Execute `request`, following up to [`MAX_REDIRECTS`] redirects.
+///
+/// A redirect that can't be followed (no usable `Location`, or a streaming
+/// body that can't be replayed) is returned as the response. Cross-origin
+/// redirects drop [`CREDENTIAL_HEADERS`] and `credential_headers`.
+async fn execute_following_redirects(
+    client: &reqwest::Client,
+    mut request: Request,
+    credential_headers: &[&str],
+) -> reqwest::Result<Response> {
+    for _ in 0..MAX_REDIRECTS {
+        let replay = request.try_clone();
+        let resp = client.execute(request).await?;
+        let status = resp.status();
+        if !status.is_redirection() {
+            return Ok(resp);
+        }
+        let location = resp
+            .headers()
+            .get(LOCATION)
+            .and_then(|l| l.to_str().ok())
+            .and_then(|l| resp.url().join(l).ok());
+        let (Some(location), Some(mut next)) = (location, replay) else {
+            return Ok(resp);
+        };
+
+        if !same_origin(resp.url(), &location) {
+            warn!(
+                from = %resp.url(),
+                to = %location,
+                "Cross-origin redirect; not forwarding credentials"
+            );
+            for header in CREDENTIAL_HEADERS.iter().chain(credential_headers) {
+                next.headers_mut().remove(*header);
+            }
+        } else {
+            debug!(from = %resp.url(), to = %location, "Following redirect");
+        }
+        // As browsers do: 303, and 301/302 after a POST, become a bodiless GET
+        let to_get = status == StatusCode::SEE_OTHER
+            || (next.method() == Method::POST
+                && matches!(status, StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND));
+        if to_get {
+            *next.method_mut() = Method::GET;
+            *next.body_mut() = None;
+            let headers = next.headers_mut();
+            headers.remove(reqwest::header::CONTENT_TYPE);
+            headers.remove(reqwest::header::CONTENT_LENGTH);
+        }
+        *next.url_mut() = location;
+        request = next;
+    }
+    client.execute(request).await
+}
+
+/// Send `req`, first waiting for a slot under the request limit if one is set.
+async fn send_limited(options: &HttpOptions, req: RequestBuilder) -> reqwest::Result<Response> {
+    let permit = match options.limit() {
+        Some(limit) => Some(limit.acquire().await),
+        None => None,
+    };
+    let (client, request) = req.build_split();
+    let mut resp =
+        execute_following_redirects(&client, request?, &options.credential_headers).await?;
+    if let Some(permit) = permit {
+        resp.extensions_mut().insert(InFlight(Arc::new(permit)));
+    }
+    Ok(resp)
+}
+
+/// Send a request built by `build`, retrying only the safe failure cases.
+///
+/// `build` is called once per attempt. After the final attempt, the last
+/// response (even a 503/429) or error is returned for the caller to handle.
+/// Each attempt takes its own slot under the request limit in `options`; none
+/// is held while backing off.
+pub(crate) async fn send_with_retry<F>(
+    policy: &RetryPolicy,
+    options: &HttpOptions,
+    build: F,
+) -> reqwest::Result<Response>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let mut attempt = 0;
+    loop {
+        let can_retry = attempt < policy.max_retries;
+        let delay = match send_limited(options, build()).await {
+            Ok(resp) if can_retry && is_retryable_status(resp.status()) => {
+                debug!(status = %resp.status(), attempt, "Server unavailable, retrying");
+                retry_after(&resp).unwrap_or_else(|| policy.backoff(attempt))
+            }
+            Err(e) if can_retry && e.is_connect() => {
+                debug!(error = %e, attempt, "Connection failed, retrying");
+                policy.backoff(attempt)
+            }
+            result => return result,
+        };
+        tokio::time::sleep(delay.min(policy.max_backoff)).await;
+        attempt += 1;
+    }
+}
+
+/// Read a response body, failing once it grows past `limit` bytes.
+pub(crate) async fn read_body(mut resp: Response, limit: usize) -> ClawRtcResult<Vec<u8>> {
+    if resp.content_length().is_some_and(|len| len > limit as u64) {
+        return Err(ClawRtcError::ResponseTooLarge(limit));
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = resp.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            return Err(ClawRtcError::ResponseTooLarge(limit));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Bytes of a non-JSON body quoted in [`ClawRtcError::UnexpectedContentType`].
+const SNIPPET_BYTES: usize = 200;
+
+/// Whether a `Content-Type` value names JSON: `application/json`,
+/// `text/json`, or a `+json` type such as `application/problem+json`.
+fn is_json_content_type(value: &str) -> bool {
+    let essence = value.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+    essence == "application/json" || essence == "text/json" || essence.ends_with("+json")
+}
+
+/// Fail with [`ClawRtcError::UnexpectedContentType`] if `resp` declares a
+/// content type other than JSON.
+///
+/// A response without a `Content-Type` header is passed through to the parser.
+pub(crate) async fn expect_json(resp: Response) -> ClawRtcResult<Response> {
+    let Some(content_type) = resp.headers().get(CONTENT_TYPE) else {
+        return Ok(resp);
+    };
+    let content_type = String::from_utf8_lossy(content_type.as_bytes()).into_owned();
+    if is_json_content_type(&content_type) {
+        return Ok(resp);
+    }
+    let status = resp.status();
+    let snippet = body_snippet(resp).await;
+    Err(ClawRtcError::UnexpectedContentType(format!(
+        "expected JSON, got {content_type} (HTTP {status}): {snippet}"
+    )))
+}
+
+/// The first [`SNIPPET_BYTES`] of a body as one line of printable text.
+async fn body_snippet(mut resp: Response) -> String {
+    let mut body = Vec::new();
+    while body.len() < SNIPPET_BYTES {
+        match resp.chunk().await {
+            Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+            _ => break,
+        }
+    }
+    let truncated = body.len() > SNIPPET_BYTES;
+    body.truncate(SNIPPET_BYTES);
+    let text = crate::sanitize::sanitize_text(&String::from_utf8_lossy(&body));
+    let snippet = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if truncated {
+        format!("{snippet}...")
+    } else {
+        snippet
+    }
+}
+
+/// Read and deserialize a JSON response body of at most `limit` bytes.
+pub(crate) async fn read_json<T: DeserializeOwned>(
+    resp: Response,
+    limit: usize,
+) -> ClawRtcResult<T> {
+    let resp = expect_json(resp).await?;
+    let body = read_body(resp, limit).await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Deserialize the elements of a top-level JSON array body as they arrive.
+///
+/// Only the element being read is buffered, so the body as a whole may be far
+/// larger than `limit`; a single element over `limit` bytes is an error. The
+/// stream ends after the first error. Callers check the content type with
+/// [`expect_json`] first.
+pub(crate) fn read_json_array<T: DeserializeOwned>(
+    resp: Response,
+    limit: usize,
+) -> impl futures::Stream<Item = ClawRtcResult<T>> {
+    let state = (resp, ArraySplitter::new(limit));
+    futures::stream::try_unfold(state, |(mut resp, mut splitter)| async move {
+        loop {
+            if let Some(element) = splitter.next_element()? {
+                let item = serde_json::from_slice(&element)?;
+                return Ok(Some((item, (resp, splitter))));
+            }
+            match resp.chunk().await? {
+                Some(chunk) => splitter.push(&chunk),
+                None => {
+                    splitter.finish()?;
+                    return Ok(None);
+                }
+            }
+        }
+    })
+}
+
+/// Where an [`ArraySplitter`] is in the array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArrayPosition {
+    /// Before the opening `[`.
+    Start,
+    /// Inside the array.
+    Elements,
+    /// After the closing `]`.
+    Done,
+}
+
+/// Splits a JSON array, fed in arbitrary chunks, into its elements' bytes.
+///
+/// Only tracks string and nesting state to find the commas between elements;
+/// each element is validated when it is deserialized.
+#[derive(Debug)]
+struct ArraySplitter {
+    buf: Vec<u8>,
+    /// Bytes of `buf` already scanned for the end of the current element.
+    scanned: usize,
+    position: ArrayPosition,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    elements: usize,
+    limit: usize,
+}
+
+impl ArraySplitter {
+    fn new(limit: usize) -> Self {
+        Self {
+            buf: Vec::new(),
+            scanned: 0,
+            position: ArrayPosition::Start,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            elements: 0,
+            limit,
+        }
+    }
+
+    fn push(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+    }
+
+    /// The next complete element, or `None` until more input arrives.
+    fn next_element(&mut self) -> ClawRtcResult<Option<Vec<u8>>> {
+        if self.position == ArrayPosition::Start {
+            let Some(open) = self.buf.iter().position(|b| !b.is_ascii_whitespace()) else {
+                self.buf.clear();
+                return Ok(None);
+            };
+            if self.buf[open] != b'[' {
+                return Err(malformed("expected a JSON array"));
+            }
+            self.buf.drain(..=open);
+            self.position = ArrayPosition::Elements;
+        }
+        if self.position == ArrayPosition::Done {
+            if self.buf.iter().any(|b| !b.is_ascii_whitespace()) {
+                return Err(malformed("trailing data after JSON array"));
+            }
+            self.buf.clear();
+            return Ok(None);
+        }
+
+        for i in self.scanned..self.buf.len() {
+            let b = self.buf[i];
+            if self.in_string {
+                match b {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match b {
+                b'"' => self.in_string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' if self.depth > 0 => self.depth -= 1,
+                b',' | b']' if self.depth == 0 => {
+                    let element = trim_whitespace(&self.buf[..i]).to_vec();
+                    self.buf.drain(..=i);
+                    self.scanned = 0;
+                    if b == b']' {
+                        self.position = ArrayPosition::Done;
+                        if element.is_empty() && self.elements == 0 {
+                            return Ok(None);
+                        }
+                    }
+                    if element.is_empty() {
+                        return Err(malformed("empty array element"));
+                    }
+                    self.elements += 1;
+                    return Ok(Some(element));
+                }
+                _ => {}
+            }
+        }
+        self.scanned = self.buf.len();
+        if self.buf.len() > self.limit {
+            return Err(ClawRtcError::ResponseTooLarge(self.limit));
+        }
+        Ok(None)
+    }
+
+    /// Check that the input ended after a complete array.
+    fn finish(&self) -> ClawRtcResult<()> {
+        if self.position == ArrayPosition::Done {
+            Ok(())
+        } else {
+            Err(malformed("JSON array ended early"))
+        }
+    }
+}
+
+fn trim_whitespace(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(start, |i| i + 1);
+    &bytes[start..end]
+}
+
+fn malformed(msg: &str) -> ClawRtcError {
+    <serde_json::Error as serde::de::Error>::custom(msg).into()
+}
+
+/// Bytes left as-is by [`urlencoded`]: RFC 3986 unreserved characters.
+const URL_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Percent-encode `s` for use as a single query value or path segment.
+///
+/// Everything but unreserved characters is escaped, so `/`, `?`, `&`, `=`
+/// and `#` can't split the component, and non-ASCII is encoded as UTF-8.
+pub(crate) fn urlencoded(s: &str) -> String {
+    utf8_percent_encode(s, URL_COMPONENT).to_string()
+}
+
+/// Read a text response body of at most `limit` bytes (lossy UTF-8).
+pub(crate) async fn read_text(resp: Response, limit: usize) -> ClawRtcResult<String> {
+    let body = read_body(resp, limit).await?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockResponse, MockServer};
+
+    #[test]
+    fn test_urlencoded() {
+        assert_eq!(urlencoded("hello world"), "hello%20world");
+        assert_eq!(urlencoded("a&b=c"), "a%26b%3Dc");
+        assert_eq!(urlencoded("café"), "caf%C3%A9");
+        assert_eq!(urlencoded("what?/why"), "what%3F%2Fwhy");
+        assert_eq!(urlencoded("50%+#1\n"), "50%25%2B%231%0A");
+        assert_eq!(urlencoded("Rust-1.75_rc~2"), "Rust-1.75_rc~2");
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(5),
+            max_backoff: Duration::from_millis(20),
+        }
+    }
+
+    fn options() -> HttpOptions {
+        HttpOptions::new(Duration::from_secs(5))
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let p = RetryPolicy::default();
+        assert_eq!(p.backoff(0), Duration::from_millis(500));
+        assert_eq!(p.backoff(1), Duration::from_secs(1));
+        assert_eq!(p.backoff(2), Duration::from_secs(2));
+        assert_eq!(p.backoff(10), Duration::from_secs(8));
+        assert_eq!(p.backoff(u32::MAX), Duration::from_secs(8));
+    }
+
+    #[tokio::test]
+    async fn test_retries_503_then_succeeds() {
+        let server = MockServer::sequence(vec![
+            MockResponse::json(503, serde_json::json!({})),
+            MockResponse::json(201, serde_json::json!({ "id": 1 })),
+        ])
+        .await;
+        let http = reqwest::Client::new();
+        let url = format!("{}/posts", server.url());
+        let resp = send_with_retry(&fast_policy(), &options(), || http.post(&url))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 201);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retries_429_with_retry_after() {
+        let server = MockServer::sequence(vec![
+            MockResponse::json(429, serde_json::json!({})).with_header("Retry-After", "0"),
+            MockResponse::json(200, serde_json::json!({})),
+        ])
+        .await;
+        let http = reqwest::Client::new();
+        let url = server.url();
+        let resp = send_with_retry(&fast_policy(), &options(), || http.post(&url))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_500() {
+        // The server may have processed the write before failing
+        let server =
+            MockServer::sequence(vec![MockResponse::json(500, serde_json::json!({}))]).await;
+        let http = reqwest::Client::new();
+        let url = server.url();
+        let resp = send_with_retry(&fast_policy(), &options(), || http.post(&url))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 500);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_timeout() {
+        let server =
+            MockServer::sequence(vec![MockResponse::json(200, serde_json::json!({}))
+                .with_delay(Duration::from_millis(500))])
+            .await;
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let url = server.url();
+        let err = send_with_retry(&fast_policy(), &options(), || http.post(&url))
+            .await
+            .unwrap_err();
+        assert!(err.is_timeout());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let server =
+            MockServer::sequence(vec![MockResponse::json(503, serde_json::json!({}))]).await;
+        let http = reqwest::Client::new();
+        let url = server.url();
+        let resp = send_with_retry(&fast_policy(), &options(), || http.post(&url))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 503);
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retries_connection_refused() {
+        // Reserve a port, then close it so connections are refused
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+        let http = reqwest::Client::new();
+        let url = format!("http://{addr}");
+        let err = send_with_retry(&fast_policy(), &options(), || {
+            attempts.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            http.post(&url)
+        })
+        .await
+        .unwrap_err();
+        assert!(err.is_connect());
+        assert_eq!(attempts.into_inner(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_none_policy_never_retries() {
+        let server =
+            MockServer::sequence(vec![MockResponse::json(503, serde_json::json!({}))]).await;
+        let http = reqwest::Client::new();
+        let url = server.url();
+        let resp = send_with_retry(&RetryPolicy::none(), &options(), || http.post(&url))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 503);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_request_limit_queues_excess_requests() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, serde_json::json!({})).with_delay(Duration::from_millis(300))
+        })
+        .await;
+        let limit = RequestLimit::new(2);
+        let all = tokio::spawn({
+            let options = HttpOptions {
+                request_limit: Some(limit.clone()),
+                ..options()
+            };
+            let http = reqwest::Client::new();
+            let url = server.url();
+            async move {
+                futures::future::join_all((0..5).map(|_| async {
+                    let resp = send_limited(&options, http.get(&url)).await.unwrap();
+                    read_text(resp, 1024).await.unwrap()
+                }))
+                .await
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(server.requests().len(), 2);
+        assert_eq!(limit.in_flight(), 2);
+
+        assert_eq!(all.await.unwrap().len(), 5);
+        assert_eq!(server.requests().len(), 5);
+        assert_eq!(limit.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cross_origin_redirect_drops_credentials() {
+        let target = MockServer::start(|_| MockResponse::json(200, serde_json::json!({}))).await;
+        let moved = format!("{}/landed", target.url());
+        let origin = MockServer::start(move |req| match req.path.as_str() {
+            "/same" => {
+                MockResponse::json(302, serde_json::json!({})).with_header("Location", "/kept")
+            }
+            "/kept" => MockResponse::json(200, serde_json::json!({})),
+            _ => MockResponse::json(301, serde_json::json!({})).with_header("Location", &moved),
+        })
+        .await;
+        let mut options = options();
+        options.register_credential_header("X-API-Key");
+        options.register_credential_header("Authorization");
+        assert_eq!(options.credential_headers, ["X-API-Key"]);
+        let http = options.build();
+        let authed = |path: &str| {
+            http.get(format!("{}{path}", origin.url()))
+                .bearer_auth("secret")
+                .header("X-API-Key", "key")
+                .header("X-Trace", "t1")
+        };
+
+        let resp = options.send(authed("/same")).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let kept = origin
+            .requests()
+            .into_iter()
+            .find(|r| r.path == "/kept")
+            .unwrap();
+        assert_eq!(kept.header("authorization"), Some("Bearer secret"));
+        assert_eq!(kept.header("x-api-key"), Some("key"));
+
+        let resp = options.send(authed("/elsewhere")).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.url().path(), "/landed");
+        let landed = &target.requests()[0];
+        assert_eq!(landed.header("authorization"), None);
+        assert_eq!(landed.header("x-api-key"), None);
+        assert_eq!(landed.header("x-trace"), Some("t1"));
+    }
+
+    #[tokio::test]
+    async fn test_redirect_after_post_becomes_get() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/submit" => {
+                MockResponse::json(303, serde_json::json!({})).with_header("Location", "/result")
+            }
+            _ => MockResponse::json(200, serde_json::json!({})),
+        })
+        .await;
+        let options = HttpOptions::new(Duration::from_secs(5));
+        let req = options
+            .build()
+            .post(format!("{}/submit", server.url()))
+            .json(&serde_json::json!({ "a": 1 }));
+        assert_eq!(options.send(req).await.unwrap().status(), 200);
+        let result = server
+            .requests()
+            .into_iter()
+            .find(|r| r.path == "/result")
+            .unwrap();
+        assert_eq!(result.method, "GET");
+        assert!(result.body.is_empty());
+    }
+
+    /// Feed `input` to a splitter `chunk` bytes at a time, collecting elements.
+    fn split(input: &str, chunk: usize) -> ClawRtcResult<Vec<String>> {
+        let mut splitter = ArraySplitter::new(1024);
+        let mut out = Vec::new();
+        for piece in input.as_bytes().chunks(chunk) {
+            splitter.push(piece);
+            while let Some(element) = splitter.next_element()? {
+                out.push(String::from_utf8(element).unwrap());
+            }
+        }
+        splitter.finish()?;
+        Ok(out)
+    }
+
+    #[test]
+    fn test_array_splitter_across_chunk_boundaries() {
+        let input = r#" [ {"a": "x,]}\"y", "b": [1, {"c": 2}]}, 3 ,"s\\",null,[] ] "#;
+        let expected = [
+            r#"{"a": "x,]}\"y", "b": [1, {"c": 2}]}"#,
+            "3",
+            r#""s\\""#,
+            "null",
+            "[]",
+        ];
+        for chunk in [1, 2, 7, input.len()] {
+            assert_eq!(split(input, chunk).unwrap(), expected, "chunk size {chunk}");
+        }
+        assert!(split("[ ]", 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_array_splitter_rejects_malformed_input() {
+        for bad in [r#"{"a": 1}"#, "[1,,2]", "[1,]", "[1, 2", "[1] 2"] {
+            assert!(split(bad, 3).is_err(), "{bad}");
+        }
+        let mut splitter = ArraySplitter::new(8);
+        splitter.push(br#"["0123456789"#);
+        let err = splitter.next_element().unwrap_err();
+        assert!(matches!(err, ClawRtcError::ResponseTooLarge(8)));
+    }
+
+    #[tokio::test]
+    async fn test_read_json_reports_html_login_wall() {
+        let page = format!(
+            "<!DOCTYPE html>\n<html>\n  <head><title>Sign in</title></head>\n  \
+             <body>{}</body>\n</html>",
+            "Please log in to continue. ".repeat(20)
+        );
+        let server = MockServer::start(move |_| {
+            MockResponse::text(200, "text/html; charset=utf-8", &page)
+        })
+        .await;
+        let resp = reqwest::get(server.url()).await.unwrap();
+        let err = read_json::<serde_json::Value>(resp, 1024 * 1024)
+            .await
+            .unwrap_err();
+        let ClawRtcError::UnexpectedContentType(msg) = &err else {
+            panic!("unexpected error: {err}");
+        };
+        assert!(msg.starts_with("expected JSON, got text/html; charset=utf-8 (HTTP 200 OK): "));
+        assert!(msg.contains("<html> <head><title>Sign in</title></head>"), "{msg}");
+        assert!(msg.ends_with("..."), "{msg}");
+        assert!(msg.len() < SNIPPET_BYTES + 100, "{msg}");
+    }
+
+    #[tokio::test]
+    async fn test_read_json_accepts_json_variants_and_missing_type() {
+        let untyped = MockResponse {
+            headers: Vec::new(),
+            ..MockResponse::json(200, serde_json::json!({ "a": 1 }))
+        };
+        let server = MockServer::sequence(vec![
+            MockResponse::text(400, "application/problem+json", r#"{"a": 2}"#),
+            untyped,
+        ])
+        .await;
+        for expected in [2, 1] {
+            let resp = reqwest::get(server.url()).await.unwrap();
+            let v: serde_json::Value = read_json(resp, 1024).await.unwrap();
+            assert_eq!(v["a"], expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_json_within_limit() {
+        let server =
+            MockServer::sequence(vec![MockResponse::json(200, serde_json::json!({ "a": 1 }))])
+                .await;
+        let resp = reqwest::get(server.url()).await.unwrap();
+        let v: serde_json::Value = read_json(resp, 1024).await.unwrap();
+        assert_eq!(v["a"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_read_json_rejects_oversized_body() {
+        let big = format!("[{}1]", "1,".repeat(10_000));
+        let server =
+            MockServer::sequence(vec![MockResponse::text(200, "application/json", &big)]).await;
+        let resp = reqwest::get(server.url()).await.unwrap();
+        let err = read_json::<serde_json::Value>(resp, 1024)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClawRtcError::ResponseTooLarge(1024)));
+    }
+
+    #[tokio::test]
+    async fn test_read_body_enforces_limit_without_content_length() {
+        // Chunked responses carry no Content-Length, so the streaming counter must catch them
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf).await;
+            let _ = stream
+                .write_all(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n")
+                .await;
+            let chunk = format!("{:x}\r\n{}\r\n", 512, "x".repeat(512));
+            for _ in 0..8 {
+                if stream.write_all(chunk.as_bytes()).await.is_err() {
+                    return;
+                }
+            }
+            let _ = stream.write_all(b"0\r\n\r\n").await;
+        });
+        let resp = reqwest::get(format!("http://{addr}")).await.unwrap();
+        assert!(resp.content_length().is_none());
+        let err = read_text(resp, 1024).await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::ResponseTooLarge(1024)));
+    }
+}
@@ -2,16 +2,21 @@
 //!
 //! Uses Argon2id for key derivation and AES-256-GCM for encryption.
 //! The JSON format matches the Python `rustchain_crypto.py` keystore.
+//! Keystores sealed with non-default Argon2 costs (see [`calibrate_params`])
+//! record them in an extra `kdf` field; files without it use the defaults.
 
 use crate::error::{ClawRtcError, ClawRtcResult};
 use aes_gcm::aead::{Aead, KeyInit};
 use aes_gcm::{Aes256Gcm, Nonce};
-use argon2::Argon2;
+use argon2::{Algorithm, Argon2, Params, Version};
 use base64::{engine::general_purpose::STANDARD as B64, Engine};
 use chrono::Utc;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use std::io::Write;
 use std::path::Path;
+use std::time::{Duration, Instant};
+use zeroize::Zeroizing;
 
 /// Encrypted keystore JSON format.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,15 +27,143 @@ pub struct Keystore {
     pub nonce: String,
     pub ciphertext: String,
     pub created: String,
+    /// Argon2 costs used to derive the key; `None` means [`Argon2Params::default`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kdf: Option<Argon2Params>,
 }
 
 impl Keystore {
     /// Encrypt a private key hex string with a password.
     pub fn encrypt(private_key_hex: &str, password: &str, address: &str) -> ClawRtcResult<Self> {
+        Self::encrypt_with_params(private_key_hex, password, address, Argon2Params::default())
+    }
+
+    /// Like [`encrypt`](Self::encrypt), deriving the key with `params`
+    /// (for example from [`calibrate_params`]).
+    pub fn encrypt_with_params(
+        private_key_hex: &str,
+        password: &str,
+        address: &str,
+        params: Argon2Params,
+    ) -> ClawRtcResult<Self> {
+        Self::encrypt_verified(private_key_hex, password, address, || {
+            Sealed::seal_with(private_key_hex.as_bytes(), password, params)
+        })
+    }
+
+    /// Build a keystore from `seal`'s output, then decrypt it with `password`
+    /// and fail with [`ClawRtcError::KeystoreEncrypt`] unless that gives back
+    /// `private_key_hex`.
+    ///
+    /// Every keystore is checked this way before it is returned, so one that
+    /// can't be opened again is never saved. This costs a second key derivation.
+    fn encrypt_verified(
+        private_key_hex: &str,
+        password: &str,
+        address: &str,
+        seal: impl FnOnce() -> ClawRtcResult<Sealed>,
+    ) -> ClawRtcResult<Self> {
+        let sealed = seal()?;
+        let keystore = Self {
+            version: 1,
+            address: address.to_string(),
+            salt: sealed.salt,
+            nonce: sealed.nonce,
+            ciphertext: sealed.ciphertext,
+            created: Utc::now().to_rfc3339(),
+            kdf: sealed.kdf,
+        };
+        let decrypted = keystore
+            .decrypt(password)
+            .map_err(|e| ClawRtcError::KeystoreEncrypt(format!("round-trip check failed: {e}")))?;
+        let decrypted = Zeroizing::new(decrypted);
+        if decrypted.as_str() != private_key_hex {
+            return Err(ClawRtcError::KeystoreEncrypt(
+                "round-trip check failed: decrypted key does not match".into(),
+            ));
+        }
+        Ok(keystore)
+    }
+
+    /// Decrypt the keystore, returning the private key hex string.
+    pub fn decrypt(&self, password: &str) -> ClawRtcResult<String> {
+        let sealed = Sealed {
+            salt: self.salt.clone(),
+            nonce: self.nonce.clone(),
+            ciphertext: self.ciphertext.clone(),
+            kdf: self.kdf,
+        };
+        let plaintext = sealed.open(password)?;
+        String::from_utf8(plaintext).map_err(|e| ClawRtcError::KeystoreDecrypt(e.to_string()))
+    }
+
+    /// Re-encrypt the same private key under `new`, with a fresh salt and nonce.
+    ///
+    /// Fails with [`ClawRtcError::KeystoreDecrypt`] if `old` is wrong. `self`
+    /// is left as it was; the returned keystore keeps its address, creation
+    /// time and KDF costs. Nothing is written until the result is [`save`](Self::save)d.
+    pub fn change_password(&self, old: &str, new: &str) -> ClawRtcResult<Keystore> {
+        let private_key_hex = Zeroizing::new(self.decrypt(old)?);
+        let params = self.kdf.unwrap_or_default();
+        let resealed = Self::encrypt_verified(&private_key_hex, new, &self.address, || {
+            Sealed::seal_with(private_key_hex.as_bytes(), new, params)
+        })?;
+        Ok(Self {
+            salt: resealed.salt,
+            nonce: resealed.nonce,
+            ciphertext: resealed.ciphertext,
+            ..self.clone()
+        })
+    }
+
+    /// Load from a JSON file.
+    pub fn load(path: &Path) -> ClawRtcResult<Self> {
+        let data = std::fs::read_to_string(path)?;
+        serde_json::from_str(&data).map_err(|e| ClawRtcError::KeystoreDecrypt(e.to_string()))
+    }
+
+    /// Save to a JSON file with restricted permissions.
+    ///
+    /// The file is replaced atomically: an interrupted save leaves the
+    /// previous keystore in place.
+    pub fn save(&self, path: &Path) -> ClawRtcResult<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        write_private(path, &json)
+    }
+}
+
+/// Password-encrypted bytes, base64-encoded: Argon2id salt, AES-GCM nonce
+/// and ciphertext. Shared by [`Keystore`] and
+/// [`EncryptedStore`](crate::store::EncryptedStore).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Sealed {
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kdf: Option<Argon2Params>,
+}
+
+impl Sealed {
+    /// Encrypt `plaintext` under a key derived from `password` and a fresh salt.
+    pub fn seal(plaintext: &[u8], password: &str) -> ClawRtcResult<Self> {
+        Self::seal_with(plaintext, password, Argon2Params::default())
+    }
+
+    /// Like [`seal`](Self::seal), deriving the key with `params`. Default
+    /// costs are left out of the output so it stays readable by Python.
+    pub fn seal_with(
+        plaintext: &[u8],
+        password: &str,
+        params: Argon2Params,
+    ) -> ClawRtcResult<Self> {
         let mut salt = [0u8; 32];
         rand::rngs::OsRng.fill_bytes(&mut salt);
 
-        let key = derive_key(password, &salt)?;
+        let key = derive_key(password, &salt, &params)?;
 
         let mut nonce_bytes = [0u8; 12];
         rand::rngs::OsRng.fill_bytes(&mut nonce_bytes);
@@ -39,21 +172,19 @@ impl Keystore {
             .map_err(|e| ClawRtcError::KeystoreEncrypt(e.to_string()))?;
         let nonce = Nonce::from_slice(&nonce_bytes);
         let ciphertext = cipher
-            .encrypt(nonce, private_key_hex.as_bytes())
+            .encrypt(nonce, plaintext)
             .map_err(|e| ClawRtcError::KeystoreEncrypt(e.to_string()))?;
 
         Ok(Self {
-            version: 1,
-            address: address.to_string(),
             salt: B64.encode(salt),
             nonce: B64.encode(nonce_bytes),
             ciphertext: B64.encode(ciphertext),
-            created: Utc::now().to_rfc3339(),
+            kdf: (params != Argon2Params::default()).then_some(params),
         })
     }
 
-    /// Decrypt the keystore, returning the private key hex string.
-    pub fn decrypt(&self, password: &str) -> ClawRtcResult<String> {
+    /// Decrypt with `password`.
+    pub fn open(&self, password: &str) -> ClawRtcResult<Vec<u8>> {
         let salt = B64
             .decode(&self.salt)
             .map_err(|e| ClawRtcError::KeystoreDecrypt(e.to_string()))?;
@@ -64,44 +195,129 @@ impl Keystore {
             .decode(&self.ciphertext)
             .map_err(|e| ClawRtcError::KeystoreDecrypt(e.to_string()))?;
 
-        let key = derive_key(password, &salt)?;
+        let key = derive_key(password, &salt, &self.kdf.unwrap_or_default())?;
 
         let cipher = Aes256Gcm::new_from_slice(&key)
             .map_err(|e| ClawRtcError::KeystoreDecrypt(e.to_string()))?;
         let nonce = Nonce::from_slice(&nonce_bytes);
-        let plaintext = cipher
+        cipher
             .decrypt(nonce, ciphertext.as_ref())
-            .map_err(|_| ClawRtcError::KeystoreDecrypt("wrong password or corrupted data".into()))?;
-
-        String::from_utf8(plaintext).map_err(|e| ClawRtcError::KeystoreDecrypt(e.to_string()))
+            .map_err(|_| ClawRtcError::KeystoreDecrypt("wrong password or corrupted data".into()))
     }
+}
 
-    /// Load from a JSON file.
-    pub fn load(path: &Path) -> ClawRtcResult<Self> {
-        let data = std::fs::read_to_string(path)?;
-        serde_json::from_str(&data).map_err(|e| ClawRtcError::KeystoreDecrypt(e.to_string()))
-    }
+/// Write `contents` to `path`, accessible to the owner only (mode `0600` on
+/// Unix, an owner-only ACL on Windows).
+///
+/// The contents are written to a temporary file beside `path` and renamed
+/// over it, so `path` holds either the old contents or the new, never a
+/// partial write.
+pub(crate) fn write_private(path: &Path, contents: &str) -> ClawRtcResult<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
 
-    /// Save to a JSON file with restricted permissions.
-    pub fn save(&self, path: &Path) -> ClawRtcResult<()> {
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-        let json = serde_json::to_string_pretty(self)?;
-        std::fs::write(path, &json)?;
+    let written = (|| {
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp)?;
         #[cfg(unix)]
         {
             use std::os::unix::fs::PermissionsExt;
-            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        }
+        #[cfg(windows)]
+        crate::windows_acl::restrict_to_owner(&tmp)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)
+    })();
+    if written.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    Ok(written?)
+}
+
+/// Argon2id cost parameters for keystore key derivation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Argon2Params {
+    /// Memory cost in KiB.
+    pub m_cost: u32,
+    /// Number of passes over memory.
+    pub t_cost: u32,
+    /// Degree of parallelism.
+    pub p_cost: u32,
+}
+
+impl Default for Argon2Params {
+    /// The `argon2` crate defaults, which the Python keystore also uses.
+    fn default() -> Self {
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl Argon2Params {
+    fn hasher(&self) -> ClawRtcResult<Argon2<'static>> {
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(32))
+            .map_err(|e| ClawRtcError::Crypto(format!("invalid Argon2 parameters: {e}")))?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
+
+/// Upper bound on the memory cost [`calibrate_params`] will pick (256 MiB);
+/// beyond it extra work goes into `t_cost`.
+const MAX_CALIBRATED_M_COST: u32 = 256 * 1024;
+
+/// Pick Argon2 costs that make one key derivation take about `target` on
+/// this machine.
+///
+/// Memory cost is raised first, up to 256 MiB, then the pass count. The
+/// result is never weaker than [`Argon2Params::default`], so a target
+/// shorter than a default derivation returns the defaults.
+pub fn calibrate_params(target: Duration) -> Argon2Params {
+    let floor = Argon2Params::default();
+    let mut params = floor;
+    // One measurement to scale from, one to correct for non-linear effects
+    // such as cache misses at larger memory sizes
+    for _ in 0..2 {
+        let Some(elapsed) = time_derivation(&params) else {
+            return params;
+        };
+        let scale = target.as_secs_f64() / elapsed.as_secs_f64().max(1e-6);
+        if (0.9..=1.1).contains(&scale) {
+            break;
         }
-        Ok(())
+        let work = f64::from(params.m_cost) * f64::from(params.t_cost) * scale;
+        let m_cost = (work / f64::from(floor.t_cost))
+            .clamp(f64::from(floor.m_cost), f64::from(MAX_CALIBRATED_M_COST));
+        let t_cost = (work / m_cost).round().clamp(f64::from(floor.t_cost), f64::from(u32::MAX));
+        params = Argon2Params {
+            m_cost: m_cost as u32,
+            t_cost: t_cost as u32,
+            p_cost: floor.p_cost,
+        };
     }
+    params
+}
+
+/// Time one derivation with `params`, or `None` if they are invalid.
+fn time_derivation(params: &Argon2Params) -> Option<Duration> {
+    let start = Instant::now();
+    derive_key("calibration", &[0u8; 32], params).ok()?;
+    Some(start.elapsed())
 }
 
 /// Derive a 32-byte key from password + salt using Argon2id.
-fn derive_key(password: &str, salt: &[u8]) -> ClawRtcResult<[u8; 32]> {
+fn derive_key(password: &str, salt: &[u8], params: &Argon2Params) -> ClawRtcResult<[u8; 32]> {
     let mut key = [0u8; 32];
-    Argon2::default()
+    params
+        .hasher()?
         .hash_password_into(password.as_bytes(), salt, &mut key)
         .map_err(|e| ClawRtcError::Crypto(format!("Argon2 KDF failed: {e}")))?;
     Ok(key)
@@ -119,6 +335,26 @@ mod tests {
         assert_eq!(decrypted, secret);
     }
 
+    #[test]
+    fn test_encrypt_rejects_keystore_that_fails_roundtrip() {
+        let secret = "deadbeefcafebabe1234567890abcdef1234567890abcdef1234567890abcdef";
+
+        // Sealed under a different password than the one being set
+        let err = Keystore::encrypt_verified(secret, "pw", "RTCtest", || {
+            Sealed::seal(secret.as_bytes(), "other")
+        })
+        .unwrap_err();
+        assert!(matches!(err, ClawRtcError::KeystoreEncrypt(_)), "{err}");
+        assert!(err.to_string().contains("round-trip check failed"), "{err}");
+
+        // Decrypts, but to the wrong key
+        let err = Keystore::encrypt_verified(secret, "pw", "RTCtest", || {
+            Sealed::seal(b"not the key", "pw")
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{err}");
+    }
+
     #[test]
     fn test_keystore_wrong_password() {
         let secret = "deadbeefcafebabe1234567890abcdef1234567890abcdef1234567890abcdef";
@@ -127,6 +363,104 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_change_password() {
+        let secret = "deadbeefcafebabe1234567890abcdef1234567890abcdef1234567890abcdef";
+        let ks = Keystore::encrypt(secret, "old_password", "RTCtest").unwrap();
+        let rotated = ks.change_password("old_password", "new_password").unwrap();
+
+        assert_eq!(rotated.decrypt("new_password").unwrap(), secret);
+        assert!(matches!(
+            rotated.decrypt("old_password"),
+            Err(ClawRtcError::KeystoreDecrypt(_))
+        ));
+        assert_eq!(rotated.address, "RTCtest");
+        assert_eq!(rotated.created, ks.created);
+        assert_ne!(rotated.salt, ks.salt);
+        assert_ne!(rotated.nonce, ks.nonce);
+        // The original still opens with the old password
+        assert_eq!(ks.decrypt("old_password").unwrap(), secret);
+
+        let err = ks.change_password("wrong", "new_password").unwrap_err();
+        assert!(matches!(err, ClawRtcError::KeystoreDecrypt(_)));
+    }
+
+    #[test]
+    fn test_save_replaces_file_without_leftovers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wallet.json");
+        let ks = Keystore::encrypt("aa".repeat(32).as_str(), "old", "RTCtest").unwrap();
+        ks.save(&path).unwrap();
+        ks.change_password("old", "new").unwrap().save(&path).unwrap();
+
+        assert!(Keystore::load(&path).unwrap().decrypt("new").is_ok());
+        let files: Vec<_> = std::fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(files.len(), 1);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        #[cfg(windows)]
+        assert!(crate::windows_acl::is_owner_only(&path).unwrap());
+    }
+
+    #[test]
+    fn test_custom_kdf_params_are_recorded() {
+        let secret = "deadbeefcafebabe1234567890abcdef1234567890abcdef1234567890abcdef";
+        let plain = Keystore::encrypt(secret, "pw", "RTCtest").unwrap();
+        assert!(plain.kdf.is_none());
+        assert!(!serde_json::to_string(&plain).unwrap().contains("kdf"));
+
+        let params = Argon2Params {
+            m_cost: 1024,
+            t_cost: 1,
+            p_cost: 1,
+        };
+        let ks = Keystore::encrypt_with_params(secret, "pw", "RTCtest", params).unwrap();
+        assert_eq!(ks.kdf, Some(params));
+        let json = serde_json::to_string(&ks).unwrap();
+        let loaded: Keystore = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.decrypt("pw").unwrap(), secret);
+
+        // The key depends on the costs, not just password and salt
+        let mut wrong = loaded.clone();
+        wrong.kdf = None;
+        assert!(wrong.decrypt("pw").is_err());
+
+        let rotated = loaded.change_password("pw", "new").unwrap();
+        assert_eq!(rotated.kdf, Some(params));
+        assert_eq!(rotated.decrypt("new").unwrap(), secret);
+    }
+
+    #[test]
+    fn test_calibrate_never_goes_below_defaults() {
+        assert_eq!(calibrate_params(Duration::ZERO), Argon2Params::default());
+    }
+
+    #[test]
+    fn test_calibrate_params_scales_with_target() {
+        // Wall-clock time is left unchecked: other tests share the CPU
+        let defaults = Argon2Params::default();
+        assert_eq!(calibrate_params(Duration::ZERO), defaults);
+
+        let floor = time_derivation(&defaults).unwrap();
+        let low = calibrate_params(floor * 2);
+        let high = calibrate_params(floor * 6);
+        for params in [low, high] {
+            assert!(params.m_cost >= defaults.m_cost, "{params:?}");
+            assert!(params.t_cost >= defaults.t_cost, "{params:?}");
+            assert_eq!(params.p_cost, defaults.p_cost);
+        }
+        assert!(high.m_cost >= low.m_cost, "{low:?} vs {high:?}");
+        assert!(high.t_cost >= low.t_cost, "{low:?} vs {high:?}");
+
+        let secret = "aa".repeat(32);
+        let ks = Keystore::encrypt_with_params(&secret, "pw", "RTCtest", low).unwrap();
+        assert_eq!(ks.decrypt("pw").unwrap(), secret);
+    }
+
     #[test]
     fn test_keystore_file_roundtrip() {
         let dir = tempfile::tempdir().unwrap();
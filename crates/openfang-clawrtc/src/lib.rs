@@ -1,32 +1,80 @@
 //! `openfang-clawrtc` — RustChain (RTC) integration for OpenFang Agent OS.
 //!
 //! Provides:
-//! - **Wallet**: Ed25519 key pair generation, signing, encrypted keystore
-//! - **Mining**: Hardware attestation, epoch enrollment, reward cycles
+//! - **Wallet**: Ed25519 key pair generation, signing, encrypted keystore, external signers,
+//!   outbox of transfers awaiting confirmation
+//! - **Mining**: Hardware attestation, epoch enrollment, reward cycles, outbound event stream,
+//!   multiple wallets on shared hardware evidence
 //! - **Fingerprints**: 6 RIP-PoA hardware validation checks
-//! - **Tools**: 15 OpenFang tool definitions for agent use
+//! - **Tools**: 33 OpenFang tool definitions for agent use
 //! - **Client**: Async HTTP client for RustChain node API
-//! - **Grazer**: Multi-platform content discovery and posting (12 platforms)
-//! - **BoTTube**: Video platform search, commenting, and voting
+//! - **Grazer**: Multi-platform content discovery, posting, and commenting (12 platforms)
+//! - **BoTTube**: Video platform search, channels, commenting, voting, reporting, and resumable
+//!   uploads
+//! - **Proof of content**: Wallet-signed, verifiable attribution footers for posts
+//! - **Signed messages**: Expiring, replay-resistant auth tokens between agents
 
+pub mod address;
+pub mod amount;
 pub mod bottube;
 pub mod client;
+pub mod clock;
+pub mod config;
+pub mod context;
 pub mod error;
+pub mod event_stream;
 pub mod fingerprint;
 pub mod grazer;
 pub mod hardware;
+pub mod http;
 pub mod keystore;
+pub mod message;
 pub mod miner;
+pub mod multi_miner;
+pub mod nonce;
+pub mod op_result;
+pub mod outbox;
+pub mod proof;
+pub mod receipt;
+pub mod response_adapter;
+pub mod sanitize;
+pub mod signer;
+pub mod store;
 pub mod tools;
 pub mod wallet;
 
+#[cfg(test)]
+mod test_support;
+#[cfg(windows)]
+mod windows_acl;
+
 // Re-exports for convenience
-pub use bottube::BoTTubeClient;
-pub use client::{RustChainClient, DEFAULT_NODE_URL};
+pub use address::AddressFormat;
+pub use bottube::{BoTTubeClient, Channel, ChunkedUpload};
+pub use client::{
+    ConfirmationStatus, EnrollmentError, NodeCapabilities, NodeProtocol, RustChainClient,
+    DEFAULT_NODE_URL,
+};
+pub use clock::{Clock, MockClock, SystemClock};
+pub use config::{Config, ConfigOverrides};
 pub use error::{ClawRtcError, ClawRtcResult};
-pub use fingerprint::FingerprintReport;
-pub use grazer::{GrazerClient, Platform};
-pub use hardware::HardwareInfo;
-pub use keystore::Keystore;
-pub use tools::{clawrtc_tool_definitions, execute_clawrtc_tool, is_clawrtc_tool};
-pub use wallet::RtcWallet;
+pub use fingerprint::{FingerprintReport, FingerprintSelection};
+pub use grazer::{
+    AgentProfile, CircuitBreaker, DiscoverSort, GrazerClient, OnboardStep, Platform,
+    PostRequirements,
+};
+pub use hardware::{HardwareInfo, MacFilter};
+pub use http::{RequestLimit, RetryPolicy, TlsVersion};
+pub use keystore::{calibrate_params, Argon2Params, Keystore};
+pub use message::SignedMessage;
+pub use nonce::NonceManager;
+pub use op_result::OpResult;
+pub use outbox::Outbox;
+pub use proof::{ContentProof, VerifiedContent};
+pub use receipt::TransferReceipt;
+pub use signer::Signer;
+pub use store::EncryptedStore;
+pub use tools::{
+    clawrtc_tool_definitions, execute_clawrtc_tool, execute_clawrtc_tool_with, is_clawrtc_tool,
+};
+pub use wallet::{BatchTransfer, EntropyAudit, RtcWallet, TransferResult};
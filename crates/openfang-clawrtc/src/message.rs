@@ -0,0 +1,188 @@
+//! Short-lived signed messages for agent-to-agent authentication.
+//!
+//! A bare signature stays valid forever, so anyone who captures a signed
+//! message can replay it. A [`SignedMessage`] also carries the time it was
+//! signed and how long it stays valid, both covered by the signature, and
+//! [`verify_message`] refuses it outside that window.
+
+use crate::address;
+use crate::clock::{Clock, SystemClock};
+use crate::error::{ClawRtcError, ClawRtcResult};
+use crate::signer::{self, Signer};
+use crate::wallet::{self, canonical_json};
+use serde::{Deserialize, Serialize};
+
+/// Domain separator, so a message signature can't pass as any other kind.
+const MESSAGE_DOMAIN: &str = "rtc-message:v1";
+
+/// How far in the future a message's timestamp may be, to allow for clock
+/// skew between agents.
+pub const MAX_CLOCK_SKEW_SECS: i64 = 30;
+
+/// A message signed together with its validity window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedMessage {
+    pub message: String,
+    pub address: String,
+    pub public_key: String,
+    /// Unix time (seconds) the message was signed.
+    pub timestamp: i64,
+    /// Seconds after `timestamp` the message stays valid.
+    pub ttl_secs: u64,
+    pub signature: String,
+}
+
+impl SignedMessage {
+    /// Unix time (seconds) after which the message is rejected.
+    pub fn expires_at(&self) -> i64 {
+        let ttl = i64::try_from(self.ttl_secs).unwrap_or(i64::MAX);
+        self.timestamp.saturating_add(ttl)
+    }
+
+    /// The bytes the signature covers.
+    fn signed_bytes(&self) -> Vec<u8> {
+        canonical_json(&serde_json::json!({
+            "domain": MESSAGE_DOMAIN,
+            "message": self.message,
+            "address": self.address,
+            "timestamp": self.timestamp,
+            "ttl_secs": self.ttl_secs,
+        }))
+        .into_bytes()
+    }
+}
+
+/// Sign `message` with `signer`, valid for `ttl_secs` from now.
+pub fn sign_message(
+    signer: &dyn Signer,
+    message: &str,
+    ttl_secs: u64,
+) -> ClawRtcResult<SignedMessage> {
+    sign_message_with_clock(signer, message, ttl_secs, &SystemClock)
+}
+
+/// [`sign_message`], taking the current time from `clock`.
+pub fn sign_message_with_clock(
+    signer: &dyn Signer,
+    message: &str,
+    ttl_secs: u64,
+    clock: &dyn Clock,
+) -> ClawRtcResult<SignedMessage> {
+    let mut signed = SignedMessage {
+        message: message.to_string(),
+        address: signer::address_of(signer)?,
+        public_key: hex::encode(signer.public_key()),
+        timestamp: clock.now().timestamp(),
+        ttl_secs,
+        signature: String::new(),
+    };
+    signed.signature = hex::encode(signer.sign(&signed.signed_bytes())?);
+    Ok(signed)
+}
+
+/// Check that `signed` is authentic and within its validity window.
+///
+/// Fails with [`ClawRtcError::Crypto`] if the address doesn't match the
+/// public key or the signature doesn't cover the message, timestamp, and
+/// TTL, and with [`ClawRtcError::MessageExpired`] if the message has
+/// expired or is dated more than [`MAX_CLOCK_SKEW_SECS`] ahead.
+pub fn verify_message(signed: &SignedMessage) -> ClawRtcResult<()> {
+    verify_message_with_clock(signed, &SystemClock)
+}
+
+/// [`verify_message`], taking the current time from `clock`.
+pub fn verify_message_with_clock(signed: &SignedMessage, clock: &dyn Clock) -> ClawRtcResult<()> {
+    let verifying_key = wallet::parse_public_key(&signed.public_key)?;
+    if address::derive_address(&verifying_key) != signed.address {
+        return Err(ClawRtcError::Crypto(
+            "message address does not match public key".to_string(),
+        ));
+    }
+    let bytes = signed.signed_bytes();
+    if !wallet::verify_signature(&signed.public_key, &bytes, &signed.signature)? {
+        return Err(ClawRtcError::Crypto(
+            "message signature does not match".to_string(),
+        ));
+    }
+
+    let now = clock.now().timestamp();
+    if signed.timestamp > now.saturating_add(MAX_CLOCK_SKEW_SECS) {
+        return Err(ClawRtcError::MessageExpired(format!(
+            "signed at {} which is in the future (now {now})",
+            signed.timestamp
+        )));
+    }
+    if now > signed.expires_at() {
+        return Err(ClawRtcError::MessageExpired(format!(
+            "expired at {} (now {now})",
+            signed.expires_at()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::wallet::RtcWallet;
+    use std::time::Duration;
+
+    #[test]
+    fn test_message_valid_only_within_ttl() {
+        let wallet = RtcWallet::generate();
+        let clock = MockClock::at_unix(1_700_000_000);
+        let signed = sign_message_with_clock(&wallet, "let me in", 60, &clock).unwrap();
+        assert_eq!(signed.timestamp, 1_700_000_000);
+        assert_eq!(signed.address, wallet.address());
+        verify_message_with_clock(&signed, &clock).unwrap();
+
+        clock.advance(Duration::from_secs(60));
+        verify_message_with_clock(&signed, &clock).unwrap();
+
+        clock.advance(Duration::from_secs(1));
+        let err = verify_message_with_clock(&signed, &clock).unwrap_err();
+        assert!(matches!(err, ClawRtcError::MessageExpired(_)), "{err}");
+    }
+
+    #[test]
+    fn test_message_window_is_signed() {
+        let wallet = RtcWallet::generate();
+        let clock = MockClock::at_unix(1_700_000_000);
+        let signed = sign_message_with_clock(&wallet, "let me in", 60, &clock).unwrap();
+
+        // Stretching the TTL or moving the timestamp breaks the signature
+        let mut extended = signed.clone();
+        extended.ttl_secs = 86_400;
+        let mut redated = signed.clone();
+        redated.timestamp += 3_600;
+        let mut edited = signed.clone();
+        edited.message = "let everyone in".into();
+        for forged in [extended, redated, edited] {
+            let err = verify_message_with_clock(&forged, &clock).unwrap_err();
+            assert!(err.to_string().contains("signature"), "{err}");
+        }
+
+        // Signed by another wallet under this one's address
+        let mut impostor =
+            sign_message_with_clock(&RtcWallet::generate(), "let me in", 60, &clock).unwrap();
+        impostor.address = wallet.address().to_string();
+        let err = verify_message_with_clock(&impostor, &clock).unwrap_err();
+        assert!(err.to_string().contains("address"), "{err}");
+    }
+
+    #[test]
+    fn test_message_from_the_future_rejected() {
+        let wallet = RtcWallet::generate();
+        let signer_clock = MockClock::at_unix(1_700_000_000 + MAX_CLOCK_SKEW_SECS);
+        let signed = sign_message_with_clock(&wallet, "hi", 300, &signer_clock).unwrap();
+        verify_message_with_clock(&signed, &MockClock::at_unix(1_700_000_000)).unwrap();
+
+        let too_far_ahead = MockClock::at_unix(1_700_000_100);
+        let signed = sign_message_with_clock(&wallet, "hi", 300, &too_far_ahead).unwrap();
+        let err =
+            verify_message_with_clock(&signed, &MockClock::at_unix(1_700_000_000)).unwrap_err();
+        assert!(matches!(err, ClawRtcError::MessageExpired(_)), "{err}");
+        assert!(err.to_string().contains("future"), "{err}");
+    }
+}
@@ -2,22 +2,248 @@
 //!
 //! Matches the Python miner protocol exactly for wire compatibility.
 
-use crate::client::{RustChainClient, BLOCK_TIME};
-use crate::error::ClawRtcResult;
+use crate::client::{EnrollmentError, RustChainClient, BLOCK_TIME};
+use crate::clock::{self, Clock};
+use crate::error::{ClawRtcError, ClawRtcResult};
+use crate::event_stream::{self, EventStreamConfig};
 use crate::fingerprint;
 use crate::hardware::HardwareInfo;
 use crate::wallet::RtcWallet;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
+/// Leading entropy samples discarded as warm-up by default: none, so the
+/// statistics match what miners have always submitted. Warm-up is opt-in.
+pub const DEFAULT_ENTROPY_WARMUP: usize = 0;
+
+/// An entropy sample taking more than this many times the median is taken
+/// to have been preempted.
+pub const PREEMPTION_FACTOR: f64 = 4.0;
+
+/// What [`collect_entropy`] does about samples inflated by preemption.
+///
+/// On a loaded system the timing loop can be descheduled mid-sample; the
+/// resulting outliers swamp the variance the node scores.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreemptionPolicy {
+    /// Don't look for preempted samples. The entropy blob is unchanged.
+    #[default]
+    Ignore,
+    /// Keep preempted samples, but report `contended` and
+    /// `preempted_samples` in the entropy blob.
+    Flag,
+    /// Like `Flag`, but also leave preempted samples out of the statistics.
+    Discard,
+}
+
+impl std::str::FromStr for PreemptionPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ignore" => Ok(Self::Ignore),
+            "flag" => Ok(Self::Flag),
+            "discard" => Ok(Self::Discard),
+            _ => Err(format!(
+                "Unknown preemption policy: {s} (expected ignore, flag or discard)"
+            )),
+        }
+    }
+}
+
+/// How long an accepted attestation stays valid before the miner re-attests.
+pub const ATTESTATION_VALIDITY: Duration = Duration::from_secs(24 * 60 * 60);
+
 /// Mining configuration.
 pub struct MinerConfig {
     pub node_url: String,
+    /// Timeout for requests to the node. `None` keeps the client's default
+    /// of 30 seconds.
+    pub node_timeout: Option<Duration>,
     pub wallet: RtcWallet,
     pub run_fingerprints: bool,
+    /// Leading entropy timing samples to discard before computing statistics.
+    pub entropy_warmup_discard: usize,
+    /// How to treat entropy samples inflated by preemption.
+    pub entropy_preemption: PreemptionPolicy,
+    /// WebSocket URL to stream [`MineEvent`]s to, if any.
+    pub events_url: Option<String>,
+    /// Upper bound on a random delay added at startup and to every wait
+    /// between cycles, so a fleet started together doesn't hit the node in
+    /// lockstep at each epoch boundary.
+    pub cycle_jitter: Option<Duration>,
+    /// Check the balance at this interval while waiting out an epoch,
+    /// emitting [`MineEvent::BalanceChecked`] when it changes. With `None`
+    /// the balance is only checked once the wait is over.
+    pub balance_poll: Option<Duration>,
+    /// Identify the miner by [`HardwareInfo::miner_id_stable`] instead of
+    /// the hostname-based [`HardwareInfo::miner_id`]. Off by default so
+    /// existing miners keep their ID and attestation history.
+    pub stable_miner_id: bool,
+}
+
+/// Progress events emitted by [`Miner::mine_loop`].
+///
+/// These only carry public data so they are safe to forward to a dashboard.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MineEvent {
+    CycleStarted { cycle: u64 },
+    AttestationAccepted,
+    AttestationFailed { error: String },
+    Enrolled { epoch: Option<i64>, weight: Option<f64> },
+    AlreadyEnrolled { epoch: Option<i64> },
+    EnrollmentRejected { error: Option<String> },
+    Balance { balance: f64 },
+    /// The balance changed while waiting out an epoch (see
+    /// [`MinerConfig::balance_poll`]).
+    BalanceChecked { balance: f64, previous: f64 },
+    Stopped,
+}
+
+/// Entropy and fingerprint results gathered for an attestation.
+///
+/// Neither depends on the wallet or the challenge nonce, so one collection can
+/// back attestations for several wallets on the same machine (see
+/// [`MultiMiner`](crate::multi_miner::MultiMiner)).
+#[derive(Debug, Clone)]
+pub struct AttestationEvidence {
+    pub entropy: serde_json::Value,
+    pub fingerprint: Option<serde_json::Value>,
+}
+
+impl AttestationEvidence {
+    /// Collect timing entropy and, if enabled, run the fingerprint checks.
+    pub async fn collect(
+        run_fingerprints: bool,
+        entropy_warmup: usize,
+        preemption: PreemptionPolicy,
+    ) -> Self {
+        // CPU-bound, run in blocking task
+        let entropy =
+            tokio::task::spawn_blocking(move || collect_entropy(entropy_warmup, preemption))
+                .await
+                .expect("Entropy collection panicked");
+
+        let fingerprint = if run_fingerprints {
+            let report = fingerprint::validate_all_checks_async().await;
+            Some(serde_json::json!({
+                "all_passed": report.all_passed,
+                "checks": report.checks,
+            }))
+        } else {
+            None
+        };
+
+        Self {
+            entropy,
+            fingerprint,
+        }
+    }
+
+    /// The attestation payload submitting this evidence for `wallet_address`
+    /// against `nonce` (matches Python format).
+    pub fn payload(
+        &self,
+        wallet_address: &str,
+        miner_id: &str,
+        hardware: &HardwareInfo,
+        nonce: &str,
+    ) -> serde_json::Value {
+        let mut payload = serde_json::json!({
+            "miner": wallet_address,
+            "miner_id": miner_id,
+            "nonce": nonce,
+            "report": AttestationReport::new(nonce, wallet_address, &self.entropy),
+            "device": hardware.device_payload(),
+            "signals": hardware.signals_payload(),
+        });
+        if let Some(fp) = &self.fingerprint {
+            payload["fingerprint"] = fp.clone();
+        }
+        payload
+    }
+}
+
+/// The `report` object of an attestation payload.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttestationReport {
+    pub nonce: String,
+    /// SHA-256 of nonce, wallet address and entropy JSON, binding the
+    /// evidence to this wallet and challenge.
+    pub commitment: String,
+    /// The entropy statistics from [`collect_entropy`].
+    pub derived: serde_json::Value,
+    /// The entropy's timing variance.
+    pub entropy_score: f64,
+}
+
+impl AttestationReport {
+    /// Build the report committing `entropy` to `nonce` and `wallet_address`.
+    pub fn new(nonce: &str, wallet_address: &str, entropy: &serde_json::Value) -> Self {
+        let commitment_input = format!("{nonce}{wallet_address}{entropy}");
+        Self {
+            nonce: nonce.to_string(),
+            commitment: hex::encode(Sha256::digest(commitment_input.as_bytes())),
+            derived: entropy.clone(),
+            entropy_score: entropy["variance_ns"].as_f64().unwrap_or_default(),
+        }
+    }
+}
+
+/// How good a miner a machine makes, each part from 0.0 to 1.0; see
+/// [`hardware_score`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct HardwareScore {
+    /// Fraction of the fingerprint checks that passed.
+    pub authenticity: f64,
+    /// Core count, memory and hashing throughput against a reference machine.
+    pub performance: f64,
+    /// `0.7 × authenticity + 0.3 × performance`.
+    pub combined: f64,
+}
+
+/// Logical cores at which the core component of [`hardware_score`] maxes out.
+const REFERENCE_CORES: f64 = 16.0;
+/// Memory, in GB, at which the memory component maxes out.
+const REFERENCE_MEMORY_GB: f64 = 32.0;
+/// SHA-256 digests per second at which the throughput component maxes out.
+const REFERENCE_HASHES_PER_SEC: f64 = 4_000_000.0;
+
+/// Combine detected hardware and a fingerprint report into one
+/// [`HardwareScore`] for ranking machines.
+///
+/// - `authenticity` is the report's [`score`](fingerprint::FingerprintReport::score):
+///   the fraction of checks that ran and passed.
+/// - `performance` is the mean of three parts, each capped at 1.0: cores / 16,
+///   memory / 32 GB, and SHA-256 throughput / 4M digests per second. The
+///   throughput comes from the clock drift check's `mean_ns` and counts as 0
+///   if that check was skipped or errored.
+/// - `combined` weights authenticity at 0.7 and performance at 0.3, since a
+///   machine that fails attestation earns nothing however fast it is.
+pub fn hardware_score(hw: &HardwareInfo, report: &fingerprint::FingerprintReport) -> HardwareScore {
+    let capped = |value: f64, reference: f64| (value / reference).clamp(0.0, 1.0);
+    let hashes_per_sec = report.checks.clock_drift.data["mean_ns"]
+        .as_f64()
+        .filter(|ns| *ns > 0.0)
+        .map_or(0.0, |ns| fingerprint::clock_drift::REFERENCE_OPS as f64 * 1e9 / ns);
+    let performance = (capped(hw.cores as f64, REFERENCE_CORES)
+        + capped(hw.memory_gb as f64, REFERENCE_MEMORY_GB)
+        + capped(hashes_per_sec, REFERENCE_HASHES_PER_SEC))
+        / 3.0;
+    let authenticity = report.score.clamp(0.0, 1.0);
+    HardwareScore {
+        authenticity,
+        performance,
+        combined: 0.7 * authenticity + 0.3 * performance,
+    }
 }
 
 /// RustChain miner — handles attestation, enrollment, and mining cycles.
@@ -27,28 +253,88 @@ pub struct Miner {
     hardware: HardwareInfo,
     miner_id: String,
     run_fingerprints: bool,
-    attestation_valid_until: Instant,
+    entropy_warmup_discard: usize,
+    entropy_preemption: PreemptionPolicy,
+    /// `None` until the first attestation is accepted.
+    attestation_valid_until: Option<Instant>,
+    clock: Arc<dyn Clock>,
+    events_url: Option<String>,
+    events: Option<mpsc::Sender<MineEvent>>,
+    cycle_jitter: Option<Duration>,
+    balance_poll: Option<Duration>,
 }
 
 impl Miner {
     /// Create a new miner instance.
     pub fn new(config: MinerConfig) -> ClawRtcResult<Self> {
-        let hardware = HardwareInfo::detect()?;
-        let miner_id = hardware.miner_id();
-        let client = RustChainClient::new(&config.node_url);
+        Ok(Self::with_hardware(config, HardwareInfo::detect()?))
+    }
+
+    /// Create a miner for already-detected hardware.
+    pub(crate) fn with_hardware(config: MinerConfig, hardware: HardwareInfo) -> Self {
+        let miner_id = if config.stable_miner_id {
+            hardware.miner_id_stable()
+        } else {
+            hardware.miner_id()
+        };
+        let mut client = RustChainClient::for_node(&config.node_url);
+        if let Some(timeout) = config.node_timeout {
+            client = client.with_timeout(timeout);
+        }
 
-        Ok(Self {
+        Self {
             client,
             wallet: config.wallet,
             hardware,
             miner_id,
             run_fingerprints: config.run_fingerprints,
-            attestation_valid_until: Instant::now(), // expired — will attest on first cycle
-        })
+            entropy_warmup_discard: config.entropy_warmup_discard,
+            entropy_preemption: config.entropy_preemption,
+            attestation_valid_until: None, // will attest on first cycle
+            clock: clock::system(),
+            events_url: config.events_url,
+            events: None,
+            cycle_jitter: config.cycle_jitter,
+            balance_poll: config.balance_poll,
+        }
+    }
+
+    /// Read the time from `clock` instead of the system clock.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Send [`MineEvent`]s to `sink` instead of the configured WebSocket URL.
+    pub fn set_event_sink(&mut self, sink: mpsc::Sender<MineEvent>) {
+        self.events = Some(sink);
+    }
+
+    /// Queue an event for publishing. Never blocks mining: if the queue is
+    /// full (e.g. the dashboard is unreachable), the event is dropped.
+    fn emit(&self, event: MineEvent) {
+        if let Some(tx) = &self.events {
+            if let Err(e) = tx.try_send(event) {
+                debug!(error = %e, "Dropped miner event");
+            }
+        }
     }
 
-    /// Run a single attestation (challenge → collect entropy → submit).
+    /// Run a single attestation (collect entropy → challenge → submit).
     pub async fn attest(&mut self) -> ClawRtcResult<()> {
+        let evidence = AttestationEvidence::collect(
+            self.run_fingerprints,
+            self.entropy_warmup_discard,
+            self.entropy_preemption,
+        )
+        .await;
+        self.attest_with(&evidence).await
+    }
+
+    /// Attest using previously collected evidence.
+    ///
+    /// Fails with [`ClawRtcError::ChallengeExpired`] instead of submitting if
+    /// the node's nonce has already expired.
+    pub async fn attest_with(&mut self, evidence: &AttestationEvidence) -> ClawRtcResult<()> {
         info!(miner_id = %self.miner_id, "Starting attestation");
 
         // 1. Get challenge nonce
@@ -56,51 +342,21 @@ impl Miner {
         let nonce = &challenge.nonce;
         debug!(nonce, "Got attestation challenge");
 
-        // 2. Collect timing entropy (CPU-bound, run in blocking task)
-        let entropy = tokio::task::spawn_blocking(collect_entropy)
-            .await
-            .expect("Entropy collection panicked");
-
-        // 3. Compute commitment hash
-        let entropy_json = serde_json::to_string(&entropy)?;
-        let commitment_input = format!("{}{}{}", nonce, self.wallet.address(), entropy_json);
-        let commitment = hex::encode(Sha256::digest(commitment_input.as_bytes()));
-
-        // 4. Run fingerprint checks if enabled
-        let fingerprint_payload = if self.run_fingerprints {
-            let report = fingerprint::validate_all_checks_async().await;
-            Some(serde_json::json!({
-                "all_passed": report.all_passed,
-                "checks": report.checks,
-            }))
-        } else {
-            None
-        };
-
-        // 5. Build attestation payload (matches Python format)
-        let mut payload = serde_json::json!({
-            "miner": self.wallet.address(),
-            "miner_id": self.miner_id,
-            "nonce": nonce,
-            "report": {
-                "nonce": nonce,
-                "commitment": commitment,
-                "derived": entropy,
-                "entropy_score": entropy["variance_ns"],
-            },
-            "device": self.hardware.device_payload(),
-            "signals": self.hardware.signals_payload(),
-        });
+        // 2. Commit the evidence to this wallet and nonce
+        let payload =
+            evidence.payload(self.wallet.address(), &self.miner_id, &self.hardware, nonce);
 
-        if let Some(fp) = fingerprint_payload {
-            payload["fingerprint"] = fp;
+        // 3. Submit, unless the nonce has already expired
+        if challenge.is_expired_at(self.clock.now().timestamp()) {
+            return Err(ClawRtcError::ChallengeExpired(format!(
+                "nonce {nonce} expired at {}",
+                challenge.expires_at.unwrap_or_default()
+            )));
         }
-
-        // 6. Submit
         self.client.submit_attestation(&payload).await?;
-        // Attestation valid for 24 hours
-        self.attestation_valid_until = Instant::now() + Duration::from_secs(86400);
+        self.attestation_valid_until = Some(self.clock.monotonic() + ATTESTATION_VALIDITY);
         info!(miner_id = %self.miner_id, "Attestation accepted");
+        self.emit(MineEvent::AttestationAccepted);
         Ok(())
     }
 
@@ -115,25 +371,41 @@ impl Miner {
             },
         });
 
-        match self.client.enroll(&payload).await {
-            Ok(resp) => {
-                if resp.ok {
+        // A retry after a transient failure may find the first attempt
+        // landed; "already enrolled" then means we are in the epoch.
+        let rejection = match self.client.enroll(&payload).await {
+            Ok(resp) => match resp.rejection() {
+                None => {
                     info!(
                         epoch = resp.epoch,
                         weight = resp.weight,
                         "Enrolled in epoch"
                     );
-                    Ok(true)
-                } else {
-                    warn!(error = ?resp.error, "Enrollment rejected");
-                    Ok(false)
+                    self.emit(MineEvent::Enrolled {
+                        epoch: resp.epoch,
+                        weight: resp.weight,
+                    });
+                    return Ok(true);
                 }
-            }
-            Err(e) => {
-                warn!(error = %e, "Enrollment failed");
-                Ok(false)
-            }
-        }
+                Some(EnrollmentError::AlreadyEnrolled) => {
+                    return Ok(self.already_enrolled(resp.epoch));
+                }
+                Some(EnrollmentError::Rejected(error)) => error,
+            },
+            Err(e) => match EnrollmentError::from_message(Some(&e.to_string())) {
+                EnrollmentError::AlreadyEnrolled => return Ok(self.already_enrolled(None)),
+                EnrollmentError::Rejected(error) => error,
+            },
+        };
+        warn!(error = ?rejection, "Enrollment rejected");
+        self.emit(MineEvent::EnrollmentRejected { error: rejection });
+        Ok(false)
+    }
+
+    fn already_enrolled(&self, epoch: Option<i64>) -> bool {
+        info!(epoch, "Already enrolled in epoch");
+        self.emit(MineEvent::AlreadyEnrolled { epoch });
+        true
     }
 
     /// Check current balance.
@@ -142,79 +414,253 @@ impl Miner {
     }
 
     /// Run the mining loop until cancelled.
+    ///
+    /// If an events URL was configured (and no sink was set), a publisher is
+    /// started that streams [`MineEvent`]s to it for the life of the miner.
     pub async fn mine_loop(&mut self, cancel: Arc<AtomicBool>) -> ClawRtcResult<()> {
-        let mut cycle = 0u64;
+        mine_loop(std::slice::from_mut(self), &cancel).await
+    }
+
+    /// Whether the current attestation has expired and must be renewed.
+    pub fn attestation_expired(&self) -> bool {
+        match self.attestation_valid_until {
+            Some(until) => self.clock.monotonic() >= until,
+            None => true,
+        }
+    }
+
+    /// Get the miner ID.
+    pub fn miner_id(&self) -> &str {
+        &self.miner_id
+    }
+
+    /// Get the wallet address.
+    pub fn wallet_address(&self) -> &str {
+        self.wallet.address()
+    }
+}
 
-        loop {
-            if cancel.load(Ordering::Relaxed) {
-                info!("Mining loop cancelled");
+/// Renew every expired attestation among `miners`, collecting evidence at
+/// most once and sharing it between them.
+///
+/// Returns one result per miner; miners that were still attested are `Ok`.
+/// Evidence is collected with the settings of the first expired miner.
+pub(crate) async fn attest_expired(miners: &mut [Miner]) -> Vec<ClawRtcResult<()>> {
+    let Some(expired) = miners.iter().find(|m| m.attestation_expired()) else {
+        return miners.iter().map(|_| Ok(())).collect();
+    };
+    let evidence = AttestationEvidence::collect(
+        expired.run_fingerprints,
+        expired.entropy_warmup_discard,
+        expired.entropy_preemption,
+    )
+    .await;
+
+    let mut results = Vec::with_capacity(miners.len());
+    for miner in miners {
+        if miner.attestation_expired() {
+            results.push(miner.attest_with(&evidence).await);
+        } else {
+            results.push(Ok(()));
+        }
+    }
+    results
+}
+
+/// The loop behind [`Miner::mine_loop`] and
+/// [`MultiMiner::mine_loop`](crate::multi_miner::MultiMiner::mine_loop):
+/// every miner is attested, enrolled and checked in step.
+///
+/// The clock, jitter and balance polling are the first miner's; a
+/// `MultiMiner` configures all of its miners alike.
+pub(crate) async fn mine_loop(miners: &mut [Miner], cancel: &AtomicBool) -> ClawRtcResult<()> {
+    let Some(first) = miners.first() else {
+        return Ok(());
+    };
+    let clock = first.clock.clone();
+    let cycle_jitter = first.cycle_jitter;
+    let balance_poll = first.balance_poll;
+
+    // Miners without a sink share one publisher for the configured URL
+    let url = miners.iter_mut().filter_map(|m| m.events_url.take()).last();
+    if let Some(url) = url.filter(|_| miners.iter().any(|m| m.events.is_none())) {
+        let (tx, _) = event_stream::spawn(EventStreamConfig::new(url), miners[0].miner_id.clone());
+        for miner in miners.iter_mut().filter(|m| m.events.is_none()) {
+            miner.events = Some(tx.clone());
+        }
+    }
+
+    if let Some(jitter) = cycle_jitter {
+        let delay = jittered(Duration::ZERO, Some(jitter));
+        debug!(delay_ms = delay.as_millis() as u64, "Startup jitter");
+        if interruptible_sleep(&*clock, delay, cancel).await {
+            emit_all(miners, MineEvent::Stopped);
+            return Ok(());
+        }
+    }
+
+    let mut cycle = 0u64;
+
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            info!("Mining loop cancelled");
+            break;
+        }
+
+        cycle += 1;
+        info!(cycle, miner_id = %miners[0].miner_id, wallets = miners.len(), "Mining cycle");
+        emit_all(miners, MineEvent::CycleStarted { cycle });
+
+        // Re-attest if needed
+        let attested = attest_expired(miners).await;
+        for (miner, result) in miners.iter().zip(&attested) {
+            if let Err(e) = result {
+                error!(wallet = miner.wallet_address(), error = %e, "Attestation failed");
+                miner.emit(MineEvent::AttestationFailed {
+                    error: e.to_string(),
+                });
+            }
+        }
+        if attested.iter().all(Result::is_err) {
+            let wait = jittered(Duration::from_secs(60), cycle_jitter);
+            if interruptible_sleep(&*clock, wait, cancel).await {
                 break;
             }
+            continue;
+        }
 
-            cycle += 1;
-            info!(cycle, miner_id = %self.miner_id, "Mining cycle");
+        // Enroll the miners that are attested
+        let mut enrolled = Vec::with_capacity(miners.len());
+        for (miner, result) in miners.iter().zip(&attested) {
+            enrolled.push(result.is_ok() && miner.enroll().await?);
+        }
 
-            // Re-attest if needed
-            if Instant::now() >= self.attestation_valid_until {
-                if let Err(e) = self.attest().await {
-                    error!(error = %e, "Attestation failed");
-                    if interruptible_sleep(Duration::from_secs(60), &cancel).await {
-                        break;
-                    }
-                    continue;
+        if enrolled.iter().any(|&ok| ok) {
+            // Wait for block time
+            info!("Enrolled — waiting {} seconds for epoch", BLOCK_TIME);
+            let wait = jittered(Duration::from_secs(BLOCK_TIME), cycle_jitter);
+            let cancelled = match balance_poll {
+                Some(interval) => {
+                    wait_polling_balance(&*clock, miners, wait, interval, cancel).await
                 }
+                None => interruptible_sleep(&*clock, wait, cancel).await,
+            };
+            if cancelled {
+                break;
             }
 
-            // Enroll
-            if self.enroll().await? {
-                // Wait for block time
-                info!("Enrolled — waiting {} seconds for epoch", BLOCK_TIME);
-                if interruptible_sleep(Duration::from_secs(BLOCK_TIME), &cancel).await {
-                    break;
-                }
-
-                // Check balance after epoch
-                match self.check_balance().await {
-                    Ok(bal) => info!(balance = bal, "Current RTC balance"),
-                    Err(e) => warn!(error = %e, "Balance check failed"),
-                }
-            } else {
-                // Retry after 60s
-                if interruptible_sleep(Duration::from_secs(60), &cancel).await {
-                    break;
+            // Check balance after epoch
+            for (miner, _) in miners.iter().zip(&enrolled).filter(|(_, &ok)| ok) {
+                match miner.check_balance().await {
+                    Ok(bal) => {
+                        info!(
+                            wallet = miner.wallet_address(),
+                            balance = bal,
+                            "Current RTC balance"
+                        );
+                        miner.emit(MineEvent::Balance { balance: bal });
+                    }
+                    Err(e) => {
+                        warn!(wallet = miner.wallet_address(), error = %e, "Balance check failed")
+                    }
                 }
             }
+        } else {
+            // Retry after 60s
+            let wait = jittered(Duration::from_secs(60), cycle_jitter);
+            if interruptible_sleep(&*clock, wait, cancel).await {
+                break;
+            }
         }
+    }
 
-        Ok(())
+    emit_all(miners, MineEvent::Stopped);
+    Ok(())
+}
+
+fn emit_all(miners: &[Miner], event: MineEvent) {
+    for miner in miners {
+        miner.emit(event.clone());
     }
+}
 
-    /// Get the miner ID.
-    pub fn miner_id(&self) -> &str {
-        &self.miner_id
+/// Wait for `duration` on `clock`, checking each miner's balance every
+/// `interval` and emitting [`MineEvent::BalanceChecked`] each time it differs
+/// from the last reading. Returns `true` if cancelled.
+///
+/// Failed checks are skipped rather than read as a zero balance, so a
+/// node hiccup doesn't show up as a change.
+async fn wait_polling_balance(
+    clock: &dyn Clock,
+    miners: &[Miner],
+    duration: Duration,
+    interval: Duration,
+    cancel: &AtomicBool,
+) -> bool {
+    let deadline = clock.monotonic() + duration;
+    let mut last = Vec::with_capacity(miners.len());
+    for miner in miners {
+        let balance = miner.client.balance_strict(miner.wallet_address()).await;
+        last.push(balance.ok());
+    }
+    loop {
+        let remaining = deadline.saturating_duration_since(clock.monotonic());
+        if interruptible_sleep(clock, interval.min(remaining), cancel).await {
+            return true;
+        }
+        if remaining <= interval {
+            return false;
+        }
+        for (miner, last) in miners.iter().zip(&mut last) {
+            match miner.client.balance_strict(miner.wallet_address()).await {
+                Ok(balance) => {
+                    if let Some(previous) = last.filter(|previous| *previous != balance) {
+                        info!(balance, previous, "RTC balance changed");
+                        miner.emit(MineEvent::BalanceChecked { balance, previous });
+                    }
+                    *last = Some(balance);
+                }
+                Err(e) => debug!(error = %e, "Balance poll failed"),
+            }
+        }
     }
+}
 
-    /// Get the wallet address.
-    pub fn wallet_address(&self) -> &str {
-        self.wallet.address()
+/// `base` plus a uniformly random delay in `0..=jitter`.
+fn jittered(base: Duration, jitter: Option<Duration>) -> Duration {
+    match jitter {
+        Some(jitter) if !jitter.is_zero() => {
+            let extra = rand::thread_rng().gen_range(0..=jitter.as_millis() as u64);
+            base + Duration::from_millis(extra)
+        }
+        _ => base,
     }
 }
 
-/// Sleep for a duration, checking the cancel flag every second.
-/// Returns `true` if cancelled, `false` if sleep completed normally.
-async fn interruptible_sleep(duration: Duration, cancel: &AtomicBool) -> bool {
-    let start = Instant::now();
-    while start.elapsed() < duration {
+/// Sleep for a duration on `clock`, checking the cancel flag at least every
+/// second. Returns `true` if cancelled, `false` if sleep completed normally.
+async fn interruptible_sleep(clock: &dyn Clock, duration: Duration, cancel: &AtomicBool) -> bool {
+    let start = clock.monotonic();
+    loop {
+        let elapsed = clock.monotonic().saturating_duration_since(start);
+        if elapsed >= duration {
+            return false;
+        }
         if cancel.load(Ordering::Relaxed) {
             return true;
         }
-        tokio::time::sleep(Duration::from_secs(1)).await;
+        clock
+            .sleep((duration - elapsed).min(Duration::from_secs(1)))
+            .await;
     }
-    false
 }
 
 /// Collect CPU timing entropy (must run on a blocking thread).
-fn collect_entropy() -> serde_json::Value {
+///
+/// The first `warmup` samples are discarded before computing statistics;
+/// `sample_count` still reports every sample taken. Preempted samples are
+/// handled according to `preemption`.
+pub(crate) fn collect_entropy(warmup: usize, preemption: PreemptionPolicy) -> serde_json::Value {
     let cycles = 48;
     let inner_loop = 25_000u64;
     let mut samples = Vec::with_capacity(cycles);
@@ -230,31 +676,482 @@ fn collect_entropy() -> serde_json::Value {
         samples.push(duration);
     }
 
-    let n = samples.len() as f64;
-    let mean_ns = samples.iter().sum::<f64>() / n;
-    let variance_ns = samples.iter().map(|x| (x - mean_ns).powi(2)).sum::<f64>() / n;
-    let min_ns = samples.iter().cloned().fold(f64::INFINITY, f64::min);
-    let max_ns = samples.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
-    let preview: Vec<f64> = samples.iter().take(12).copied().collect();
+    entropy_stats(&samples, warmup, preemption)
+}
 
-    serde_json::json!({
+/// Summarize entropy samples after discarding `warmup` leading samples.
+///
+/// A sample taking more than [`PREEMPTION_FACTOR`] times the median is
+/// counted as preempted, unless `preemption` is [`PreemptionPolicy::Ignore`].
+fn entropy_stats(
+    samples: &[f64],
+    warmup: usize,
+    preemption: PreemptionPolicy,
+) -> serde_json::Value {
+    let warmed = fingerprint::discard_warmup(samples, warmup);
+    let warmup_discarded = samples.len() - warmed.len();
+    let mut measured = warmed.to_vec();
+    let mut preempted = 0;
+    if preemption != PreemptionPolicy::Ignore && !measured.is_empty() {
+        let mut sorted = measured.clone();
+        sorted.sort_by(f64::total_cmp);
+        let limit = sorted[sorted.len() / 2] * PREEMPTION_FACTOR;
+        preempted = measured.iter().filter(|&&s| s > limit).count();
+        if preemption == PreemptionPolicy::Discard {
+            measured.retain(|&s| s <= limit);
+        }
+    }
+    let n = measured.len() as f64;
+    let mean_ns = measured.iter().sum::<f64>() / n;
+    let variance_ns = measured.iter().map(|x| (x - mean_ns).powi(2)).sum::<f64>() / n;
+    let min_ns = measured.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max_ns = measured.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let preview: Vec<f64> = measured.iter().take(12).copied().collect();
+
+    let mut stats = serde_json::json!({
         "mean_ns": mean_ns,
         "variance_ns": variance_ns,
         "min_ns": min_ns,
         "max_ns": max_ns,
         "sample_count": samples.len(),
         "samples_preview": preview,
-    })
+    });
+    if warmup_discarded > 0 {
+        stats["warmup_discarded"] = warmup_discarded.into();
+    }
+    if preemption != PreemptionPolicy::Ignore {
+        stats["contended"] = (preempted > 0).into();
+        stats["preempted_samples"] = preempted.into();
+    }
+    stats
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::MockClock;
+    use crate::test_support::{MockResponse, MockServer};
+
+    #[test]
+    fn test_attestation_report_matches_hand_built_json() {
+        let nonce = "abc123";
+        let wallet = "RTC0123456789abcdef0123456789abcdef01234567";
+        let entropy = serde_json::json!({
+            "mean_ns": 1200.5,
+            "variance_ns": 42.25,
+            "min_ns": 1100,
+            "max_ns": 1300,
+            "sample_count": 48,
+        });
+
+        // The payload's report as it was built inline before the struct
+        let entropy_json = serde_json::to_string(&entropy).unwrap();
+        let commitment_input = format!("{}{}{}", nonce, wallet, entropy_json);
+        let commitment = hex::encode(Sha256::digest(commitment_input.as_bytes()));
+        let hand_built = serde_json::json!({
+            "nonce": nonce,
+            "commitment": commitment,
+            "derived": entropy,
+            "entropy_score": entropy["variance_ns"],
+        });
+
+        let report = AttestationReport::new(nonce, wallet, &entropy);
+        let serialized = serde_json::to_value(&report).unwrap();
+        assert_eq!(serialized, hand_built);
+        assert_eq!(serialized.to_string(), hand_built.to_string());
+    }
 
     #[test]
     fn test_collect_entropy() {
-        let entropy = collect_entropy();
+        let entropy = collect_entropy(DEFAULT_ENTROPY_WARMUP, PreemptionPolicy::Ignore);
         assert!(entropy["mean_ns"].as_f64().unwrap() > 0.0);
         assert!(entropy["sample_count"].as_u64().unwrap() == 48);
+        // By default the blob has the same fields miners have always sent
+        let mut keys: Vec<_> = entropy.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            ["max_ns", "mean_ns", "min_ns", "sample_count", "samples_preview", "variance_ns"]
+        );
+    }
+
+    #[test]
+    fn test_jittered_varies_within_bound() {
+        let base = Duration::from_secs(60);
+        let jitter = Duration::from_secs(5);
+        let waits: Vec<Duration> = (0..50).map(|_| jittered(base, Some(jitter))).collect();
+        assert!(waits.iter().all(|w| *w >= base && *w <= base + jitter));
+        assert!(waits.iter().any(|w| *w != waits[0]), "jitter never varied");
+
+        assert_eq!(jittered(base, None), base);
+        assert_eq!(jittered(base, Some(Duration::ZERO)), base);
+    }
+
+    #[tokio::test]
+    async fn test_interruptible_sleep_waits_on_clock() {
+        let clock = MockClock::at_unix(0);
+        let started = Instant::now();
+        let cancelled =
+            interruptible_sleep(&clock, Duration::from_secs(BLOCK_TIME), &AtomicBool::new(false))
+                .await;
+        assert!(!cancelled);
+        assert_eq!(clock.now().timestamp(), BLOCK_TIME as i64);
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        let cancelled =
+            interruptible_sleep(&clock, Duration::from_secs(60), &AtomicBool::new(true)).await;
+        assert!(cancelled);
+        assert_eq!(clock.now().timestamp(), BLOCK_TIME as i64);
+    }
+
+    #[tokio::test]
+    async fn test_attest_refuses_expired_nonce() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/attest/challenge" => MockResponse::json(
+                200,
+                serde_json::json!({ "nonce": "stale", "issued_at": 1, "expires_at": 2 }),
+            ),
+            _ => MockResponse::json(200, serde_json::json!({ "ok": true })),
+        })
+        .await;
+        let mut miner = Miner::new(MinerConfig {
+            node_url: server.url(),
+            node_timeout: None,
+            wallet: RtcWallet::generate(),
+            run_fingerprints: false,
+            entropy_warmup_discard: DEFAULT_ENTROPY_WARMUP,
+            entropy_preemption: PreemptionPolicy::Ignore,
+            events_url: None,
+            cycle_jitter: None,
+            balance_poll: None,
+            stable_miner_id: false,
+        })
+        .unwrap();
+
+        let err = miner.attest().await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::ChallengeExpired(_)), "{err}");
+        assert!(err.to_string().contains("stale"));
+        assert!(miner.attestation_expired());
+        assert!(server.requests().iter().all(|r| r.path != "/attest/submit"));
+    }
+
+    #[test]
+    fn test_stable_miner_id_is_opt_in() {
+        let hw = HardwareInfo::detect().unwrap();
+        let miner = |stable_miner_id| {
+            let config = MinerConfig {
+                node_url: "http://localhost".into(),
+                node_timeout: None,
+                wallet: RtcWallet::generate(),
+                run_fingerprints: false,
+                entropy_warmup_discard: DEFAULT_ENTROPY_WARMUP,
+                entropy_preemption: PreemptionPolicy::Ignore,
+                events_url: None,
+                cycle_jitter: None,
+                balance_poll: None,
+                stable_miner_id,
+            };
+            Miner::with_hardware(config, hw.clone())
+        };
+        assert_eq!(miner(false).miner_id(), hw.miner_id());
+        assert_eq!(miner(true).miner_id(), hw.miner_id_stable());
+    }
+
+    #[tokio::test]
+    async fn test_node_timeout_applies_to_client() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, serde_json::json!({ "balance_rtc": 1.0 }))
+                .with_delay(Duration::from_secs(5))
+        })
+        .await;
+        let miner = Miner::with_hardware(
+            MinerConfig {
+                node_url: server.url(),
+                node_timeout: Some(Duration::from_millis(200)),
+                wallet: RtcWallet::generate(),
+                run_fingerprints: false,
+                entropy_warmup_discard: DEFAULT_ENTROPY_WARMUP,
+                entropy_preemption: PreemptionPolicy::Ignore,
+                events_url: None,
+                cycle_jitter: None,
+                balance_poll: None,
+                stable_miner_id: false,
+            },
+            HardwareInfo::detect().unwrap(),
+        );
+
+        let started = std::time::Instant::now();
+        let err = miner.check_balance().await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::Network(_)), "{err}");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    fn polling_miner(node_url: String) -> Miner {
+        Miner::with_hardware(
+            MinerConfig {
+                node_url,
+                node_timeout: None,
+                wallet: RtcWallet::generate(),
+                run_fingerprints: false,
+                entropy_warmup_discard: DEFAULT_ENTROPY_WARMUP,
+                entropy_preemption: PreemptionPolicy::Ignore,
+                events_url: None,
+                cycle_jitter: None,
+                balance_poll: Some(Duration::from_millis(100)),
+                stable_miner_id: false,
+            },
+            HardwareInfo::detect().unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_balance_poll_reports_rewards_mid_wait() {
+        let checks = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = checks.clone();
+        let server = MockServer::start(move |req| {
+            if !req.path.contains("balance") {
+                return MockResponse::json(404, serde_json::json!({}));
+            }
+            // Rewards land on the third check
+            let balance = if counter.fetch_add(1, Ordering::SeqCst) < 2 { 1.0 } else { 2.5 };
+            MockResponse::json(200, serde_json::json!({ "balance_rtc": balance }))
+        })
+        .await;
+        let mut miner = polling_miner(server.url());
+        let clock = MockClock::at_unix(0);
+        let (tx, mut rx) = mpsc::channel(16);
+        miner.set_event_sink(tx);
+        let cancel = AtomicBool::new(false);
+
+        let wait = Duration::from_secs(1);
+        let interval = Duration::from_millis(100);
+        let miners = std::slice::from_ref(&miner);
+        let cancelled = wait_polling_balance(&clock, miners, wait, interval, &cancel).await;
+        assert!(!cancelled);
+        assert_eq!(clock.now().timestamp_millis(), 1_000);
+        assert_eq!(
+            rx.try_recv().ok(),
+            Some(MineEvent::BalanceChecked {
+                balance: 2.5,
+                previous: 1.0
+            })
+        );
+        // Unchanged readings are not reported
+        assert!(rx.try_recv().is_err());
+        // One reading up front, then one per interval short of the deadline
+        assert_eq!(checks.load(Ordering::SeqCst), 10);
+    }
+
+    #[tokio::test]
+    async fn test_balance_poll_stops_when_cancelled() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, serde_json::json!({ "balance_rtc": 1.0 }))
+        })
+        .await;
+        let miner = polling_miner(server.url());
+        let clock = MockClock::at_unix(0);
+        let cancel = AtomicBool::new(true);
+        let miners = std::slice::from_ref(&miner);
+        let wait = Duration::from_secs(30);
+        let cancelled =
+            wait_polling_balance(&clock, miners, wait, Duration::from_secs(5), &cancel).await;
+        assert!(cancelled);
+        assert_eq!(clock.now().timestamp(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_attestation_expiry_follows_clock() {
+        // The nonce is valid until Unix time 1_030
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/attest/challenge" => MockResponse::json(
+                200,
+                serde_json::json!({ "nonce": "n", "issued_at": 1_000, "expires_at": 1_030 }),
+            ),
+            _ => MockResponse::json(200, serde_json::json!({ "ok": true })),
+        })
+        .await;
+        let mut miner = Miner::with_hardware(
+            MinerConfig {
+                node_url: server.url(),
+                node_timeout: None,
+                wallet: RtcWallet::generate(),
+                run_fingerprints: false,
+                entropy_warmup_discard: DEFAULT_ENTROPY_WARMUP,
+                entropy_preemption: PreemptionPolicy::Ignore,
+                events_url: None,
+                cycle_jitter: None,
+                balance_poll: None,
+                stable_miner_id: false,
+            },
+            HardwareInfo::detect().unwrap(),
+        );
+        let clock = MockClock::at_unix(1_029);
+        miner.set_clock(Arc::new(clock.clone()));
+        let evidence = AttestationEvidence {
+            entropy: serde_json::json!({ "variance_ns": 1.0 }),
+            fingerprint: None,
+        };
+        assert!(miner.attestation_expired());
+
+        miner.attest_with(&evidence).await.unwrap();
+        assert!(!miner.attestation_expired());
+        clock.advance(ATTESTATION_VALIDITY - Duration::from_secs(1));
+        assert!(!miner.attestation_expired());
+        clock.advance(Duration::from_secs(1));
+        assert!(miner.attestation_expired());
+
+        // Now long past the nonce's expiry
+        let err = miner.attest_with(&evidence).await.unwrap_err();
+        assert!(matches!(err, ClawRtcError::ChallengeExpired(_)), "{err}");
+        let submits = server.requests().iter().filter(|r| r.path == "/attest/submit").count();
+        assert_eq!(submits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_enroll_treats_already_enrolled_as_success() {
+        let server = MockServer::sequence(vec![
+            MockResponse::json(200, serde_json::json!({ "ok": true, "epoch": 7, "weight": 1.5 })),
+            MockResponse::json(
+                200,
+                serde_json::json!({ "ok": false, "epoch": 7, "error": "Miner already enrolled" }),
+            ),
+            MockResponse::json(409, serde_json::json!({ "error": "already_enrolled" })),
+            MockResponse::json(200, serde_json::json!({ "ok": false, "error": "no attestation" })),
+        ])
+        .await;
+        let mut miner = Miner::new(MinerConfig {
+            node_url: server.url(),
+            node_timeout: None,
+            wallet: RtcWallet::generate(),
+            run_fingerprints: false,
+            entropy_warmup_discard: DEFAULT_ENTROPY_WARMUP,
+            entropy_preemption: PreemptionPolicy::Ignore,
+            events_url: None,
+            cycle_jitter: None,
+            balance_poll: None,
+            stable_miner_id: false,
+        })
+        .unwrap();
+        let (tx, mut rx) = mpsc::channel(8);
+        miner.set_event_sink(tx);
+
+        assert!(miner.enroll().await.unwrap());
+        assert!(miner.enroll().await.unwrap());
+        assert!(miner.enroll().await.unwrap());
+        assert!(!miner.enroll().await.unwrap());
+
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(event);
+        }
+        assert_eq!(
+            events,
+            [
+                MineEvent::Enrolled {
+                    epoch: Some(7),
+                    weight: Some(1.5)
+                },
+                MineEvent::AlreadyEnrolled { epoch: Some(7) },
+                MineEvent::AlreadyEnrolled { epoch: None },
+                MineEvent::EnrollmentRejected {
+                    error: Some("no attestation".into())
+                },
+            ]
+        );
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[test]
+    fn test_hardware_score_ranks_fast_real_machine_above_slow_vm() {
+        use crate::fingerprint::{CheckResult, FingerprintChecks, FingerprintReport};
+
+        let machine = |cores, memory_gb| HardwareInfo {
+            platform: "linux".into(),
+            machine: "x86_64".into(),
+            hostname: "host".into(),
+            family: "x86".into(),
+            arch: "modern".into(),
+            cpu: "cpu".into(),
+            cores,
+            memory_gb,
+            macs: vec![],
+            gpus: vec![],
+            machine_id: None,
+        };
+        let report = |passed: usize, clock_mean_ns: f64| {
+            let check = |i: usize, data| CheckResult {
+                passed: i < passed,
+                data,
+            };
+            let empty = || serde_json::json!({});
+            FingerprintReport::from_checks(FingerprintChecks {
+                clock_drift: check(0, serde_json::json!({ "mean_ns": clock_mean_ns })),
+                cache_timing: check(1, empty()),
+                simd_identity: check(2, empty()),
+                thermal_drift: check(3, empty()),
+                instruction_jitter: check(4, empty()),
+                anti_emulation: check(5, empty()),
+            })
+        };
+
+        // 16 cores, 64 GB, 5000 digests in 1 ms = 5M/s
+        let fast = hardware_score(&machine(16, 64), &report(6, 1_000_000.0));
+        assert_eq!(fast.authenticity, 1.0);
+        assert_eq!(fast.performance, 1.0);
+        assert_eq!(fast.combined, 1.0);
+
+        // 2 cores, 4 GB, 1M digests/s, and failing the VM-sensitive checks
+        let slow_vm = hardware_score(&machine(2, 4), &report(2, 5_000_000.0));
+        assert!((slow_vm.authenticity - 2.0 / 6.0).abs() < 1e-9);
+        assert!((slow_vm.performance - (0.125 + 0.125 + 0.25) / 3.0).abs() < 1e-9);
+        assert!(fast.authenticity > slow_vm.authenticity);
+        assert!(fast.performance > slow_vm.performance);
+        assert!(fast.combined > slow_vm.combined);
+
+        // No clock drift timing: the throughput part counts as zero
+        let untimed = hardware_score(&machine(16, 64), &report(6, 0.0));
+        assert!((untimed.performance - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_entropy_stats_warmup_discard() {
+        // Two cold outliers followed by steady samples
+        let samples = [5000.0, 3000.0, 1000.0, 1000.0, 1000.0, 1000.0];
+
+        let raw = entropy_stats(&samples, 0, PreemptionPolicy::Ignore);
+        assert!(raw["variance_ns"].as_f64().unwrap() > 0.0);
+        assert_eq!(raw["max_ns"], 5000.0);
+
+        let warmed = entropy_stats(&samples, 2, PreemptionPolicy::Ignore);
+        assert_eq!(warmed["variance_ns"], 0.0);
+        assert_eq!(warmed["mean_ns"], 1000.0);
+        assert_eq!(warmed["max_ns"], 1000.0);
+        assert_eq!(warmed["sample_count"], 6);
+        assert_eq!(warmed["warmup_discarded"], 2);
+        assert!(warmed.get("contended").is_none());
+    }
+
+    #[test]
+    fn test_entropy_stats_preempted_sample() {
+        // One sample descheduled mid-loop
+        let mut samples = vec![1000.0; 12];
+        samples[7] = 50_000.0;
+
+        let flagged = entropy_stats(&samples, 0, PreemptionPolicy::Flag);
+        assert_eq!(flagged["contended"], true);
+        assert_eq!(flagged["preempted_samples"], 1);
+        assert_eq!(flagged["max_ns"], 50_000.0);
+        assert!(flagged["variance_ns"].as_f64().unwrap() > 0.0);
+
+        let discarded = entropy_stats(&samples, 0, PreemptionPolicy::Discard);
+        assert_eq!(discarded["contended"], true);
+        assert_eq!(discarded["preempted_samples"], 1);
+        assert_eq!(discarded["max_ns"], 1000.0);
+        assert_eq!(discarded["variance_ns"], 0.0);
+        assert_eq!(discarded["sample_count"], 12);
+
+        let steady = entropy_stats(&[1000.0, 1100.0, 900.0], 0, PreemptionPolicy::Flag);
+        assert_eq!(steady["contended"], false);
+        assert_eq!(steady["preempted_samples"], 0);
     }
 }
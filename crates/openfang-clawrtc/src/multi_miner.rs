@@ -0,0 +1,269 @@
+//! Mining with several wallets from one machine.
+//!
+//! Hardware detection, timing entropy and fingerprint checks are expensive and
+//! describe the machine, not the wallet, so [`MultiMiner`] runs them once per
+//! attestation round and reuses the results for every wallet. Each wallet
+//! still gets its own challenge nonce and commitment.
+//!
+//! # Node policy
+//!
+//! All wallets report the same `miner_id` and device, since they run on the
+//! same hardware. Whether that is accepted is up to the node:
+//!
+//! - A node that allows one wallet per hardware ID may reject or replace
+//!   enrollments after the first; those wallets simply report `false` from
+//!   [`MultiMiner::enroll_all`].
+//! - Epoch rewards are weighted per device, so running several wallets may
+//!   split the reward rather than multiply it.
+//! - Nodes that require evidence to be fresh per submission will reject the
+//!   shared entropy; use one [`Miner`] per wallet there instead.
+
+use crate::error::{ClawRtcError, ClawRtcResult};
+use crate::hardware::HardwareInfo;
+use crate::miner::{self, Miner, MinerConfig, PreemptionPolicy};
+use crate::wallet::RtcWallet;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Configuration for a [`MultiMiner`].
+pub struct MultiMinerConfig {
+    pub node_url: String,
+    /// See [`MinerConfig::node_timeout`].
+    pub node_timeout: Option<Duration>,
+    pub wallets: Vec<RtcWallet>,
+    pub run_fingerprints: bool,
+    /// Leading entropy timing samples to discard before computing statistics.
+    pub entropy_warmup_discard: usize,
+    /// How to treat entropy samples inflated by preemption.
+    pub entropy_preemption: PreemptionPolicy,
+    /// WebSocket URL to stream every wallet's [`MineEvent`](crate::miner::MineEvent)s
+    /// to. All wallets share one connection under the common `miner_id`.
+    pub events_url: Option<String>,
+    /// See [`MinerConfig::cycle_jitter`].
+    pub cycle_jitter: Option<Duration>,
+    /// See [`MinerConfig::balance_poll`]; every wallet's balance is polled.
+    pub balance_poll: Option<Duration>,
+    /// See [`MinerConfig::stable_miner_id`].
+    pub stable_miner_id: bool,
+}
+
+/// Runs one [`Miner`] per wallet on shared hardware evidence.
+pub struct MultiMiner {
+    miners: Vec<Miner>,
+}
+
+impl MultiMiner {
+    /// Detect hardware once and create a miner for each wallet.
+    pub fn new(config: MultiMinerConfig) -> ClawRtcResult<Self> {
+        Self::with_hardware(config, HardwareInfo::detect()?)
+    }
+
+    fn with_hardware(config: MultiMinerConfig, hardware: HardwareInfo) -> ClawRtcResult<Self> {
+        if config.wallets.is_empty() {
+            return Err(ClawRtcError::Config(
+                "MultiMiner needs at least one wallet".into(),
+            ));
+        }
+        let miners = config
+            .wallets
+            .into_iter()
+            .map(|wallet| {
+                Miner::with_hardware(
+                    MinerConfig {
+                        node_url: config.node_url.clone(),
+                        node_timeout: config.node_timeout,
+                        wallet,
+                        run_fingerprints: config.run_fingerprints,
+                        entropy_warmup_discard: config.entropy_warmup_discard,
+                        entropy_preemption: config.entropy_preemption,
+                        events_url: config.events_url.clone(),
+                        cycle_jitter: config.cycle_jitter,
+                        balance_poll: config.balance_poll,
+                        stable_miner_id: config.stable_miner_id,
+                    },
+                    hardware.clone(),
+                )
+            })
+            .collect();
+
+        Ok(Self { miners })
+    }
+
+    /// The per-wallet miners, in wallet order.
+    pub fn miners(&self) -> &[Miner] {
+        &self.miners
+    }
+
+    /// Renew every expired attestation, collecting evidence at most once.
+    ///
+    /// Returns one result per wallet; wallets that were still attested are `Ok`.
+    pub async fn attest_expired(&mut self) -> Vec<ClawRtcResult<()>> {
+        miner::attest_expired(&mut self.miners).await
+    }
+
+    /// Enroll every wallet in the current epoch. Returns one flag per wallet.
+    pub async fn enroll_all(&self) -> Vec<bool> {
+        let mut enrolled = Vec::with_capacity(self.miners.len());
+        for miner in &self.miners {
+            enrolled.push(miner.enroll().await.unwrap_or(false));
+        }
+        enrolled
+    }
+
+    /// Run the mining loop for all wallets until cancelled.
+    ///
+    /// This is [`Miner::mine_loop`] run for every wallet in step. A wallet
+    /// whose attestation failed sits out that cycle's enrollment.
+    pub async fn mine_loop(&mut self, cancel: Arc<AtomicBool>) -> ClawRtcResult<()> {
+        miner::mine_loop(&mut self.miners, &cancel).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::BLOCK_TIME;
+    use crate::clock::{Clock, MockClock};
+    use crate::miner::MineEvent;
+    use crate::test_support::{MockResponse, MockServer};
+    use std::sync::atomic::Ordering;
+    use tokio::sync::mpsc;
+
+    async fn mock_node() -> MockServer {
+        MockServer::start(|req| match req.path.as_str() {
+            "/attest/challenge" => MockResponse::json(200, serde_json::json!({ "nonce": "n1" })),
+            "/attest/submit" => MockResponse::json(200, serde_json::json!({ "ok": true })),
+            "/epoch/enroll" => MockResponse::json(
+                200,
+                serde_json::json!({ "ok": true, "epoch": 7, "weight": 1.0 }),
+            ),
+            path if path.starts_with("/balance/") => {
+                MockResponse::json(200, serde_json::json!({ "balance_rtc": 1.0 }))
+            }
+            _ => MockResponse::json(404, serde_json::json!({})),
+        })
+        .await
+    }
+
+    fn config(node_url: String, wallets: Vec<RtcWallet>) -> MultiMinerConfig {
+        MultiMinerConfig {
+            node_url,
+            node_timeout: None,
+            wallets,
+            run_fingerprints: false,
+            entropy_warmup_discard: crate::miner::DEFAULT_ENTROPY_WARMUP,
+            entropy_preemption: PreemptionPolicy::Ignore,
+            events_url: None,
+            cycle_jitter: None,
+            balance_poll: None,
+            stable_miner_id: false,
+        }
+    }
+
+    #[test]
+    fn test_requires_a_wallet() {
+        let hw = HardwareInfo::detect().unwrap();
+        let result = MultiMiner::with_hardware(config("http://localhost".into(), vec![]), hw);
+        assert!(matches!(result, Err(ClawRtcError::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_two_wallets_enroll_with_shared_hardware() {
+        let server = mock_node().await;
+        let wallets = vec![RtcWallet::generate(), RtcWallet::generate()];
+        let addresses: Vec<String> = wallets.iter().map(|w| w.address().to_string()).collect();
+        let mut multi = MultiMiner::new(config(server.url(), wallets)).unwrap();
+
+        let attested = multi.attest_expired().await;
+        assert!(attested.iter().all(Result::is_ok));
+        assert!(multi.miners().iter().all(|m| !m.attestation_expired()));
+        assert_eq!(multi.enroll_all().await, vec![true, true]);
+
+        let requests = server.requests();
+        let submits: Vec<_> = requests
+            .iter()
+            .filter(|r| r.path == "/attest/submit")
+            .map(|r| r.json())
+            .collect();
+        let enrolls: Vec<_> = requests
+            .iter()
+            .filter(|r| r.path == "/epoch/enroll")
+            .map(|r| r.json())
+            .collect();
+        assert_eq!(submits.len(), 2);
+        assert_eq!(enrolls.len(), 2);
+
+        // Entropy was collected once and shared; commitments stay per wallet
+        assert_eq!(
+            submits[0]["report"]["derived"],
+            submits[1]["report"]["derived"]
+        );
+        assert_ne!(
+            submits[0]["report"]["commitment"],
+            submits[1]["report"]["commitment"]
+        );
+        assert_eq!(submits[0]["device"], submits[1]["device"]);
+
+        assert_eq!(enrolls[0]["miner_pubkey"], addresses[0].as_str());
+        assert_eq!(enrolls[1]["miner_pubkey"], addresses[1].as_str());
+        assert_eq!(enrolls[0]["miner_id"], enrolls[1]["miner_id"]);
+        assert_eq!(enrolls[0]["device"], enrolls[1]["device"]);
+    }
+
+    #[tokio::test]
+    async fn test_mine_loop_runs_every_wallet_on_the_clock() {
+        let server = mock_node().await;
+        let wallets = vec![RtcWallet::generate(), RtcWallet::generate()];
+        let mut multi = MultiMiner::new(config(server.url(), wallets)).unwrap();
+        let clock = MockClock::at_unix(0);
+        let (tx, mut rx) = mpsc::channel(64);
+        for miner in &mut multi.miners {
+            miner.set_clock(Arc::new(clock.clone()));
+            miner.set_event_sink(tx.clone());
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        // Stop once both wallets have been paid out for an epoch
+        let stop = cancel.clone();
+        let watch = async move {
+            let mut balances = 0;
+            while let Some(event) = rx.recv().await {
+                if matches!(event, MineEvent::Balance { .. }) {
+                    balances += 1;
+                    if balances == 2 {
+                        stop.store(true, Ordering::Relaxed);
+                        return;
+                    }
+                }
+            }
+        };
+        let (result, ()) = tokio::join!(multi.mine_loop(cancel), watch);
+        result.unwrap();
+
+        // The epoch wait ran on the mock clock
+        assert!(clock.now().timestamp() >= BLOCK_TIME as i64);
+        // One shared attestation round; the attestations outlive the epochs
+        let challenges = server
+            .requests()
+            .iter()
+            .filter(|r| r.path == "/attest/challenge")
+            .count();
+        assert_eq!(challenges, 2);
+    }
+
+    #[tokio::test]
+    async fn test_attest_expired_skips_valid_attestations() {
+        let server = mock_node().await;
+        let mut multi = MultiMiner::new(config(server.url(), vec![RtcWallet::generate()])).unwrap();
+        multi.attest_expired().await;
+        multi.attest_expired().await;
+
+        let challenges = server
+            .requests()
+            .iter()
+            .filter(|r| r.path == "/attest/challenge")
+            .count();
+        assert_eq!(challenges, 1);
+    }
+}
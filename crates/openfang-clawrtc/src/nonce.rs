@@ -0,0 +1,142 @@
+//! Transfer nonce allocation.
+//!
+//! The node expects each wallet's transfer nonces to be unique and
+//! increasing; they are millisecond timestamps for compatibility with the
+//! Python wallet. Taking `now()` directly collides when two transfers are
+//! signed in the same millisecond, so nonces are handed out by a
+//! [`NonceManager`] that never returns the same value twice.
+
+use crate::clock::{self, Clock};
+use std::ops::Range;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// Hands out unique, increasing transfer nonces.
+///
+/// Nonces track the wall clock in milliseconds but always move forward, even
+/// when many are reserved at once or the clock steps back.
+#[derive(Debug)]
+pub struct NonceManager {
+    /// Lowest nonce that has not been handed out yet.
+    next: AtomicI64,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for NonceManager {
+    fn default() -> Self {
+        Self::with_clock(clock::system())
+    }
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A manager reading the time from `clock`.
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            next: AtomicI64::new(0),
+            clock,
+        }
+    }
+
+    /// The process-wide manager, used by every [`RtcWallet`](crate::RtcWallet)
+    /// not given its own with
+    /// [`with_nonce_manager`](crate::RtcWallet::with_nonce_manager).
+    pub fn global() -> &'static NonceManager {
+        global_arc()
+    }
+
+    /// A handle to [`NonceManager::global`].
+    pub(crate) fn shared() -> Arc<NonceManager> {
+        Arc::clone(global_arc())
+    }
+
+    /// Reserve a single nonce.
+    pub fn next(&self) -> i64 {
+        self.reserve(1).start
+    }
+
+    /// Atomically reserve `count` contiguous nonces.
+    ///
+    /// Concurrent callers always receive non-overlapping ranges.
+    pub fn reserve(&self, count: u32) -> Range<i64> {
+        self.reserve_at(count, self.clock.now().timestamp_millis())
+    }
+
+    fn reserve_at(&self, count: u32, now_ms: i64) -> Range<i64> {
+        let mut start = 0;
+        self.next
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |next| {
+                start = next.max(now_ms);
+                Some(start + i64::from(count))
+            })
+            .expect("update closure never returns None");
+        start..start + i64::from(count)
+    }
+}
+
+fn global_arc() -> &'static Arc<NonceManager> {
+    static GLOBAL: OnceLock<Arc<NonceManager>> = OnceLock::new();
+    GLOBAL.get_or_init(|| Arc::new(NonceManager::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use std::time::Duration;
+
+    #[test]
+    fn test_reserve_is_contiguous_and_increasing() {
+        let nm = NonceManager::new();
+        let a = nm.reserve_at(3, 1_000);
+        assert_eq!(a, 1_000..1_003);
+        // Same millisecond: continues after the previous block
+        let b = nm.reserve_at(2, 1_000);
+        assert_eq!(b, 1_003..1_005);
+        // Clock stepped back: still moves forward
+        assert_eq!(nm.reserve_at(1, 500), 1_005..1_006);
+        // Clock jumped ahead: follows it
+        assert_eq!(nm.reserve_at(1, 9_000), 9_000..9_001);
+    }
+
+    #[test]
+    fn test_nonces_follow_clock() {
+        let clock = MockClock::at_unix(1_000);
+        let nm = NonceManager::with_clock(Arc::new(clock.clone()));
+        assert_eq!(nm.next(), 1_000_000);
+        assert_eq!(nm.next(), 1_000_001);
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(nm.reserve(2), 1_002_000..1_002_002);
+    }
+
+    #[test]
+    fn test_reserve_zero_is_empty() {
+        let nm = NonceManager::new();
+        assert!(nm.reserve(0).is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_reserves_do_not_overlap() {
+        let nm = NonceManager::new();
+        let (a, b) = std::thread::scope(|s| {
+            let a = s.spawn(|| (0..100).map(|_| nm.reserve(10)).collect::<Vec<_>>());
+            let b = s.spawn(|| (0..100).map(|_| nm.reserve(10)).collect::<Vec<_>>());
+            (a.join().unwrap(), b.join().unwrap())
+        });
+
+        let mut all: Vec<Range<i64>> = a.into_iter().chain(b).collect();
+        all.sort_by_key(|r| r.start);
+        for pair in all.windows(2) {
+            assert!(
+                pair[0].end <= pair[1].start,
+                "{:?} overlaps {:?}",
+                pair[0],
+                pair[1]
+            );
+        }
+        assert!(all.iter().all(|r| r.end - r.start == 10));
+    }
+}
@@ -0,0 +1,166 @@
+//! Uniform results for write operations (posts, comments, votes).
+//!
+//! Every platform acknowledges a write differently: some return the created
+//! object, some wrap it in `data` or `post`, some only return `{"ok": true}`.
+//! [`OpResult`] pulls out what agents care about — did it work, and what was
+//! created — while keeping the raw response for anything else.
+
+use serde::{Deserialize, Serialize};
+
+/// Keys that hold the created resource's ID, most specific first.
+const ID_KEYS: &[&str] = &[
+    "id",
+    "post_id",
+    "comment_id",
+    "thread_id",
+    "story_id",
+    "task_id",
+];
+
+/// Keys that hold a link to the created resource.
+const URL_KEYS: &[&str] = &["url", "permalink", "link", "html_url"];
+
+/// Objects that platforms wrap the created resource in.
+const WRAPPER_KEYS: &[&str] = &[
+    "data", "post", "comment", "thread", "story", "task", "result",
+];
+
+/// Normalized outcome of a post, comment, or vote.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpResult {
+    pub platform: String,
+    pub success: bool,
+    /// ID of the created resource, if the platform returned one.
+    pub id: Option<String>,
+    /// Absolute URL of the created resource, if the platform returned one.
+    pub url: Option<String>,
+    /// The platform's response, unchanged.
+    pub raw: serde_json::Value,
+}
+
+impl OpResult {
+    /// Normalize a successful (2xx) response from `platform`.
+    ///
+    /// `success` is false only if the body itself reports failure through an
+    /// `ok`/`success` flag of `false` or an `error` field. Relative URLs are
+    /// resolved against `base_url`.
+    pub fn from_response(platform: &str, base_url: &str, raw: serde_json::Value) -> Self {
+        let flag = ["ok", "success"].iter().find_map(|k| raw[*k].as_bool());
+        let has_error = !raw["error"].is_null() && raw["error"] != false;
+        let success = flag.unwrap_or(!has_error);
+
+        let id = find(&raw, ID_KEYS).and_then(id_string);
+        let url = find(&raw, URL_KEYS)
+            .and_then(|v| v.as_str())
+            .map(|u| absolute_url(base_url, u));
+
+        Self {
+            platform: platform.to_string(),
+            success,
+            id,
+            url,
+            raw,
+        }
+    }
+}
+
+/// First of `keys` present at the top level, then inside a wrapper object.
+fn find<'a>(raw: &'a serde_json::Value, keys: &[&str]) -> Option<&'a serde_json::Value> {
+    let lookup = |obj: &'a serde_json::Value| keys.iter().map(|k| &obj[*k]).find(|v| !v.is_null());
+    lookup(raw).or_else(|| {
+        WRAPPER_KEYS
+            .iter()
+            .map(|w| &raw[*w])
+            .filter(|v| v.is_object())
+            .find_map(lookup)
+    })
+}
+
+/// IDs come back as strings or numbers depending on the platform.
+fn id_string(v: &serde_json::Value) -> Option<String> {
+    match v {
+        serde_json::Value::String(s) if !s.is_empty() => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn absolute_url(base_url: &str, url: &str) -> String {
+    if url.starts_with('/') {
+        format!("{}{}", base_url.trim_end_matches('/'), url)
+    } else {
+        url.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_top_level_id_and_url() {
+        // ClawNews-style: the created story at the top level
+        let op = OpResult::from_response(
+            "clawnews",
+            "https://clawnews.io",
+            json!({ "id": 4812, "url": "https://clawnews.io/s/4812", "title": "t" }),
+        );
+        assert!(op.success);
+        assert_eq!(op.id.as_deref(), Some("4812"));
+        assert_eq!(op.url.as_deref(), Some("https://clawnews.io/s/4812"));
+        assert_eq!(op.raw["title"], "t");
+    }
+
+    #[test]
+    fn test_wrapped_resource_with_relative_url() {
+        // Moltbook-style: `{ success, post: { id, permalink } }`
+        let op = OpResult::from_response(
+            "moltbook",
+            "https://www.moltbook.com/",
+            json!({ "success": true, "post": { "id": "p_9a", "permalink": "/m/tech/p_9a" } }),
+        );
+        assert!(op.success);
+        assert_eq!(op.id.as_deref(), Some("p_9a"));
+        assert_eq!(
+            op.url.as_deref(),
+            Some("https://www.moltbook.com/m/tech/p_9a")
+        );
+    }
+
+    #[test]
+    fn test_specific_id_key() {
+        // BoTTube comment response
+        let op = OpResult::from_response(
+            "bottube",
+            "https://bottube.ai",
+            json!({ "ok": true, "comment_id": 77 }),
+        );
+        assert!(op.success);
+        assert_eq!(op.id.as_deref(), Some("77"));
+        assert_eq!(op.url, None);
+    }
+
+    #[test]
+    fn test_acknowledgement_only() {
+        let op = OpResult::from_response("bottube", "https://bottube.ai", json!({ "ok": true }));
+        assert!(op.success);
+        assert_eq!(op.id, None);
+    }
+
+    #[test]
+    fn test_body_reported_failure() {
+        let op = OpResult::from_response(
+            "4claw",
+            "https://www.4claw.org",
+            json!({ "error": "rate limited" }),
+        );
+        assert!(!op.success);
+        let op = OpResult::from_response(
+            "4claw",
+            "https://www.4claw.org",
+            json!({ "success": false }),
+        );
+        assert!(!op.success);
+    }
+}
@@ -0,0 +1,338 @@
+//! Local queue of signed transfers awaiting confirmation.
+//!
+//! A signed transfer is queued before it is broadcast, so a network failure
+//! doesn't lose it, and stays queued until the node confirms it.
+//! [`Outbox::flush`] rebroadcasts whatever hasn't been accepted yet. A
+//! rebroadcast sends the original signed payload unchanged — same nonce,
+//! same signature — so the node sees a repeat of one transfer, never a
+//! second spend.
+
+use crate::client::{ConfirmationStatus, RustChainClient};
+use crate::clock::{self, Clock};
+use crate::error::ClawRtcResult;
+use crate::store::EncryptedStore;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// Serializes read-modify-write cycles on outbox files within the process.
+static UPDATE_LOCK: Mutex<()> = Mutex::new(());
+
+/// A signed transfer in the outbox.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedTransfer {
+    /// The signed payload, exactly as first signed.
+    pub payload: serde_json::Value,
+    /// Hash the node returned on accepting the transfer; `None` until then.
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+    /// Unix time (seconds) the transfer was queued.
+    pub queued_at: i64,
+    /// Broadcasts and status checks tried by [`Outbox::flush`].
+    #[serde(default)]
+    pub attempts: u32,
+    /// Why the last attempt failed.
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl QueuedTransfer {
+    /// The payload's signature, which identifies the transfer in the queue.
+    pub fn signature(&self) -> &str {
+        self.payload["signature"].as_str().unwrap_or_default()
+    }
+}
+
+/// What [`Outbox::flush`] did with each queued transfer.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FlushReport {
+    /// Confirmed on-chain and removed.
+    pub confirmed: Vec<QueuedTransfer>,
+    /// Rejected by the node or failed on-chain, and removed.
+    pub failed: Vec<QueuedTransfer>,
+    /// Still queued, awaiting broadcast or confirmation.
+    pub pending: Vec<QueuedTransfer>,
+}
+
+/// A JSON file of [`QueuedTransfer`]s.
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    store: EncryptedStore,
+    clock: Arc<dyn Clock>,
+}
+
+impl Outbox {
+    /// An outbox kept as plaintext JSON at `path`.
+    ///
+    /// Queued payloads are already signed and hold no key material.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self::with_store(EncryptedStore::plaintext(path))
+    }
+
+    /// An outbox in `store`, e.g. one encrypted with the wallet password.
+    pub fn with_store(store: EncryptedStore) -> Self {
+        Self {
+            store,
+            clock: clock::system(),
+        }
+    }
+
+    /// Stamp queued transfers with the time from `clock`.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// `~/.clawrtc/outbox.json`.
+    pub fn default_path() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".clawrtc")
+            .join("outbox.json")
+    }
+
+    /// The file backing this outbox.
+    pub fn path(&self) -> &Path {
+        self.store.path()
+    }
+
+    /// Every queued transfer, oldest first.
+    pub fn entries(&self) -> ClawRtcResult<Vec<QueuedTransfer>> {
+        Ok(self.store.load()?.unwrap_or_default())
+    }
+
+    /// Queue a signed transfer. Queuing the same payload twice is a no-op.
+    pub fn push(&self, payload: &serde_json::Value) -> ClawRtcResult<()> {
+        let entry = QueuedTransfer {
+            payload: payload.clone(),
+            tx_hash: None,
+            queued_at: self.clock.now().timestamp(),
+            attempts: 0,
+            last_error: None,
+        };
+        self.update(|entries| {
+            if !entries.iter().any(|e| e.signature() == entry.signature()) {
+                entries.push(entry);
+            }
+        })
+    }
+
+    /// Record that the node accepted the transfer signed with `signature`.
+    pub fn mark_sent(&self, signature: &str, tx_hash: &str) -> ClawRtcResult<()> {
+        self.update(|entries| {
+            if let Some(entry) = entries.iter_mut().find(|e| e.signature() == signature) {
+                entry.tx_hash = Some(tx_hash.to_string());
+            }
+        })
+    }
+
+    /// Drop the transfer signed with `signature`. Returns whether it was queued.
+    pub fn remove(&self, signature: &str) -> ClawRtcResult<bool> {
+        self.update(|entries| {
+            let before = entries.len();
+            entries.retain(|e| e.signature() != signature);
+            entries.len() != before
+        })
+    }
+
+    /// Broadcast each transfer the node hasn't accepted yet and check the
+    /// status of the rest.
+    ///
+    /// Confirmed transfers are removed, as are ones the node rejects
+    /// (`ok: false` or a 4xx status other than 408 and 429) or reports
+    /// failed. Network errors and other HTTP statuses leave a transfer queued
+    /// for the next flush, with the error recorded.
+    pub async fn flush(&self, client: &RustChainClient) -> ClawRtcResult<FlushReport> {
+        let mut report = FlushReport::default();
+        for mut entry in self.entries()? {
+            entry.attempts += 1;
+            entry.last_error = None;
+            if entry.tx_hash.is_none() {
+                debug!(
+                    signature = entry.signature(),
+                    "Rebroadcasting queued transfer"
+                );
+                match client.transfer_signed_final(&entry.payload).await {
+                    Ok(resp) if resp.ok => entry.tx_hash = resp.tx_hash,
+                    Ok(resp) => {
+                        entry.last_error =
+                            Some(resp.error.unwrap_or_else(|| "rejected by node".to_string()));
+                        report.failed.push(entry);
+                        continue;
+                    }
+                    Err(e) => {
+                        entry.last_error = Some(e.to_string());
+                        report.pending.push(entry);
+                        continue;
+                    }
+                }
+            }
+
+            // Accepted without a hash: nothing left to track
+            let Some(tx_hash) = entry.tx_hash.clone() else {
+                report.confirmed.push(entry);
+                continue;
+            };
+            match client.transaction_status(&tx_hash).await {
+                Ok(ConfirmationStatus::Confirmed) => report.confirmed.push(entry),
+                Ok(ConfirmationStatus::Failed) => {
+                    entry.last_error = Some("failed on-chain".to_string());
+                    report.failed.push(entry);
+                }
+                Ok(ConfirmationStatus::Pending) => report.pending.push(entry),
+                Err(e) => {
+                    entry.last_error = Some(e.to_string());
+                    report.pending.push(entry);
+                }
+            }
+        }
+
+        // Merge into the file as it is now, keeping anything queued meanwhile
+        self.update(|entries| {
+            entries.retain(|e| {
+                !report
+                    .confirmed
+                    .iter()
+                    .chain(&report.failed)
+                    .any(|done| done.signature() == e.signature())
+            });
+            for entry in entries.iter_mut() {
+                if let Some(updated) = report
+                    .pending
+                    .iter()
+                    .find(|p| p.signature() == entry.signature())
+                {
+                    *entry = updated.clone();
+                }
+            }
+        })?;
+        Ok(report)
+    }
+
+    fn update<R>(&self, f: impl FnOnce(&mut Vec<QueuedTransfer>) -> R) -> ClawRtcResult<R> {
+        let _guard = UPDATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut entries = self.entries()?;
+        let result = f(&mut entries);
+        self.store.save(&entries)?;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amount::Decimal;
+    use crate::clock::MockClock;
+    use crate::test_support::{MockResponse, MockServer};
+    use crate::wallet::RtcWallet;
+
+    const TO: &str = "RTC0123456789abcdef0123456789abcdef01234567";
+
+    #[tokio::test]
+    async fn test_queued_transfer_rebroadcast_until_confirmed() {
+        let dir = tempfile::tempdir().unwrap();
+        let outbox = Outbox::new(dir.path().join("outbox.json"));
+        let payload = RtcWallet::generate()
+            .sign_transaction(TO, "1.5".parse().unwrap(), "rent")
+            .unwrap();
+        outbox.push(&payload).unwrap();
+        outbox.push(&payload).unwrap();
+        assert_eq!(outbox.entries().unwrap().len(), 1);
+
+        let server = MockServer::sequence(vec![
+            MockResponse::json(503, serde_json::json!({ "error": "down" })),
+            MockResponse::json(200, serde_json::json!({ "ok": true, "tx_hash": "abc" })),
+            MockResponse::json(200, serde_json::json!({ "status": "pending" })),
+            MockResponse::json(200, serde_json::json!({ "status": "confirmed" })),
+        ])
+        .await;
+        let client = RustChainClient::new(&server.url());
+
+        // Node down: stays queued with the error
+        let report = outbox.flush(&client).await.unwrap();
+        assert_eq!(report.pending.len(), 1);
+        let queued = &outbox.entries().unwrap()[0];
+        assert_eq!(queued.attempts, 1);
+        assert!(queued.last_error.as_deref().unwrap().contains("503"));
+
+        // Accepted but not yet confirmed
+        let report = outbox.flush(&client).await.unwrap();
+        assert_eq!(report.pending[0].tx_hash.as_deref(), Some("abc"));
+        assert_eq!(outbox.entries().unwrap()[0].tx_hash.as_deref(), Some("abc"));
+
+        // Confirmed: removed without another broadcast
+        let report = outbox.flush(&client).await.unwrap();
+        assert_eq!(report.confirmed.len(), 1);
+        assert!(outbox.entries().unwrap().is_empty());
+
+        let requests = server.requests();
+        let paths: Vec<&str> = requests.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "/wallet/transfer/signed",
+                "/wallet/transfer/signed",
+                "/api/transactions/abc",
+                "/api/transactions/abc",
+            ]
+        );
+        // Rebroadcast carries the original nonce and signature
+        assert_eq!(requests[1].json(), payload);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_transfer_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let outbox = Outbox::new(dir.path().join("outbox.json"));
+        let payload = RtcWallet::generate()
+            .sign_transaction(TO, Decimal::ONE, "")
+            .unwrap();
+        outbox.push(&payload).unwrap();
+
+        let server = MockServer::sequence(vec![MockResponse::json(
+            200,
+            serde_json::json!({ "ok": false, "error": "nonce already used" }),
+        )])
+        .await;
+        let report = outbox
+            .flush(&RustChainClient::new(&server.url()))
+            .await
+            .unwrap();
+        assert_eq!(
+            report.failed[0].last_error.as_deref(),
+            Some("nonce already used")
+        );
+        assert!(outbox.entries().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_client_error_status_fails_transfer() {
+        let dir = tempfile::tempdir().unwrap();
+        let clock = Arc::new(MockClock::at_unix(1_700_000_000));
+        let outbox = Outbox::new(dir.path().join("outbox.json")).with_clock(clock);
+        let wallet = RtcWallet::generate();
+        let rejected = wallet.sign_transaction(TO, Decimal::ONE, "").unwrap();
+        let throttled = wallet.sign_transaction(TO, Decimal::TWO, "").unwrap();
+        outbox.push(&rejected).unwrap();
+        outbox.push(&throttled).unwrap();
+        assert_eq!(outbox.entries().unwrap()[0].queued_at, 1_700_000_000);
+
+        let server = MockServer::sequence(vec![
+            MockResponse::json(400, serde_json::json!({ "error": "bad signature" })),
+            MockResponse::json(429, serde_json::json!({ "error": "slow down" })),
+        ])
+        .await;
+        let report = outbox
+            .flush(&RustChainClient::new(&server.url()))
+            .await
+            .unwrap();
+        assert_eq!(
+            report.failed[0].last_error.as_deref(),
+            Some("bad signature")
+        );
+        let throttled_error = report.pending[0].last_error.as_deref().unwrap();
+        assert!(throttled_error.contains("429"), "{throttled_error}");
+        assert_eq!(outbox.entries().unwrap(), report.pending);
+    }
+}
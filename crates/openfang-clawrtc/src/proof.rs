@@ -0,0 +1,174 @@
+//! Proof of content — wallet-signed attribution for posted content.
+//!
+//! A post is signed with the author's wallet and a one-line footer carrying the
+//! signature, public key, and RTC address is appended:
+//!
+//! ```text
+//! Hello from my agent!
+//!
+//! [rtc-proof:v1 address=RTC… pubkey=… sig=…]
+//! ```
+//!
+//! Anyone can check the footer with [`verify_footer`]: the signature must
+//! verify against the content above it, and the address must derive from the
+//! embedded public key.
+
+use crate::address;
+use crate::error::{ClawRtcError, ClawRtcResult};
+use crate::wallet::{self, RtcWallet};
+use serde::{Deserialize, Serialize};
+
+/// Opening marker of the proof footer.
+const FOOTER_MARKER: &str = "[rtc-proof:v1 ";
+
+/// Signature, public key, and address attached to a piece of content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentProof {
+    pub address: String,
+    pub public_key: String,
+    pub signature: String,
+}
+
+/// Content whose embedded proof has been verified.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedContent {
+    /// The content the signature covers (footer removed).
+    pub content: String,
+    pub proof: ContentProof,
+}
+
+/// Sign `content` with `wallet`.
+///
+/// Trailing whitespace is not signed, since platforms commonly strip it.
+pub fn sign_content(wallet: &RtcWallet, content: &str) -> ContentProof {
+    ContentProof {
+        address: wallet.address().to_string(),
+        public_key: wallet.public_key_hex(),
+        signature: wallet.sign(content.trim_end().as_bytes()),
+    }
+}
+
+/// Format the proof footer line.
+pub fn format_footer(proof: &ContentProof) -> String {
+    format!(
+        "{FOOTER_MARKER}address={} pubkey={} sig={}]",
+        proof.address, proof.public_key, proof.signature
+    )
+}
+
+/// Sign `content` and return it with the proof footer appended.
+pub fn append_proof(wallet: &RtcWallet, content: &str) -> (String, ContentProof) {
+    let proof = sign_content(wallet, content);
+    let signed = format!("{}\n\n{}", content.trim_end(), format_footer(&proof));
+    (signed, proof)
+}
+
+/// Extract and verify the proof footer from posted text.
+///
+/// Fails if there is no footer, it is malformed, the address does not match
+/// the public key, or the signature does not cover the content.
+pub fn verify_footer(text: &str) -> ClawRtcResult<VerifiedContent> {
+    let text = text.trim_end();
+    let start = text
+        .rfind(FOOTER_MARKER)
+        .ok_or_else(|| ClawRtcError::Crypto("No rtc-proof footer found".to_string()))?;
+    let footer = text[start + FOOTER_MARKER.len()..]
+        .strip_suffix(']')
+        .ok_or_else(|| ClawRtcError::Crypto("Malformed rtc-proof footer".to_string()))?;
+
+    let mut address = None;
+    let mut public_key = None;
+    let mut signature = None;
+    for field in footer.split_whitespace() {
+        match field.split_once('=') {
+            Some(("address", v)) => address = Some(v.to_string()),
+            Some(("pubkey", v)) => public_key = Some(v.to_string()),
+            Some(("sig", v)) => signature = Some(v.to_string()),
+            _ => {}
+        }
+    }
+    let missing = |name: &str| ClawRtcError::Crypto(format!("rtc-proof footer missing {name}"));
+    let proof = ContentProof {
+        address: address.ok_or_else(|| missing("address"))?,
+        public_key: public_key.ok_or_else(|| missing("pubkey"))?,
+        signature: signature.ok_or_else(|| missing("sig"))?,
+    };
+
+    let verifying_key = wallet::parse_public_key(&proof.public_key)?;
+    if address::derive_address(&verifying_key) != proof.address {
+        return Err(ClawRtcError::Crypto(
+            "rtc-proof address does not match public key".to_string(),
+        ));
+    }
+
+    let content = text[..start].trim_end().to_string();
+    if !wallet::verify_signature(&proof.public_key, content.as_bytes(), &proof.signature)? {
+        return Err(ClawRtcError::Crypto(
+            "rtc-proof signature does not match content".to_string(),
+        ));
+    }
+
+    Ok(VerifiedContent { content, proof })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_footer_format() {
+        let w = RtcWallet::generate();
+        let (signed, proof) = append_proof(&w, "Hello agents\n");
+        let expected = format!(
+            "Hello agents\n\n[rtc-proof:v1 address={} pubkey={} sig={}]",
+            w.address(),
+            w.public_key_hex(),
+            proof.signature
+        );
+        assert_eq!(signed, expected);
+        assert_eq!(proof.signature.len(), 128);
+    }
+
+    #[test]
+    fn test_roundtrip_verifies() {
+        let w = RtcWallet::generate();
+        let content = "Multi-line post\n\nwith [brackets] and rtc-proof text";
+        let (signed, proof) = append_proof(&w, content);
+        let verified = verify_footer(&signed).unwrap();
+        assert_eq!(verified.content, content);
+        assert_eq!(verified.proof, proof);
+    }
+
+    #[test]
+    fn test_verify_tolerates_trailing_whitespace() {
+        let w = RtcWallet::generate();
+        let (signed, _) = append_proof(&w, "Hello");
+        assert!(verify_footer(&format!("{signed}\n  ")).is_ok());
+    }
+
+    #[test]
+    fn test_tampered_content_rejected() {
+        let w = RtcWallet::generate();
+        let (signed, _) = append_proof(&w, "Send 1 RTC");
+        let tampered = signed.replacen("1 RTC", "9 RTC", 1);
+        let err = verify_footer(&tampered).unwrap_err().to_string();
+        assert!(err.contains("signature"), "{err}");
+    }
+
+    #[test]
+    fn test_mismatched_address_rejected() {
+        let author = RtcWallet::generate();
+        let other = RtcWallet::generate();
+        let (signed, _) = append_proof(&author, "Hello");
+        let forged = signed.replace(author.address(), other.address());
+        let err = verify_footer(&forged).unwrap_err().to_string();
+        assert!(err.contains("address"), "{err}");
+    }
+
+    #[test]
+    fn test_missing_or_malformed_footer() {
+        assert!(verify_footer("just a post").is_err());
+        assert!(verify_footer("post\n\n[rtc-proof:v1 address=RTC123").is_err());
+        assert!(verify_footer("post\n\n[rtc-proof:v1 address=RTC123]").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! Transfer receipts — wallet-signed proof that a transfer was authorized.
+//!
+//! A receipt records the hash of a signed transfer together with when it was
+//! signed, the sender, recipient and amount, and is itself signed by the
+//! sending wallet. It is kept locally as non-repudiable evidence that the
+//! wallet authorized the transfer, whether or not the node ever confirms it.
+//!
+//! The `tx_hash` is the hex SHA-256 of the exact message signed in the
+//! transfer payload, so anyone holding the payload (including the node) can
+//! recompute it with [`transaction_hash`] and check the receipt against it.
+
+use crate::address;
+use crate::amount::{self, Decimal};
+use crate::error::{ClawRtcError, ClawRtcResult};
+use crate::wallet::{self, RtcWallet};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A signed record of an authorized transfer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferReceipt {
+    pub tx_hash: String,
+    /// RFC 3339 time the receipt was issued.
+    pub timestamp: String,
+    pub from: String,
+    pub to: String,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub amount_rtc: Decimal,
+    pub public_key: String,
+    pub signature: String,
+}
+
+impl TransferReceipt {
+    /// Issue a receipt for `signed_tx`, a payload from
+    /// [`RtcWallet::sign_transaction`] signed by `wallet`.
+    pub(crate) fn issue(wallet: &RtcWallet, signed_tx: &serde_json::Value) -> ClawRtcResult<Self> {
+        let mut receipt = Self {
+            tx_hash: transaction_hash(signed_tx)?,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            from: wallet.address().to_string(),
+            to: field_str(signed_tx, "to_address")?.to_string(),
+            amount_rtc: field_amount(signed_tx, "amount_rtc")?,
+            public_key: wallet.public_key_hex(),
+            signature: String::new(),
+        };
+        receipt.signature = wallet.sign(receipt.message()?.as_bytes());
+        Ok(receipt)
+    }
+
+    /// The canonical message covered by the receipt signature.
+    fn message(&self) -> ClawRtcResult<String> {
+        Ok(serde_json::to_string(&serde_json::json!({
+            "tx_hash": self.tx_hash,
+            "timestamp": self.timestamp,
+            "from": self.from,
+            "to": self.to,
+            "amount": amount::to_f64(self.amount_rtc),
+        }))?)
+    }
+
+    /// Check that the sender address derives from the public key and the
+    /// signature covers the receipt fields.
+    pub fn verify(&self) -> ClawRtcResult<()> {
+        let key = wallet::parse_public_key(&self.public_key)?;
+        if address::derive_address(&key) != self.from {
+            return Err(ClawRtcError::Crypto(
+                "Receipt address does not match its public key".to_string(),
+            ));
+        }
+        if !wallet::verify_signature(
+            &self.public_key,
+            self.message()?.as_bytes(),
+            &self.signature,
+        )? {
+            return Err(ClawRtcError::Crypto(
+                "Receipt signature is invalid".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether this receipt describes `signed_tx`: same hash, sender,
+    /// recipient and amount.
+    pub fn matches(&self, signed_tx: &serde_json::Value) -> bool {
+        transaction_hash(signed_tx).is_ok_and(|h| h == self.tx_hash)
+            && signed_tx["from_address"] == self.from.as_str()
+            && signed_tx["to_address"] == self.to.as_str()
+            && field_amount(signed_tx, "amount_rtc").is_ok_and(|a| a == self.amount_rtc)
+    }
+}
+
+/// Hex SHA-256 of the message signed in a transfer payload.
+pub fn transaction_hash(signed_tx: &serde_json::Value) -> ClawRtcResult<String> {
+    let message = wallet::transfer_message(
+        field_str(signed_tx, "from_address")?,
+        field_str(signed_tx, "to_address")?,
+        field_amount(signed_tx, "amount_rtc")?,
+        field_str(signed_tx, "memo")?,
+        signed_tx["nonce"]
+            .as_i64()
+            .ok_or_else(|| missing_field("nonce"))?,
+    );
+    let canonical = wallet::canonical_json(&message);
+    Ok(hex::encode(Sha256::digest(canonical.as_bytes())))
+}
+
+fn field_str<'a>(tx: &'a serde_json::Value, name: &str) -> ClawRtcResult<&'a str> {
+    tx[name].as_str().ok_or_else(|| missing_field(name))
+}
+
+fn field_amount(tx: &serde_json::Value, name: &str) -> ClawRtcResult<Decimal> {
+    tx[name]
+        .as_f64()
+        .and_then(amount::from_f64)
+        .ok_or_else(|| missing_field(name))
+}
+
+fn missing_field(name: &str) -> ClawRtcError {
+    ClawRtcError::Crypto(format!("Transfer payload is missing {name}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TO: &str = "RTCdeadbeef00000000000000000000000000000000";
+
+    #[test]
+    fn test_receipt_verifies_and_binds_to_payload() {
+        let w = RtcWallet::generate();
+        let amount: Decimal = "12.5".parse().unwrap();
+        let (tx, receipt) = w.sign_transaction_with_receipt(TO, amount, "rent").unwrap();
+
+        receipt.verify().unwrap();
+        assert!(receipt.matches(&tx));
+        assert_eq!(receipt.from, w.address());
+        assert_eq!(receipt.to, TO);
+        assert_eq!(receipt.amount_rtc, amount);
+        assert_eq!(receipt.public_key, tx["public_key"]);
+
+        // The hash covers exactly what the payload signature covers
+        let message = wallet::transfer_message(
+            w.address(),
+            TO,
+            amount,
+            "rent",
+            tx["nonce"].as_i64().unwrap(),
+        );
+        let canonical = wallet::canonical_json(&message);
+        assert!(wallet::verify_signature(
+            &receipt.public_key,
+            canonical.as_bytes(),
+            tx["signature"].as_str().unwrap()
+        )
+        .unwrap());
+        assert_eq!(
+            receipt.tx_hash,
+            hex::encode(Sha256::digest(canonical.as_bytes()))
+        );
+    }
+
+    #[test]
+    fn test_receipt_does_not_match_other_transfer() {
+        let w = RtcWallet::generate();
+        let (_, receipt) = w
+            .sign_transaction_with_receipt(TO, Decimal::ONE, "")
+            .unwrap();
+        let other = w.sign_transaction(TO, Decimal::ONE, "").unwrap();
+        // Same details, different nonce
+        assert!(!receipt.matches(&other));
+    }
+
+    #[test]
+    fn test_tampered_receipt_fails_verification() {
+        let w = RtcWallet::generate();
+        let (_, receipt) = w
+            .sign_transaction_with_receipt(TO, Decimal::ONE, "")
+            .unwrap();
+
+        let mut amount = receipt.clone();
+        amount.amount_rtc = Decimal::ONE_HUNDRED;
+        assert!(amount.verify().is_err());
+
+        let mut sender = receipt.clone();
+        sender.from = RtcWallet::generate().address().to_string();
+        assert!(sender.verify().is_err());
+    }
+
+    #[test]
+    fn test_transaction_hash_requires_fields() {
+        let err = transaction_hash(&serde_json::json!({ "to_address": TO })).unwrap_err();
+        assert!(err.to_string().contains("from_address"));
+    }
+}
@@ -0,0 +1,259 @@
+//! Per-platform adapters that normalize Grazer discover responses.
+//!
+//! Each platform returns its items under its own key (`data`, `agents`,
+//! `posts`, …). A [`ResponseAdapter`] records where that list lives so the
+//! client can apply the caller's `limit` and hand back a stable shape. When a
+//! platform renames the key, add the new name to the adapter's `aliases` and
+//! bump its `version`: the items are moved back under the canonical key, so
+//! callers keep seeing the same shape.
+//!
+//! Responses that match none of an adapter's keys are passed through
+//! unchanged.
+//!
+//! For platforms that can't sort server-side, [`ResponseAdapter::normalize_sorted`]
+//! orders the items by the first timestamp or score field they carry.
+
+use crate::grazer::{DiscoverSort, Platform};
+use serde::Serialize;
+use tracing::debug;
+
+/// Where a platform's discover response keeps its list of items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ResponseAdapter {
+    /// Bumped whenever the adapter changes; included in logs.
+    pub version: u32,
+    /// Canonical key of the items array, or `None` for a bare top-level array.
+    pub items_key: Option<&'static str>,
+    /// Other keys the platform has used for the same array.
+    pub aliases: &'static [&'static str],
+}
+
+impl ResponseAdapter {
+    const fn keyed(items_key: &'static str) -> Self {
+        Self {
+            version: 1,
+            items_key: Some(items_key),
+            aliases: &[],
+        }
+    }
+
+    /// The built-in adapter for `platform`.
+    pub fn for_platform(platform: Platform) -> Self {
+        match platform {
+            Platform::Bottube => Self::keyed("videos"),
+            Platform::Moltbook => Self::keyed("posts"),
+            Platform::FourClaw => Self::keyed("threads"),
+            Platform::Clawhub => Self::keyed("skills"),
+            Platform::Pinchedin => Self::keyed("posts"),
+            Platform::Agentchan => Self::keyed("data"),
+            Platform::Clawsta => Self::keyed("posts"),
+            Platform::Clawnews => Self::keyed("stories"),
+            Platform::Clawtasks => Self::keyed("bounties"),
+            Platform::Swarmhub => Self::keyed("agents"),
+            Platform::Directory => Self::keyed("services"),
+            Platform::Clawcities => Self::keyed("sites"),
+        }
+    }
+
+    /// Move the items under the canonical key and truncate them to `limit`.
+    pub fn normalize(
+        &self,
+        platform: Platform,
+        raw: serde_json::Value,
+        limit: u32,
+    ) -> serde_json::Value {
+        self.normalize_sorted(platform, raw, limit, DiscoverSort::Trending)
+    }
+
+    /// Like [`ResponseAdapter::normalize`], but order the items by `sort`
+    /// before truncating. [`DiscoverSort::Trending`] keeps the platform's
+    /// order.
+    pub fn normalize_sorted(
+        &self,
+        platform: Platform,
+        mut raw: serde_json::Value,
+        limit: u32,
+        sort: DiscoverSort,
+    ) -> serde_json::Value {
+        let items = match self.items_key {
+            None => raw.as_array_mut(),
+            Some(key) => {
+                if let Some(obj) = raw.as_object_mut() {
+                    if !obj.get(key).is_some_and(|v| v.is_array()) {
+                        let alias = self
+                            .aliases
+                            .iter()
+                            .find(|a| obj.get(**a).is_some_and(|v| v.is_array()));
+                        if let Some(items) = alias.and_then(|a| obj.remove(*a)) {
+                            obj.insert(key.to_string(), items);
+                        }
+                    }
+                }
+                raw.get_mut(key).and_then(|v| v.as_array_mut())
+            }
+        };
+
+        match items {
+            Some(items) => {
+                sort_items(items, sort);
+                items.truncate(limit as usize);
+                debug!(
+                    ?platform,
+                    adapter_version = self.version,
+                    "Normalized discover response"
+                );
+            }
+            None => debug!(
+                ?platform,
+                adapter_version = self.version,
+                "No items array found; passing response through"
+            ),
+        }
+        raw
+    }
+}
+
+/// Item fields holding a creation time, most specific first.
+const TIMESTAMP_FIELDS: &[&str] = &[
+    "created_at",
+    "published_at",
+    "posted_at",
+    "timestamp",
+    "created",
+    "time",
+];
+
+/// Item fields holding a popularity score, most specific first.
+const SCORE_FIELDS: &[&str] = &[
+    "score",
+    "votes",
+    "upvotes",
+    "points",
+    "likes",
+    "views",
+    "downloads",
+];
+
+/// Read a sort key: a number, a numeric string, or an RFC 3339 timestamp
+/// (as Unix milliseconds).
+fn sort_key(item: &serde_json::Value, fields: &[&str]) -> Option<f64> {
+    fields.iter().find_map(|field| match &item[*field] {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse().ok().or_else(|| {
+            chrono::DateTime::parse_from_rfc3339(s.trim())
+                .ok()
+                .map(|t| t.timestamp_millis() as f64)
+        }),
+        _ => None,
+    })
+}
+
+/// Order `items` by `sort`, highest key first. Items without a key keep their
+/// relative order after the rest.
+fn sort_items(items: &mut [serde_json::Value], sort: DiscoverSort) {
+    let fields = match sort {
+        DiscoverSort::Trending => return,
+        DiscoverSort::Newest => TIMESTAMP_FIELDS,
+        DiscoverSort::Top => SCORE_FIELDS,
+    };
+    items.sort_by(|a, b| match (sort_key(a, fields), sort_key(b, fields)) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_truncates_canonical_key() {
+        let adapter = ResponseAdapter::for_platform(Platform::Swarmhub);
+        let out = adapter.normalize(
+            Platform::Swarmhub,
+            json!({ "agents": [1, 2, 3, 4], "total": 4 }),
+            2,
+        );
+        assert_eq!(out, json!({ "agents": [1, 2], "total": 4 }));
+    }
+
+    #[test]
+    fn test_renamed_key_passes_through_until_adapter_updated() {
+        // The platform renamed `data` to `results`
+        let changed = json!({ "results": [{ "id": 1 }, { "id": 2 }, { "id": 3 }] });
+
+        let v1 = ResponseAdapter::for_platform(Platform::Agentchan);
+        let out = v1.normalize(Platform::Agentchan, changed.clone(), 2);
+        assert_eq!(out, changed);
+
+        let v2 = ResponseAdapter {
+            version: 2,
+            aliases: &["results"],
+            ..v1
+        };
+        let out = v2.normalize(Platform::Agentchan, changed, 2);
+        assert_eq!(out, json!({ "data": [{ "id": 1 }, { "id": 2 }] }));
+    }
+
+    #[test]
+    fn test_canonical_key_wins_over_alias() {
+        let adapter = ResponseAdapter {
+            version: 2,
+            items_key: Some("data"),
+            aliases: &["results"],
+        };
+        let out = adapter.normalize(
+            Platform::Agentchan,
+            json!({ "data": [1, 2, 3], "results": "meta" }),
+            1,
+        );
+        assert_eq!(out, json!({ "data": [1], "results": "meta" }));
+    }
+
+    #[test]
+    fn test_top_level_array() {
+        let adapter = ResponseAdapter {
+            version: 1,
+            items_key: None,
+            aliases: &[],
+        };
+        let out = adapter.normalize(Platform::Directory, json!([1, 2, 3]), 2);
+        assert_eq!(out, json!([1, 2]));
+    }
+
+    #[test]
+    fn test_sort_before_truncate() {
+        let adapter = ResponseAdapter::for_platform(Platform::Clawtasks);
+        let raw = json!({ "bounties": [
+            { "id": 1, "created": 100, "points": "7" },
+            { "id": 2, "created": 300, "points": 3 },
+            { "id": 3, "created": 200 },
+        ] });
+        let ids = |out: serde_json::Value| -> Vec<serde_json::Value> {
+            out["bounties"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|b| b["id"].clone())
+                .collect()
+        };
+        let newest =
+            adapter.normalize_sorted(Platform::Clawtasks, raw.clone(), 2, DiscoverSort::Newest);
+        assert_eq!(ids(newest), [json!(2), json!(3)]);
+        let top = adapter.normalize_sorted(Platform::Clawtasks, raw, 3, DiscoverSort::Top);
+        assert_eq!(ids(top), [json!(1), json!(2), json!(3)]);
+    }
+
+    #[test]
+    fn test_non_matching_shape_is_unchanged() {
+        let adapter = ResponseAdapter::for_platform(Platform::Clawcities);
+        let note = json!({ "platform": "clawcities", "note": "n/a" });
+        assert_eq!(
+            adapter.normalize(Platform::Clawcities, note.clone(), 1),
+            note
+        );
+    }
+}
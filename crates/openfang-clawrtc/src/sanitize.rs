@@ -0,0 +1,118 @@
+//! Cleaning of untrusted platform text before it reaches an agent or terminal.
+//!
+//! Discovered posts can carry ANSI escape sequences, raw control characters,
+//! and invisible zero-width or bidi-override characters. None of them render
+//! as visible text, but they can rewrite a terminal's display or hide
+//! instructions inside an agent's context. [`sanitize_text`] strips them;
+//! newlines and tabs are kept.
+
+/// Whether `c` is invisible formatting: zero-width characters, bidi controls,
+/// and the byte-order mark.
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{FEFF}'
+    )
+}
+
+/// Skip the rest of an escape sequence whose `ESC` has been consumed.
+fn skip_escape(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    match chars.next() {
+        // CSI: parameters and intermediates, then one final byte
+        Some('[') => {
+            for c in chars.by_ref() {
+                if ('\u{40}'..='\u{7E}').contains(&c) {
+                    break;
+                }
+            }
+        }
+        // OSC / DCS / APC / PM / SOS: string terminated by BEL or ESC \
+        Some(']' | 'P' | '_' | '^' | 'X') => {
+            while let Some(c) = chars.next() {
+                if c == '\u{07}' {
+                    break;
+                }
+                if c == '\u{1B}' {
+                    chars.next_if_eq(&'\\');
+                    break;
+                }
+            }
+        }
+        // Two-character sequences (e.g. ESC c)
+        _ => {}
+    }
+}
+
+/// Strip ANSI escapes, control characters (other than `\n` and `\t`), and
+/// invisible formatting characters from `text`.
+pub fn sanitize_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{1B}' => skip_escape(&mut chars),
+            '\n' | '\t' => out.push(c),
+            c if c.is_control() || is_invisible(c) => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Apply [`sanitize_text`] to every string in `value`, including object keys.
+pub fn sanitize_value(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(s) => *s = sanitize_text(s),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(sanitize_value),
+        serde_json::Value::Object(map) => {
+            let entries = std::mem::take(map);
+            for (key, mut v) in entries {
+                sanitize_value(&mut v);
+                map.insert(sanitize_text(&key), v);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_ansi_and_zero_width() {
+        let title = "\u{1B}[1;31mFree\u{1B}[0m RTC\u{200B}\u{FEFF} now\u{1B}]0;pwned\u{07}!";
+        assert_eq!(sanitize_text(title), "Free RTC now!");
+    }
+
+    #[test]
+    fn test_keeps_newlines_tabs_and_unicode() {
+        let text = "line one\n\tline two — café 🦀";
+        assert_eq!(sanitize_text(text), text);
+    }
+
+    #[test]
+    fn test_strips_controls_and_bidi_overrides() {
+        assert_eq!(sanitize_text("a\u{0}b\rc\u{7F}d\u{9B}e"), "abcde");
+        assert_eq!(sanitize_text("txt.\u{202E}exe"), "txt.exe");
+        // An OSC terminated by ESC \ rather than BEL
+        assert_eq!(sanitize_text("x\u{1B}]8;;http://evil\u{1B}\\y"), "xy");
+    }
+
+    #[test]
+    fn test_sanitize_value_recurses() {
+        let mut v = serde_json::json!({
+            "posts": [{ "title": "hi\u{200B}", "score": 3 }],
+            "k\u{1B}[2Jey": "v",
+        });
+        sanitize_value(&mut v);
+        assert_eq!(
+            v,
+            serde_json::json!({ "posts": [{ "title": "hi", "score": 3 }], "key": "v" })
+        );
+    }
+}
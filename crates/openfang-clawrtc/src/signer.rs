@@ -0,0 +1,155 @@
+//! Delegated signing for keys kept outside the process.
+//!
+//! [`Signer`] abstracts over where the Ed25519 private key lives.
+//! [`RtcWallet`](crate::RtcWallet) implements it with an in-memory key; a
+//! hardware wallet or remote KMS can implement it too and sign transfers
+//! through [`sign_transaction`] without the key ever reaching this crate.
+
+use crate::address::derive_address;
+use crate::amount::{self, Decimal};
+use crate::error::{ClawRtcError, ClawRtcResult};
+use crate::nonce::NonceManager;
+use crate::wallet::{canonical_json, transfer_message};
+use ed25519_dalek::VerifyingKey;
+
+/// Something that can produce Ed25519 signatures for one key pair.
+pub trait Signer: Send + Sync {
+    /// Sign `message`, returning the 64-byte Ed25519 signature.
+    fn sign(&self, message: &[u8]) -> ClawRtcResult<[u8; 64]>;
+
+    /// The 32-byte Ed25519 public key.
+    fn public_key(&self) -> [u8; 32];
+}
+
+/// RTC address of `signer`'s public key.
+pub fn address_of(signer: &dyn Signer) -> ClawRtcResult<String> {
+    let key = VerifyingKey::from_bytes(&signer.public_key())
+        .map_err(|e| ClawRtcError::Crypto(format!("Invalid public key: {e}")))?;
+    Ok(derive_address(&key))
+}
+
+/// Sign a transfer with `signer`, returning the full signed payload.
+///
+/// The payload is the same as [`RtcWallet::sign_transaction`](crate::RtcWallet::sign_transaction)
+/// produces; the nonce comes from the process-wide [`NonceManager`].
+pub fn sign_transaction(
+    signer: &dyn Signer,
+    to_address: &str,
+    amount_rtc: Decimal,
+    memo: &str,
+) -> ClawRtcResult<serde_json::Value> {
+    let nonce = NonceManager::global().next();
+    sign_transaction_with_nonce(signer, to_address, amount_rtc, memo, nonce)
+}
+
+/// Fails with [`ClawRtcError::InvalidAmount`] if `amount_rtc` has more
+/// digits than the payload's `f64` can carry.
+pub(crate) fn sign_transaction_with_nonce(
+    signer: &dyn Signer,
+    to_address: &str,
+    amount_rtc: Decimal,
+    memo: &str,
+    nonce: i64,
+) -> ClawRtcResult<serde_json::Value> {
+    let wire_amount = amount::to_wire(amount_rtc).ok_or_else(|| {
+        ClawRtcError::InvalidAmount(format!(
+            "{amount_rtc} RTC cannot be signed without rounding"
+        ))
+    })?;
+    let from_address = address_of(signer)?;
+    let payload = transfer_message(&from_address, to_address, amount_rtc, memo, nonce);
+    let canonical = canonical_json(&payload);
+    let signature = signer.sign(canonical.as_bytes())?;
+
+    Ok(serde_json::json!({
+        "from_address": from_address,
+        "to_address": to_address,
+        "amount_rtc": wire_amount,
+        "memo": memo,
+        "nonce": nonce,
+        "signature": hex::encode(signature),
+        "public_key": hex::encode(signer.public_key()),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::{verify_signature, RtcWallet};
+    use ed25519_dalek::{Signer as _, SigningKey};
+
+    const TO: &str = "RTC0123456789abcdef0123456789abcdef01234567";
+
+    /// Stands in for a hardware wallet: holds its own key, exposes only signatures.
+    struct MockSigner {
+        key: SigningKey,
+        fail: bool,
+    }
+
+    impl Signer for MockSigner {
+        fn sign(&self, message: &[u8]) -> ClawRtcResult<[u8; 64]> {
+            if self.fail {
+                return Err(ClawRtcError::Crypto("device unplugged".into()));
+            }
+            Ok(self.key.sign(message).to_bytes())
+        }
+
+        fn public_key(&self) -> [u8; 32] {
+            self.key.verifying_key().to_bytes()
+        }
+    }
+
+    #[test]
+    fn test_external_signer_signs_transactions() {
+        let signer = MockSigner {
+            key: SigningKey::from_bytes(&[7u8; 32]),
+            fail: false,
+        };
+        let amount: Decimal = "2.5".parse().unwrap();
+        let signed = sign_transaction(&signer, TO, amount, "rent").unwrap();
+        assert_eq!(signed["from_address"], address_of(&signer).unwrap());
+        assert_eq!(signed["public_key"], hex::encode(signer.public_key()));
+
+        let message = transfer_message(
+            signed["from_address"].as_str().unwrap(),
+            TO,
+            amount,
+            "rent",
+            signed["nonce"].as_i64().unwrap(),
+        );
+        assert!(verify_signature(
+            signed["public_key"].as_str().unwrap(),
+            canonical_json(&message).as_bytes(),
+            signed["signature"].as_str().unwrap(),
+        )
+        .unwrap());
+
+        // Same key, same payload as a local wallet would produce
+        let wallet = RtcWallet::from_private_key_hex(&hex::encode([7u8; 32])).unwrap();
+        assert_eq!(
+            sign_transaction_with_nonce(&signer, TO, amount, "rent", 42).unwrap(),
+            sign_transaction_with_nonce(&wallet, TO, amount, "rent", 42).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_amount_that_would_round_is_rejected() {
+        let signer = MockSigner {
+            key: SigningKey::from_bytes(&[7u8; 32]),
+            fail: false,
+        };
+        let amount: Decimal = "0.123456789012345678901".parse().unwrap();
+        let err = sign_transaction(&signer, TO, amount, "").unwrap_err();
+        assert!(matches!(err, ClawRtcError::InvalidAmount(_)), "{err}");
+    }
+
+    #[test]
+    fn test_signer_error_is_returned() {
+        let signer = MockSigner {
+            key: SigningKey::from_bytes(&[7u8; 32]),
+            fail: true,
+        };
+        let err = sign_transaction(&signer, TO, Decimal::ONE, "").unwrap_err();
+        assert!(err.to_string().contains("device unplugged"), "{err}");
+    }
+}
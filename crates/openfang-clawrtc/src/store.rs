@@ -0,0 +1,162 @@
+//! Optionally encrypted JSON files for local state under `~/.clawrtc`.
+//!
+//! Contacts, config and miner-state files are plaintext JSON by default.
+//! Users who encrypt their wallet can opt in to encrypting these too with
+//! the same password; the file then holds a sealed envelope (Argon2id +
+//! AES-256-GCM, as in [`Keystore`](crate::keystore::Keystore)) instead of
+//! the data itself.
+
+use crate::error::{ClawRtcError, ClawRtcResult};
+use crate::keystore::{write_private, Sealed};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+/// On-disk format of an encrypted store.
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    encrypted_store: u32,
+    #[serde(flatten)]
+    sealed: Sealed,
+}
+
+/// A JSON file that is encrypted when a password is set.
+#[derive(Clone)]
+pub struct EncryptedStore {
+    path: PathBuf,
+    password: Option<Zeroizing<String>>,
+}
+
+impl std::fmt::Debug for EncryptedStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptedStore")
+            .field("path", &self.path)
+            .field("encrypted", &self.is_encrypted())
+            .finish()
+    }
+}
+
+impl EncryptedStore {
+    /// A store kept as plaintext JSON.
+    pub fn plaintext(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            password: None,
+        }
+    }
+
+    /// A store encrypted with `password`, typically the wallet's keystore
+    /// password.
+    pub fn encrypted(path: impl Into<PathBuf>, password: &str) -> Self {
+        Self {
+            path: path.into(),
+            password: Some(Zeroizing::new(password.to_string())),
+        }
+    }
+
+    /// The file backing this store.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether saves are encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.password.is_some()
+    }
+
+    /// Read the stored value, or `None` if the file does not exist.
+    ///
+    /// Either format is accepted when a password is set, so an existing
+    /// plaintext file can be loaded and re-saved encrypted. An encrypted
+    /// file without a password is an error.
+    pub fn load<T: DeserializeOwned>(&self) -> ClawRtcResult<Option<T>> {
+        let data = match std::fs::read_to_string(&self.path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let value: serde_json::Value = serde_json::from_str(&data)?;
+        if value.get("encrypted_store").is_none() {
+            return Ok(Some(serde_json::from_value(value)?));
+        }
+
+        let envelope: Envelope = serde_json::from_value(value)
+            .map_err(|e| ClawRtcError::KeystoreDecrypt(e.to_string()))?;
+        let password = self.password.as_ref().ok_or_else(|| {
+            ClawRtcError::KeystoreDecrypt(format!(
+                "{} is encrypted; a password is required",
+                self.path.display()
+            ))
+        })?;
+        let plaintext = envelope.sealed.open(password)?;
+        Ok(Some(serde_json::from_slice(&plaintext)?))
+    }
+
+    /// Write `value`, encrypting it if a password is set.
+    pub fn save<T: Serialize>(&self, value: &T) -> ClawRtcResult<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(value)?;
+        let contents = match &self.password {
+            Some(password) => serde_json::to_string_pretty(&Envelope {
+                encrypted_store: 1,
+                sealed: Sealed::seal(json.as_bytes(), password)?,
+            })?,
+            None => json,
+        };
+        write_private(&self.path, &contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn contacts() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("alice".to_string(), format!("RTC{}", "a".repeat(40))),
+            ("bob".to_string(), format!("RTC{}", "b".repeat(40))),
+        ])
+    }
+
+    #[test]
+    fn test_encrypted_contacts_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts.json");
+        let store = EncryptedStore::encrypted(&path, "wallet_password");
+        store.save(&contacts()).unwrap();
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("alice") && !raw.contains("RTC"));
+
+        let loaded: BTreeMap<String, String> = store.load().unwrap().unwrap();
+        assert_eq!(loaded, contacts());
+
+        let wrong = EncryptedStore::encrypted(&path, "other_password");
+        assert!(wrong.load::<BTreeMap<String, String>>().is_err());
+        let plain = EncryptedStore::plaintext(&path);
+        assert!(plain.load::<BTreeMap<String, String>>().is_err());
+    }
+
+    #[test]
+    fn test_plaintext_store_and_upgrade() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts.json");
+        let plain = EncryptedStore::plaintext(&path);
+        assert!(plain.load::<BTreeMap<String, String>>().unwrap().is_none());
+        plain.save(&contacts()).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("alice"));
+
+        let store = EncryptedStore::encrypted(&path, "wallet_password");
+        let loaded: BTreeMap<String, String> = store.load().unwrap().unwrap();
+        store.save(&loaded).unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("alice"));
+        assert_eq!(
+            store.load::<BTreeMap<String, String>>().unwrap().unwrap(),
+            contacts()
+        );
+    }
+}
@@ -1,31 +1,27 @@
-//! OpenFang tool integration — 15 tools for agent use.
+//! OpenFang tool integration — 33 tools for agent use.
 //!
-//! Covers RustChain (8 tools), Grazer (2 tools), BoTTube (3 tools), and ClawHub (1 tool).
+//! Covers RustChain (19 tools), Grazer (5 tools), BoTTube (7 tools), ClawHub (1 tool), and
+//! `clawrtc_help`, which describes the others.
 //! Each tool is registered as a `ToolDefinition` and dispatched via `execute_clawrtc_tool()`.
 
-use crate::bottube::BoTTubeClient;
+use crate::amount::{self, Decimal};
+use crate::bottube::{ChunkedUpload, SearchPage};
 use crate::client::RustChainClient;
+use crate::context::ToolContext;
+use crate::error::ClawRtcError;
 use crate::fingerprint;
-use crate::grazer::{GrazerClient, Platform};
-use crate::hardware::HardwareInfo;
+use crate::grazer::{AgentProfile, DiscoverSort, Platform};
+use crate::miner::AttestationEvidence;
+use crate::op_result::OpResult;
 use crate::wallet::RtcWallet;
 use openfang_types::tool::ToolDefinition;
-use sha2::Digest;
-use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
 
-/// Default wallet directory under ~/.clawrtc/wallets/.
-fn default_wallet_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".clawrtc")
-        .join("wallets")
-        .join("default.json")
-}
-
-/// Return all 15 ClawRTC tool definitions for the OpenFang tool registry.
+/// Return all 33 ClawRTC tool definitions for the OpenFang tool registry.
 pub fn clawrtc_tool_definitions() -> Vec<ToolDefinition> {
     vec![
-        // ─── RustChain tools (8) ─────────────────────────────────────────
+        // ─── RustChain tools (19) ────────────────────────────────────────
         ToolDefinition {
             name: "rustchain_balance".to_string(),
             description: "Check the RTC token balance for a wallet address on the RustChain network.".to_string(),
@@ -68,6 +64,30 @@ pub fn clawrtc_tool_definitions() -> Vec<ToolDefinition> {
                 "required": []
             }),
         },
+        ToolDefinition {
+            name: "rustchain_attest_benchmark".to_string(),
+            description: "Time each phase of a hardware attestation (challenge fetch, entropy collection, fingerprint run, submit) to see whether slow hardware risks nonce expiry.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "node_url": { "type": "string", "description": "RustChain node URL. Default: https://bulbous-bouffant.metalseed.net" },
+                    "submit": { "type": "boolean", "description": "Actually submit the attestation. Default false, which skips the submit phase." },
+                    "fingerprints": { "type": "boolean", "description": "Run the hardware fingerprint checks. Default true." }
+                },
+                "required": []
+            }),
+        },
+        ToolDefinition {
+            name: "rustchain_challenge".to_string(),
+            description: "Fetch an attestation challenge from the node and return the raw nonce and expiry without attesting. Use to check the node's challenge endpoint on its own.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "node_url": { "type": "string", "description": "RustChain node URL. Default: https://bulbous-bouffant.metalseed.net" }
+                },
+                "required": []
+            }),
+        },
         ToolDefinition {
             name: "rustchain_enroll".to_string(),
             description: "Enroll in the current RustChain epoch to earn RTC mining rewards.".to_string(),
@@ -85,37 +105,153 @@ pub fn clawrtc_tool_definitions() -> Vec<ToolDefinition> {
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
-                    "node_url": { "type": "string", "description": "RustChain node URL. Default: https://bulbous-bouffant.metalseed.net" }
+                    "node_url": { "type": "string", "description": "RustChain node URL. Default: https://bulbous-bouffant.metalseed.net" },
+                    "verbosity": { "type": "string", "enum": ["summary", "full"], "description": "summary returns counts and the top few items only; full (default) returns everything." }
                 },
                 "required": []
             }),
         },
         ToolDefinition {
             name: "rustchain_fingerprint".to_string(),
-            description: "Run all 6 RIP-PoA hardware fingerprint checks (clock drift, cache timing, SIMD identity, thermal drift, instruction jitter, anti-emulation).".to_string(),
+            description: "Run all 6 RIP-PoA hardware fingerprint checks (clock drift, cache timing, SIMD identity, thermal drift, instruction jitter, anti-emulation), or only a chosen subset.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "checks": {
+                        "type": "array",
+                        "items": { "type": "string", "enum": fingerprint::CheckName::ALL.map(fingerprint::CheckName::as_str) },
+                        "description": "Only run these checks, e.g. [\"simd_identity\", \"anti_emulation\"] to skip the multi-second timing checks. The rest are reported as skipped and don't affect all_passed. Default: all."
+                    }
+                },
+                "required": []
+            }),
+        },
+        ToolDefinition {
+            name: "rustchain_fingerprint_csv".to_string(),
+            description: "Run the fingerprint checks and return the key numeric metrics as a CSV (or TSV) row with a stable header, for comparing machines in a spreadsheet.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "format": { "type": "string", "enum": ["csv", "tsv"], "description": "Output format. Default csv." },
+                    "header": { "type": "boolean", "description": "Include the header row. Default true." }
+                },
+                "required": []
+            }),
+        },
+        ToolDefinition {
+            name: "rustchain_hardware_info".to_string(),
+            description: "Show how this machine is classified for RustChain mining (family, arch, CPU, cores, memory, reward multiplier) plus a quick VM check. Fast: does not run the full fingerprint suite or contact the node.".to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {},
                 "required": []
             }),
         },
+        ToolDefinition {
+            name: "rustchain_fingerprint_stability".to_string(),
+            description: "Run the full fingerprint check set several times and report per-check pass rates and metric variance. Use this to see whether borderline hardware passes consistently.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "runs": { "type": "integer", "description": "Number of runs (1-10). Default 3." },
+                    "interval_secs": { "type": "integer", "description": "Seconds to wait between runs. Default 5." }
+                },
+                "required": []
+            }),
+        },
+        ToolDefinition {
+            name: "rustchain_fingerprint_compare".to_string(),
+            description: "Run the fingerprint checks and compare each metric against the range the node expects for this architecture, listing the metrics that fall outside it. Shows local results only if the node has no reference data.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "arch": { "type": "string", "description": "Architecture to compare against, e.g. \"x86_64\". Default: this machine's." },
+                    "checks": {
+                        "type": "array",
+                        "items": { "type": "string", "enum": fingerprint::CheckName::ALL.map(fingerprint::CheckName::as_str) },
+                        "description": "Only run these checks. Metrics of skipped checks are reported as missing. Default: all."
+                    },
+                    "node_url": { "type": "string", "description": "RustChain node URL. Default: https://bulbous-bouffant.metalseed.net" }
+                },
+                "required": []
+            }),
+        },
         ToolDefinition {
             name: "rustchain_transfer".to_string(),
-            description: "Send a signed RTC token transfer to another wallet.".to_string(),
+            description: "Send a signed RTC token transfer to another wallet. The signed transfer is kept in the local outbox until confirmed, so a failed broadcast can be retried with rustchain_flush_outbox.".to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
                     "to": { "type": "string", "description": "Recipient RTC wallet address" },
-                    "amount": { "type": "number", "description": "Amount of RTC to send" },
+                    "amount": { "type": ["number", "string"], "description": "Amount of RTC to send. Pass a string such as \"0.000001\" to send an exact decimal." },
                     "memo": { "type": "string", "description": "Optional transfer memo" }
                 },
                 "required": ["to", "amount"]
             }),
         },
-        // ─── Grazer tools (2) ────────────────────────────────────────────
+        ToolDefinition {
+            name: "rustchain_sweep".to_string(),
+            description: "Transfer the local wallet's entire RTC balance, minus the transfer fee, to another address. Use when decommissioning a wallet.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "to": { "type": "string", "description": "Destination RTC address." },
+                    "fee_rtc": { "type": ["number", "string"], "description": "Transfer fee to assume, at least 0. If omitted, the node is asked." },
+                    "node_url": { "type": "string", "description": "RustChain node URL. Default: https://bulbous-bouffant.metalseed.net" }
+                },
+                "required": ["to"]
+            }),
+        },
+        ToolDefinition {
+            name: "rustchain_validate_address".to_string(),
+            description: "Check that an RTC wallet address is well-formed (prefix, length, hex, and case checksum if present) without sending anything. Works offline.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "address": { "type": "string", "description": "RTC wallet address to check" }
+                },
+                "required": ["address"]
+            }),
+        },
+        ToolDefinition {
+            name: "rustchain_transfer_confirm".to_string(),
+            description: "Wait for a submitted RTC transfer to be confirmed on-chain. Returns pending, confirmed, or failed.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "tx_hash": { "type": "string", "description": "Transaction hash returned by rustchain_transfer" },
+                    "timeout_secs": { "type": "integer", "description": "Maximum seconds to wait. Default 60." }
+                },
+                "required": ["tx_hash"]
+            }),
+        },
+        ToolDefinition {
+            name: "rustchain_flush_outbox".to_string(),
+            description: "Rebroadcast transfers left in the local outbox (~/.clawrtc/outbox.json) by failed or unconfirmed rustchain_transfer calls, and remove the ones now confirmed. Rebroadcasts reuse the original signature and nonce, so a transfer is never sent twice.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "node_url": { "type": "string", "description": "RustChain node URL. Default: https://bulbous-bouffant.metalseed.net" }
+                },
+                "required": []
+            }),
+        },
+        ToolDefinition {
+            name: "rustchain_history".to_string(),
+            description: "List recent RTC transfers sent from or received by a wallet.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "wallet": { "type": "string", "description": "RTC wallet address. If omitted, uses the default wallet." },
+                    "limit": { "type": "integer", "description": "Maximum number of transfers to return. Default 20." }
+                },
+                "required": []
+            }),
+        },
+        // ─── Grazer tools (5) ────────────────────────────────────────────
         ToolDefinition {
             name: "grazer_discover".to_string(),
-            description: "Discover trending content across Elyan Labs platforms (BoTTube, Moltbook, 4claw, ClawHub, PinchedIn, AgentChan, ClawSta, ClawNews, ClawTasks, SwarmHub, Agent Directory). Returns top posts/videos/skills from each platform.".to_string(),
+            description: "Discover trending content across Elyan Labs platforms (BoTTube, Moltbook, 4claw, ClawHub, PinchedIn, AgentChan, ClawSta, ClawNews, ClawTasks, ClawCities, SwarmHub, Agent Directory). Returns top posts/videos/skills/sites from each platform.".to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -126,41 +262,119 @@ pub fn clawrtc_tool_definitions() -> Vec<ToolDefinition> {
                     "query": {
                         "type": "string",
                         "description": "Optional search query to filter results."
-                    }
+                    },
+                    "sort": {
+                        "type": "string",
+                        "enum": ["trending", "newest", "top"],
+                        "description": "Result order. Default trending (each platform's own order)."
+                    },
+                    "timeout_secs": {
+                        "type": "integer",
+                        "description": "When discovering from all platforms, stop after this many seconds and return the platforms gathered so far. Default 30."
+                    },
+                    "verbosity": { "type": "string", "enum": ["summary", "full"], "description": "summary returns counts and the top few items only; full (default) returns everything." }
                 },
                 "required": []
             }),
         },
         ToolDefinition {
             name: "grazer_post".to_string(),
-            description: "Post content to an Elyan Labs platform (Moltbook, 4claw, AgentChan, ClawSta, ClawNews, PinchedIn, or ClawTasks). Requires an API key for the target platform.".to_string(),
+            description: "Post content to an Elyan Labs platform (Moltbook, 4claw, AgentChan, ClawSta, ClawNews, PinchedIn, or ClawTasks), or comment on a ClawCities site. Requires an API key for the target platform.".to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
                     "platform": {
                         "type": "string",
-                        "description": "Target platform: moltbook, fourclaw, agentchan, clawsta, clawnews, pinchedin, or clawtasks."
+                        "description": "Target platform: moltbook, fourclaw, agentchan, clawsta, clawnews, pinchedin, clawtasks, or clawcities."
                     },
                     "title": { "type": "string", "description": "Post title." },
                     "content": { "type": "string", "description": "Post body content." },
                     "api_key": { "type": "string", "description": "API key for the target platform (e.g. moltbook_sk_... or clawchan_...)." },
-                    "submolt": { "type": "string", "description": "(Moltbook only) Submolt name to post to." },
+                    "submolt": { "type": "string", "description": "(Moltbook only, required) Submolt name to post to." },
+                    "board": { "type": "string", "description": "(4claw only) Board name to post to." },
+                    "reply_to": { "type": "string", "description": "(AgentChan only) Post ID to reply to." },
+                    "category": { "type": "string", "description": "(ClawNews/ClawSta only) Content category." },
+                    "url": { "type": "string", "description": "(ClawNews only, required) Link the story points to." },
+                    "tags": { "type": "array", "items": { "type": "string" }, "description": "(ClawNews/ClawTasks only) Tags." },
+                    "deadline_hours": { "type": "integer", "minimum": 1, "description": "(ClawTasks only, required) Hours until the bounty closes." },
+                    "site": { "type": "string", "description": "(ClawCities only, required) Name of the site to comment on." },
+                    "page": { "type": "string", "description": "(ClawCities only) Page of the site to comment on. Default its home page." },
+                    "strict_api_key": { "type": "boolean", "description": "Reject an api_key whose prefix doesn't match the platform instead of warning. Default false." }
+                },
+                "required": ["platform", "content", "api_key"]
+            }),
+        },
+        ToolDefinition {
+            name: "grazer_post_signed".to_string(),
+            description: "Post content to an Elyan Labs platform with a proof-of-content footer signed by the local RTC wallet, so readers can verify the wallet authored it. Takes the same fields as grazer_post.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "platform": {
+                        "type": "string",
+                        "description": "Target platform: moltbook, fourclaw, agentchan, clawsta, clawnews, pinchedin, clawtasks, or clawcities."
+                    },
+                    "title": { "type": "string", "description": "Post title." },
+                    "content": { "type": "string", "description": "Post body content. The signature footer is appended to it." },
+                    "api_key": { "type": "string", "description": "API key for the target platform (e.g. moltbook_sk_... or clawchan_...)." },
+                    "submolt": { "type": "string", "description": "(Moltbook only, required) Submolt name to post to." },
                     "board": { "type": "string", "description": "(4claw only) Board name to post to." },
                     "reply_to": { "type": "string", "description": "(AgentChan only) Post ID to reply to." },
-                    "category": { "type": "string", "description": "(ClawNews/ClawSta only) Content category." }
+                    "category": { "type": "string", "description": "(ClawNews/ClawSta only) Content category." },
+                    "url": { "type": "string", "description": "(ClawNews only, required) Link the story points to." },
+                    "tags": { "type": "array", "items": { "type": "string" }, "description": "(ClawNews/ClawTasks only) Tags." },
+                    "deadline_hours": { "type": "integer", "minimum": 1, "description": "(ClawTasks only, required) Hours until the bounty closes." },
+                    "site": { "type": "string", "description": "(ClawCities only, required) Name of the site to comment on." },
+                    "page": { "type": "string", "description": "(ClawCities only) Page of the site to comment on. Default its home page." },
+                    "strict_api_key": { "type": "boolean", "description": "Reject an api_key whose prefix doesn't match the platform instead of warning. Default false." }
+                },
+                "required": ["platform", "content", "api_key"]
+            }),
+        },
+        ToolDefinition {
+            name: "grazer_comment".to_string(),
+            description: "Comment on an existing post on an Elyan Labs platform, or reply to a comment. Currently supported on Moltbook only. Requires an API key for the target platform.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "platform": { "type": "string", "description": "Target platform: moltbook." },
+                    "parent_id": { "type": "string", "description": "(Moltbook: required) ID of the post to comment on." },
+                    "content": { "type": "string", "description": "Comment text." },
+                    "api_key": { "type": "string", "description": "API key for the target platform (e.g. moltbook_sk_...)." },
+                    "reply_to": { "type": "string", "description": "(Moltbook only) Comment ID to reply to within the post." },
+                    "strict_api_key": { "type": "boolean", "description": "Reject an api_key whose prefix doesn't match the platform instead of warning. Default false." }
                 },
                 "required": ["platform", "content", "api_key"]
             }),
         },
-        // ─── BoTTube tools (4) ───────────────────────────────────────────
+        ToolDefinition {
+            name: "grazer_onboard".to_string(),
+            description: "Announce an agent across Elyan Labs platforms: post an introduction built from a profile to each platform with an API key, then list the agent in the Agent Directory (directory.ctxly.app). Returns the outcome for each platform.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Agent name." },
+                    "bio": { "type": "string", "description": "Short description of the agent." },
+                    "links": { "type": "array", "items": { "type": "string" }, "description": "Homepage, repository, or other links, most important first." },
+                    "api_keys": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "API keys by platform name (e.g. {\"moltbook\": \"moltbook_sk_...\"}). An intro is posted to each. A \"directory\" key ties the listing to a directory account."
+                    }
+                },
+                "required": ["name", "bio", "api_keys"]
+            }),
+        },
+        // ─── BoTTube tools (7) ───────────────────────────────────────────
         ToolDefinition {
             name: "bottube_search".to_string(),
-            description: "Search for videos on BoTTube (bottube.ai), the AI video platform.".to_string(),
+            description: "Search for videos on BoTTube (bottube.ai), the AI video platform. Reports total_pages and has_next for paging through results.".to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
                     "query": { "type": "string", "description": "Search query." },
-                    "page": { "type": "integer", "description": "Page number for pagination. Default 1." }
+                    "page": { "type": "integer", "description": "Page number for pagination. Default 1." },
+                    "verbosity": { "type": "string", "enum": ["summary", "full"], "description": "summary returns counts and the top few items only; full (default) returns everything." }
                 },
                 "required": ["query"]
             }),
@@ -174,6 +388,18 @@ pub fn clawrtc_tool_definitions() -> Vec<ToolDefinition> {
                 "required": []
             }),
         },
+        ToolDefinition {
+            name: "bottube_channel".to_string(),
+            description: "Get a BoTTube channel's profile and one page of its uploads.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "channel_id": { "type": "string", "description": "The channel ID (usually the agent name)." },
+                    "page": { "type": "integer", "description": "Page of uploads to return. Default 1." }
+                },
+                "required": ["channel_id"]
+            }),
+        },
         ToolDefinition {
             name: "bottube_comment".to_string(),
             description: "Post a comment on a BoTTube video. Requires a BoTTube API key.".to_string(),
@@ -183,7 +409,8 @@ pub fn clawrtc_tool_definitions() -> Vec<ToolDefinition> {
                     "video_id": { "type": "string", "description": "The video ID to comment on." },
                     "content": { "type": "string", "description": "Comment text." },
                     "api_key": { "type": "string", "description": "BoTTube API key (bottube_sk_...)." },
-                    "parent_id": { "type": "string", "description": "Optional parent comment ID for replies." }
+                    "parent_id": { "type": "string", "description": "Optional parent comment ID for replies." },
+                    "strict_api_key": { "type": "boolean", "description": "Reject an api_key whose prefix doesn't match the platform instead of warning. Default false." }
                 },
                 "required": ["video_id", "content", "api_key"]
             }),
@@ -196,11 +423,42 @@ pub fn clawrtc_tool_definitions() -> Vec<ToolDefinition> {
                 "properties": {
                     "video_id": { "type": "string", "description": "The video ID to vote on." },
                     "vote": { "type": "integer", "description": "1 = like, -1 = dislike, 0 = remove vote." },
-                    "api_key": { "type": "string", "description": "BoTTube API key (bottube_sk_...)." }
+                    "api_key": { "type": "string", "description": "BoTTube API key (bottube_sk_...)." },
+                    "strict_api_key": { "type": "boolean", "description": "Reject an api_key whose prefix doesn't match the platform instead of warning. Default false." }
                 },
                 "required": ["video_id", "vote", "api_key"]
             }),
         },
+        ToolDefinition {
+            name: "bottube_report".to_string(),
+            description: "Report an abusive or rule-breaking BoTTube video for moderation. Requires a BoTTube API key.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "video_id": { "type": "string", "description": "The video ID to report." },
+                    "reason": { "type": "string", "description": "Why the video is being reported (e.g. spam, harassment)." },
+                    "api_key": { "type": "string", "description": "BoTTube API key (bottube_sk_...)." },
+                    "strict_api_key": { "type": "boolean", "description": "Reject an api_key whose prefix doesn't match the platform instead of warning. Default false." }
+                },
+                "required": ["video_id", "reason", "api_key"]
+            }),
+        },
+        ToolDefinition {
+            name: "bottube_upload".to_string(),
+            description: "Upload a video file to BoTTube in resumable parts. If the upload is interrupted, the error ends with the upload's saved state; pass it back as upload to resume. Requires a BoTTube API key.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Path of the video file to upload. Required unless resuming." },
+                    "title": { "type": "string", "description": "Video title. Required unless resuming." },
+                    "description": { "type": "string", "description": "Video description. Default empty." },
+                    "upload": { "type": "object", "description": "Saved state of an interrupted upload, from an earlier call's error. Resumes it instead of starting a new upload." },
+                    "api_key": { "type": "string", "description": "BoTTube API key (bottube_sk_...)." },
+                    "strict_api_key": { "type": "boolean", "description": "Reject an api_key whose prefix doesn't match the platform instead of warning. Default false." }
+                },
+                "required": ["api_key"]
+            }),
+        },
         // ─── ClawHub tools (1) ───────────────────────────────────────────
         ToolDefinition {
             name: "clawhub_search".to_string(),
@@ -213,6 +471,18 @@ pub fn clawrtc_tool_definitions() -> Vec<ToolDefinition> {
                 "required": ["query"]
             }),
         },
+        // ─── Meta tools (1) ──────────────────────────────────────────────
+        ToolDefinition {
+            name: "clawrtc_help".to_string(),
+            description: "List the available ClawRTC tools with their descriptions and input schemas, optionally limited to one category.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "category": { "type": "string", "enum": TOOL_CATEGORIES, "description": "Only list tools in this category. If omitted, lists every tool." }
+                },
+                "required": []
+            }),
+        },
     ]
 }
 
@@ -221,61 +491,204 @@ pub async fn execute_clawrtc_tool(
     tool_name: &str,
     input: &serde_json::Value,
 ) -> Result<String, String> {
+    execute_clawrtc_tool_with(ToolContext::global(), tool_name, input).await
+}
+
+/// Execute a ClawRTC tool with an explicit [`ToolContext`] instead of the
+/// process-wide one, e.g. to supply pre-collected hardware info.
+pub async fn execute_clawrtc_tool_with(
+    ctx: &ToolContext,
+    tool_name: &str,
+    input: &serde_json::Value,
+) -> Result<String, String> {
+    if ctx.is_offline() && needs_network(tool_name) {
+        return Err(ClawRtcError::Network("offline mode".into()).to_string());
+    }
     match tool_name {
         // RustChain tools
-        "rustchain_balance" => tool_balance(input).await,
-        "rustchain_wallet_create" => tool_wallet_create(input),
-        "rustchain_wallet_show" => tool_wallet_show(input).await,
-        "rustchain_attest" => tool_attest(input).await,
-        "rustchain_enroll" => tool_enroll(input).await,
-        "rustchain_network_status" => tool_network_status(input).await,
-        "rustchain_fingerprint" => tool_fingerprint().await,
-        "rustchain_transfer" => tool_transfer(input).await,
+        "rustchain_balance" => tool_balance(ctx, input).await,
+        "rustchain_wallet_create" => tool_wallet_create(ctx, input),
+        "rustchain_wallet_show" => tool_wallet_show(ctx, input).await,
+        "rustchain_attest" => tool_attest(ctx, input).await,
+        "rustchain_attest_benchmark" => tool_attest_benchmark(ctx, input).await,
+        "rustchain_challenge" => tool_challenge(ctx, input).await,
+        "rustchain_enroll" => tool_enroll(ctx, input).await,
+        "rustchain_network_status" => tool_network_status(ctx, input).await,
+        "rustchain_fingerprint" => tool_fingerprint(input).await,
+        "rustchain_fingerprint_stability" => tool_fingerprint_stability(input).await,
+        "rustchain_fingerprint_compare" => tool_fingerprint_compare(ctx, input).await,
+        "rustchain_hardware_info" => tool_hardware_info(ctx),
+        "rustchain_fingerprint_csv" => tool_fingerprint_csv(input).await,
+        "rustchain_transfer" => tool_transfer(ctx, input).await,
+        "rustchain_transfer_confirm" => tool_transfer_confirm(ctx, input).await,
+        "rustchain_flush_outbox" => tool_flush_outbox(ctx, input).await,
+        "rustchain_history" => tool_history(ctx, input).await,
+        "rustchain_sweep" => tool_sweep(ctx, input).await,
+        "rustchain_validate_address" => tool_validate_address(input),
         // Grazer tools
-        "grazer_discover" => tool_grazer_discover(input).await,
-        "grazer_post" => tool_grazer_post(input).await,
+        "grazer_discover" => tool_grazer_discover(ctx, input).await,
+        "grazer_post" => tool_grazer_post(ctx, input).await,
+        "grazer_post_signed" => tool_grazer_post_signed(ctx, input).await,
+        "grazer_comment" => tool_grazer_comment(ctx, input).await,
+        "grazer_onboard" => tool_grazer_onboard(ctx, input).await,
         // BoTTube tools
-        "bottube_search" => tool_bottube_search(input).await,
-        "bottube_trending" => tool_bottube_trending().await,
-        "bottube_comment" => tool_bottube_comment(input).await,
-        "bottube_vote" => tool_bottube_vote(input).await,
+        "bottube_search" => tool_bottube_search(ctx, input).await,
+        "bottube_trending" => tool_bottube_trending(ctx).await,
+        "bottube_channel" => tool_bottube_channel(ctx, input).await,
+        "bottube_comment" => tool_bottube_comment(ctx, input).await,
+        "bottube_vote" => tool_bottube_vote(ctx, input).await,
+        "bottube_report" => tool_bottube_report(ctx, input).await,
+        "bottube_upload" => tool_bottube_upload(ctx, input).await,
         // ClawHub tools
-        "clawhub_search" => tool_clawhub_search(input).await,
+        "clawhub_search" => tool_clawhub_search(ctx, input).await,
+        // Meta tools
+        "clawrtc_help" => tool_help(input),
         _ => Err(format!("Unknown clawrtc tool: {tool_name}")),
     }
 }
 
+/// Tool name prefixes, each naming a category of tools.
+const TOOL_CATEGORIES: &[&str] = &["rustchain", "grazer", "bottube", "clawhub", "clawrtc"];
+
+/// The category of a tool, taken from its name prefix.
+fn tool_category(name: &str) -> Option<&'static str> {
+    TOOL_CATEGORIES.iter().copied().find(|category| {
+        name.strip_prefix(category)
+            .is_some_and(|rest| rest.starts_with('_'))
+    })
+}
+
 /// Check if a tool name belongs to the clawrtc module.
 pub fn is_clawrtc_tool(name: &str) -> bool {
-    name.starts_with("rustchain_")
-        || name.starts_with("grazer_")
+    tool_category(name).is_some()
+}
+
+/// Whether a tool can't do anything useful without reaching a remote service.
+///
+/// `rustchain_wallet_show` is not listed: offline it shows the wallet without
+/// a balance.
+fn needs_network(name: &str) -> bool {
+    matches!(
+        name,
+        "rustchain_balance"
+            | "rustchain_attest"
+            | "rustchain_attest_benchmark"
+            | "rustchain_challenge"
+            | "rustchain_enroll"
+            | "rustchain_network_status"
+            | "rustchain_transfer"
+            | "rustchain_transfer_confirm"
+            | "rustchain_flush_outbox"
+            | "rustchain_history"
+            | "rustchain_sweep"
+    ) || name.starts_with("grazer_")
         || name.starts_with("bottube_")
         || name.starts_with("clawhub_")
 }
 
+/// How much of a result a tool returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verbosity {
+    /// Counts and the first few items, trimmed to identifying fields, as
+    /// compact JSON. Keeps large listings from filling an agent's context.
+    Summary,
+    /// Everything, pretty-printed.
+    Full,
+}
+
+/// Overall deadline for `grazer_discover` across all platforms, in seconds.
+const DISCOVER_ALL_TIMEOUT_SECS: u64 = 30;
+
+/// Items kept per listing in summary output.
+const SUMMARY_TOP_ITEMS: usize = 3;
+
+/// Item fields kept in summary output.
+const SUMMARY_ITEM_FIELDS: &[&str] = &["id", "title", "name", "url", "score", "views"];
+
+impl Verbosity {
+    fn from_input(input: &serde_json::Value) -> Result<Self, String> {
+        match input["verbosity"].as_str().unwrap_or("full") {
+            "summary" => Ok(Self::Summary),
+            "full" => Ok(Self::Full),
+            other => Err(format!("Unsupported verbosity: {other} (expected summary or full)")),
+        }
+    }
+
+    fn render(self, value: &serde_json::Value) -> String {
+        match self {
+            Self::Summary => serde_json::to_string(value).unwrap(),
+            Self::Full => serde_json::to_string_pretty(value).unwrap(),
+        }
+    }
+}
+
+/// Replace every array in `value` with its length and first few items, each
+/// trimmed to [`SUMMARY_ITEM_FIELDS`]. Nested objects are summarized the same
+/// way, so per-platform result maps shrink too.
+fn summarize(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Array(items) => serde_json::json!({
+            "count": items.len(),
+            "top": items.iter().take(SUMMARY_TOP_ITEMS).map(summarize_item).collect::<Vec<_>>(),
+        }),
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| (k.clone(), summarize(v)))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        other => other.clone(),
+    }
+}
+
+fn summarize_item(item: &serde_json::Value) -> serde_json::Value {
+    match item.as_object() {
+        Some(map) => SUMMARY_ITEM_FIELDS
+            .iter()
+            .filter_map(|k| map.get(*k).map(|v| (k.to_string(), v.clone())))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        None => item.clone(),
+    }
+}
+
 // ─── Tool implementations ───────────────────────────────────────────────────
 
-fn get_client(input: &serde_json::Value) -> RustChainClient {
-    let url = input["node_url"]
-        .as_str()
-        .unwrap_or(crate::client::DEFAULT_NODE_URL);
-    RustChainClient::new(url)
+async fn get_client(ctx: &ToolContext, input: &serde_json::Value) -> Arc<RustChainClient> {
+    ctx.client(input["node_url"].as_str()).await
 }
 
-async fn tool_balance(input: &serde_json::Value) -> Result<String, String> {
-    let wallet_addr = if let Some(addr) = input["wallet"].as_str() {
-        addr.to_string()
-    } else {
-        let path = default_wallet_path();
-        let w = RtcWallet::from_file(&path).map_err(|e| format!("No wallet found: {e}"))?;
-        w.address().to_string()
+/// The `wallet` input if given, otherwise the context wallet's address.
+fn wallet_address(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
+    if let Some(addr) = input["wallet"].as_str() {
+        return Ok(addr.to_string());
+    }
+    let w = ctx
+        .load_wallet()
+        .map_err(|e| format!("No wallet found: {e}"))?;
+    Ok(w.address().to_string())
+}
+
+/// Read an RTC amount given as a JSON number or a decimal string.
+fn amount_field(input: &serde_json::Value, field: &str) -> Result<Option<Decimal>, String> {
+    let text = match &input[field] {
+        serde_json::Value::Null => return Ok(None),
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        _ => return Err(format!("{field} must be a number or a decimal string")),
     };
+    amount::parse(&text)
+        .map(Some)
+        .map_err(|e| format!("Invalid {field} {text:?}: {e}"))
+}
 
-    let client = get_client(input);
+async fn tool_balance(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
+    let wallet_addr = wallet_address(ctx, input)?;
+
+    let client = get_client(ctx, input).await;
     let balance = client
-        .balance(&wallet_addr)
+        .balance_decimal(&wallet_addr)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| format!("Balance unavailable (node unreachable or erroring): {e}"))?;
 
     Ok(serde_json::to_string_pretty(&serde_json::json!({
         "wallet": wallet_addr,
@@ -284,8 +697,8 @@ async fn tool_balance(input: &serde_json::Value) -> Result<String, String> {
     .unwrap())
 }
 
-fn tool_wallet_create(input: &serde_json::Value) -> Result<String, String> {
-    let path = default_wallet_path();
+fn tool_wallet_create(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
+    let path = ctx.wallet_path();
     let force = input["force"].as_bool().unwrap_or(false);
 
     if path.exists() && !force {
@@ -297,7 +710,7 @@ fn tool_wallet_create(input: &serde_json::Value) -> Result<String, String> {
 
     let wallet = RtcWallet::generate();
     wallet
-        .save_plaintext(&path)
+        .save_plaintext(path)
         .map_err(|e| format!("Failed to save wallet: {e}"))?;
 
     Ok(serde_json::to_string_pretty(&serde_json::json!({
@@ -309,30 +722,41 @@ fn tool_wallet_create(input: &serde_json::Value) -> Result<String, String> {
     .unwrap())
 }
 
-async fn tool_wallet_show(input: &serde_json::Value) -> Result<String, String> {
-    let path = default_wallet_path();
-    let wallet = RtcWallet::from_file(&path)
+async fn tool_wallet_show(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
+    let path = ctx.wallet_path();
+    let wallet = ctx
+        .load_wallet()
         .map_err(|e| format!("No wallet found at {}: {e}", path.display()))?;
 
-    let client = get_client(input);
-    let balance = client.balance(wallet.address()).await.unwrap_or(0.0);
+    // A null balance with an error means "unknown", never "zero"
+    let (balance, balance_error) = if ctx.is_offline() {
+        (None, Some("offline mode".to_string()))
+    } else {
+        let client = get_client(ctx, input).await;
+        match client.balance_decimal(wallet.address()).await {
+            Ok(balance) => (Some(balance), None),
+            Err(e) => (None, Some(e.to_string())),
+        }
+    };
 
     Ok(serde_json::to_string_pretty(&serde_json::json!({
         "address": wallet.address(),
         "public_key": wallet.public_key_hex(),
         "balance_rtc": balance,
+        "balance_error": balance_error,
         "wallet_file": path.display().to_string(),
     }))
     .unwrap())
 }
 
-async fn tool_attest(input: &serde_json::Value) -> Result<String, String> {
-    let path = default_wallet_path();
-    let wallet = RtcWallet::from_file(&path)
+async fn tool_attest(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
+    let wallet = ctx
+        .load_wallet()
         .map_err(|e| format!("No wallet found: {e}"))?;
+    let wallet_addr = wallet.address();
 
-    let hw = HardwareInfo::detect().map_err(|e| e.to_string())?;
-    let client = get_client(input);
+    let hw = ctx.hardware().map_err(|e| e.to_string())?;
+    let client = get_client(ctx, input).await;
 
     // Challenge
     let challenge = client.challenge().await.map_err(|e| e.to_string())?;
@@ -340,48 +764,19 @@ async fn tool_attest(input: &serde_json::Value) -> Result<String, String> {
 
     // Entropy (blocking)
     let entropy = tokio::task::spawn_blocking(|| {
-        let cycles = 48;
-        let inner_loop = 25_000u64;
-        let mut samples = Vec::with_capacity(cycles);
-        for _ in 0..cycles {
-            let start = std::time::Instant::now();
-            let mut acc: u64 = 0;
-            for j in 0..inner_loop {
-                acc ^= std::hint::black_box((j.wrapping_mul(31)) & 0xFFFFFFFF);
-            }
-            std::hint::black_box(acc);
-            samples.push(start.elapsed().as_nanos() as f64);
-        }
-        let n = samples.len() as f64;
-        let mean = samples.iter().sum::<f64>() / n;
-        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
-        serde_json::json!({
-            "mean_ns": mean,
-            "variance_ns": variance,
-            "sample_count": samples.len(),
-        })
+        crate::miner::collect_entropy(
+            crate::miner::DEFAULT_ENTROPY_WARMUP,
+            crate::miner::PreemptionPolicy::Ignore,
+        )
     })
     .await
     .unwrap();
 
-    // Commitment
-    let entropy_json = serde_json::to_string(&entropy).unwrap();
-    let commitment_input = format!("{}{}{}", nonce, wallet.address(), entropy_json);
-    let commitment = hex::encode(sha2::Sha256::digest(commitment_input.as_bytes()));
-
-    let payload = serde_json::json!({
-        "miner": wallet.address(),
-        "miner_id": hw.miner_id(),
-        "nonce": nonce,
-        "report": {
-            "nonce": nonce,
-            "commitment": commitment,
-            "derived": entropy,
-            "entropy_score": entropy["variance_ns"],
-        },
-        "device": hw.device_payload(),
-        "signals": hw.signals_payload(),
-    });
+    let evidence = AttestationEvidence {
+        entropy,
+        fingerprint: None,
+    };
+    let payload = evidence.payload(wallet_addr, &hw.miner_id(), &hw, nonce);
 
     client
         .submit_attestation(&payload)
@@ -391,22 +786,124 @@ async fn tool_attest(input: &serde_json::Value) -> Result<String, String> {
     Ok(serde_json::to_string_pretty(&serde_json::json!({
         "status": "accepted",
         "miner_id": hw.miner_id(),
-        "wallet": wallet.address(),
+        "wallet": wallet_addr,
         "device_arch": hw.arch,
     }))
     .unwrap())
 }
 
-async fn tool_enroll(input: &serde_json::Value) -> Result<String, String> {
-    let path = default_wallet_path();
-    let wallet = RtcWallet::from_file(&path)
+async fn tool_attest_benchmark(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
+    let wallet = ctx
+        .load_wallet()
+        .map_err(|e| format!("No wallet found: {e}"))?;
+    let wallet_addr = wallet.address();
+    let submit = input["submit"].as_bool().unwrap_or(false);
+    let run_fingerprints = input["fingerprints"].as_bool().unwrap_or(true);
+
+    let hw = ctx.hardware().map_err(|e| e.to_string())?;
+    let client = get_client(ctx, input).await;
+    let elapsed_ms = |since: Instant| since.elapsed().as_secs_f64() * 1000.0;
+    let start = Instant::now();
+
+    // Challenge
+    let phase = Instant::now();
+    let challenge = client.challenge().await.map_err(|e| e.to_string())?;
+    let challenge_ms = elapsed_ms(phase);
+    let nonce = &challenge.nonce;
+
+    // Entropy (blocking)
+    let phase = Instant::now();
+    let entropy = tokio::task::spawn_blocking(|| {
+        crate::miner::collect_entropy(
+            crate::miner::DEFAULT_ENTROPY_WARMUP,
+            crate::miner::PreemptionPolicy::Ignore,
+        )
+    })
+    .await
+    .unwrap();
+    let entropy_ms = elapsed_ms(phase);
+
+    // Fingerprints
+    let phase = Instant::now();
+    let fingerprint = if run_fingerprints {
+        let report = fingerprint::validate_all_checks_async().await;
+        Some(serde_json::json!({
+            "all_passed": report.all_passed,
+            "checks": report.checks,
+        }))
+    } else {
+        None
+    };
+    let fingerprint_ms = elapsed_ms(phase);
+
+    // Submit
+    let submit_ms = if submit {
+        let evidence = AttestationEvidence {
+            entropy,
+            fingerprint,
+        };
+        let payload = evidence.payload(wallet_addr, &hw.miner_id(), &hw, nonce);
+
+        let phase = Instant::now();
+        client
+            .submit_attestation(&payload)
+            .await
+            .map_err(|e| e.to_string())?;
+        Some(elapsed_ms(phase))
+    } else {
+        None
+    };
+
+    let nonce_ttl_secs = challenge
+        .issued_at
+        .zip(challenge.expires_at)
+        .map(|(issued, expires)| expires - issued);
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "node": client.base_url(),
+        "wallet": wallet_addr,
+        "submitted": submit,
+        "phases_ms": {
+            "challenge": challenge_ms,
+            "entropy": entropy_ms,
+            "fingerprint": fingerprint_ms,
+            "submit": submit_ms,
+        },
+        "total_ms": elapsed_ms(start),
+        "nonce_ttl_secs": nonce_ttl_secs,
+        "nonce_expired": challenge.is_expired(),
+    }))
+    .unwrap())
+}
+
+async fn tool_challenge(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
+    let client = get_client(ctx, input).await;
+    let challenge = client.challenge().await.map_err(|e| e.to_string())?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "node": client.base_url(),
+        "nonce": challenge.nonce,
+        "issued_at": challenge.issued_at,
+        "expires_at": challenge.expires_at,
+        "expired": challenge.is_expired(),
+    }))
+    .unwrap())
+}
+
+async fn tool_enroll(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
+    let wallet = ctx
+        .load_wallet()
         .map_err(|e| format!("No wallet found: {e}"))?;
+    let wallet_addr = wallet.address();
 
-    let hw = HardwareInfo::detect().map_err(|e| e.to_string())?;
-    let client = get_client(input);
+    let hw = ctx.hardware().map_err(|e| e.to_string())?;
+    let client = get_client(ctx, input).await;
 
     let payload = serde_json::json!({
-        "miner_pubkey": wallet.address(),
+        "miner_pubkey": wallet_addr,
         "miner_id": hw.miner_id(),
         "device": {
             "family": hw.family,
@@ -424,80 +921,329 @@ async fn tool_enroll(input: &serde_json::Value) -> Result<String, String> {
     .unwrap())
 }
 
-async fn tool_network_status(input: &serde_json::Value) -> Result<String, String> {
-    let client = get_client(input);
+async fn tool_network_status(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
+    let verbosity = Verbosity::from_input(input)?;
+    let client = get_client(ctx, input).await;
 
     let health = client.health().await.map_err(|e| e.to_string())?;
-    let miners = client.miners().await.unwrap_or_default();
+    let miners = client.miners_deduped().await.unwrap_or_default();
 
-    Ok(serde_json::to_string_pretty(&serde_json::json!({
+    let mut status = serde_json::json!({
         "node": client.base_url(),
         "healthy": health.ok,
         "version": health.version,
         "uptime_s": health.uptime_s,
         "active_miners": miners.len(),
-        "miners": miners,
+    });
+    if verbosity == Verbosity::Full {
+        status["miners"] = serde_json::json!(miners);
+    }
+    Ok(verbosity.render(&status))
+}
+
+/// Run the checks named in the `checks` input, or all of them.
+async fn run_fingerprint_checks(
+    input: &serde_json::Value,
+) -> Result<fingerprint::FingerprintReport, String> {
+    Ok(match input.get("checks").filter(|c| !c.is_null()) {
+        Some(checks) => {
+            let names: Vec<fingerprint::CheckName> = serde_json::from_value(checks.clone())
+                .map_err(|e| format!("Invalid 'checks': {e}"))?;
+            fingerprint::validate_selected_async(names.into_iter().collect()).await
+        }
+        None => fingerprint::validate_all_checks_async().await,
+    })
+}
+
+async fn tool_fingerprint(input: &serde_json::Value) -> Result<String, String> {
+    let report = run_fingerprint_checks(input).await?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "all_passed": report.all_passed,
+        "score": report.score,
+        "passed_count": report.passed_count,
+        "summary": report.summary_lines(),
+        "checks": report.checks,
     }))
     .unwrap())
 }
 
-async fn tool_fingerprint() -> Result<String, String> {
+async fn tool_fingerprint_csv(input: &serde_json::Value) -> Result<String, String> {
+    let tsv = match input["format"].as_str().unwrap_or("csv") {
+        "csv" => false,
+        "tsv" => true,
+        other => return Err(format!("Unsupported format: {other} (expected csv or tsv)")),
+    };
+    let with_header = input["header"].as_bool().unwrap_or(true);
+
     let report = fingerprint::validate_all_checks_async().await;
+    let (header, row) = if tsv {
+        (fingerprint::FingerprintReport::tsv_header(), report.to_tsv())
+    } else {
+        (fingerprint::FingerprintReport::csv_header(), report.to_csv())
+    };
 
-    let mut summary = Vec::new();
-    let checks = &report.checks;
-    summary.push(format!("Clock Drift:        {}", pass_fail(checks.clock_drift.passed)));
-    summary.push(format!("Cache Timing:       {}", pass_fail(checks.cache_timing.passed)));
-    summary.push(format!("SIMD Identity:      {}", pass_fail(checks.simd_identity.passed)));
-    summary.push(format!("Thermal Drift:      {}", pass_fail(checks.thermal_drift.passed)));
-    summary.push(format!("Instruction Jitter: {}", pass_fail(checks.instruction_jitter.passed)));
-    summary.push(format!("Anti-Emulation:     {}", pass_fail(checks.anti_emulation.passed)));
+    Ok(if with_header {
+        format!("{header}\n{row}")
+    } else {
+        row
+    })
+}
+
+fn tool_hardware_info(ctx: &ToolContext) -> Result<String, String> {
+    let hw = ctx.hardware().map_err(|e| e.to_string())?;
+    let vm_indicators = fingerprint::anti_emulation::quick_indicators();
+    let multiplier = hw.arch_multiplier();
+
+    let summary = format!(
+        "{} / {} ({}), {} cores, {} GB RAM, {multiplier}x multiplier{}",
+        hw.family,
+        hw.arch,
+        hw.cpu,
+        hw.cores,
+        hw.memory_gb,
+        if vm_indicators.is_empty() {
+            ""
+        } else {
+            " — looks like a VM"
+        }
+    );
 
     Ok(serde_json::to_string_pretty(&serde_json::json!({
-        "all_passed": report.all_passed,
         "summary": summary,
-        "checks": report.checks,
+        "family": hw.family,
+        "arch": hw.arch,
+        "machine": hw.machine,
+        "cpu": hw.cpu,
+        "cores": hw.cores,
+        "memory_gb": hw.memory_gb,
+        "arch_multiplier": multiplier,
+        "miner_id": hw.miner_id(),
+        "vm_check": {
+            "likely_vm": !vm_indicators.is_empty(),
+            "indicators": vm_indicators,
+        },
+    }))
+    .unwrap())
+}
+
+async fn tool_fingerprint_stability(input: &serde_json::Value) -> Result<String, String> {
+    let runs = input["runs"].as_u64().unwrap_or(3).clamp(1, 10) as usize;
+    let interval = std::time::Duration::from_secs(input["interval_secs"].as_u64().unwrap_or(5));
+
+    let report = tokio::task::spawn_blocking(move || fingerprint::sample_over_time(runs, interval))
+        .await
+        .map_err(|e| format!("Fingerprint sampling failed: {e}"))?;
+
+    Ok(serde_json::to_string_pretty(&report).unwrap())
+}
+
+async fn tool_fingerprint_compare(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
+    let arch = match input["arch"].as_str() {
+        Some(arch) => arch.to_string(),
+        None => ctx.hardware().map_err(|e| e.to_string())?.arch,
+    };
+
+    // Without a reference the local results are still worth reporting
+    let (reference, note) = if ctx.is_offline() {
+        (None, Some("offline mode".to_string()))
+    } else {
+        let client = get_client(ctx, input).await;
+        match client.fingerprint_reference(&arch).await {
+            Ok(Some(reference)) => (Some(reference), None),
+            Ok(None) => (
+                None,
+                Some(format!("Node has no fingerprint reference for {arch}")),
+            ),
+            Err(e) => (None, Some(e.to_string())),
+        }
+    };
+
+    let report = run_fingerprint_checks(input).await?;
+    let comparison = reference.map(|r| r.compare(&report));
+    let outside: Vec<_> = comparison.iter().flat_map(|c| c.outside()).collect();
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "arch": arch,
+        "all_passed": report.all_passed,
+        "score": report.score,
+        "summary": report.summary_lines(),
+        "outside": outside,
+        "comparison": comparison,
+        "note": note,
     }))
     .unwrap())
 }
 
-async fn tool_transfer(input: &serde_json::Value) -> Result<String, String> {
+async fn tool_transfer(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
     let to = input["to"]
         .as_str()
         .ok_or("Missing required field: to")?;
-    let amount = input["amount"]
-        .as_f64()
-        .ok_or("Missing required field: amount")?;
+    let amount = amount_field(input, "amount")?.ok_or("Missing required field: amount")?;
     let memo = input["memo"].as_str().unwrap_or("");
 
-    if !to.starts_with("RTC") || to.len() != 43 {
-        return Err(format!("Invalid RTC address: {to}"));
-    }
-    if amount <= 0.0 {
+    crate::wallet::validate_rtc_address(to).map_err(|e| e.to_string())?;
+    if amount <= Decimal::ZERO {
         return Err("Amount must be positive".to_string());
     }
 
-    let path = default_wallet_path();
-    let wallet = RtcWallet::from_file(&path)
+    let wallet = ctx
+        .load_wallet()
         .map_err(|e| format!("No wallet found: {e}"))?;
 
     let tx_payload = wallet
         .sign_transaction(to, amount, memo)
         .map_err(|e| e.to_string())?;
 
-    let client = get_client(input);
-    let result = client
-        .transfer_signed(&tx_payload)
+    // Queued first so a failed broadcast can be retried with the same nonce
+    let outbox = ctx.outbox();
+    outbox.push(&tx_payload).map_err(|e| e.to_string())?;
+    let signature = tx_payload["signature"].as_str().unwrap_or_default();
+
+    let client = get_client(ctx, input).await;
+    let result = client.transfer_signed(&tx_payload).await.map_err(|e| {
+        format!("{e} (transfer kept in the outbox; retry with rustchain_flush_outbox)")
+    })?;
+    match &result.tx_hash {
+        Some(tx_hash) if result.ok => outbox.mark_sent(signature, tx_hash),
+        _ => outbox.remove(signature).map(drop),
+    }
+    .map_err(|e| e.to_string())?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "ok": result.ok,
+        "to": to,
+        "amount_rtc": amount,
+        "tx_hash": result.tx_hash,
+        "new_balance": result.new_balance,
+        "error": result.error,
+    }))
+    .unwrap())
+}
+
+async fn tool_sweep(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
+    let to = input["to"]
+        .as_str()
+        .ok_or("Missing required field: to")?;
+    let fee = amount_field(input, "fee_rtc")?;
+    if fee.is_some_and(|fee| fee < Decimal::ZERO) {
+        return Err("fee_rtc must be a non-negative amount".to_string());
+    }
+
+    let wallet = ctx
+        .load_wallet()
+        .map_err(|e| format!("No wallet found: {e}"))?;
+
+    // A fixed fee goes on a copy of the shared client, keeping its settings
+    let shared = get_client(ctx, input).await;
+    let configured;
+    let client = match fee {
+        Some(fee) => {
+            configured = shared.as_ref().clone().with_transfer_fee(fee);
+            &configured
+        }
+        None => shared.as_ref(),
+    };
+    let result = wallet
+        .sweep_to(client, to)
         .await
         .map_err(|e| e.to_string())?;
 
     Ok(serde_json::to_string_pretty(&result).unwrap())
 }
 
+fn tool_validate_address(input: &serde_json::Value) -> Result<String, String> {
+    let address = input["address"]
+        .as_str()
+        .ok_or("Missing required field: address")?;
+
+    let reason = match crate::wallet::validate_rtc_address(address) {
+        Ok(()) => None,
+        Err(ClawRtcError::InvalidAddress(reason)) => Some(reason),
+        Err(e) => Some(e.to_string()),
+    };
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "address": address,
+        "valid": reason.is_none(),
+        "reason": reason,
+    }))
+    .unwrap())
+}
+
+async fn tool_transfer_confirm(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
+    let tx_hash = input["tx_hash"]
+        .as_str()
+        .ok_or("Missing required field: tx_hash")?;
+    let timeout = std::time::Duration::from_secs(input["timeout_secs"].as_u64().unwrap_or(60));
+
+    let client = get_client(ctx, input).await;
+    let status = client
+        .wait_for_confirmation(tx_hash, timeout)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "tx_hash": tx_hash,
+        "status": status,
+    }))
+    .unwrap())
+}
+
+async fn tool_flush_outbox(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
+    let outbox = ctx.outbox();
+    let client = get_client(ctx, input).await;
+    let report = outbox.flush(&client).await.map_err(|e| e.to_string())?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "confirmed": report.confirmed,
+        "failed": report.failed,
+        "pending": report.pending,
+        "remaining": report.pending.len(),
+    }))
+    .unwrap())
+}
+
+async fn tool_history(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
+    let wallet_addr = wallet_address(ctx, input)?;
+    crate::wallet::validate_rtc_address(&wallet_addr).map_err(|e| e.to_string())?;
+    let limit = u32::try_from(input["limit"].as_u64().unwrap_or(20)).unwrap_or(u32::MAX);
+
+    let client = get_client(ctx, input).await;
+    let transactions = client
+        .transactions(&wallet_addr, limit)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "wallet": wallet_addr,
+        "count": transactions.len(),
+        "transactions": transactions,
+    }))
+    .unwrap())
+}
+
 // ─── Grazer tool implementations ─────────────────────────────────────────────
 
-async fn tool_grazer_discover(input: &serde_json::Value) -> Result<String, String> {
-    let grazer = GrazerClient::new();
+async fn tool_grazer_discover(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
+    let verbosity = Verbosity::from_input(input)?;
+    let sort: DiscoverSort = match input["sort"].as_str() {
+        Some(sort) => sort.parse()?,
+        None => DiscoverSort::default(),
+    };
+    let grazer = ctx.grazer();
     let extra = input.clone();
 
     if let Some(platform_str) = input["platform"].as_str() {
@@ -505,46 +1251,98 @@ async fn tool_grazer_discover(input: &serde_json::Value) -> Result<String, Strin
             .parse()
             .map_err(|e: String| e)?;
 
-        let result = grazer
-            .discover(platform, None, 20, &extra)
+        let mut result = grazer
+            .discover_sorted(platform, None, 20, &extra, sort)
             .await
             .map_err(|e| e.to_string())?;
+        if verbosity == Verbosity::Summary {
+            result = summarize(&result);
+        }
 
-        Ok(serde_json::to_string_pretty(&serde_json::json!({
+        Ok(verbosity.render(&serde_json::json!({
             "platform": platform_str,
             "results": result,
-        }))
-        .unwrap())
+        })))
     } else {
         // Discover from all platforms (best-effort, skip auth-required ones)
-        let platforms = Platform::all_names();
-        let mut results = serde_json::Map::new();
-
-        for name in platforms {
-            if let Ok(platform) = name.parse::<Platform>() {
-                match grazer.discover(platform, None, 10, &extra).await {
-                    Ok(data) => {
-                        results.insert(name.to_string(), data);
-                    }
-                    Err(_) => {
-                        results.insert(
-                            name.to_string(),
-                            serde_json::json!({"error": "unavailable"}),
-                        );
-                    }
-                }
+        let platforms = Platform::ALL.to_vec();
+
+        let cancel = tokio_util::sync::CancellationToken::new();
+        let secs = input["timeout_secs"]
+            .as_u64()
+            .unwrap_or(DISCOVER_ALL_TIMEOUT_SECS);
+        let timer = tokio::spawn({
+            let cancel = cancel.clone();
+            async move {
+                tokio::time::sleep(std::time::Duration::from_secs(secs)).await;
+                cancel.cancel();
             }
+        });
+        let discovered = grazer
+            .discover_all(&platforms, 10, &extra, sort, &cancel)
+            .await;
+        timer.abort();
+
+        let mut results = serde_json::Value::Object(discovered.results);
+        if verbosity == Verbosity::Summary {
+            results = summarize(&results);
         }
 
-        Ok(serde_json::to_string_pretty(&serde_json::json!({
-            "platforms_queried": platforms.len(),
+        Ok(verbosity.render(&serde_json::json!({
+            "platforms_queried": results.as_object().map_or(0, |r| r.len()),
+            "cancelled": discovered.cancelled,
             "results": results,
-        }))
-        .unwrap())
+        })))
     }
 }
 
-async fn tool_grazer_post(input: &serde_json::Value) -> Result<String, String> {
+async fn tool_grazer_post(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
+    let content = input["content"]
+        .as_str()
+        .ok_or("Missing required field: content")?;
+
+    let op = grazer_post_content(ctx, input, content).await?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "platform": op.platform,
+        "posted": op.success,
+        "id": op.id,
+        "url": op.url,
+        "result": op.raw,
+    }))
+    .unwrap())
+}
+
+async fn tool_grazer_post_signed(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
+    let content = input["content"]
+        .as_str()
+        .ok_or("Missing required field: content")?;
+
+    let wallet = ctx
+        .load_wallet()
+        .map_err(|e| format!("No wallet found: {e}"))?;
+    let (signed_content, proof) = crate::proof::append_proof(&wallet, content);
+
+    let op = grazer_post_content(ctx, input, &signed_content).await?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "platform": op.platform,
+        "posted": op.success,
+        "id": op.id,
+        "url": op.url,
+        "proof": proof,
+        "result": op.raw,
+    }))
+    .unwrap())
+}
+
+async fn tool_grazer_comment(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
     let platform_str = input["platform"]
         .as_str()
         .ok_or("Missing required field: platform")?;
@@ -554,54 +1352,147 @@ async fn tool_grazer_post(input: &serde_json::Value) -> Result<String, String> {
     let api_key = input["api_key"]
         .as_str()
         .ok_or("Missing required field: api_key")?;
+    let parent_id = input["parent_id"].as_str();
+
+    let platform: Platform = platform_str.parse()?;
+    let key_hint = check_api_key(platform, api_key, input)?;
+
+    let op = ctx
+        .grazer()
+        .comment_op(platform, api_key, parent_id, content, input)
+        .await
+        .map_err(|e| with_key_hint(e, key_hint))?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "platform": op.platform,
+        "commented": op.success,
+        "parent_id": parent_id,
+        "id": op.id,
+        "url": op.url,
+        "result": op.raw,
+    }))
+    .unwrap())
+}
+
+async fn tool_grazer_onboard(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
+    let profile: AgentProfile = serde_json::from_value(input.clone())
+        .map_err(|e| format!("Invalid profile (name and bio are required): {e}"))?;
+    let keys = input["api_keys"]
+        .as_object()
+        .ok_or("Missing required field: api_keys")?;
+    let api_keys = keys
+        .iter()
+        .map(|(name, key)| {
+            let platform: Platform = name.parse()?;
+            let key = key
+                .as_str()
+                .ok_or_else(|| format!("API key for {name} must be a string"))?;
+            Ok((platform, key.to_string()))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let steps = ctx.grazer().onboard(&profile, &api_keys).await;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "name": profile.name,
+        "succeeded": steps.iter().filter(|s| s.succeeded()).count(),
+        "attempted": steps.len(),
+        "results": steps,
+    }))
+    .unwrap())
+}
+
+/// Shared body of the Grazer post tools: post `content` using the platform,
+/// title, API key, and platform-specific fields from `input`.
+async fn grazer_post_content(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+    content: &str,
+) -> Result<OpResult, String> {
+    let platform_str = input["platform"]
+        .as_str()
+        .ok_or("Missing required field: platform")?;
+    let api_key = input["api_key"]
+        .as_str()
+        .ok_or("Missing required field: api_key")?;
 
     let platform: Platform = platform_str
         .parse()
         .map_err(|e: String| e)?;
-    let grazer = GrazerClient::new();
+    let grazer = ctx.grazer();
+
+    let key_hint = check_api_key(platform, api_key, input)?;
 
     let title = input["title"].as_str().unwrap_or("");
 
     // Build extra context for platform-specific fields
     let extra = input.clone();
 
-    let result = grazer
-        .post(platform, api_key, title, content, &extra)
+    grazer
+        .post_op(platform, api_key, title, content, &extra)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| with_key_hint(e, key_hint))
+}
 
-    Ok(serde_json::to_string_pretty(&serde_json::json!({
-        "platform": platform_str,
-        "posted": true,
-        "result": result,
-    }))
-    .unwrap())
+/// Check `api_key` against the platform's key prefix.
+///
+/// With `strict_api_key` a mismatch is an error; otherwise it is returned as
+/// a hint for [`with_key_hint`] to attach if the platform rejects the call.
+fn check_api_key(
+    platform: Platform,
+    api_key: &str,
+    input: &serde_json::Value,
+) -> Result<Option<String>, String> {
+    let strict = input["strict_api_key"].as_bool().unwrap_or(false);
+    platform
+        .check_api_key(api_key, strict)
+        .map_err(|e| e.to_string())
+}
+
+/// Error message for a failed platform call, noting a suspicious API key.
+fn with_key_hint(err: ClawRtcError, key_hint: Option<String>) -> String {
+    match key_hint {
+        Some(hint) => format!("{err} (note: {hint})"),
+        None => err.to_string(),
+    }
 }
 
 // ─── BoTTube tool implementations ────────────────────────────────────────────
 
-async fn tool_bottube_search(input: &serde_json::Value) -> Result<String, String> {
+async fn tool_bottube_search(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
     let query = input["query"]
         .as_str()
         .ok_or("Missing required field: query")?;
     let page = input["page"].as_u64().unwrap_or(1) as u32;
+    let verbosity = Verbosity::from_input(input)?;
 
-    let client = BoTTubeClient::new(None);
-    let results = client
+    let client = ctx.bottube(None);
+    let mut results = client
         .search(query, page)
         .await
         .map_err(|e| e.to_string())?;
+    let pagination = SearchPage::from_value(results.clone(), page).ok();
+    if verbosity == Verbosity::Summary {
+        results = summarize(&results);
+    }
 
-    Ok(serde_json::to_string_pretty(&serde_json::json!({
+    Ok(verbosity.render(&serde_json::json!({
         "query": query,
         "page": page,
+        "total_pages": pagination.as_ref().and_then(|p| p.total_pages),
+        "has_next": pagination.map(|p| p.has_next),
         "results": results,
-    }))
-    .unwrap())
+    })))
 }
 
-async fn tool_bottube_trending() -> Result<String, String> {
-    let client = BoTTubeClient::new(None);
+async fn tool_bottube_trending(ctx: &ToolContext) -> Result<String, String> {
+    let client = ctx.bottube(None);
     let results = client.trending().await.map_err(|e| e.to_string())?;
 
     Ok(serde_json::to_string_pretty(&serde_json::json!({
@@ -610,7 +1501,39 @@ async fn tool_bottube_trending() -> Result<String, String> {
     .unwrap())
 }
 
-async fn tool_bottube_comment(input: &serde_json::Value) -> Result<String, String> {
+async fn tool_bottube_channel(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
+    let channel_id = input["channel_id"]
+        .as_str()
+        .ok_or("Missing required field: channel_id")?;
+    let page = input["page"].as_u64().unwrap_or(1) as u32;
+
+    let client = ctx.bottube(None);
+    let channel = client
+        .get_channel(channel_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let videos = client
+        .channel_videos(channel_id, page)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "channel": channel,
+        "page": videos.page,
+        "total_pages": videos.total_pages,
+        "has_next": videos.has_next,
+        "videos": videos.results,
+    }))
+    .unwrap())
+}
+
+async fn tool_bottube_comment(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
     let video_id = input["video_id"]
         .as_str()
         .ok_or("Missing required field: video_id")?;
@@ -621,22 +1544,24 @@ async fn tool_bottube_comment(input: &serde_json::Value) -> Result<String, Strin
         .as_str()
         .ok_or("Missing required field: api_key")?;
     let parent_id = input["parent_id"].as_str();
+    let key_hint = check_api_key(Platform::Bottube, api_key, input)?;
 
-    let client = BoTTubeClient::new(Some(api_key));
-    let result = client
-        .comment(video_id, content, parent_id)
+    let client = ctx.bottube(Some(api_key));
+    let op = client
+        .comment_op(video_id, content, parent_id)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| with_key_hint(e, key_hint))?;
 
     Ok(serde_json::to_string_pretty(&serde_json::json!({
-        "commented": true,
+        "commented": op.success,
         "video_id": video_id,
-        "result": result,
+        "comment_id": op.id,
+        "result": op.raw,
     }))
     .unwrap())
 }
 
-async fn tool_bottube_vote(input: &serde_json::Value) -> Result<String, String> {
+async fn tool_bottube_vote(ctx: &ToolContext, input: &serde_json::Value) -> Result<String, String> {
     let video_id = input["video_id"]
         .as_str()
         .ok_or("Missing required field: video_id")?;
@@ -646,12 +1571,13 @@ async fn tool_bottube_vote(input: &serde_json::Value) -> Result<String, String>
     let api_key = input["api_key"]
         .as_str()
         .ok_or("Missing required field: api_key")?;
+    let key_hint = check_api_key(Platform::Bottube, api_key, input)?;
 
-    let client = BoTTubeClient::new(Some(api_key));
-    let result = client
-        .vote(video_id, vote)
+    let client = ctx.bottube(Some(api_key));
+    let op = client
+        .vote_op(video_id, vote)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| with_key_hint(e, key_hint))?;
 
     let action = match vote {
         1 => "liked",
@@ -661,20 +1587,100 @@ async fn tool_bottube_vote(input: &serde_json::Value) -> Result<String, String>
 
     Ok(serde_json::to_string_pretty(&serde_json::json!({
         "action": action,
+        "success": op.success,
+        "video_id": video_id,
+        "result": op.raw,
+    }))
+    .unwrap())
+}
+
+async fn tool_bottube_report(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
+    let video_id = input["video_id"]
+        .as_str()
+        .ok_or("Missing required field: video_id")?;
+    let reason = input["reason"]
+        .as_str()
+        .ok_or("Missing required field: reason")?;
+    if reason.trim().is_empty() {
+        return Err("reason must not be empty".to_string());
+    }
+    let api_key = input["api_key"]
+        .as_str()
+        .ok_or("Missing required field: api_key")?;
+    let key_hint = check_api_key(Platform::Bottube, api_key, input)?;
+
+    let client = ctx.bottube(Some(api_key));
+    let result = client
+        .report(video_id, reason)
+        .await
+        .map_err(|e| with_key_hint(e, key_hint))?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "reported": true,
         "video_id": video_id,
         "result": result,
     }))
     .unwrap())
 }
 
+async fn tool_bottube_upload(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
+    let api_key = input["api_key"]
+        .as_str()
+        .ok_or("Missing required field: api_key")?;
+    let key_hint = check_api_key(Platform::Bottube, api_key, input)?;
+
+    let client = ctx.bottube(Some(api_key));
+    let mut upload = match input.get("upload").filter(|u| !u.is_null()) {
+        Some(saved) => serde_json::from_value::<ChunkedUpload>(saved.clone())
+            .map_err(|e| format!("Invalid upload: {e}"))?,
+        None => {
+            let path = input["path"]
+                .as_str()
+                .ok_or("Missing required field: path")?;
+            let title = input["title"]
+                .as_str()
+                .ok_or("Missing required field: title")?;
+            let description = input["description"].as_str().unwrap_or("");
+            client
+                .start_upload(std::path::Path::new(path), title, description)
+                .await
+                .map_err(|e| with_key_hint(e, key_hint.clone()))?
+        }
+    };
+    let video = client.upload_parts(&mut upload).await.map_err(|e| {
+        format!(
+            "{}; resume with upload: {}",
+            with_key_hint(e, key_hint),
+            serde_json::to_string(&upload).unwrap()
+        )
+    })?;
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "uploaded": true,
+        "upload_id": upload.upload_id,
+        "parts": upload.part_count(),
+        "result": video,
+    }))
+    .unwrap())
+}
+
 // ─── ClawHub tool implementations ────────────────────────────────────────────
 
-async fn tool_clawhub_search(input: &serde_json::Value) -> Result<String, String> {
+async fn tool_clawhub_search(
+    ctx: &ToolContext,
+    input: &serde_json::Value,
+) -> Result<String, String> {
     let query = input["query"]
         .as_str()
         .ok_or("Missing required field: query")?;
 
-    let grazer = GrazerClient::new();
+    let grazer = ctx.grazer();
     let results = grazer
         .search_clawhub(query, 20)
         .await
@@ -687,18 +1693,52 @@ async fn tool_clawhub_search(input: &serde_json::Value) -> Result<String, String
     .unwrap())
 }
 
-fn pass_fail(passed: bool) -> &'static str {
-    if passed { "PASS" } else { "FAIL" }
+// ─── Meta tool implementations ───────────────────────────────────────────────
+
+fn tool_help(input: &serde_json::Value) -> Result<String, String> {
+    let category = input["category"].as_str();
+    if let Some(category) = category {
+        if !TOOL_CATEGORIES.contains(&category) {
+            return Err(format!(
+                "Unknown category: {category} (expected one of: {})",
+                TOOL_CATEGORIES.join(", ")
+            ));
+        }
+    }
+
+    let tools: Vec<serde_json::Value> = clawrtc_tool_definitions()
+        .into_iter()
+        .filter_map(|def| {
+            let def_category = tool_category(&def.name)?;
+            if category.is_some_and(|c| c != def_category) {
+                return None;
+            }
+            Some(serde_json::json!({
+                "name": def.name,
+                "category": def_category,
+                "description": def.description,
+                "input_schema": def.input_schema,
+            }))
+        })
+        .collect();
+
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "count": tools.len(),
+        "tools": tools,
+    }))
+    .unwrap())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hardware::HardwareInfo;
+    use crate::test_support::{MockResponse, MockServer};
 
     #[test]
     fn test_tool_definitions_count() {
         let defs = clawrtc_tool_definitions();
-        assert_eq!(defs.len(), 15);
+        assert_eq!(defs.len(), 33);
     }
 
     #[test]
@@ -710,20 +1750,638 @@ mod tests {
         assert!(names.contains(&"rustchain_wallet_create"));
         assert!(names.contains(&"rustchain_wallet_show"));
         assert!(names.contains(&"rustchain_attest"));
+        assert!(names.contains(&"rustchain_attest_benchmark"));
+        assert!(names.contains(&"rustchain_challenge"));
         assert!(names.contains(&"rustchain_enroll"));
         assert!(names.contains(&"rustchain_network_status"));
         assert!(names.contains(&"rustchain_fingerprint"));
+        assert!(names.contains(&"rustchain_fingerprint_stability"));
+        assert!(names.contains(&"rustchain_fingerprint_compare"));
+        assert!(names.contains(&"rustchain_hardware_info"));
+        assert!(names.contains(&"rustchain_fingerprint_csv"));
         assert!(names.contains(&"rustchain_transfer"));
+        assert!(names.contains(&"rustchain_transfer_confirm"));
+        assert!(names.contains(&"rustchain_flush_outbox"));
+        assert!(names.contains(&"rustchain_sweep"));
+        assert!(names.contains(&"rustchain_validate_address"));
+        assert!(names.contains(&"rustchain_history"));
         // Grazer tools
         assert!(names.contains(&"grazer_discover"));
         assert!(names.contains(&"grazer_post"));
+        assert!(names.contains(&"grazer_post_signed"));
+        assert!(names.contains(&"grazer_comment"));
+        assert!(names.contains(&"grazer_onboard"));
         // BoTTube tools
         assert!(names.contains(&"bottube_search"));
         assert!(names.contains(&"bottube_trending"));
+        assert!(names.contains(&"bottube_channel"));
         assert!(names.contains(&"bottube_comment"));
+        assert!(names.contains(&"bottube_report"));
         assert!(names.contains(&"bottube_vote"));
+        assert!(names.contains(&"bottube_upload"));
         // ClawHub tools
         assert!(names.contains(&"clawhub_search"));
+        // Meta tools
+        assert!(names.contains(&"clawrtc_help"));
+    }
+
+    #[tokio::test]
+    async fn test_hardware_info_reports_family_and_arch() {
+        let hw = HardwareInfo::detect().unwrap();
+        let out = execute_clawrtc_tool("rustchain_hardware_info", &serde_json::json!({}))
+            .await
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(report["family"], hw.family);
+        assert_eq!(report["arch"], hw.arch);
+        assert!(report["summary"].as_str().unwrap().contains(&hw.arch));
+        assert!(report["arch_multiplier"].as_f64().unwrap() >= 1.0);
+        assert!(report["vm_check"]["likely_vm"].is_boolean());
+    }
+
+    #[tokio::test]
+    async fn test_tool_calls_share_client() {
+        let input = serde_json::json!({ "node_url": "http://shared-node.test:8099" });
+        let ctx = ToolContext::global();
+        let first = get_client(ctx, &input).await;
+        let second = get_client(ctx, &input).await;
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let other = get_client(ctx, &serde_json::json!({ "node_url": "http://other.test" })).await;
+        assert!(!Arc::ptr_eq(&first, &other));
+    }
+
+    fn validate(address: &str) -> serde_json::Value {
+        let out = tool_validate_address(&serde_json::json!({ "address": address })).unwrap();
+        serde_json::from_str(&out).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_balance_tool_distinguishes_down_node_from_zero() {
+        let down = MockServer::start(|_| MockResponse::json(503, serde_json::json!({}))).await;
+        let input = serde_json::json!({ "node_url": down.url(), "wallet": "RTCabc" });
+        let err = execute_clawrtc_tool("rustchain_balance", &input)
+            .await
+            .unwrap_err();
+        assert!(err.contains("Balance unavailable") && err.contains("503"), "{err}");
+
+        let empty = MockServer::start(|_| {
+            MockResponse::json(200, serde_json::json!({ "balance_rtc": 0.0 }))
+        })
+        .await;
+        let input = serde_json::json!({ "node_url": empty.url(), "wallet": "RTCabc" });
+        let out = execute_clawrtc_tool("rustchain_balance", &input)
+            .await
+            .unwrap();
+        let out: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(out["balance_rtc"], "0.0");
+    }
+
+    #[tokio::test]
+    async fn test_balance_tool_shows_exact_balance() {
+        // 2^53 + 1 has no f64 representation
+        let server = MockServer::start(|_| {
+            MockResponse::text(
+                200,
+                "application/json",
+                r#"{"balance_rtc": 9007199254740993}"#,
+            )
+        })
+        .await;
+        let input = serde_json::json!({ "node_url": server.url(), "wallet": "RTCabc" });
+        let out = execute_clawrtc_tool("rustchain_balance", &input)
+            .await
+            .unwrap();
+        let out: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(out["balance_rtc"], "9007199254740993");
+    }
+
+    #[tokio::test]
+    async fn test_history_tool() {
+        let me = RtcWallet::generate().address().to_string();
+        let query = format!("wallet={me}&limit=5");
+        let sender = me.clone();
+        let server = MockServer::start(move |req| {
+            if req.path.contains(&query) {
+                MockResponse::json(
+                    200,
+                    serde_json::json!([{ "from": "RTCxyz", "to": sender, "amount_rtc": 2.0 }]),
+                )
+            } else {
+                MockResponse::json(404, serde_json::json!({}))
+            }
+        })
+        .await;
+        let input = serde_json::json!({ "node_url": server.url(), "wallet": me, "limit": 5 });
+        let out = execute_clawrtc_tool("rustchain_history", &input)
+            .await
+            .unwrap();
+        let history: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(history["count"], 1);
+        assert_eq!(history["transactions"][0]["direction"], "received");
+
+        let other = RtcWallet::generate().address().to_string();
+        let input = serde_json::json!({ "node_url": server.url(), "wallet": other });
+        let out = execute_clawrtc_tool("rustchain_history", &input)
+            .await
+            .unwrap();
+        assert!(out.contains("\"count\": 0"), "{out}");
+    }
+
+    #[tokio::test]
+    async fn test_history_rejects_malformed_wallet() {
+        let server = MockServer::start(|_| MockResponse::json(200, serde_json::json!([]))).await;
+        let wallet = format!("{}&limit=100000&admin=1", RtcWallet::generate().address());
+        let input = serde_json::json!({ "node_url": server.url(), "wallet": wallet });
+        let err = execute_clawrtc_tool("rustchain_history", &input)
+            .await
+            .unwrap_err();
+        assert!(err.contains("Invalid RTC address"), "{err}");
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_rejects_non_hex_address() {
+        let input = serde_json::json!({ "to": format!("RTC{}", "z".repeat(40)), "amount": 1.0 });
+        let err = execute_clawrtc_tool("rustchain_transfer", &input)
+            .await
+            .unwrap_err();
+        assert!(err.contains("Invalid RTC address"), "{err}");
+        assert!(err.contains("hex"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_sweep_fee_applies_to_shared_client() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            p if p.starts_with("/api/balance") => {
+                MockResponse::json(200, serde_json::json!({ "balance_rtc": 3.0 }))
+            }
+            _ => MockResponse::json(200, serde_json::json!({ "ok": true, "tx_hash": "t1" })),
+        })
+        .await;
+        let (_dir, _, ctx) = wallet_context();
+        let to = RtcWallet::generate().address().to_string();
+        let sweep =
+            |fee: f64| serde_json::json!({ "node_url": server.url(), "to": to, "fee_rtc": fee });
+
+        let err = execute_clawrtc_tool_with(&ctx, "rustchain_sweep", &sweep(-0.5))
+            .await
+            .unwrap_err();
+        assert!(err.contains("non-negative"), "{err}");
+        assert!(server.requests().is_empty());
+
+        let out = execute_clawrtc_tool_with(&ctx, "rustchain_sweep", &sweep(0.5))
+            .await
+            .unwrap();
+        let out: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(out["amount_rtc"], "2.5");
+        assert!(server.requests().iter().all(|r| r.path != "/wallet/fee"));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_tracks_payload_in_outbox() {
+        let server = MockServer::sequence(vec![
+            MockResponse::json(200, serde_json::json!({ "ok": true, "tx_hash": "abc" })),
+            MockResponse::json(200, serde_json::json!({ "ok": false, "error": "no funds" })),
+            MockResponse::json(503, serde_json::json!({ "error": "down" })),
+        ])
+        .await;
+        let (_dir, _, ctx) = wallet_context();
+        let to = RtcWallet::generate().address().to_string();
+        let input = serde_json::json!({ "node_url": server.url(), "to": to, "amount": 1.0 });
+
+        // Accepted: kept with its hash until flushed as confirmed
+        execute_clawrtc_tool_with(&ctx, "rustchain_transfer", &input)
+            .await
+            .unwrap();
+        let queued = ctx.outbox().entries().unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].tx_hash.as_deref(), Some("abc"));
+
+        // Rejected: removed again
+        let out = execute_clawrtc_tool_with(&ctx, "rustchain_transfer", &input)
+            .await
+            .unwrap();
+        assert!(out.contains("no funds"), "{out}");
+        assert_eq!(ctx.outbox().entries().unwrap(), queued);
+
+        // Not delivered: stays queued for a flush
+        let err = execute_clawrtc_tool_with(&ctx, "rustchain_transfer", &input)
+            .await
+            .unwrap_err();
+        assert!(err.contains("kept in the outbox"), "{err}");
+        let queued = ctx.outbox().entries().unwrap();
+        assert_eq!(queued.len(), 2);
+        assert_eq!(queued[1].tx_hash, None);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_amount_is_exact() {
+        let server =
+            MockServer::start(|_| MockResponse::json(200, serde_json::json!({ "ok": true }))).await;
+        let (_dir, _, ctx) = wallet_context();
+        let to = RtcWallet::generate().address().to_string();
+        let mut input = serde_json::json!({ "node_url": server.url(), "to": to, "amount": "0.3" });
+
+        let out = execute_clawrtc_tool_with(&ctx, "rustchain_transfer", &input)
+            .await
+            .unwrap();
+        let out: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(out["amount_rtc"], "0.3");
+        assert_eq!(server.requests()[0].json()["amount_rtc"], 0.3);
+
+        // More digits than the signed payload can carry: refused, not rounded
+        input["amount"] = "0.123456789012345678901".into();
+        let err = execute_clawrtc_tool_with(&ctx, "rustchain_transfer", &input)
+            .await
+            .unwrap_err();
+        assert!(err.contains("Invalid amount"), "{err}");
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_offline_mode() {
+        let ctx = ToolContext::default();
+        ctx.set_offline(true);
+        // Unroutable address: without the offline check this would wait for a timeout
+        let input =
+            serde_json::json!({ "node_url": "http://10.255.255.1:8099", "wallet": "RTCabc" });
+
+        let started = std::time::Instant::now();
+        let err = execute_clawrtc_tool_with(&ctx, "rustchain_balance", &input)
+            .await
+            .unwrap_err();
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+        assert!(err.contains("offline mode"), "{err}");
+        for tool in ["grazer_discover", "bottube_trending", "rustchain_transfer"] {
+            assert!(execute_clawrtc_tool_with(&ctx, tool, &input).await.is_err());
+        }
+
+        let out = execute_clawrtc_tool_with(&ctx, "rustchain_fingerprint", &input)
+            .await
+            .unwrap();
+        assert!(out.contains("all_passed"));
+        let address = serde_json::json!({ "address": RtcWallet::generate().address() });
+        assert!(execute_clawrtc_tool_with(&ctx, "rustchain_validate_address", &address)
+            .await
+            .is_ok());
+        assert!(!needs_network("rustchain_hardware_info"));
+        assert!(!needs_network("rustchain_wallet_create"));
+    }
+
+    #[tokio::test]
+    async fn test_wallet_tools_unlock_encrypted_wallet() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ks.json");
+        let wallet = RtcWallet::generate();
+        wallet.save_keystore(&path, "hunter2hunter2").unwrap();
+        let ctx = ToolContext::default()
+            .with_wallet_path(&path)
+            .with_wallet_password("hunter2hunter2");
+        ctx.set_offline(true);
+
+        let out = execute_clawrtc_tool_with(&ctx, "rustchain_wallet_show", &serde_json::json!({}))
+            .await
+            .unwrap();
+        assert!(out.contains(wallet.address()), "{out}");
+
+        // Signing happens before the platform is looked at
+        ctx.set_offline(false);
+        let post = serde_json::json!({ "content": "hello" });
+        let err = execute_clawrtc_tool_with(&ctx, "grazer_post_signed", &post)
+            .await
+            .unwrap_err();
+        assert!(err.contains("platform"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_fingerprint_compare() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/api/fingerprint/reference?arch=x86_64" => MockResponse::json(
+                200,
+                serde_json::json!({
+                    "arch": "x86_64",
+                    "checks": {
+                        "simd_identity": { "simd_flags_count": { "min": 100000 } },
+                        "clock_drift": { "cv": { "min": 0.0001 } },
+                    },
+                }),
+            ),
+            _ => MockResponse::json(404, serde_json::json!({ "error": "unknown arch" })),
+        })
+        .await;
+        let input = serde_json::json!({
+            "arch": "x86_64",
+            "checks": ["simd_identity"],
+            "node_url": server.url(),
+        });
+        let out = execute_clawrtc_tool("rustchain_fingerprint_compare", &input)
+            .await
+            .unwrap();
+        let out: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(out["comparison"]["outside_count"], 2);
+        // Skipped checks report their metrics as missing
+        assert_eq!(out["outside"][0]["metric"], "cv");
+        assert_eq!(out["outside"][0]["status"], "missing");
+        assert_eq!(out["outside"][1]["metric"], "simd_flags_count");
+        assert_eq!(out["outside"][1]["status"], "below");
+        assert!(out["note"].is_null());
+
+        // No reference for this arch: local results only
+        let input = serde_json::json!({
+            "arch": "m68k",
+            "checks": ["simd_identity"],
+            "node_url": server.url(),
+        });
+        let out = execute_clawrtc_tool("rustchain_fingerprint_compare", &input)
+            .await
+            .unwrap();
+        let out: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert!(out["comparison"].is_null());
+        assert_eq!(out["outside"], serde_json::json!([]));
+        assert!(out["note"].as_str().unwrap().contains("m68k"));
+        assert!(out["summary"].is_array());
+    }
+
+    #[tokio::test]
+    async fn test_fingerprint_selected_checks() {
+        let input = serde_json::json!({ "checks": ["simd_identity"] });
+        let out = execute_clawrtc_tool("rustchain_fingerprint", &input).await.unwrap();
+        let out: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(out["checks"]["clock_drift"]["data"]["skipped"], true);
+        assert!(out["checks"]["simd_identity"]["data"]["skipped"].is_null());
+        assert_eq!(out["summary"][0], "Clock Drift:        SKIP");
+
+        let input = serde_json::json!({ "checks": ["cpu_speed"] });
+        let err = execute_clawrtc_tool("rustchain_fingerprint", &input).await.unwrap_err();
+        assert!(err.starts_with("Invalid 'checks'"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_strict_api_key_rejects_mismatch_before_sending() {
+        let input = serde_json::json!({
+            "video_id": "v1",
+            "vote": 1,
+            "api_key": "moltbook_sk_abc",
+            "strict_api_key": true,
+        });
+        let err = execute_clawrtc_tool("bottube_vote", &input).await.unwrap_err();
+        assert!(err.starts_with("Invalid API key"), "{err}");
+        assert!(err.contains("moltbook's prefix"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_bottube_upload_validates_input_before_sending() {
+        let key = "bottube_sk_abc";
+        let input = serde_json::json!({ "api_key": key, "title": "G4" });
+        let err = execute_clawrtc_tool("bottube_upload", &input).await.unwrap_err();
+        assert_eq!(err, "Missing required field: path");
+
+        let input = serde_json::json!({ "api_key": key, "upload": { "upload_id": "u1" } });
+        let err = execute_clawrtc_tool("bottube_upload", &input).await.unwrap_err();
+        assert!(err.starts_with("Invalid upload"), "{err}");
+
+        let upload = ChunkedUpload {
+            upload_id: "u1".into(),
+            path: "g4.mp4".into(),
+            size: 10,
+            part_size: 0,
+            parts_done: 0,
+        };
+        let input = serde_json::json!({ "api_key": key, "upload": upload });
+        let err = execute_clawrtc_tool("bottube_upload", &input).await.unwrap_err();
+        assert!(err.contains("part_size of 0"), "{err}");
+        assert!(err.contains(r#"resume with upload: {"upload_id":"u1""#), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_onboard_validates_keys_before_posting() {
+        let input = serde_json::json!({
+            "name": "Sophia",
+            "bio": "miner",
+            "api_keys": { "moltbook": "moltbook_sk_a", "myspace": "k" },
+        });
+        let err = execute_clawrtc_tool("grazer_onboard", &input).await.unwrap_err();
+        assert_eq!(err, "Unknown platform: myspace");
+
+        let input = serde_json::json!({ "bio": "miner", "api_keys": {} });
+        let err = execute_clawrtc_tool("grazer_onboard", &input).await.unwrap_err();
+        assert!(err.starts_with("Invalid profile"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_comment_checks_parent_and_platform_before_sending() {
+        let input = serde_json::json!({
+            "platform": "moltbook",
+            "content": "Agreed",
+            "api_key": "moltbook_sk_abc",
+        });
+        let err = execute_clawrtc_tool("grazer_comment", &input).await.unwrap_err();
+        assert!(err.contains("parent_id"), "{err}");
+
+        let input = serde_json::json!({
+            "platform": "clawsta",
+            "parent_id": "p1",
+            "content": "Agreed",
+            "api_key": "clawsta_abc",
+        });
+        let err = execute_clawrtc_tool("grazer_comment", &input).await.unwrap_err();
+        assert!(err.contains("Commenting not supported"), "{err}");
+    }
+
+    #[test]
+    fn test_key_hint_is_attached_to_errors() {
+        let err = ClawRtcError::Grazer("401 Unauthorized".into());
+        let hint = check_api_key(Platform::Moltbook, "clawchan_abc", &serde_json::json!({}))
+            .unwrap();
+        let msg = with_key_hint(err, hint);
+        assert!(msg.contains("401 Unauthorized"), "{msg}");
+        assert!(msg.contains("agentchan's prefix clawchan_"), "{msg}");
+    }
+
+    #[test]
+    fn test_summary_omits_heavy_fields() {
+        let video = |i: u32| {
+            serde_json::json!({
+                "id": i,
+                "title": format!("video {i}"),
+                "description": "a long description ".repeat(20),
+                "thumbnail": "data:image/png;base64,AAAA",
+            })
+        };
+        let full = serde_json::json!({
+            "bottube": { "videos": (0..10).map(video).collect::<Vec<_>>(), "page": 1 },
+        });
+
+        let summary = summarize(&full);
+        assert_eq!(summary["bottube"]["page"], 1);
+        assert_eq!(summary["bottube"]["videos"]["count"], 10);
+        let top = summary["bottube"]["videos"]["top"].as_array().unwrap();
+        assert_eq!(top.len(), SUMMARY_TOP_ITEMS);
+        assert_eq!(top[0], serde_json::json!({ "id": 0, "title": "video 0" }));
+
+        let rendered = Verbosity::Summary.render(&summary);
+        assert!(!rendered.contains("description") && !rendered.contains("thumbnail"));
+        assert!(!rendered.contains('\n'));
+        assert!(Verbosity::Full.render(&full).contains("description"));
+    }
+
+    #[tokio::test]
+    async fn test_network_status_summary_omits_miner_list() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/health" => MockResponse::json(200, serde_json::json!({ "ok": true })),
+            _ => MockResponse::json(
+                200,
+                serde_json::json!([{ "miner": "RTCa" }, { "miner": "RTCb" }]),
+            ),
+        })
+        .await;
+        let status = |verbosity: &str| {
+            let input = serde_json::json!({ "node_url": server.url(), "verbosity": verbosity });
+            async move {
+                let out = execute_clawrtc_tool("rustchain_network_status", &input)
+                    .await
+                    .unwrap();
+                serde_json::from_str::<serde_json::Value>(&out).unwrap()
+            }
+        };
+
+        let full = status("full").await;
+        assert_eq!(full["miners"].as_array().unwrap().len(), 2);
+        let summary = status("summary").await;
+        assert_eq!(summary["active_miners"], 2);
+        assert!(summary.get("miners").is_none());
+
+        let input = serde_json::json!({ "node_url": server.url(), "verbosity": "terse" });
+        assert!(execute_clawrtc_tool("rustchain_network_status", &input).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_challenge_returns_nonce_verbatim() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                serde_json::json!({ "nonce": "  N0nce/+= ", "issued_at": 100, "expires_at": "400" }),
+            )
+        })
+        .await;
+        let input = serde_json::json!({ "node_url": server.url() });
+        let out = execute_clawrtc_tool("rustchain_challenge", &input)
+            .await
+            .unwrap();
+        let out: serde_json::Value = serde_json::from_str(&out).unwrap();
+
+        assert_eq!(out["nonce"], "  N0nce/+= ");
+        assert_eq!(out["issued_at"], 100);
+        assert_eq!(out["expires_at"], 400);
+        assert_eq!(out["expired"], true);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/attest/challenge");
+    }
+
+    /// A context signing with a fresh wallet, kept alive by the returned dir.
+    fn wallet_context() -> (tempfile::TempDir, RtcWallet, ToolContext) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wallet.json");
+        let wallet = RtcWallet::generate();
+        wallet.save_plaintext(&path).unwrap();
+        let ctx = ToolContext::default()
+            .with_wallet_path(path)
+            .with_outbox_path(dir.path().join("outbox.json"));
+        (dir, wallet, ctx)
+    }
+
+    fn benchmark_phases(out: &serde_json::Value) -> Vec<Option<f64>> {
+        ["challenge", "entropy", "fingerprint", "submit"]
+            .iter()
+            .map(|p| out["phases_ms"][*p].as_f64())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_attest_benchmark_times_each_phase() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/attest/challenge" => MockResponse::json(
+                200,
+                serde_json::json!({ "nonce": "n1", "issued_at": 100, "expires_at": i64::MAX }),
+            ),
+            _ => MockResponse::json(200, serde_json::json!({ "ok": true }))
+                .with_delay(std::time::Duration::from_millis(20)),
+        })
+        .await;
+        let (_dir, wallet, ctx) = wallet_context();
+        let input = serde_json::json!({ "node_url": server.url(), "submit": true });
+        let out = execute_clawrtc_tool_with(&ctx, "rustchain_attest_benchmark", &input)
+            .await
+            .unwrap();
+        let out: serde_json::Value = serde_json::from_str(&out).unwrap();
+
+        let phases: Vec<f64> = benchmark_phases(&out).into_iter().map(Option::unwrap).collect();
+        let total = out["total_ms"].as_f64().unwrap();
+        assert!(phases.iter().all(|&ms| ms >= 0.0));
+        assert!(phases[3] >= 20.0, "submit should include the node's delay: {out}");
+        assert!(phases.iter().sum::<f64>() <= total + 1e-6, "{out}");
+        assert_eq!(out["submitted"], true);
+        assert_eq!(out["nonce_expired"], false);
+        assert_eq!(out["nonce_ttl_secs"], i64::MAX - 100);
+        let submitted = server.requests().into_iter().find(|r| r.path == "/attest/submit").unwrap();
+        assert_eq!(submitted.json()["miner"], wallet.address());
+        assert!(submitted.json()["fingerprint"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_attest_benchmark_can_skip_submit() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, serde_json::json!({ "nonce": "n1" }))
+        })
+        .await;
+        let (_dir, _, ctx) = wallet_context();
+        // Submitting is opt-in
+        let input = serde_json::json!({ "node_url": server.url(), "fingerprints": false });
+        let out = execute_clawrtc_tool_with(&ctx, "rustchain_attest_benchmark", &input)
+            .await
+            .unwrap();
+        let out: serde_json::Value = serde_json::from_str(&out).unwrap();
+
+        let phases = benchmark_phases(&out);
+        assert!(phases[..3].iter().all(Option::is_some));
+        assert_eq!(phases[3], None);
+        assert_eq!(out["submitted"], false);
+        assert!(out["nonce_ttl_secs"].is_null());
+        assert!(server.requests().iter().all(|r| r.path != "/attest/submit"));
+    }
+
+    #[test]
+    fn test_validate_address_tool() {
+        let w = crate::RtcWallet::generate();
+        let ok = validate(w.address());
+        assert_eq!(ok["valid"], true);
+        assert!(ok["reason"].is_null());
+
+        let prefix = validate(&w.address().replacen("RTC", "XYZ", 1));
+        assert_eq!(prefix["valid"], false);
+        assert!(prefix["reason"].as_str().unwrap().contains("RTC"));
+
+        let short = validate("RTC1234");
+        assert_eq!(short["valid"], false);
+        assert!(short["reason"].as_str().unwrap().contains("43"));
+
+        // Upcase one lowercase letter of a checksummed address, keeping it mixed-case
+        let bad = std::iter::repeat_with(crate::RtcWallet::generate)
+            .find_map(|w| {
+                let c = crate::wallet::checksum_address(w.address()).unwrap();
+                let pos = c[3..].find(|ch: char| ch.is_ascii_lowercase())? + 3;
+                let mut t = c.clone();
+                t.replace_range(pos..pos + 1, &c[pos..pos + 1].to_ascii_uppercase());
+                t[3..].contains(|ch: char| ch.is_ascii_lowercase()).then_some(t)
+            })
+            .unwrap();
+        let res = validate(&bad);
+        assert_eq!(res["valid"], false);
+        assert!(res["reason"].as_str().unwrap().contains("checksum"));
+
+        assert!(tool_validate_address(&serde_json::json!({})).is_err());
     }
 
     #[test]
@@ -744,12 +2402,54 @@ mod tests {
         assert!(is_clawrtc_tool("rustchain_transfer"));
         assert!(is_clawrtc_tool("grazer_discover"));
         assert!(is_clawrtc_tool("grazer_post"));
+        assert!(is_clawrtc_tool("grazer_comment"));
         assert!(is_clawrtc_tool("bottube_search"));
         assert!(is_clawrtc_tool("bottube_trending"));
+        assert!(is_clawrtc_tool("bottube_channel"));
         assert!(is_clawrtc_tool("bottube_comment"));
         assert!(is_clawrtc_tool("bottube_vote"));
+        assert!(is_clawrtc_tool("bottube_upload"));
         assert!(is_clawrtc_tool("clawhub_search"));
+        assert!(is_clawrtc_tool("clawrtc_help"));
         assert!(!is_clawrtc_tool("file_read"));
+        assert!(!is_clawrtc_tool("grazerx_post"));
         assert!(!is_clawrtc_tool("web_search"));
     }
+
+    #[tokio::test]
+    async fn test_help_lists_every_tool_by_category() {
+        let ctx = ToolContext::default();
+        let out = execute_clawrtc_tool_with(&ctx, "clawrtc_help", &serde_json::json!({}))
+            .await
+            .unwrap();
+        let help: serde_json::Value = serde_json::from_str(&out).unwrap();
+        let tools = help["tools"].as_array().unwrap();
+        let defs = clawrtc_tool_definitions();
+        assert_eq!(help["count"], defs.len());
+        assert_eq!(tools.len(), defs.len());
+        for (tool, def) in tools.iter().zip(&defs) {
+            assert_eq!(tool["name"], def.name.as_str());
+            assert_eq!(tool["description"], def.description.as_str());
+            assert_eq!(tool["input_schema"], def.input_schema);
+            assert!(is_clawrtc_tool(&def.name));
+            let category = tool["category"].as_str().unwrap();
+            assert!(
+                def.name.starts_with(&format!("{category}_")),
+                "{}",
+                def.name
+            );
+        }
+
+        let out = tool_help(&serde_json::json!({ "category": "bottube" })).unwrap();
+        let help: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(help["count"], 7);
+        assert!(help["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|t| t["category"] == "bottube"));
+
+        let err = tool_help(&serde_json::json!({ "category": "ethereum" })).unwrap_err();
+        assert!(err.contains("rustchain"), "{err}");
+    }
 }
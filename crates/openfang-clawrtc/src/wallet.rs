@@ -1,23 +1,129 @@
 //! Ed25519 wallet for RustChain (RTC).
 //!
 //! Generates Ed25519 key pairs, derives RTC addresses, and signs transactions.
-//! Address format: `"RTC"` + first 40 hex chars of `SHA-256(public_key_bytes)`.
+//! Address derivation and validation live in [`crate::address`].
 
+use crate::address::derive_address;
+pub use crate::address::{
+    checksum_address, is_valid_rtc_address, validate_rtc_address, verify_address_checksum,
+};
+use crate::amount::{self, Decimal};
+use crate::client::{RustChainClient, TransferResponse};
 use crate::error::{ClawRtcError, ClawRtcResult};
-use crate::keystore::Keystore;
+use crate::keystore::{write_private, Keystore};
+use crate::nonce::NonceManager;
+use crate::receipt::TransferReceipt;
+use crate::signer::{self, Signer};
 use chrono::Utc;
-use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
+use ed25519_dalek::{Signature, Signer as _, SigningKey, Verifier, VerifyingKey};
+use hmac::{Hmac, Mac};
 use rand::rngs::OsRng;
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::Path;
-use zeroize::Zeroize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Bytes drawn from the RNG for the [`EntropyAudit`] self-test.
+const AUDIT_SAMPLE_BYTES: usize = 4096;
+
+/// Domain separator for viewing-key derivation and its attestation.
+const VIEWING_KEY_DOMAIN: &[u8] = b"rtc-viewing-key-v1";
 
 /// An RTC wallet backed by an Ed25519 key pair.
+///
+/// The secret key is wiped from memory when the wallet is dropped.
 pub struct RtcWallet {
     signing_key: SigningKey,
     verifying_key: VerifyingKey,
     address: String,
+    nonces: Arc<NonceManager>,
+}
+
+// The wipe is `SigningKey`'s own `Drop`, which ed25519-dalek only provides
+// with its `zeroize` feature. Require it here so the feature can't be lost
+// without breaking the build.
+const _: fn() = || {
+    fn wiped_on_drop<T: ZeroizeOnDrop>() {}
+    wiped_on_drop::<SigningKey>();
+};
+
+impl ZeroizeOnDrop for RtcWallet {}
+
+/// One transfer in a [`RtcWallet::sign_batch_transfer`] batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchTransfer {
+    pub to_address: String,
+    pub amount_rtc: Decimal,
+    #[serde(default)]
+    pub memo: String,
+}
+
+/// Record of the randomness behind a wallet created with
+/// [`RtcWallet::generate_audited`].
+///
+/// The self-test draws a separate sample from the same RNG and runs two cheap
+/// statistical checks on it. Passing them cannot prove the RNG is secure; it
+/// catches gross failures such as a stuck or heavily biased source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntropyAudit {
+    /// RNG that produced the key.
+    pub source: String,
+    /// Operating system the RNG drew from.
+    pub os: String,
+    /// Size of the self-test sample in bytes.
+    pub sample_bytes: usize,
+    /// Fraction of 1 bits in the sample (ideal 0.5).
+    pub ones_ratio: f64,
+    /// Chi-square statistic of byte frequencies (255 degrees of freedom, mean 255).
+    pub chi_square: f64,
+    /// Whether both checks were within bounds.
+    pub passed: bool,
+}
+
+impl EntropyAudit {
+    /// Run the self-test on `sample`.
+    fn from_sample(source: &str, sample: &[u8]) -> Self {
+        let ones: u64 = sample.iter().map(|b| u64::from(b.count_ones())).sum();
+        let bits = (sample.len() * 8).max(1) as f64;
+        let ones_ratio = ones as f64 / bits;
+
+        let mut counts = [0u64; 256];
+        for &b in sample {
+            counts[b as usize] += 1;
+        }
+        let expected = (sample.len() as f64 / 256.0).max(f64::MIN_POSITIVE);
+        let chi_square = counts
+            .iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum::<f64>();
+
+        // Both bounds sit more than 6 standard deviations from the mean for a
+        // 4 KiB sample, so a healthy RNG essentially never trips them.
+        let passed = (0.47..=0.53).contains(&ones_ratio) && chi_square < 400.0;
+
+        Self {
+            source: source.to_string(),
+            os: std::env::consts::OS.to_string(),
+            sample_bytes: sample.len(),
+            ones_ratio,
+            chi_square,
+            passed,
+        }
+    }
+}
+
+/// Outcome of a transfer submitted on the wallet's behalf.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferResult {
+    pub to_address: String,
+    pub amount_rtc: Decimal,
+    pub fee_rtc: Decimal,
+    /// The node's response to the signed transfer.
+    pub response: TransferResponse,
 }
 
 /// Plaintext wallet JSON (Python-compatible format).
@@ -41,9 +147,22 @@ impl RtcWallet {
             signing_key,
             verifying_key,
             address,
+            nonces: NonceManager::shared(),
         }
     }
 
+    /// Generate a new random wallet and report on the entropy used.
+    ///
+    /// Keys come from `OsRng` exactly as in [`RtcWallet::generate`]; the
+    /// audit is for transparency only.
+    pub fn generate_audited() -> (Self, EntropyAudit) {
+        let mut sample = vec![0u8; AUDIT_SAMPLE_BYTES];
+        OsRng.fill_bytes(&mut sample);
+        let audit = EntropyAudit::from_sample("OsRng", &sample);
+        sample.zeroize();
+        (Self::generate(), audit)
+    }
+
     /// Restore from a hex-encoded private key (64 hex chars = 32 bytes).
     pub fn from_private_key_hex(hex_key: &str) -> ClawRtcResult<Self> {
         let bytes = hex::decode(hex_key).map_err(|e| ClawRtcError::Crypto(e.to_string()))?;
@@ -63,22 +182,56 @@ impl RtcWallet {
             signing_key,
             verifying_key,
             address,
+            nonces: NonceManager::shared(),
         })
     }
 
+    /// Take transfer nonces from `nonces` instead of [`NonceManager::global`].
+    ///
+    /// Every transfer this wallet signs draws from the one manager, so give
+    /// it one shared with anything else signing for the same address.
+    pub fn with_nonce_manager(mut self, nonces: Arc<NonceManager>) -> Self {
+        self.nonces = nonces;
+        self
+    }
+
     /// Load from a plaintext wallet JSON file.
+    ///
+    /// Fails if the stored `address` doesn't match the one derived from the
+    /// private key, which means the file was corrupted or edited.
     pub fn from_file(path: &Path) -> ClawRtcResult<Self> {
         let data = std::fs::read_to_string(path)?;
         let wf: WalletFile =
             serde_json::from_str(&data).map_err(|e| ClawRtcError::Crypto(e.to_string()))?;
-        Self::from_private_key_hex(&wf.private_key)
+        let wallet = Self::from_private_key_hex(&wf.private_key)?;
+        wallet.verify_stored_address(&wf.address)?;
+        Ok(wallet)
+    }
+
+    /// Check an address stored alongside the key against the derived one.
+    pub fn verify_stored_address(&self, stored: &str) -> ClawRtcResult<()> {
+        if stored != self.address {
+            return Err(ClawRtcError::Crypto(format!(
+                "address/key mismatch: file says {stored}, key derives {}",
+                self.address
+            )));
+        }
+        Ok(())
     }
 
     /// Load from an AES-256-GCM encrypted keystore file.
+    ///
+    /// Like [`RtcWallet::from_file`], fails if the keystore's `address`
+    /// doesn't match the decrypted key.
     pub fn from_keystore(path: &Path, password: &str) -> ClawRtcResult<Self> {
-        let ks = Keystore::load(path)?;
-        let private_key_hex = ks.decrypt(password)?;
-        Self::from_private_key_hex(&private_key_hex)
+        Self::unlock(&Keystore::load(path)?, password)
+    }
+
+    fn unlock(ks: &Keystore, password: &str) -> ClawRtcResult<Self> {
+        let private_key_hex = Zeroizing::new(ks.decrypt(password)?);
+        let wallet = Self::from_private_key_hex(&private_key_hex)?;
+        wallet.verify_stored_address(&ks.address)?;
+        Ok(wallet)
     }
 
     /// The wallet's RTC address.
@@ -92,8 +245,12 @@ impl RtcWallet {
     }
 
     /// Hex-encoded private key (64 chars). Handle with care.
-    pub fn private_key_hex(&self) -> String {
-        hex::encode(self.signing_key.to_bytes())
+    ///
+    /// Wiped when the returned value is dropped; a copy taken out of it is
+    /// the caller's to wipe.
+    pub fn private_key_hex(&self) -> Zeroizing<String> {
+        let secret = Zeroizing::new(self.signing_key.to_bytes());
+        Zeroizing::new(hex::encode(secret.as_slice()))
     }
 
     /// Sign an arbitrary message, returning the hex-encoded signature (128 chars).
@@ -102,79 +259,412 @@ impl RtcWallet {
         hex::encode(sig.to_bytes())
     }
 
+    /// Check a hex signature from [`RtcWallet::sign`] against this wallet's key.
+    ///
+    /// Returns `Ok(false)` for a well-formed signature that does not match.
+    pub fn verify(&self, message: &[u8], signature_hex: &str) -> ClawRtcResult<bool> {
+        let signature = parse_signature(signature_hex)?;
+        Ok(self.verifying_key.verify(message, &signature).is_ok())
+    }
+
+    /// Hex-encoded viewing key (64 chars) derived from the signing key.
+    ///
+    /// The viewing key is a deterministic HMAC of the signing key. It cannot
+    /// sign transactions and the signing key cannot be recovered from it, so it
+    /// can be handed to a third party (an auditor, an exchange) that needs to
+    /// check data tagged by this wallet with [`RtcWallet::view_tag`].
+    ///
+    /// Guarantees are limited: anyone holding the viewing key can produce
+    /// valid tags, so a tag proves possession of the viewing key, not of the
+    /// signing key. The viewing key cannot be rotated without rotating the
+    /// wallet, and once shared it stays valid for every past and future tag.
+    /// Use [`RtcWallet::viewing_key_attestation`] to bind it to the address.
+    pub fn viewing_key(&self) -> String {
+        hex::encode(self.viewing_key_bytes().as_slice())
+    }
+
+    /// Signature binding [`RtcWallet::viewing_key`] to this wallet's public key.
+    ///
+    /// Check with [`verify_viewing_key`].
+    pub fn viewing_key_attestation(&self) -> String {
+        self.sign(&viewing_key_message(&self.viewing_key()))
+    }
+
+    /// Hex-encoded HMAC tag over `data` keyed by the viewing key.
+    ///
+    /// Check with [`verify_view_tag`].
+    pub fn view_tag(&self, data: &[u8]) -> String {
+        view_tag_with_key(self.viewing_key_bytes().as_slice(), data)
+    }
+
+    fn viewing_key_bytes(&self) -> Zeroizing<[u8; 32]> {
+        let secret = Zeroizing::new(self.signing_key.to_bytes());
+        let mut mac = HmacSha256::new_from_slice(secret.as_slice())
+            .expect("HMAC accepts any key length");
+        mac.update(VIEWING_KEY_DOMAIN);
+        Zeroizing::new(mac.finalize().into_bytes().into())
+    }
+
     /// Sign a transfer transaction, returning the full signed payload.
+    ///
+    /// The nonce comes from the wallet's [`NonceManager`]. See
+    /// [`signer::sign_transaction`] to sign with a key held elsewhere.
     pub fn sign_transaction(
         &self,
         to_address: &str,
-        amount_rtc: f64,
+        amount_rtc: Decimal,
         memo: &str,
     ) -> ClawRtcResult<serde_json::Value> {
-        let nonce = Utc::now().timestamp_millis();
-        let payload = serde_json::json!({
-            "from": self.address,
-            "to": to_address,
-            "amount": amount_rtc,
-            "memo": memo,
-            "nonce": nonce,
-        });
-        let canonical = serde_json::to_string(&payload)?;
-        let signature = self.sign(canonical.as_bytes());
+        let nonce = self.nonces.next();
+        signer::sign_transaction_with_nonce(self, to_address, amount_rtc, memo, nonce)
+    }
 
-        Ok(serde_json::json!({
-            "from_address": self.address,
-            "to_address": to_address,
-            "amount_rtc": amount_rtc,
-            "memo": memo,
-            "nonce": nonce,
-            "signature": signature,
-            "public_key": self.public_key_hex(),
-        }))
+    /// Sign a transfer and issue a [`TransferReceipt`] for it.
+    ///
+    /// The receipt is for the wallet owner to keep; only the payload is
+    /// submitted to the node.
+    pub fn sign_transaction_with_receipt(
+        &self,
+        to_address: &str,
+        amount_rtc: Decimal,
+        memo: &str,
+    ) -> ClawRtcResult<(serde_json::Value, TransferReceipt)> {
+        let signed = self.sign_transaction(to_address, amount_rtc, memo)?;
+        let receipt = TransferReceipt::issue(self, &signed)?;
+        Ok((signed, receipt))
+    }
+
+    /// Sign several transfers with a contiguous block of nonces.
+    ///
+    /// The block is reserved atomically from the same [`NonceManager`] as
+    /// [`RtcWallet::sign_transaction`], so concurrent batches and single
+    /// transfers never share or interleave nonces. Payloads are returned in
+    /// input order and should be submitted in that order.
+    pub fn sign_batch_transfer(
+        &self,
+        transfers: &[BatchTransfer],
+    ) -> ClawRtcResult<Vec<serde_json::Value>> {
+        let count = u32::try_from(transfers.len())
+            .map_err(|_| ClawRtcError::Crypto("batch too large".into()))?;
+        self.nonces
+            .reserve(count)
+            .zip(transfers)
+            .map(|(nonce, t)| {
+                signer::sign_transaction_with_nonce(
+                    self,
+                    &t.to_address,
+                    t.amount_rtc,
+                    &t.memo,
+                    nonce,
+                )
+            })
+            .collect()
+    }
+
+    /// Transfer the wallet's entire balance, less the transfer fee, to
+    /// `destination`.
+    ///
+    /// The amount is the exact balance less the fee, truncated to a millionth
+    /// of an RTC. Fails with [`ClawRtcError::InsufficientFunds`] if nothing
+    /// would be left to send, and with [`ClawRtcError::Config`] if the fee is
+    /// negative.
+    pub async fn sweep_to(
+        &self,
+        client: &RustChainClient,
+        destination: &str,
+    ) -> ClawRtcResult<TransferResult> {
+        validate_rtc_address(destination)?;
+        if destination.eq_ignore_ascii_case(&self.address) {
+            return Err(ClawRtcError::InvalidAddress(
+                "cannot sweep a wallet into itself".to_string(),
+            ));
+        }
+
+        let balance = client.balance_decimal(&self.address).await?;
+        let fee = client.transfer_fee().await?;
+        if fee < Decimal::ZERO {
+            return Err(ClawRtcError::Config(format!(
+                "transfer fee must be a non-negative amount, got {fee}"
+            )));
+        }
+        let amount = (balance - fee).trunc_with_scale(6).normalize();
+        if amount <= Decimal::ZERO {
+            return Err(ClawRtcError::InsufficientFunds(format!(
+                "balance {balance} RTC does not cover the {fee} RTC fee"
+            )));
+        }
+
+        let signed = self.sign_transaction(destination, amount, "sweep")?;
+        let response = client.transfer_signed(&signed).await?;
+        Ok(TransferResult {
+            to_address: destination.to_string(),
+            amount_rtc: amount,
+            fee_rtc: fee,
+            response,
+        })
     }
 
     /// Save as plaintext JSON (Python-compatible format).
     pub fn save_plaintext(&self, path: &Path) -> ClawRtcResult<()> {
-        let wf = WalletFile {
+        let mut wf = WalletFile {
             address: self.address.clone(),
             public_key: self.public_key_hex(),
-            private_key: self.private_key_hex(),
+            private_key: self.private_key_hex().to_string(),
             created: Utc::now().to_rfc3339(),
             curve: "Ed25519".to_string(),
             network: "rustchain-mainnet".to_string(),
         };
-        let json = serde_json::to_string_pretty(&wf)?;
+        let json = serde_json::to_string_pretty(&wf);
+        wf.private_key.zeroize();
+        let json = Zeroizing::new(json?);
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
-        std::fs::write(path, &json)?;
-        // Restrict permissions on Unix
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
-        }
-        Ok(())
+        // Owner-only before the key is written, on Unix and Windows alike
+        write_private(path, &json)
     }
 
     /// Save as an encrypted keystore file.
     pub fn save_keystore(&self, path: &Path, password: &str) -> ClawRtcResult<()> {
-        let ks = Keystore::encrypt(&self.private_key_hex(), password, &self.address)?;
+        let secret = self.private_key_hex();
+        let ks = Keystore::encrypt(&secret, password, &self.address)?;
         ks.save(path)?;
         Ok(())
     }
 }
 
-/// Derive an RTC address from a verifying (public) key.
+impl Signer for RtcWallet {
+    fn sign(&self, message: &[u8]) -> ClawRtcResult<[u8; 64]> {
+        Ok(self.signing_key.sign(message).to_bytes())
+    }
+
+    fn public_key(&self) -> [u8; 32] {
+        self.verifying_key.to_bytes()
+    }
+}
+
+/// Load a wallet from disk, auto-detecting the file format.
+///
+/// Encrypted keystores (JSON with `ciphertext`/`salt`) require `password`;
+/// plaintext wallet files (JSON with `private_key`) ignore it.
+pub fn load(path: &Path, password: Option<&str>) -> ClawRtcResult<RtcWallet> {
+    let data = std::fs::read_to_string(path)?;
+    let value: serde_json::Value = serde_json::from_str(&data)
+        .map_err(|e| ClawRtcError::Crypto(format!("Unreadable wallet file: {e}")))?;
+
+    if value.get("ciphertext").is_some() || value.get("salt").is_some() {
+        let password = password.ok_or_else(|| {
+            ClawRtcError::KeystoreDecrypt(format!(
+                "{} is an encrypted keystore; a password is required",
+                path.display()
+            ))
+        })?;
+        let ks: Keystore = serde_json::from_value(value)
+            .map_err(|e| ClawRtcError::KeystoreDecrypt(e.to_string()))?;
+        return RtcWallet::unlock(&ks, password);
+    }
+
+    if let Some(private_key) = value.get("private_key").and_then(|v| v.as_str()) {
+        let wallet = RtcWallet::from_private_key_hex(private_key)?;
+        if let Some(stored) = value.get("address").and_then(|v| v.as_str()) {
+            wallet.verify_stored_address(stored)?;
+        }
+        return Ok(wallet);
+    }
+
+    Err(ClawRtcError::Crypto(format!(
+        "Unrecognized wallet format: {}",
+        path.display()
+    )))
+}
+
+/// Path of the integrity seal written next to a backup by [`export_sealed`].
+pub fn seal_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".sha256");
+    path.with_file_name(name)
+}
+
+/// Seal a wallet backup (plaintext or keystore) against corruption.
+///
+/// Writes the SHA-256 of the file's bytes to [`seal_path`] in `sha256sum`
+/// format, so the backup can also be checked with `sha256sum -c`. Returns
+/// the seal's path.
+pub fn export_sealed(path: &Path) -> ClawRtcResult<PathBuf> {
+    let data = std::fs::read(path)?;
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let seal = seal_path(path);
+    std::fs::write(
+        &seal,
+        format!("{}  {file_name}\n", hex::encode(Sha256::digest(&data))),
+    )?;
+    Ok(seal)
+}
+
+/// Load a wallet backup sealed by [`export_sealed`], checking the seal first.
 ///
-/// Format: `"RTC"` + first 40 hex chars of `SHA-256(public_key_bytes)`.
-fn derive_address(verifying_key: &VerifyingKey) -> String {
-    let hash = Sha256::digest(verifying_key.as_bytes());
-    let hex_hash = hex::encode(hash);
-    format!("RTC{}", &hex_hash[..40])
+/// A missing seal, or a file whose digest no longer matches (bit-rot,
+/// truncation, edits), fails with [`ClawRtcError::Crypto`] before the key is
+/// parsed. `password` is passed to [`load`].
+pub fn import_sealed(path: &Path, password: Option<&str>) -> ClawRtcResult<RtcWallet> {
+    let seal = seal_path(path);
+    let seal_text = std::fs::read_to_string(&seal).map_err(|e| {
+        ClawRtcError::Crypto(format!("Missing backup seal {}: {e}", seal.display()))
+    })?;
+    let expected = seal_text.split_whitespace().next().unwrap_or_default();
+    let actual = hex::encode(Sha256::digest(std::fs::read(path)?));
+    if !expected.eq_ignore_ascii_case(&actual) {
+        return Err(ClawRtcError::Crypto(format!(
+            "Backup integrity check failed for {}: expected SHA-256 {expected}, got {actual}",
+            path.display()
+        )));
+    }
+    load(path, password)
+}
+
+/// Serialize `value` as compact JSON with object keys sorted lexicographically
+/// at every level.
+///
+/// Signed messages go through this rather than `serde_json::to_string`, whose
+/// key order follows the map implementation (insertion order under serde_json's
+/// `preserve_order` feature). The node re-serializes with sorted keys before
+/// verifying, so any other order breaks the signature.
+pub fn canonical_json(value: &serde_json::Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, v)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::from(key.as_str()).to_string());
+                out.push(':');
+                write_canonical(v, out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, v) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(v, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// The message whose [`canonical_json`] is signed for a transfer.
+///
+/// The amount is signed as the `f64` the payload carries; see
+/// [`amount::to_wire`].
+pub(crate) fn transfer_message(
+    from: &str,
+    to: &str,
+    amount_rtc: Decimal,
+    memo: &str,
+    nonce: i64,
+) -> serde_json::Value {
+    serde_json::json!({
+        "from": from,
+        "to": to,
+        "amount": amount::to_f64(amount_rtc),
+        "memo": memo,
+        "nonce": nonce,
+    })
+}
+
+/// Verify a hex-encoded Ed25519 signature over `message` against a hex public key.
+///
+/// Returns `Ok(false)` for a well-formed signature that does not match, and an
+/// error if either hex value is malformed. A relayer can use this to check a
+/// [`RtcWallet::sign_transaction`] payload before forwarding it.
+pub fn verify_signature(
+    public_key_hex: &str,
+    message: &[u8],
+    signature_hex: &str,
+) -> ClawRtcResult<bool> {
+    let verifying_key = parse_public_key(public_key_hex)?;
+    let signature = parse_signature(signature_hex)?;
+    Ok(verifying_key.verify(message, &signature).is_ok())
+}
+
+/// Check that `attestation` (from [`RtcWallet::viewing_key_attestation`]) binds
+/// `viewing_key` to the wallet with `public_key_hex`.
+pub fn verify_viewing_key(
+    public_key_hex: &str,
+    viewing_key: &str,
+    attestation: &str,
+) -> ClawRtcResult<bool> {
+    verify_signature(public_key_hex, &viewing_key_message(viewing_key), attestation)
+}
+
+/// Check a tag produced by [`RtcWallet::view_tag`] using the shared viewing key.
+pub fn verify_view_tag(viewing_key: &str, data: &[u8], tag: &str) -> ClawRtcResult<bool> {
+    let key = hex::decode(viewing_key)
+        .map(Zeroizing::new)
+        .map_err(|e| ClawRtcError::Crypto(format!("Invalid viewing key hex: {e}")))?;
+    let tag = hex::decode(tag)
+        .map_err(|e| ClawRtcError::Crypto(format!("Invalid view tag hex: {e}")))?;
+    let mut mac = HmacSha256::new_from_slice(&key).expect("HMAC accepts any key length");
+    mac.update(data);
+    Ok(mac.verify_slice(&tag).is_ok())
+}
+
+fn viewing_key_message(viewing_key: &str) -> Vec<u8> {
+    let mut msg = VIEWING_KEY_DOMAIN.to_vec();
+    msg.push(b':');
+    msg.extend_from_slice(viewing_key.as_bytes());
+    msg
+}
+
+fn view_tag_with_key(key: &[u8], data: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Derive the mainnet RTC address of a hex-encoded Ed25519 public key.
+///
+/// For watch-only wallets and external signers that never expose the
+/// private key.
+pub fn address_from_public_key_hex(pubkey_hex: &str) -> ClawRtcResult<String> {
+    Ok(derive_address(&parse_public_key(pubkey_hex.trim())?))
+}
+
+fn parse_signature(signature_hex: &str) -> ClawRtcResult<Signature> {
+    let sig_bytes: [u8; 64] = hex::decode(signature_hex)
+        .map_err(|e| ClawRtcError::Crypto(format!("Invalid signature hex: {e}")))?
+        .try_into()
+        .map_err(|_| ClawRtcError::Crypto("Signature must be 64 bytes".to_string()))?;
+    Ok(Signature::from_bytes(&sig_bytes))
+}
+
+/// Parse a hex-encoded Ed25519 public key.
+pub(crate) fn parse_public_key(public_key_hex: &str) -> ClawRtcResult<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(public_key_hex)
+        .map_err(|e| ClawRtcError::Crypto(format!("Invalid public key hex: {e}")))?
+        .try_into()
+        .map_err(|_| ClawRtcError::Crypto("Public key must be 32 bytes".to_string()))?;
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| ClawRtcError::Crypto(format!("Invalid public key: {e}")))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{MockResponse, MockServer, RecordedRequest};
 
     #[test]
     fn test_wallet_generate() {
@@ -185,6 +675,34 @@ mod tests {
         assert_eq!(w.private_key_hex().len(), 64);
     }
 
+    #[test]
+    fn test_generate_audited() {
+        let (w, audit) = RtcWallet::generate_audited();
+        assert!(is_valid_rtc_address(w.address()));
+        let sig = w.sign(b"audit");
+        assert!(verify_signature(&w.public_key_hex(), b"audit", &sig).unwrap());
+
+        assert_eq!(audit.source, "OsRng");
+        assert_eq!(audit.os, std::env::consts::OS);
+        assert_eq!(audit.sample_bytes, AUDIT_SAMPLE_BYTES);
+        assert!(audit.passed, "{audit:?}");
+    }
+
+    #[test]
+    fn test_entropy_audit_flags_bad_sample() {
+        let stuck = EntropyAudit::from_sample("test", &[0u8; AUDIT_SAMPLE_BYTES]);
+        assert_eq!(stuck.ones_ratio, 0.0);
+        assert!(!stuck.passed);
+
+        // Balanced bits but only two byte values
+        let biased: Vec<u8> = (0..AUDIT_SAMPLE_BYTES)
+            .map(|i| if i % 2 == 0 { 0x0f } else { 0xf0 })
+            .collect();
+        let audit = EntropyAudit::from_sample("test", &biased);
+        assert_eq!(audit.ones_ratio, 0.5);
+        assert!(!audit.passed);
+    }
+
     #[test]
     fn test_wallet_roundtrip_hex() {
         let w1 = RtcWallet::generate();
@@ -194,6 +712,20 @@ mod tests {
         assert_eq!(w1.public_key_hex(), w2.public_key_hex());
     }
 
+    #[test]
+    fn test_wallet_roundtrips_after_original_dropped() {
+        fn wiped_on_drop<T: ZeroizeOnDrop>() {}
+        wiped_on_drop::<RtcWallet>();
+
+        let w1 = RtcWallet::generate();
+        let (address, sig) = (w1.address().to_string(), w1.sign(b"msg"));
+        let pk = w1.private_key_hex();
+        drop(w1);
+        let w2 = RtcWallet::from_private_key_hex(&pk).unwrap();
+        assert_eq!(w2.address(), address);
+        assert_eq!(w2.sign(b"msg"), sig);
+    }
+
     #[test]
     fn test_wallet_sign_verify() {
         let w = RtcWallet::generate();
@@ -209,6 +741,197 @@ mod tests {
         w1.save_plaintext(&path).unwrap();
         let w2 = RtcWallet::from_file(&path).unwrap();
         assert_eq!(w1.address(), w2.address());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        #[cfg(windows)]
+        assert!(crate::windows_acl::is_owner_only(&path).unwrap());
+    }
+
+    #[test]
+    fn test_wallet_file_address_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tampered.json");
+        let key = RtcWallet::from_private_key_hex(&"11".repeat(32)).unwrap();
+        let other = RtcWallet::from_private_key_hex(&"22".repeat(32)).unwrap();
+        let fixture = serde_json::json!({
+            "address": other.address(),
+            "public_key": key.public_key_hex(),
+            "private_key": key.private_key_hex().as_str(),
+            "created": "2025-01-01T00:00:00Z",
+            "curve": "Ed25519",
+            "network": "rustchain-mainnet",
+        });
+        std::fs::write(&path, fixture.to_string()).unwrap();
+
+        let err = RtcWallet::from_file(&path).err().unwrap();
+        assert!(matches!(err, ClawRtcError::Crypto(_)));
+        assert!(err.to_string().contains("address/key mismatch"), "{err}");
+        assert!(load(&path, None).is_err());
+    }
+
+    #[test]
+    fn test_address_from_public_key_hex() {
+        // RFC 8032 test vector 1
+        let pubkey = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+        let address = address_from_public_key_hex(pubkey).unwrap();
+        assert_eq!(address, "RTC21fe31dfa154a261626bf854046fd2271b7bed4b");
+        assert!(crate::address::is_valid_rtc_address(&address));
+
+        let w = RtcWallet::generate();
+        assert_eq!(
+            address_from_public_key_hex(&w.public_key_hex()).unwrap(),
+            w.address()
+        );
+
+        for bad in ["", "zz", &pubkey[..62], &format!("{pubkey}00")] {
+            assert!(matches!(
+                address_from_public_key_hex(bad),
+                Err(ClawRtcError::Crypto(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_sealed_backup_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let w = RtcWallet::generate();
+
+        let plain = dir.path().join("backup.json");
+        w.save_plaintext(&plain).unwrap();
+        let seal = export_sealed(&plain).unwrap();
+        assert_eq!(seal, dir.path().join("backup.json.sha256"));
+        assert!(std::fs::read_to_string(&seal)
+            .unwrap()
+            .ends_with("  backup.json\n"));
+        assert_eq!(import_sealed(&plain, None).unwrap().address(), w.address());
+
+        let keystore = dir.path().join("backup.keystore");
+        w.save_keystore(&keystore, "pw").unwrap();
+        export_sealed(&keystore).unwrap();
+        let restored = import_sealed(&keystore, Some("pw")).unwrap();
+        assert_eq!(restored.address(), w.address());
+    }
+
+    #[test]
+    fn test_sealed_backup_detects_tampering() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backup.json");
+        RtcWallet::generate().save_plaintext(&path).unwrap();
+        export_sealed(&path).unwrap();
+
+        // Truncation
+        let data = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &data[..data.len() - 10]).unwrap();
+        let err = import_sealed(&path, None).err().unwrap();
+        assert!(err.to_string().contains("integrity check failed"), "{err}");
+
+        // A single flipped bit in an otherwise valid file
+        let mut flipped = data.clone();
+        flipped[20] ^= 0x01;
+        std::fs::write(&path, &flipped).unwrap();
+        assert!(import_sealed(&path, None).is_err());
+
+        std::fs::write(&path, &data).unwrap();
+        assert!(import_sealed(&path, None).is_ok());
+        std::fs::remove_file(seal_path(&path)).unwrap();
+        let err = import_sealed(&path, None).err().unwrap();
+        assert!(err.to_string().contains("Missing backup seal"), "{err}");
+    }
+
+    fn sweep_node(balance: f64, fee: f64) -> impl Fn(&RecordedRequest) -> MockResponse {
+        move |req| match req.path.as_str() {
+            p if p.starts_with("/api/balance") => {
+                MockResponse::json(200, serde_json::json!({ "balance_rtc": balance }))
+            }
+            "/wallet/fee" => MockResponse::json(200, serde_json::json!({ "fee_rtc": fee })),
+            _ => MockResponse::json(200, serde_json::json!({ "ok": true, "tx_hash": "t1" })),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sweep_transfers_balance_minus_fee() {
+        let server = MockServer::start(sweep_node(10.5, 0.01)).await;
+        let client = RustChainClient::new(&server.url());
+        let w = RtcWallet::generate();
+        let dest = RtcWallet::generate();
+
+        let result = w.sweep_to(&client, dest.address()).await.unwrap();
+        assert_eq!(result.amount_rtc, "10.49".parse().unwrap());
+        assert_eq!(result.fee_rtc, "0.01".parse().unwrap());
+        assert_eq!(result.response.tx_hash.as_deref(), Some("t1"));
+
+        let transfer = server
+            .requests()
+            .into_iter()
+            .find(|r| r.path == "/wallet/transfer/signed")
+            .unwrap()
+            .json();
+        assert_eq!(transfer["amount_rtc"], 10.49);
+        assert_eq!(transfer["from_address"], w.address());
+        assert_eq!(transfer["to_address"], dest.address());
+
+        // A configured fee skips the node lookup
+        let server = MockServer::start(sweep_node(3.0, 99.0)).await;
+        let fee = "0.5".parse().unwrap();
+        let client = RustChainClient::new(&server.url()).with_transfer_fee(fee);
+        let result = w.sweep_to(&client, dest.address()).await.unwrap();
+        assert_eq!(result.amount_rtc, "2.5".parse().unwrap());
+        assert!(server.requests().iter().all(|r| r.path != "/wallet/fee"));
+    }
+
+    #[tokio::test]
+    async fn test_sweep_amount_is_exact() {
+        // 0.3 - 0.1 is 0.19999999999999998 in f64
+        let server = MockServer::start(sweep_node(0.3, 0.1)).await;
+        let client = RustChainClient::new(&server.url());
+        let w = RtcWallet::generate();
+        let dest = RtcWallet::generate();
+
+        let result = w.sweep_to(&client, dest.address()).await.unwrap();
+        assert_eq!(result.amount_rtc.to_string(), "0.2");
+        let transfer = server.requests().pop().unwrap().json();
+        assert_eq!(transfer["amount_rtc"], 0.2);
+
+        // Digits past a millionth are left behind, never rounded up
+        let server = MockServer::start(sweep_node(1.2345679, 0.0)).await;
+        let client = RustChainClient::new(&server.url());
+        let result = w.sweep_to(&client, dest.address()).await.unwrap();
+        assert_eq!(result.amount_rtc.to_string(), "1.234567");
+    }
+
+    #[tokio::test]
+    async fn test_sweep_refuses_empty_wallet() {
+        let server = MockServer::start(sweep_node(0.01, 0.01)).await;
+        let client = RustChainClient::new(&server.url());
+        let w = RtcWallet::generate();
+
+        let err = w
+            .sweep_to(&client, RtcWallet::generate().address())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClawRtcError::InsufficientFunds(_)), "{err}");
+        assert!(w.sweep_to(&client, w.address()).await.is_err());
+        assert!(server.requests().iter().all(|r| r.path != "/wallet/transfer/signed"));
+    }
+
+    #[tokio::test]
+    async fn test_sweep_rejects_invalid_fee() {
+        let server = MockServer::start(sweep_node(10.0, -0.01)).await;
+        let w = RtcWallet::generate();
+        let dest = RtcWallet::generate();
+        let clients = [
+            RustChainClient::new(&server.url()),
+            RustChainClient::new(&server.url()).with_transfer_fee(Decimal::NEGATIVE_ONE),
+        ];
+        for client in clients {
+            let err = w.sweep_to(&client, dest.address()).await.unwrap_err();
+            assert!(matches!(err, ClawRtcError::Config(_)), "{err}");
+        }
+        assert!(server.requests().iter().all(|r| r.path != "/wallet/transfer/signed"));
     }
 
     #[test]
@@ -222,8 +945,314 @@ mod tests {
     #[test]
     fn test_sign_transaction() {
         let w = RtcWallet::generate();
-        let tx = w.sign_transaction("RTCdeadbeef00000000000000000000000000000000", 10.5, "test").unwrap();
+        let amount = "10.5".parse().unwrap();
+        let tx = w
+            .sign_transaction(
+                "RTCdeadbeef00000000000000000000000000000000",
+                amount,
+                "test",
+            )
+            .unwrap();
         assert!(tx["signature"].as_str().unwrap().len() == 128);
         assert_eq!(tx["from_address"], w.address());
     }
+
+    #[test]
+    fn test_sign_transaction_nonces_are_unique() {
+        let w = RtcWallet::generate();
+        let to = "RTCdeadbeef00000000000000000000000000000000";
+        let a = w.sign_transaction(to, Decimal::ONE, "").unwrap();
+        let b = w.sign_transaction(to, Decimal::ONE, "").unwrap();
+        assert!(b["nonce"].as_i64().unwrap() > a["nonce"].as_i64().unwrap());
+    }
+
+    #[test]
+    fn test_sign_transaction_nonce_follows_injected_clock() {
+        let clock = crate::clock::MockClock::at_unix(1_000);
+        let nonces = Arc::new(NonceManager::with_clock(Arc::new(clock)));
+        let w = RtcWallet::generate().with_nonce_manager(nonces);
+        let tx = w
+            .sign_transaction(
+                "RTCdeadbeef00000000000000000000000000000000",
+                Decimal::ONE,
+                "",
+            )
+            .unwrap();
+        assert_eq!(tx["nonce"], 1_000_000);
+    }
+
+    #[test]
+    fn test_sign_batch_transfer() {
+        let w = RtcWallet::generate().with_nonce_manager(Arc::new(NonceManager::new()));
+        let transfers: Vec<BatchTransfer> = (1..=3)
+            .map(|i| BatchTransfer {
+                to_address: "RTCdeadbeef00000000000000000000000000000000".into(),
+                amount_rtc: Decimal::from(i),
+                memo: format!("payout {i}"),
+            })
+            .collect();
+
+        let signed = w.sign_batch_transfer(&transfers).unwrap();
+        assert_eq!(signed.len(), 3);
+        let first = signed[0]["nonce"].as_i64().unwrap();
+        for (i, tx) in signed.iter().enumerate() {
+            assert_eq!(tx["nonce"].as_i64().unwrap(), first + i as i64);
+            assert_eq!(tx["amount_rtc"], (i + 1) as f64);
+            assert_eq!(tx["memo"], format!("payout {}", i + 1));
+        }
+        // Single transfers continue after the batch
+        let single = w.sign_transaction(
+            "RTCdeadbeef00000000000000000000000000000000",
+            Decimal::ONE,
+            "",
+        );
+        assert_eq!(single.unwrap()["nonce"], first + 3);
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let w = RtcWallet::generate();
+        let sig = w.sign(b"hello");
+        assert!(verify_signature(&w.public_key_hex(), b"hello", &sig).unwrap());
+        assert!(!verify_signature(&w.public_key_hex(), b"hellp", &sig).unwrap());
+        assert!(verify_signature(&w.public_key_hex(), b"hello", "zz").is_err());
+        assert!(verify_signature("abcd", b"hello", &sig).is_err());
+        assert!(matches!(
+            verify_signature(&w.public_key_hex(), b"hello", &sig[..126]),
+            Err(ClawRtcError::Crypto(_))
+        ));
+    }
+
+    #[test]
+    fn test_wallet_verify() {
+        let w = RtcWallet::generate();
+        let other = RtcWallet::generate();
+        let sig = w.sign(b"hello");
+        assert!(w.verify(b"hello", &sig).unwrap());
+        assert!(!w.verify(b"hellp", &sig).unwrap());
+        assert!(!other.verify(b"hello", &sig).unwrap());
+        assert!(w.verify(b"hello", &format!("{sig}00")).is_err());
+    }
+
+    #[test]
+    fn test_verify_signed_transaction() {
+        let w = RtcWallet::generate();
+        let dest = RtcWallet::generate();
+        let signed = w
+            .sign_transaction(dest.address(), "1.25".parse().unwrap(), "rent")
+            .unwrap();
+        let message = transfer_message(
+            signed["from_address"].as_str().unwrap(),
+            signed["to_address"].as_str().unwrap(),
+            amount::from_f64(signed["amount_rtc"].as_f64().unwrap()).unwrap(),
+            signed["memo"].as_str().unwrap(),
+            signed["nonce"].as_i64().unwrap(),
+        );
+        let canonical = canonical_json(&message);
+        let public_key = signed["public_key"].as_str().unwrap();
+        let signature = signed["signature"].as_str().unwrap();
+        assert!(verify_signature(public_key, canonical.as_bytes(), signature).unwrap());
+        assert!(w.verify(canonical.as_bytes(), signature).unwrap());
+        assert!(!dest.verify(canonical.as_bytes(), signature).unwrap());
+    }
+
+    #[test]
+    fn test_canonical_json_sorts_keys_at_every_level() {
+        let mut inner = serde_json::Map::new();
+        inner.insert("z".into(), 1.into());
+        inner.insert("a".into(), serde_json::json!([{ "y": "\"q\"", "b": null }]));
+        let mut forward = serde_json::Map::new();
+        forward.insert("memo".into(), inner.clone().into());
+        forward.insert("amount".into(), 2.5.into());
+        let mut reversed = serde_json::Map::new();
+        reversed.insert("amount".into(), 2.5.into());
+        reversed.insert("memo".into(), inner.into());
+
+        let expected = r#"{"amount":2.5,"memo":{"a":[{"b":null,"y":"\"q\""}],"z":1}}"#;
+        assert_eq!(canonical_json(&forward.into()), expected);
+        assert_eq!(canonical_json(&reversed.into()), expected);
+    }
+
+    #[test]
+    fn test_signed_transfer_bytes_are_sorted_and_stable() {
+        let w = RtcWallet::from_private_key_hex(&"42".repeat(32)).unwrap();
+        let to = "RTC0000000000000000000000000000000000000001";
+        let amount = "1.25".parse().unwrap();
+        let signed = signer::sign_transaction_with_nonce(&w, to, amount, "rent", 7).unwrap();
+        let expected = format!(
+            r#"{{"amount":1.25,"from":"{}","memo":"rent","nonce":7,"to":"{to}"}}"#,
+            w.address()
+        );
+        assert_eq!(
+            canonical_json(&transfer_message(w.address(), to, amount, "rent", 7)),
+            expected
+        );
+        // Ed25519 is deterministic: same key and bytes, same signature
+        assert_eq!(signed["signature"], w.sign(expected.as_bytes()));
+    }
+
+    #[test]
+    fn test_viewing_key_deterministic_and_distinct() {
+        let w = RtcWallet::generate();
+        let vk = w.viewing_key();
+        assert_eq!(vk.len(), 64);
+        assert_eq!(vk, w.viewing_key());
+        assert_ne!(vk, w.public_key_hex());
+        assert_ne!(vk, *w.private_key_hex());
+
+        // Same signing key, same viewing key; different wallet, different key
+        let restored = RtcWallet::from_private_key_hex(&w.private_key_hex()).unwrap();
+        assert_eq!(restored.viewing_key(), vk);
+        assert_ne!(RtcWallet::generate().viewing_key(), vk);
+    }
+
+    #[test]
+    fn test_viewing_key_attestation() {
+        let w = RtcWallet::generate();
+        let att = w.viewing_key_attestation();
+        assert!(verify_viewing_key(&w.public_key_hex(), &w.viewing_key(), &att).unwrap());
+
+        let other = RtcWallet::generate();
+        assert!(!verify_viewing_key(&w.public_key_hex(), &other.viewing_key(), &att).unwrap());
+        assert!(!verify_viewing_key(&other.public_key_hex(), &w.viewing_key(), &att).unwrap());
+    }
+
+    #[test]
+    fn test_view_tag_verifies_with_viewing_key() {
+        let w = RtcWallet::generate();
+        let tag = w.view_tag(b"incoming tx abc123");
+        assert!(verify_view_tag(&w.viewing_key(), b"incoming tx abc123", &tag).unwrap());
+        assert!(!verify_view_tag(&w.viewing_key(), b"incoming tx abc124", &tag).unwrap());
+        assert!(!verify_view_tag(&RtcWallet::generate().viewing_key(), b"incoming tx abc123", &tag).unwrap());
+        assert!(verify_view_tag("not hex", b"x", &tag).is_err());
+    }
+
+    /// Flip the case of the first hex letter in an address.
+    fn flip_first_letter_case(address: &str) -> String {
+        let mut flipped = false;
+        address
+            .char_indices()
+            .map(|(i, c)| {
+                if i >= 3 && !flipped && c.is_ascii_alphabetic() {
+                    flipped = true;
+                    if c.is_ascii_uppercase() {
+                        c.to_ascii_lowercase()
+                    } else {
+                        c.to_ascii_uppercase()
+                    }
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_address_valid() {
+        let w = RtcWallet::generate();
+        assert!(validate_rtc_address(w.address()).is_ok());
+        let checksummed = checksum_address(w.address()).unwrap();
+        assert!(validate_rtc_address(&checksummed).is_ok());
+        // Single-case addresses carry no checksum
+        assert!(is_valid_rtc_address(&checksummed.to_ascii_uppercase()));
+    }
+
+    #[test]
+    fn test_validate_address_wrong_prefix() {
+        let w = RtcWallet::generate();
+        let addr = w.address().replacen("RTC", "BTC", 1);
+        let err = validate_rtc_address(&addr).unwrap_err().to_string();
+        assert!(err.contains("RTC"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_address_wrong_length() {
+        let err = validate_rtc_address("RTCdeadbeef").unwrap_err().to_string();
+        assert!(err.contains("43 characters"), "{err}");
+        assert!(!is_valid_rtc_address(&format!("{}0", RtcWallet::generate().address())));
+    }
+
+    #[test]
+    fn test_validate_address_non_hex() {
+        let addr = format!("RTC{}", "g".repeat(40));
+        assert!(!is_valid_rtc_address(&addr));
+    }
+
+    #[test]
+    fn test_validate_address_bad_checksum() {
+        // Find an address where flipping one letter still leaves mixed case,
+        // so the result is still treated as checksummed
+        let is_mixed = |a: &str| {
+            a[3..].chars().any(|c| c.is_ascii_uppercase())
+                && a[3..].chars().any(|c| c.is_ascii_lowercase())
+        };
+        let (checksummed, tampered) = std::iter::repeat_with(RtcWallet::generate)
+            .map(|w| {
+                let c = checksum_address(w.address()).unwrap();
+                let t = flip_first_letter_case(&c);
+                (c, t)
+            })
+            .find(|(c, t)| is_mixed(c) && is_mixed(t))
+            .unwrap();
+        assert!(verify_address_checksum(&checksummed));
+        assert!(!verify_address_checksum(&tampered));
+        let err = validate_rtc_address(&tampered).unwrap_err().to_string();
+        assert!(err.contains("checksum"), "{err}");
+    }
+
+    #[test]
+    fn test_load_plaintext() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plain.json");
+        let w1 = RtcWallet::generate();
+        w1.save_plaintext(&path).unwrap();
+        let w2 = load(&path, None).unwrap();
+        assert_eq!(w1.address(), w2.address());
+        // A password is ignored for plaintext files
+        let w3 = load(&path, Some("unused")).unwrap();
+        assert_eq!(w1.address(), w3.address());
+    }
+
+    #[test]
+    fn test_load_keystore() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keystore.json");
+        let w1 = RtcWallet::generate();
+        w1.save_keystore(&path, "hunter2hunter2").unwrap();
+        let w2 = load(&path, Some("hunter2hunter2")).unwrap();
+        assert_eq!(w1.address(), w2.address());
+        assert!(load(&path, Some("wrong")).is_err());
+    }
+
+    #[test]
+    fn test_load_keystore_rejects_mismatched_address() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keystore.json");
+        let secret = RtcWallet::generate().private_key_hex();
+        let other = RtcWallet::generate();
+        let ks = Keystore::encrypt(&secret, "pw", other.address()).unwrap();
+        ks.save(&path).unwrap();
+
+        let err = load(&path, Some("pw")).err().unwrap();
+        assert!(err.to_string().contains("mismatch"), "{err}");
+        assert!(RtcWallet::from_keystore(&path, "pw").is_err());
+    }
+
+    #[test]
+    fn test_load_keystore_without_password() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keystore.json");
+        RtcWallet::generate().save_keystore(&path, "pw").unwrap();
+        let err = load(&path, None).err().unwrap();
+        assert!(matches!(err, ClawRtcError::KeystoreDecrypt(_)));
+        assert!(err.to_string().contains("password is required"));
+    }
+
+    #[test]
+    fn test_load_unknown_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("other.json");
+        std::fs::write(&path, r#"{"hello": "world"}"#).unwrap();
+        assert!(load(&path, None).is_err());
+    }
 }
@@ -0,0 +1,161 @@
+//! Owner-only file access on Windows, the counterpart of mode `0600`.
+//!
+//! New files inherit their directory's ACL, which under a user profile
+//! usually also grants Administrators and SYSTEM, and elsewhere may grant
+//! every user. [`restrict_to_owner`] replaces that with a protected DACL
+//! holding a single entry for the current user.
+
+use std::io;
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+use windows_sys::Win32::Foundation::{
+    CloseHandle, LocalFree, ERROR_SUCCESS, GENERIC_ALL, HANDLE, WIN32_ERROR,
+};
+use windows_sys::Win32::Security::Authorization::{
+    SetEntriesInAclW, SetNamedSecurityInfoW, EXPLICIT_ACCESS_W, NO_MULTIPLE_TRUSTEE, SET_ACCESS,
+    SE_FILE_OBJECT, TRUSTEE_IS_SID, TRUSTEE_IS_USER, TRUSTEE_W,
+};
+use windows_sys::Win32::Security::{
+    GetTokenInformation, TokenUser, ACL, DACL_SECURITY_INFORMATION, NO_INHERITANCE,
+    PROTECTED_DACL_SECURITY_INFORMATION, PSID, TOKEN_QUERY, TOKEN_USER,
+};
+use windows_sys::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+/// The current process's user, as returned by `GetTokenInformation`.
+struct CurrentUser {
+    /// A `TOKEN_USER` followed by the SID it points to; `u64`s for alignment.
+    buf: Vec<u64>,
+}
+
+impl CurrentUser {
+    fn get() -> io::Result<Self> {
+        let mut token: HANDLE = std::ptr::null_mut();
+        // SAFETY: `token` is a valid out-pointer, and the handle is closed
+        // below. The second `GetTokenInformation` call writes at most `len`
+        // bytes, which `buf` holds.
+        unsafe {
+            if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut len = 0u32;
+            GetTokenInformation(token, TokenUser, std::ptr::null_mut(), 0, &mut len);
+            let mut buf = vec![0u64; (len as usize).div_ceil(8)];
+            let ok = GetTokenInformation(token, TokenUser, buf.as_mut_ptr().cast(), len, &mut len);
+            let err = io::Error::last_os_error();
+            CloseHandle(token);
+            if ok == 0 {
+                return Err(err);
+            }
+            Ok(Self { buf })
+        }
+    }
+
+    /// The user's SID, valid while `self` lives.
+    fn sid(&self) -> PSID {
+        // SAFETY: `buf` was filled with a `TOKEN_USER` by `get`.
+        unsafe { (*self.buf.as_ptr().cast::<TOKEN_USER>()).User.Sid }
+    }
+}
+
+fn check(err: WIN32_ERROR) -> io::Result<()> {
+    if err == ERROR_SUCCESS {
+        Ok(())
+    } else {
+        Err(io::Error::from_raw_os_error(err as i32))
+    }
+}
+
+fn wide(path: &Path) -> Vec<u16> {
+    path.as_os_str().encode_wide().chain(Some(0)).collect()
+}
+
+/// Make `path` accessible to the current user only.
+///
+/// Its DACL is replaced by one granting the current user full control, and
+/// marked protected so nothing is inherited from the parent directory.
+pub(crate) fn restrict_to_owner(path: &Path) -> io::Result<()> {
+    let user = CurrentUser::get()?;
+    let access = EXPLICIT_ACCESS_W {
+        grfAccessPermissions: GENERIC_ALL,
+        grfAccessMode: SET_ACCESS,
+        grfInheritance: NO_INHERITANCE,
+        Trustee: TRUSTEE_W {
+            pMultipleTrustee: std::ptr::null_mut(),
+            MultipleTrusteeOperation: NO_MULTIPLE_TRUSTEE,
+            TrusteeForm: TRUSTEE_IS_SID,
+            TrusteeType: TRUSTEE_IS_USER,
+            ptstrName: user.sid().cast(),
+        },
+    };
+    let name = wide(path);
+    let mut acl: *mut ACL = std::ptr::null_mut();
+    // SAFETY: `access` points at the live SID in `user`, `name` is
+    // NUL-terminated, and the ACL allocated by `SetEntriesInAclW` is freed
+    // once applied.
+    unsafe {
+        check(SetEntriesInAclW(1, &access, std::ptr::null(), &mut acl))?;
+        let applied = SetNamedSecurityInfoW(
+            name.as_ptr(),
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION | PROTECTED_DACL_SECURITY_INFORMATION,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            acl,
+            std::ptr::null(),
+        );
+        LocalFree(acl.cast());
+        check(applied)
+    }
+}
+
+/// Whether `path`'s DACL grants access to the current user and nobody else.
+#[cfg(test)]
+pub(crate) fn is_owner_only(path: &Path) -> io::Result<bool> {
+    use windows_sys::Win32::Security::Authorization::GetNamedSecurityInfoW;
+    use windows_sys::Win32::Security::{
+        AclSizeInformation, EqualSid, GetAce, GetAclInformation, ACCESS_ALLOWED_ACE,
+        ACL_SIZE_INFORMATION, PSECURITY_DESCRIPTOR,
+    };
+
+    let user = CurrentUser::get()?;
+    let name = wide(path);
+    let mut dacl: *mut ACL = std::ptr::null_mut();
+    let mut descriptor: PSECURITY_DESCRIPTOR = std::ptr::null_mut();
+    // SAFETY: `dacl` points into `descriptor`, which is freed last; the ACE
+    // pointer returned by `GetAce` points into `dacl`.
+    unsafe {
+        check(GetNamedSecurityInfoW(
+            name.as_ptr(),
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut dacl,
+            std::ptr::null_mut(),
+            &mut descriptor,
+        ))?;
+        let mut info = ACL_SIZE_INFORMATION {
+            AceCount: 0,
+            AclBytesInUse: 0,
+            AclBytesFree: 0,
+        };
+        let mut owner_only = !dacl.is_null()
+            && GetAclInformation(
+                dacl,
+                std::ptr::addr_of_mut!(info).cast(),
+                std::mem::size_of::<ACL_SIZE_INFORMATION>() as u32,
+                AclSizeInformation,
+            ) != 0
+            && info.AceCount == 1;
+        if owner_only {
+            let mut ace = std::ptr::null_mut();
+            owner_only = GetAce(dacl, 0, &mut ace) != 0
+                && EqualSid(
+                    std::ptr::addr_of_mut!((*ace.cast::<ACCESS_ALLOWED_ACE>()).SidStart).cast(),
+                    user.sid(),
+                ) != 0;
+        }
+        LocalFree(descriptor);
+        Ok(owner_only)
+    }
+}